@@ -0,0 +1,41 @@
+//! Minimal receiver for trying out the club webhooks locally.
+//!
+//! ```sh
+//! WEBHOOK_SECRET=<secret from /webhooks/:id> cargo run --example webhook_receiver --features ssr
+//! ```
+//!
+//! Then add a webhook with the URL `http://127.0.0.1:3456/` and press "Test senden".
+
+#[cfg(feature = "ssr")]
+#[tokio::main]
+async fn main() {
+    use std::env;
+
+    use as_manager::webhook::{verify_payload, EVENT_HEADER, SIGNATURE_HEADER, TIMESTAMP_HEADER};
+    use axum::{http::{HeaderMap, StatusCode}, routing::post, Router};
+
+    let secret = env::var("WEBHOOK_SECRET").expect("WEBHOOK_SECRET must be set");
+    let addr = env::var("WEBHOOK_RECEIVER_ADDR").unwrap_or("127.0.0.1:3456".to_string());
+
+    let app = Router::new().route("/", post(move |headers: HeaderMap, body: String| async move {
+        let header = |name: &str| headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default()
+            .to_string();
+
+        let timestamp: i64 = header(TIMESTAMP_HEADER).parse().unwrap_or_default();
+        let valid = verify_payload(&secret, timestamp, &body, &header(SIGNATURE_HEADER));
+
+        println!("{} ({}): {}", header(EVENT_HEADER), if valid { "signature ok" } else { "INVALID signature" }, body);
+
+        if valid { StatusCode::NO_CONTENT } else { StatusCode::UNAUTHORIZED }
+    }));
+
+    println!("listening on http://{}", addr);
+    let listener = tokio::net::TcpListener::bind(&addr).await.unwrap();
+    axum::serve(listener, app).await.unwrap();
+}
+
+#[cfg(not(feature = "ssr"))]
+pub fn main() {}
@@ -0,0 +1,2 @@
+drop table "play_event_registrations";
+drop table "play_events";
@@ -0,0 +1,31 @@
+create table "play_events"
+(
+	id bigserial primary key,
+	name text not null,
+	description text not null,
+	starts_at timestamptz not null,
+	ends_at timestamptz not null,
+	organizer_id bigint references players(id),
+	capacity integer check (capacity > 0),
+	registration_deadline timestamptz,
+	created_at timestamptz not null default now(),
+	updated_at timestamptz
+);
+
+select trigger_updated_at('"play_events"');
+
+create table "play_event_registrations"
+(
+	id bigserial primary key,
+	play_event_id bigint not null references play_events(id),
+	player_id bigint not null references players(id),
+	registered_by_id bigint not null references players(id),
+	status text not null check (status in ('registered', 'waitlisted', 'withdrawn')),
+	registered_at timestamptz not null default now(),
+	withdrawn_at timestamptz,
+	created_at timestamptz not null default now(),
+	updated_at timestamptz,
+	unique (play_event_id, player_id)
+);
+
+select trigger_updated_at('"play_event_registrations"');
@@ -0,0 +1,2 @@
+drop table "faction_assignments";
+drop table "factions";
@@ -0,0 +1,22 @@
+create table "factions"
+(
+	id bigserial primary key,
+	play_event_id bigint not null references play_events(id),
+	name text not null,
+	created_at timestamptz not null default now(),
+	updated_at timestamptz
+);
+
+select trigger_updated_at('"factions"');
+
+create table "faction_assignments"
+(
+	play_event_id bigint not null references play_events(id),
+	player_id bigint not null references players(id),
+	faction_id bigint not null references factions(id) on delete cascade,
+	created_at timestamptz not null default now(),
+	updated_at timestamptz,
+	primary key (play_event_id, player_id)
+);
+
+select trigger_updated_at('"faction_assignments"');
@@ -0,0 +1,3 @@
+alter table "play_event_registrations"
+drop column checked_in_by_id,
+drop column checked_in_at;
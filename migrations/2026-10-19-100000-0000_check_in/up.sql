@@ -0,0 +1,3 @@
+alter table "play_event_registrations"
+add column checked_in_at timestamptz,
+add column checked_in_by_id bigint references players(id);
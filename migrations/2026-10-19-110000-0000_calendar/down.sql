@@ -0,0 +1,5 @@
+drop table "calendar_tokens";
+
+alter table "play_events"
+drop column cancelled_at,
+drop column sequence;
@@ -0,0 +1,13 @@
+alter table "play_events"
+add column sequence integer not null default 0,
+add column cancelled_at timestamptz;
+
+create table "calendar_tokens"
+(
+	player_id bigint primary key references players(id),
+	token text not null unique,
+	created_at timestamptz not null default now(),
+	updated_at timestamptz
+);
+
+select trigger_updated_at('"calendar_tokens"');
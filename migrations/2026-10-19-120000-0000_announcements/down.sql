@@ -0,0 +1,16 @@
+alter table "announcements"
+drop column author_id,
+drop column expires_at,
+drop column publish_at;
+
+alter index "announcements_pkey"
+rename to "announcement_pkey";
+
+alter sequence "announcements_id_seq"
+rename to "announcement_id_seq";
+
+alter table "announcements"
+rename to "announcement";
+
+alter table "players"
+drop column admin;
@@ -0,0 +1,16 @@
+alter table "players"
+add column admin boolean not null default false;
+
+alter table "announcement"
+rename to "announcements";
+
+alter sequence "announcement_id_seq"
+rename to "announcements_id_seq";
+
+alter index "announcement_pkey"
+rename to "announcements_pkey";
+
+alter table "announcements"
+add column publish_at timestamptz not null default now(),
+add column expires_at timestamptz,
+add column author_id bigint references players(id);
@@ -0,0 +1,6 @@
+drop table "announcement_reads";
+
+alter table "announcements"
+drop constraint announcement_audience_id,
+drop column audience_id,
+drop column audience;
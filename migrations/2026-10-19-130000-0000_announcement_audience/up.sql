@@ -0,0 +1,15 @@
+alter table "announcements"
+add column audience text not null default 'everyone' check (audience in ('everyone', 'platoon', 'team', 'play_event')),
+add column audience_id bigint,
+add constraint announcement_audience_id check ((audience = 'everyone') = (audience_id is null));
+
+create table "announcement_reads"
+(
+	announcement_id bigint not null references announcements(id) on delete cascade,
+	player_id bigint not null references players(id),
+	created_at timestamptz not null default now(),
+	updated_at timestamptz,
+	primary key (announcement_id, player_id)
+);
+
+select trigger_updated_at('"announcement_reads"');
@@ -0,0 +1,4 @@
+drop table "survey_answers";
+drop table "survey_question_options";
+drop table "survey_questions";
+drop table "surveys";
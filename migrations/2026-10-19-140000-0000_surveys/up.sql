@@ -0,0 +1,57 @@
+create table "surveys"
+(
+	id bigserial primary key,
+	title text not null,
+	description text not null,
+	audience text not null check (audience in ('platoon', 'team')),
+	audience_id bigint not null,
+	author_id bigint references players(id),
+	anonymous boolean not null default false,
+	deadline timestamptz,
+	created_at timestamptz not null default now(),
+	updated_at timestamptz
+);
+
+select trigger_updated_at('"surveys"');
+
+create table "survey_questions"
+(
+	id bigserial primary key,
+	survey_id bigint not null references surveys(id) on delete cascade,
+	position integer not null,
+	text text not null,
+	kind text not null check (kind in ('single_choice', 'multiple_choice', 'free_text')),
+	created_at timestamptz not null default now(),
+	updated_at timestamptz
+);
+
+select trigger_updated_at('"survey_questions"');
+
+create table "survey_question_options"
+(
+	id bigserial primary key,
+	question_id bigint not null references survey_questions(id) on delete cascade,
+	position integer not null,
+	text text not null,
+	created_at timestamptz not null default now(),
+	updated_at timestamptz
+);
+
+select trigger_updated_at('"survey_question_options"');
+
+create table "survey_answers"
+(
+	id bigserial primary key,
+	survey_id bigint not null references surveys(id) on delete cascade,
+	question_id bigint not null references survey_questions(id) on delete cascade,
+	player_id bigint not null references players(id),
+	option_id bigint references survey_question_options(id) on delete cascade,
+	text text,
+	created_at timestamptz not null default now(),
+	updated_at timestamptz,
+	check ((option_id is null) <> (text is null))
+);
+
+select trigger_updated_at('"survey_answers"');
+
+create index survey_answers_survey_player on survey_answers (survey_id, player_id);
@@ -0,0 +1,2 @@
+drop table "play_event_class_limits";
+drop table "replicas";
@@ -0,0 +1,26 @@
+create table "replicas"
+(
+	id bigserial primary key,
+	player_id bigint not null references players(id) on delete cascade,
+	name text not null,
+	class text not null check (class in ('aeg', 'dmr', 'sniper', 'pistol')),
+	fps double precision not null check (fps > 0),
+	bb_weight double precision not null check (bb_weight > 0),
+	created_at timestamptz not null default now(),
+	updated_at timestamptz
+);
+
+select trigger_updated_at('"replicas"');
+
+create table "play_event_class_limits"
+(
+	play_event_id bigint not null references play_events(id) on delete cascade,
+	class text not null check (class in ('aeg', 'dmr', 'sniper', 'pistol')),
+	max_joules double precision not null check (max_joules > 0),
+	min_distance_meters integer not null default 0 check (min_distance_meters >= 0),
+	created_at timestamptz not null default now(),
+	updated_at timestamptz,
+	primary key (play_event_id, class)
+);
+
+select trigger_updated_at('"play_event_class_limits"');
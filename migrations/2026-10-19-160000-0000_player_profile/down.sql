@@ -0,0 +1,6 @@
+alter table "players"
+drop column phone,
+drop column role,
+drop column emergency_contact,
+drop column birth_date,
+drop column real_name;
@@ -0,0 +1,6 @@
+alter table "players"
+add column real_name text,
+add column birth_date date,
+add column emergency_contact text,
+add column role text check (role in ('rifleman', 'medic', 'sniper', 'support')),
+add column phone text;
@@ -0,0 +1,4 @@
+drop table "parental_consents";
+
+alter table "play_events"
+drop column min_age;
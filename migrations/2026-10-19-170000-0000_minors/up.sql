@@ -0,0 +1,17 @@
+alter table "play_events"
+add column min_age integer check (min_age > 0);
+
+create table "parental_consents"
+(
+	id bigserial primary key,
+	player_id bigint not null references players(id) on delete cascade,
+	kind text not null check (kind in ('signed', 'document')),
+	guardian_name text not null,
+	note text not null default '',
+	recorded_by_id bigint references players(id) on delete set null,
+	valid_until date not null,
+	created_at timestamptz not null default now(),
+	updated_at timestamptz
+);
+
+select trigger_updated_at('"parental_consents"');
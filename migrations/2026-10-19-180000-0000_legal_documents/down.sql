@@ -0,0 +1,2 @@
+drop table "legal_document_acceptances";
+drop table "legal_documents";
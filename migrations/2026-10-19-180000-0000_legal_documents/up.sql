@@ -0,0 +1,27 @@
+create table "legal_documents"
+(
+	id bigserial primary key,
+	kind text not null check (kind in ('waiver', 'code_of_conduct')),
+	version integer not null check (version > 0),
+	title text not null,
+	content text not null,
+	author_id bigint references players(id) on delete set null,
+	published_at timestamptz,
+	created_at timestamptz not null default now(),
+	updated_at timestamptz,
+	unique (kind, version)
+);
+
+select trigger_updated_at('"legal_documents"');
+
+create table "legal_document_acceptances"
+(
+	document_id bigint not null references legal_documents(id) on delete cascade,
+	player_id bigint not null references players(id) on delete cascade,
+	ip_address text,
+	created_at timestamptz not null default now(),
+	updated_at timestamptz,
+	primary key (document_id, player_id)
+);
+
+select trigger_updated_at('"legal_document_acceptances"');
@@ -0,0 +1,43 @@
+create table "fees"
+(
+	id bigserial primary key,
+	name text not null,
+	kind text not null check (kind in ('membership', 'event', 'rental')),
+	amount_cents bigint not null check (amount_cents > 0),
+	play_event_id bigint references play_events(id) on delete set null,
+	created_at timestamptz not null default now(),
+	updated_at timestamptz
+);
+
+select trigger_updated_at('"fees"');
+
+create table "charges"
+(
+	id bigserial primary key,
+	player_id bigint not null references players(id) on delete cascade,
+	fee_id bigint references fees(id) on delete set null,
+	description text not null,
+	amount_cents bigint not null check (amount_cents > 0),
+	created_by_id bigint references players(id) on delete set null,
+	created_at timestamptz not null default now(),
+	updated_at timestamptz,
+	unique (player_id, fee_id)
+);
+
+select trigger_updated_at('"charges"');
+
+create table "payments"
+(
+	id bigserial primary key,
+	player_id bigint not null references players(id) on delete cascade,
+	charge_id bigint references charges(id) on delete set null,
+	amount_cents bigint not null check (amount_cents > 0),
+	method text not null check (method in ('cash', 'transfer')),
+	reference text not null default '',
+	paid_on date not null,
+	recorded_by_id bigint references players(id) on delete set null,
+	created_at timestamptz not null default now(),
+	updated_at timestamptz
+);
+
+select trigger_updated_at('"payments"');
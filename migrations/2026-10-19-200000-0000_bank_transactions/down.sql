@@ -0,0 +1 @@
+drop table "bank_transactions";
@@ -0,0 +1,16 @@
+create table "bank_transactions"
+(
+	id bigserial primary key,
+	import_key text not null unique,
+	booked_on date not null,
+	amount_cents bigint not null check (amount_cents > 0),
+	counterparty text not null default '',
+	reference text not null default '',
+	payment_id bigint references payments(id) on delete set null,
+	ignored boolean not null default false,
+	imported_by_id bigint references players(id) on delete set null,
+	created_at timestamptz not null default now(),
+	updated_at timestamptz
+);
+
+select trigger_updated_at('"bank_transactions"');
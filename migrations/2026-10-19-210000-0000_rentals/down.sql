@@ -0,0 +1,2 @@
+drop table "rental_loans";
+drop table "rental_items";
@@ -0,0 +1,34 @@
+create table "rental_items"
+(
+	id bigserial primary key,
+	kind text not null check (kind in ('replica', 'mask', 'vest')),
+	name text not null,
+	serial_number text not null default '',
+	condition text not null default 'good' check (condition in ('good', 'worn', 'damaged')),
+	status text not null default 'available' check (status in ('available', 'maintenance', 'retired')),
+	created_at timestamptz not null default now(),
+	updated_at timestamptz
+);
+
+create unique index rental_items_serial_number on rental_items (serial_number) where serial_number <> '';
+
+select trigger_updated_at('"rental_items"');
+
+create table "rental_loans"
+(
+	id bigserial primary key,
+	item_id bigint not null references rental_items(id) on delete cascade,
+	player_id bigint not null references players(id) on delete cascade,
+	play_event_id bigint not null references play_events(id) on delete cascade,
+	checked_out_at timestamptz,
+	returned_at timestamptz,
+	damage_note text not null default '',
+	issued_by_id bigint references players(id) on delete set null,
+	created_at timestamptz not null default now(),
+	updated_at timestamptz,
+	check (returned_at is null or checked_out_at is not null)
+);
+
+create unique index rental_loans_open_item on rental_loans (item_id, play_event_id) where returned_at is null;
+
+select trigger_updated_at('"rental_loans"');
@@ -0,0 +1,5 @@
+alter table "play_events"
+drop column location_id;
+
+drop table "location_maps";
+drop table "locations";
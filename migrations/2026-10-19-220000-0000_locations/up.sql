@@ -0,0 +1,30 @@
+create table "locations"
+(
+	id bigserial primary key,
+	name text not null,
+	address text not null default '',
+	latitude double precision check (latitude between -90 and 90),
+	longitude double precision check (longitude between -180 and 180),
+	field_rules text not null default '',
+	parking_info text not null default '',
+	safe_zone_notes text not null default '',
+	created_at timestamptz not null default now(),
+	updated_at timestamptz,
+	check ((latitude is null) = (longitude is null))
+);
+
+select trigger_updated_at('"locations"');
+
+create table "location_maps"
+(
+	location_id bigint primary key references locations(id) on delete cascade,
+	content_type text not null check (content_type in ('image/png', 'image/jpeg', 'image/webp')),
+	data bytea not null,
+	created_at timestamptz not null default now(),
+	updated_at timestamptz
+);
+
+select trigger_updated_at('"location_maps"');
+
+alter table "play_events"
+add column location_id bigint references locations(id) on delete set null;
@@ -0,0 +1,4 @@
+drop table "round_scores";
+drop table "round_objectives";
+drop table "game_rounds";
+drop table "game_modes";
@@ -0,0 +1,50 @@
+create table "game_modes"
+(
+	id bigserial primary key,
+	name text not null unique,
+	description text not null default '',
+	objectives text not null default '',
+	created_at timestamptz not null default now(),
+	updated_at timestamptz
+);
+
+select trigger_updated_at('"game_modes"');
+
+create table "game_rounds"
+(
+	id bigserial primary key,
+	play_event_id bigint not null references play_events(id) on delete cascade,
+	game_mode_id bigint references game_modes(id) on delete set null,
+	number integer not null check (number > 0),
+	name text not null,
+	notes text not null default '',
+	created_at timestamptz not null default now(),
+	updated_at timestamptz,
+	unique (play_event_id, number)
+);
+
+select trigger_updated_at('"game_rounds"');
+
+create table "round_objectives"
+(
+	id bigserial primary key,
+	round_id bigint not null references game_rounds(id) on delete cascade,
+	name text not null,
+	faction_id bigint references factions(id) on delete set null,
+	created_at timestamptz not null default now(),
+	updated_at timestamptz
+);
+
+select trigger_updated_at('"round_objectives"');
+
+create table "round_scores"
+(
+	round_id bigint not null references game_rounds(id) on delete cascade,
+	faction_id bigint not null references factions(id) on delete cascade,
+	points integer not null check (points >= 0),
+	created_at timestamptz not null default now(),
+	updated_at timestamptz,
+	primary key (round_id, faction_id)
+);
+
+select trigger_updated_at('"round_scores"');
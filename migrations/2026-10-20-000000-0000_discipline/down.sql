@@ -0,0 +1,2 @@
+drop table "suspensions";
+drop table "incident_reports";
@@ -0,0 +1,28 @@
+create table "incident_reports"
+(
+	id bigserial primary key,
+	player_id bigint not null references players(id) on delete cascade,
+	play_event_id bigint references play_events(id) on delete set null,
+	reported_by_id bigint references players(id) on delete set null,
+	category text not null check (category in ('cheating', 'safety', 'misconduct')),
+	description text not null,
+	created_at timestamptz not null default now(),
+	updated_at timestamptz
+);
+
+select trigger_updated_at('"incident_reports"');
+
+create table "suspensions"
+(
+	id bigserial primary key,
+	player_id bigint not null references players(id) on delete cascade,
+	incident_report_id bigint references incident_reports(id) on delete set null,
+	issued_by_id bigint references players(id) on delete set null,
+	reason text not null,
+	starts_on date not null,
+	ends_on date check (ends_on >= starts_on),
+	created_at timestamptz not null default now(),
+	updated_at timestamptz
+);
+
+select trigger_updated_at('"suspensions"');
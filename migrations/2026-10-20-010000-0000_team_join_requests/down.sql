@@ -0,0 +1,2 @@
+drop table "team_memberships";
+drop table "team_join_requests";
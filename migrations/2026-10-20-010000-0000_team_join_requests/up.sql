@@ -0,0 +1,34 @@
+create table "team_join_requests"
+(
+	id bigserial primary key,
+	team_id bigint not null references teams(id) on delete cascade,
+	player_id bigint not null references players(id) on delete cascade,
+	message text not null default '',
+	status text not null default 'pending' check (status in ('pending', 'approved', 'rejected', 'withdrawn')),
+	decided_by_id bigint references players(id) on delete set null,
+	decided_at timestamptz,
+	created_at timestamptz not null default now(),
+	updated_at timestamptz
+);
+
+create unique index team_join_requests_pending on team_join_requests (team_id, player_id) where status = 'pending';
+
+select trigger_updated_at('"team_join_requests"');
+
+create table "team_memberships"
+(
+	id bigserial primary key,
+	player_id bigint not null references players(id) on delete cascade,
+	team_id bigint not null references teams(id) on delete cascade,
+	joined_on date not null,
+	left_on date check (left_on >= joined_on),
+	created_at timestamptz not null default now(),
+	updated_at timestamptz
+);
+
+create unique index team_memberships_open on team_memberships (player_id) where left_on is null;
+
+select trigger_updated_at('"team_memberships"');
+
+insert into team_memberships (player_id, team_id, joined_on)
+select id, team_id, created_at::date from players where team_id is not null;
@@ -0,0 +1,3 @@
+drop table "ride_bookings";
+drop table "ride_requests";
+drop table "ride_offers";
@@ -0,0 +1,44 @@
+create table "ride_offers"
+(
+	id bigserial primary key,
+	play_event_id bigint not null references play_events(id) on delete cascade,
+	driver_id bigint not null references players(id) on delete cascade,
+	area text not null,
+	start_location text not null,
+	seats integer not null check (seats > 0),
+	departs_at timestamptz not null,
+	notes text not null default '',
+	created_at timestamptz not null default now(),
+	updated_at timestamptz,
+	unique (play_event_id, driver_id)
+);
+
+select trigger_updated_at('"ride_offers"');
+
+create table "ride_requests"
+(
+	id bigserial primary key,
+	play_event_id bigint not null references play_events(id) on delete cascade,
+	passenger_id bigint not null references players(id) on delete cascade,
+	area text not null,
+	notes text not null default '',
+	created_at timestamptz not null default now(),
+	updated_at timestamptz,
+	unique (play_event_id, passenger_id)
+);
+
+select trigger_updated_at('"ride_requests"');
+
+create table "ride_bookings"
+(
+	id bigserial primary key,
+	ride_offer_id bigint not null references ride_offers(id) on delete cascade,
+	passenger_id bigint not null references players(id) on delete cascade,
+	status text not null default 'requested' check (status in ('requested', 'confirmed', 'declined', 'cancelled')),
+	driver_seen boolean not null default false,
+	created_at timestamptz not null default now(),
+	updated_at timestamptz,
+	unique (ride_offer_id, passenger_id)
+);
+
+select trigger_updated_at('"ride_bookings"');
@@ -0,0 +1,7 @@
+drop table "notification_settings";
+drop table "notification_preferences";
+drop table "notification_deliveries";
+drop table "notifications";
+
+alter table "announcements"
+drop column notified_at;
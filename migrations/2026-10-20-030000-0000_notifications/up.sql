@@ -0,0 +1,64 @@
+create table "notifications"
+(
+	id bigserial primary key,
+	player_id bigint not null references players(id) on delete cascade,
+	kind text not null check (kind in ('event', 'announcement', 'team_join_request', 'waitlist_promotion', 'carpool')),
+	title text not null,
+	body text not null default '',
+	link text not null default '',
+	read_at timestamptz,
+	created_at timestamptz not null default now(),
+	updated_at timestamptz
+);
+
+create index notifications_unread on notifications (player_id) where read_at is null;
+
+select trigger_updated_at('"notifications"');
+
+create table "notification_deliveries"
+(
+	id bigserial primary key,
+	notification_id bigint not null references notifications(id) on delete cascade,
+	channel text not null check (channel in ('email', 'webhook')),
+	status text not null default 'pending' check (status in ('pending', 'sent', 'failed')),
+	attempts integer not null default 0,
+	next_attempt_at timestamptz not null default now(),
+	last_error text,
+	sent_at timestamptz,
+	created_at timestamptz not null default now(),
+	updated_at timestamptz
+);
+
+create index notification_deliveries_due on notification_deliveries (next_attempt_at) where status = 'pending';
+
+select trigger_updated_at('"notification_deliveries"');
+
+create table "notification_preferences"
+(
+	id bigserial primary key,
+	player_id bigint not null references players(id) on delete cascade,
+	kind text not null check (kind in ('event', 'announcement', 'team_join_request', 'waitlist_promotion', 'carpool')),
+	email boolean not null default true,
+	webhook boolean not null default false,
+	created_at timestamptz not null default now(),
+	updated_at timestamptz,
+	unique (player_id, kind)
+);
+
+select trigger_updated_at('"notification_preferences"');
+
+create table "notification_settings"
+(
+	id bigserial primary key,
+	player_id bigint not null unique references players(id) on delete cascade,
+	webhook_url text not null default '',
+	created_at timestamptz not null default now(),
+	updated_at timestamptz
+);
+
+select trigger_updated_at('"notification_settings"');
+
+alter table "announcements"
+add column notified_at timestamptz;
+
+update "announcements" set notified_at = now() where publish_at <= now();
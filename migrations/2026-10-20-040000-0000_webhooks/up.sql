@@ -0,0 +1,43 @@
+create table "webhook_endpoints"
+(
+	id bigserial primary key,
+	name text not null,
+	url text not null,
+	secret text not null,
+	active boolean not null default true,
+	created_at timestamptz not null default now(),
+	updated_at timestamptz
+);
+
+select trigger_updated_at('"webhook_endpoints"');
+
+create table "webhook_subscriptions"
+(
+	endpoint_id bigint not null references webhook_endpoints(id) on delete cascade,
+	event_type text not null check (event_type in ('event.published', 'event.registration_opened', 'event.cancelled', 'announcement.published')),
+	created_at timestamptz not null default now(),
+	updated_at timestamptz,
+	primary key (endpoint_id, event_type)
+);
+
+select trigger_updated_at('"webhook_subscriptions"');
+
+create table "webhook_deliveries"
+(
+	id bigserial primary key,
+	endpoint_id bigint not null references webhook_endpoints(id) on delete cascade,
+	event_type text not null,
+	payload text not null,
+	status text not null default 'pending' check (status in ('pending', 'sent', 'failed')),
+	attempts integer not null default 0,
+	next_attempt_at timestamptz not null default now(),
+	last_error text,
+	sent_at timestamptz,
+	created_at timestamptz not null default now(),
+	updated_at timestamptz
+);
+
+create index webhook_deliveries_due on webhook_deliveries (next_attempt_at) where status = 'pending';
+create index webhook_deliveries_endpoint on webhook_deliveries (endpoint_id, created_at);
+
+select trigger_updated_at('"webhook_deliveries"');
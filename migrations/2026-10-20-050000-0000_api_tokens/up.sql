@@ -0,0 +1,14 @@
+create table "api_tokens"
+(
+	id bigserial primary key,
+	player_id bigint not null references players(id) on delete cascade,
+	name text not null,
+	token_hash text not null unique,
+	last_used_at timestamptz,
+	created_at timestamptz not null default now(),
+	updated_at timestamptz
+);
+
+create index api_tokens_player on api_tokens (player_id);
+
+select trigger_updated_at('"api_tokens"');
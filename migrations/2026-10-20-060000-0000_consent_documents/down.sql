@@ -0,0 +1 @@
+drop table "parental_consent_documents";
@@ -0,0 +1,10 @@
+create table "parental_consent_documents"
+(
+	consent_id bigint primary key references parental_consents(id) on delete cascade,
+	content_type text not null check (content_type in ('application/pdf', 'image/png', 'image/jpeg')),
+	data bytea not null,
+	created_at timestamptz not null default now(),
+	updated_at timestamptz
+);
+
+select trigger_updated_at('"parental_consent_documents"');
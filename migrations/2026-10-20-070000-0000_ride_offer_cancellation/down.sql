@@ -0,0 +1,2 @@
+alter table "ride_offers"
+drop column cancelled_at;
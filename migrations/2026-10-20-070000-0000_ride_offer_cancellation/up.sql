@@ -0,0 +1,2 @@
+alter table "ride_offers"
+add column cancelled_at timestamptz;
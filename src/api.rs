@@ -0,0 +1,434 @@
+use axum::{
+    extract::{FromRequestParts, Path, Query},
+    http::{header, request::Parts, StatusCode},
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Extension, Json, Router,
+};
+use crypto_hashes::sha2::{Digest, Sha256};
+use leptos::logging::warn;
+use serde::{Deserialize, Serialize};
+
+use crate::app::AppError;
+use crate::auth::{visible_players_for, AuthError};
+use crate::database::{self, models, DatabaseError, DieselPool, PageWindow};
+use crate::domain::{Participant, Platoon, PlayEvent, Player, RegistrationStatus, Team};
+
+const TOKEN_PREFIX: &str = "asm_";
+const TOKEN_LENGTH: usize = 40;
+const DEFAULT_PER_PAGE: usize = 50;
+const MAX_PER_PAGE: usize = 200;
+
+pub fn generate_api_token() -> String {
+    use rand::{distributions::Alphanumeric, Rng};
+
+    let token: String = rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(TOKEN_LENGTH)
+        .map(char::from)
+        .collect();
+
+    format!("{}{}", TOKEN_PREFIX, token)
+}
+
+/// Tokens are random enough that a plain SHA-256 is sufficient, unlike passwords.
+pub fn hash_api_token(token: &str) -> String {
+    format!("{:x}", Sha256::digest(token.as_bytes()))
+}
+
+/// The versioned REST API. Every request needs an `Authorization: Bearer <token>` header and
+/// is answered with the same visibility rules as the pages of the token's owner.
+pub fn router<S>(pool: DieselPool) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    let v1 = Router::new()
+        .route("/players", get(list_players))
+        .route("/players/{id}", get(show_player))
+        .route("/teams", get(list_teams))
+        .route("/teams/{id}", get(show_team))
+        .route("/teams/{id}/players", get(list_team_players))
+        .route("/platoons", get(list_platoons))
+        .route("/platoons/{id}", get(show_platoon))
+        .route("/events", get(list_events))
+        .route("/events/{id}", get(show_event))
+        .route("/events/{id}/participants", get(list_participants))
+        .route("/events/{id}/registration", post(register).delete(withdraw));
+
+    Router::new()
+        .nest("/api/v1", v1)
+        .layer(Extension(pool))
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ApiError {
+    #[error("missing or invalid api token")]
+    Unauthorized,
+    #[error("not allowed")]
+    Forbidden,
+    #[error("the current legal documents have to be accepted first")]
+    AcceptancePending,
+    #[error("invalid {0}")]
+    InvalidInput(String),
+    #[error(transparent)]
+    Database(#[from] DatabaseError),
+    #[error("internal error")]
+    Internal,
+}
+
+impl ApiError {
+    pub fn status(&self) -> StatusCode {
+        match self {
+            ApiError::Unauthorized => StatusCode::UNAUTHORIZED,
+            ApiError::Forbidden | ApiError::AcceptancePending => StatusCode::FORBIDDEN,
+            ApiError::InvalidInput(_) => StatusCode::BAD_REQUEST,
+            ApiError::Database(error) => match error {
+                DatabaseError::EntityNotFound => StatusCode::NOT_FOUND,
+                DatabaseError::CreateUserEmailExists(_)
+                | DatabaseError::CreateUserTagNameExists(_)
+                | DatabaseError::AlreadyRegistered
+                | DatabaseError::AlreadyBooked
+                | DatabaseError::AlreadyReserved
+                | DatabaseError::AlreadyTeamMember
+                | DatabaseError::JoinRequestPending
+                | DatabaseError::JoinRequestDecided
+                | DatabaseError::RideAlreadyOffered
+                | DatabaseError::RideAlreadyRequested
+                | DatabaseError::SeatAlreadyBooked => StatusCode::CONFLICT,
+                DatabaseError::RegistrationClosed
+                | DatabaseError::NotRegistered
+                | DatabaseError::PlayerInactive
+                | DatabaseError::NotEligible(_)
+                | DatabaseError::RentalUnavailable
+                | DatabaseError::NotTeamMember
+                | DatabaseError::RideFull
+                | DatabaseError::BookingClosed => StatusCode::UNPROCESSABLE_ENTITY,
+                DatabaseError::Diesel => StatusCode::INTERNAL_SERVER_ERROR,
+            },
+            ApiError::Internal => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
+impl From<AppError> for ApiError {
+    fn from(value: AppError) -> Self {
+        match value {
+            AppError::Database(error) => ApiError::Database(error),
+            AppError::AuthError(AuthError::Forbidden) => ApiError::Forbidden,
+            AppError::AuthError(AuthError::AcceptancePending) => ApiError::AcceptancePending,
+            AppError::AuthError(AuthError::NotLoggedIn | AuthError::InvalidLogin) => ApiError::Unauthorized,
+            AppError::InvalidInput(field) => ApiError::InvalidInput(field),
+            AppError::AuthError(AuthError::Backend) | AppError::LeptosError(_) | AppError::MissingContext => ApiError::Internal,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct ErrorBody {
+    error: String,
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.status(), Json(ErrorBody { error: self.to_string() })).into_response()
+    }
+}
+
+/// The player owning the bearer token of the request, once they accepted the current legal documents.
+pub struct ApiPlayer(pub models::Player);
+
+impl<S> FromRequestParts<S> for ApiPlayer
+where
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let Extension(pool) = Extension::<DieselPool>::from_request_parts(parts, state)
+            .await
+            .map_err(|_| ApiError::Internal)?;
+
+        let token = parts
+            .headers
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .ok_or(ApiError::Unauthorized)?;
+
+        let player = database::find_player_for_api_token(&hash_api_token(token.trim()), &pool)?
+            .ok_or(ApiError::Unauthorized)?;
+
+        if !database::get_pending_legal_documents(player.id, &pool)?.is_empty() {
+            return Err(ApiError::AcceptancePending);
+        }
+
+        Ok(ApiPlayer(player))
+    }
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct Pagination {
+    page: Option<usize>,
+    per_page: Option<usize>,
+}
+
+#[derive(Serialize)]
+pub struct Page<T> {
+    data: Vec<T>,
+    page: usize,
+    per_page: usize,
+    total: usize,
+}
+
+impl Pagination {
+    /// Pages start at 1, `per_page` defaults to 50 and is capped at 200.
+    fn window(self) -> Result<PageWindow, ApiError> {
+        let page = self.page.unwrap_or(1);
+        let per_page = self.per_page.unwrap_or(DEFAULT_PER_PAGE);
+
+        if page == 0 {
+            return Err(ApiError::InvalidInput("page".to_string()));
+        }
+
+        if per_page == 0 || per_page > MAX_PER_PAGE {
+            return Err(ApiError::InvalidInput("per_page".to_string()));
+        }
+
+        let offset = (page - 1).saturating_mul(per_page);
+
+        Ok(PageWindow {
+            limit: per_page as i64,
+            offset: i64::try_from(offset).unwrap_or(i64::MAX),
+        })
+    }
+
+    /// Wraps the rows loaded for [`Pagination::window`] with the total of the whole listing.
+    fn page<T, U: Into<T>>(self, (rows, total): (Vec<U>, i64)) -> Page<T> {
+        Page {
+            data: rows.into_iter().map(Into::into).collect(),
+            page: self.page.unwrap_or(1),
+            per_page: self.per_page.unwrap_or(DEFAULT_PER_PAGE),
+            total: total as usize,
+        }
+    }
+}
+
+async fn list_players(
+    ApiPlayer(viewer): ApiPlayer,
+    Extension(pool): Extension<DieselPool>,
+    Query(pagination): Query<Pagination>,
+) -> Result<Json<Page<Player>>, ApiError> {
+    let (players, total) = database::get_players_page(pagination.window()?, &pool)?;
+    let players = visible_players_for(Some(&viewer), players, &pool)?;
+
+    Ok(Json(pagination.page((players, total))))
+}
+
+async fn show_player(
+    ApiPlayer(viewer): ApiPlayer,
+    Extension(pool): Extension<DieselPool>,
+    Path(id): Path<i64>,
+) -> Result<Json<Player>, ApiError> {
+    let player = database::find_player_for_id(id, &pool)?
+        .ok_or(DatabaseError::EntityNotFound)?;
+
+    Ok(Json(visible_players_for(Some(&viewer), vec![player], &pool)?.remove(0)))
+}
+
+async fn list_teams(
+    _: ApiPlayer,
+    Extension(pool): Extension<DieselPool>,
+    Query(pagination): Query<Pagination>,
+) -> Result<Json<Page<Team>>, ApiError> {
+    Ok(Json(pagination.page(database::get_teams_page(pagination.window()?, &pool)?)))
+}
+
+async fn show_team(
+    _: ApiPlayer,
+    Extension(pool): Extension<DieselPool>,
+    Path(id): Path<i64>,
+) -> Result<Json<Team>, ApiError> {
+    let team = database::find_team_for_id(id, &pool)?
+        .ok_or(DatabaseError::EntityNotFound)?;
+
+    Ok(Json(team.into()))
+}
+
+async fn list_team_players(
+    ApiPlayer(viewer): ApiPlayer,
+    Extension(pool): Extension<DieselPool>,
+    Path(id): Path<i64>,
+    Query(pagination): Query<Pagination>,
+) -> Result<Json<Page<Player>>, ApiError> {
+    database::find_team_for_id(id, &pool)?
+        .ok_or(DatabaseError::EntityNotFound)?;
+
+    let (players, total) = database::get_players_for_team_page(id, pagination.window()?, &pool)?;
+    let players = visible_players_for(Some(&viewer), players, &pool)?;
+
+    Ok(Json(pagination.page((players, total))))
+}
+
+async fn list_platoons(
+    _: ApiPlayer,
+    Extension(pool): Extension<DieselPool>,
+    Query(pagination): Query<Pagination>,
+) -> Result<Json<Page<Platoon>>, ApiError> {
+    Ok(Json(pagination.page(database::get_platoons_page(pagination.window()?, &pool)?)))
+}
+
+async fn show_platoon(
+    _: ApiPlayer,
+    Extension(pool): Extension<DieselPool>,
+    Path(id): Path<i64>,
+) -> Result<Json<Platoon>, ApiError> {
+    let platoon = database::find_platoon_for_id(id, &pool)?
+        .ok_or(DatabaseError::EntityNotFound)?;
+
+    Ok(Json(platoon.into()))
+}
+
+async fn list_events(
+    _: ApiPlayer,
+    Extension(pool): Extension<DieselPool>,
+    Query(pagination): Query<Pagination>,
+) -> Result<Json<Page<PlayEvent>>, ApiError> {
+    Ok(Json(pagination.page(database::get_play_events_page(pagination.window()?, &pool)?)))
+}
+
+async fn show_event(
+    _: ApiPlayer,
+    Extension(pool): Extension<DieselPool>,
+    Path(id): Path<i64>,
+) -> Result<Json<PlayEvent>, ApiError> {
+    let play_event = database::find_play_event_for_id(id, &pool)?
+        .ok_or(DatabaseError::EntityNotFound)?;
+
+    Ok(Json(play_event.into()))
+}
+
+async fn list_participants(
+    _: ApiPlayer,
+    Extension(pool): Extension<DieselPool>,
+    Path(id): Path<i64>,
+    Query(pagination): Query<Pagination>,
+) -> Result<Json<Page<Participant>>, ApiError> {
+    database::find_play_event_for_id(id, &pool)?
+        .ok_or(DatabaseError::EntityNotFound)?;
+
+    Ok(Json(pagination.page(database::get_participants_for_event_page(id, pagination.window()?, &pool)?)))
+}
+
+#[derive(Serialize)]
+struct RegistrationBody {
+    status: RegistrationStatus,
+}
+
+/// Registers the token's owner, who ends up on the waitlist when the event is full.
+async fn register(
+    ApiPlayer(player): ApiPlayer,
+    Extension(pool): Extension<DieselPool>,
+    Path(id): Path<i64>,
+) -> Result<(StatusCode, Json<RegistrationBody>), ApiError> {
+    let status = database::register_player_for_event(id, player.id, player.id, &pool)?;
+
+    Ok((StatusCode::CREATED, Json(RegistrationBody { status })))
+}
+
+async fn withdraw(
+    ApiPlayer(player): ApiPlayer,
+    Extension(pool): Extension<DieselPool>,
+    Path(id): Path<i64>,
+) -> Result<StatusCode, ApiError> {
+    use crate::notification::{notify_promoted, notify_ride_cancelled};
+
+    let withdrawal = database::withdraw_player_from_event(id, player.id, &pool)?;
+
+    // the withdrawal is already committed, a failed notification must not report it as failed
+    let notified = notify_ride_cancelled(id, &player.tag_name, &withdrawal.stranded_passenger_ids, &pool)
+        .and_then(|_| notify_promoted(id, &withdrawal.promoted_ids, &pool));
+
+    if let Err(e) = notified {
+        warn!("failed to notify about the withdrawal from event {}: {}", id, e);
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn window(page: Option<usize>, per_page: Option<usize>) -> Result<PageWindow, ApiError> {
+        Pagination { page, per_page }.window()
+    }
+
+    #[test]
+    fn pagination_defaults_to_the_first_fifty_rows() {
+        assert_eq!(window(None, None).unwrap(), PageWindow { limit: 50, offset: 0 });
+
+        let page: Page<i64> = Pagination { page: None, per_page: None }.page((vec![1, 2], 120));
+        assert_eq!((page.page, page.per_page, page.total), (1, DEFAULT_PER_PAGE, 120));
+    }
+
+    #[test]
+    fn pagination_skips_the_previous_pages() {
+        assert_eq!(window(Some(3), Some(20)).unwrap(), PageWindow { limit: 20, offset: 40 });
+        assert_eq!(window(Some(1), Some(MAX_PER_PAGE)).unwrap(), PageWindow { limit: 200, offset: 0 });
+
+        let page: Page<i64> = Pagination { page: Some(4), per_page: Some(20) }.page((Vec::<i64>::new(), 55));
+        assert!(page.data.is_empty());
+        assert_eq!((page.page, page.per_page, page.total), (4, 20, 55));
+    }
+
+    #[test]
+    fn pagination_far_past_the_end_does_not_overflow() {
+        assert_eq!(window(Some(usize::MAX), Some(MAX_PER_PAGE)).unwrap().offset, i64::MAX);
+    }
+
+    #[test]
+    fn pagination_rejects_page_zero_and_out_of_range_page_sizes() {
+        assert!(matches!(window(Some(0), None), Err(ApiError::InvalidInput(field)) if field == "page"));
+        assert!(matches!(window(None, Some(0)), Err(ApiError::InvalidInput(field)) if field == "per_page"));
+        assert!(matches!(
+            window(None, Some(MAX_PER_PAGE + 1)),
+            Err(ApiError::InvalidInput(field)) if field == "per_page"
+        ));
+    }
+
+    #[test]
+    fn database_errors_map_to_http_statuses() {
+        let cases = [
+            (DatabaseError::EntityNotFound, StatusCode::NOT_FOUND),
+            (DatabaseError::AlreadyRegistered, StatusCode::CONFLICT),
+            (DatabaseError::CreateUserEmailExists("a@example.com".to_string()), StatusCode::CONFLICT),
+            (DatabaseError::SeatAlreadyBooked, StatusCode::CONFLICT),
+            (DatabaseError::RegistrationClosed, StatusCode::UNPROCESSABLE_ENTITY),
+            (DatabaseError::BookingClosed, StatusCode::UNPROCESSABLE_ENTITY),
+            (DatabaseError::NotTeamMember, StatusCode::UNPROCESSABLE_ENTITY),
+            (DatabaseError::Diesel, StatusCode::INTERNAL_SERVER_ERROR),
+        ];
+
+        for (error, status) in cases {
+            let description = error.to_string();
+            assert_eq!(ApiError::from(error).status(), status, "{}", description);
+        }
+    }
+
+    #[test]
+    fn app_errors_map_to_http_statuses() {
+        let cases = [
+            (AppError::AuthError(AuthError::NotLoggedIn), StatusCode::UNAUTHORIZED),
+            (AppError::AuthError(AuthError::Forbidden), StatusCode::FORBIDDEN),
+            (AppError::AuthError(AuthError::AcceptancePending), StatusCode::FORBIDDEN),
+            (AppError::InvalidInput("name".to_string()), StatusCode::BAD_REQUEST),
+            (AppError::Database(DatabaseError::EntityNotFound), StatusCode::NOT_FOUND),
+            (AppError::MissingContext, StatusCode::INTERNAL_SERVER_ERROR),
+        ];
+
+        for (error, status) in cases {
+            let description = error.to_string();
+            assert_eq!(ApiError::from(error).status(), status, "{}", description);
+        }
+    }
+}
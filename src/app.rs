@@ -8,7 +8,7 @@ use leptos_router::{
 };
 use serde::{Deserialize, Serialize};
 
-use crate::components::{auth::*, play_event::*, player::*, team::*};
+use crate::components::{announcement::*, api_token::*, attendance::*, auth::*, bank_import::*, check_in::*, faction::*, ledger::*, legal::*, location::*, notification::*, play_event::*, player::*, rental::*, scoreboard::*, scoring::*, survey::*, team::*, webhook::*};
 use crate::{auth::AuthError, database::DatabaseError};
 
 pub fn shell(options: LeptosOptions) -> impl IntoView {
@@ -36,6 +36,7 @@ pub enum AppError {
     LeptosError(ServerFnErrorErr),
     Database(DatabaseError),
     AuthError(AuthError),
+    InvalidInput(String),
     MissingContext,
 }
 
@@ -80,6 +81,8 @@ pub fn App() -> impl IntoView {
                 <Routes fallback=|| "Page not found.".into_view()>
                     <Route path=path!("/login") view=Login/>
                     <Route path=path!("/register") view=Register/>
+                    <Route path=path!("/accept") view=LegalAcceptancePage/>
+                    <Route path=path!("/scoreboard/:id") view=ScoreboardScreen/>
                     <ParentRoute path=path!("/") view=Base>
                         <Route path=path!("") view=LandingPage/>
                         <ParentRoute path=path!("/players") view=Players>
@@ -96,8 +99,57 @@ pub fn App() -> impl IntoView {
                         </ParentRoute>
                         <ParentRoute path=path!("/events") view=PlayEvents>
                             <Route path=path!("") view=PlayEventsTable/>
+                            <Route path=path!("new") view=PlayEventNew/>
+                            <Route path=path!(":id/edit") view=PlayEventEdit/>
+                            <Route path=path!(":id/factions") view=PlayEventFactions/>
+                            <Route path=path!(":id/checkin") view=PlayEventCheckIn/>
+                            <Route path=path!(":id/referee") view=RefereeControl/>
                             <Route path=path!(":id") view=PlayEventDetails/>
                         </ParentRoute>
+                        <ParentRoute path=path!("/locations") view=Locations>
+                            <Route path=path!("") view=LocationsTable/>
+                            <Route path=path!("new") view=LocationNew/>
+                            <Route path=path!(":id/edit") view=LocationEdit/>
+                            <Route path=path!(":id") view=LocationPage/>
+                        </ParentRoute>
+                        <ParentRoute path=path!("/announcements") view=Announcements>
+                            <Route path=path!("") view=AnnouncementsTable/>
+                            <Route path=path!("new") view=AnnouncementNew/>
+                            <Route path=path!(":id/edit") view=AnnouncementEdit/>
+                            <Route path=path!(":id/reads") view=AnnouncementReads/>
+                        </ParentRoute>
+                        <ParentRoute path=path!("/surveys") view=Surveys>
+                            <Route path=path!("") view=SurveysTable/>
+                            <Route path=path!("new") view=SurveyNew/>
+                            <Route path=path!(":id/edit") view=SurveyEdit/>
+                            <Route path=path!(":id") view=SurveyPage/>
+                        </ParentRoute>
+                        <ParentRoute path=path!("/legal") view=LegalDocuments>
+                            <Route path=path!("") view=LegalDocumentsTable/>
+                            <Route path=path!("new") view=LegalDocumentNew/>
+                            <Route path=path!(":id") view=LegalDocumentPage/>
+                        </ParentRoute>
+                        <ParentRoute path=path!("/ledger") view=Ledger>
+                            <Route path=path!("") view=TreasurerReportPage/>
+                            <Route path=path!("fees") view=FeesPage/>
+                            <Route path=path!("bank") view=BankImportPage/>
+                        </ParentRoute>
+                        <ParentRoute path=path!("/rentals") view=Rentals>
+                            <Route path=path!("") view=RentalInventory/>
+                            <Route path=path!("loans") view=RentalLoansPage/>
+                            <Route path=path!(":id") view=RentalItemPage/>
+                        </ParentRoute>
+                        <ParentRoute path=path!("/standings") view=Standings>
+                            <Route path=path!("") view=SeasonStandingsPage/>
+                            <Route path=path!("modes") view=GameModesPage/>
+                        </ParentRoute>
+                        <Route path=path!("/attendance") view=AttendanceReportPage/>
+                        <Route path=path!("/notifications") view=NotificationsPage/>
+                        <Route path=path!("/api-tokens") view=ApiTokensPage/>
+                        <ParentRoute path=path!("/webhooks") view=Webhooks>
+                            <Route path=path!("") view=WebhookEndpointsPage/>
+                            <Route path=path!(":id") view=WebhookEndpointPage/>
+                        </ParentRoute>
                     </ParentRoute>
                 </Routes>
             </main>
@@ -107,7 +159,45 @@ pub fn App() -> impl IntoView {
 
 #[component]
 fn Base() -> impl IntoView {
-    use leptos_router::components::{Outlet, A};
+    use leptos_router::components::{Outlet, Redirect, A};
+
+    let mark_read = ServerAction::<MarkAnnouncementRead>::new();
+    provide_context(mark_read);
+    let mark_notifications_read = ServerAction::<MarkNotificationsRead>::new();
+    provide_context(mark_notifications_read);
+
+    let unread_count = Resource::new(move || mark_read.version().get(), |_| get_unread_announcement_count());
+    let unread_notifications = Resource::new(
+        move || mark_notifications_read.version().get(),
+        |_| get_unread_notification_count(),
+    );
+    let can_write = Resource::new(|| {}, |_| can_write_announcements());
+    let treasurer = Resource::new(|| {}, |_| is_treasurer());
+    let rental_manager = Resource::new(|| {}, |_| can_manage_rentals());
+    let webhook_manager = Resource::new(|| {}, |_| can_manage_webhooks());
+    let pending_documents = Resource::new(|| {}, |_| has_pending_legal_documents());
+
+    let acceptance_redirect = move || pending_documents
+        .get()
+        .and_then(|result| result.ok())
+        .filter(|pending| *pending)
+        .map(|_| view! { <Redirect path="/accept"/> });
+
+    let unread_badge = move || unread_count
+        .get()
+        .and_then(|result| result.ok())
+        .filter(|count| *count > 0)
+        .map(|count| view! {
+            <span class="ml-1 px-1.5 py-0.5 rounded-full bg-red-600 text-white text-xs">{ count }</span>
+        });
+
+    let notification_badge = move || unread_notifications
+        .get()
+        .and_then(|result| result.ok())
+        .filter(|count| *count > 0)
+        .map(|count| view! {
+            <span class="ml-1 px-1.5 py-0.5 rounded-full bg-red-600 text-white text-xs">{ count }</span>
+        });
 
     view! {
         <header class="bg-blue-600 text-white shadow-md sticky top-0 z-50">
@@ -118,13 +208,44 @@ fn Base() -> impl IntoView {
 
                 <nav class="space-x-6 text-sm font-medium text-m">
                     <A href="/" attr:class="hover:underline">Home</A>
+                    <Transition>{ unread_badge }</Transition>
                     <A href="/events" attr:class="hover:underline">Events</A>
+                    <A href="/locations" attr:class="hover:underline">Orte</A>
                     <A href="/teams" attr:class="hover:underline">Teams</A>
                     <A href="/players" attr:class="hover:underline">Spieler</A>
+                    <A href="/attendance" attr:class="hover:underline">Anwesenheit</A>
+                    <A href="/standings" attr:class="hover:underline">Wertung</A>
+                    <A href="/surveys" attr:class="hover:underline">Umfragen</A>
+                    <A href="/legal" attr:class="hover:underline">Dokumente</A>
+                    <A href="/notifications" attr:class="hover:underline">Nachrichten</A>
+                    <Transition>{ notification_badge }</Transition>
+                    <A href="/api-tokens" attr:class="hover:underline">API</A>
+                    <Transition>
+                        <Show when=move || can_write.get().and_then(|result| result.ok()).unwrap_or(false)>
+                            <A href="/announcements" attr:class="hover:underline">Ankündigungen</A>
+                        </Show>
+                    </Transition>
+                    <Transition>
+                        <Show when=move || treasurer.get().and_then(|result| result.ok()).unwrap_or(false)>
+                            <A href="/ledger" attr:class="hover:underline">Kasse</A>
+                        </Show>
+                    </Transition>
+                    <Transition>
+                        <Show when=move || rental_manager.get().and_then(|result| result.ok()).unwrap_or(false)>
+                            <A href="/rentals" attr:class="hover:underline">Verleih</A>
+                        </Show>
+                    </Transition>
+                    <Transition>
+                        <Show when=move || webhook_manager.get().and_then(|result| result.ok()).unwrap_or(false)>
+                            <A href="/webhooks" attr:class="hover:underline">Webhooks</A>
+                        </Show>
+                    </Transition>
                 </nav>
             </div>
         </header>
 
+        <Transition>{ acceptance_redirect }</Transition>
+
         <main>
             <Outlet/>
         </main>
@@ -133,12 +254,8 @@ fn Base() -> impl IntoView {
 
 #[component]
 fn LandingPage() -> impl IntoView {
-    let count = RwSignal::new(0);
-    let on_click = move |_| *count.write() += 1;
 
     view! {
-        <h1>"Welcome to Leptos!"</h1>
-        <button on:click=on_click>"Click Me: " {count}</button>
-        <p>"Double count: " {move || count.get() * 2}</p>
+        <AnnouncementFeed/>
     }
 }
@@ -4,6 +4,8 @@ use axum_login::{AuthUser, AuthnBackend, UserId};
 use crypto_hashes::sha3::{Digest, Sha3_512};
 use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "ssr")]
+use crate::app::AppError;
 #[cfg(feature = "ssr")]
 use crate::database::{models::Player, DieselPool};
 
@@ -99,10 +101,152 @@ impl AuthUser for Player {
 #[cfg(feature = "ssr")]
 pub type AuthSession = axum_login::AuthSession<Backend>;
 
+/// The logged in player, who has to accept the current legal documents before doing anything else.
+#[cfg(feature = "ssr")]
+pub async fn current_player() -> Result<Player, AppError> {
+    use leptos::prelude::use_context;
+
+    use crate::database::get_pending_legal_documents;
+
+    let player = current_player_pending_acceptance().await?;
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    if !get_pending_legal_documents(player.id, &pool)?.is_empty() {
+        return Err(AppError::AuthError(AuthError::AcceptancePending));
+    }
+
+    Ok(player)
+}
+
+/// Only for reading and accepting the legal documents, everything else uses `current_player`.
+#[cfg(feature = "ssr")]
+pub async fn current_player_pending_acceptance() -> Result<Player, AppError> {
+    let auth_session: AuthSession = leptos_axum::extract().await?;
+
+    auth_session
+        .user
+        .ok_or(AppError::AuthError(AuthError::NotLoggedIn))
+}
+
+#[cfg(feature = "ssr")]
+pub async fn current_player_if_logged_in() -> Result<Option<Player>, AppError> {
+    let auth_session: AuthSession = leptos_axum::extract().await?;
+
+    Ok(auth_session.user)
+}
+
+#[cfg(feature = "ssr")]
+pub async fn require_organizer(play_event_id: i64, pool: &DieselPool) -> Result<Player, AppError> {
+    use crate::database::{find_play_event_for_id, DatabaseError};
+
+    let player = current_player().await?;
+    let play_event = find_play_event_for_id(play_event_id, pool)?
+        .ok_or(DatabaseError::EntityNotFound)?;
+
+    if play_event.organizer_id == Some(player.id) {
+        Ok(player)
+    } else {
+        Err(AppError::AuthError(AuthError::Forbidden))
+    }
+}
+
+#[cfg(feature = "ssr")]
+pub async fn require_admin() -> Result<Player, AppError> {
+    let player = current_player().await?;
+
+    if player.admin {
+        Ok(player)
+    } else {
+        Err(AppError::AuthError(AuthError::Forbidden))
+    }
+}
+
+/// Converts players for the current player and hides the private profile fields they may not see.
+/// Players always see their own profile and admins every profile, see `get_player_ids_led_by`
+/// for organizers and leaders.
+#[cfg(feature = "ssr")]
+pub async fn visible_players(
+    players: Vec<Player>,
+    pool: &DieselPool,
+) -> Result<Vec<crate::domain::Player>, AppError> {
+    let viewer = current_player_if_logged_in().await?;
+
+    Ok(visible_players_for(viewer.as_ref(), players, pool)?)
+}
+
+/// Same as [`visible_players`] for an explicitly given viewer, e.g. the owner of an API token.
+#[cfg(feature = "ssr")]
+pub fn visible_players_for(
+    viewer: Option<&Player>,
+    players: Vec<Player>,
+    pool: &DieselPool,
+) -> Result<Vec<crate::domain::Player>, crate::database::DatabaseError> {
+    use std::collections::HashSet;
+
+    use crate::database::get_player_ids_led_by;
+
+    let full_access: HashSet<i64> = match viewer {
+        Some(viewer) if viewer.admin => players.iter().map(|player| player.id).collect(),
+        Some(viewer) => {
+            let mut player_ids = get_player_ids_led_by(viewer.id, pool)?;
+            player_ids.insert(viewer.id);
+            player_ids
+        },
+        None => HashSet::new(),
+    };
+
+    Ok(players
+        .into_iter()
+        .map(|player| {
+            let full = full_access.contains(&player.id);
+            let player = crate::domain::Player::from(player);
+
+            if full {
+                player
+            } else {
+                player.without_private_fields()
+            }
+        })
+        .collect())
+}
+
+#[cfg(feature = "ssr")]
+pub async fn can_see_private_fields(player_id: i64, pool: &DieselPool) -> Result<bool, AppError> {
+    use crate::database::get_player_ids_led_by;
+
+    match current_player_if_logged_in().await? {
+        Some(viewer) if viewer.admin || viewer.id == player_id => Ok(true),
+        Some(viewer) => Ok(get_player_ids_led_by(viewer.id, pool)?.contains(&player_id)),
+        None => Ok(false),
+    }
+}
+
+/// Disciplinary records are kept from the player and only shown to admins, the contact person
+/// of the player's team and the leaders of their platoon. Event organizers are left out on purpose.
+#[cfg(feature = "ssr")]
+pub async fn can_see_disciplinary_records(player_id: i64, pool: &DieselPool) -> Result<bool, AppError> {
+    use crate::database::get_member_ids_led_by;
+
+    match current_player_if_logged_in().await? {
+        Some(viewer) if viewer.admin => Ok(true),
+        Some(viewer) if viewer.id == player_id => Ok(false),
+        Some(viewer) => Ok(get_member_ids_led_by(viewer.id, pool)?.contains(&player_id)),
+        None => Ok(false),
+    }
+}
+
 #[derive(Debug, Clone, thiserror::Error, Deserialize, Serialize, PartialEq, Eq)]
 pub enum AuthError {
     #[error("invalid login")]
     InvalidLogin,
     #[error("backend error")]
     Backend,
+    #[error("not logged in")]
+    NotLoggedIn,
+    #[error("not allowed")]
+    Forbidden,
+    #[error("legal documents not accepted")]
+    AcceptancePending,
 }
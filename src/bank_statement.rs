@@ -0,0 +1,463 @@
+use std::collections::HashMap;
+
+use chrono::NaiveDate;
+use roxmltree::{Document, Node};
+
+use crate::domain::{parse_cents, BankTransaction};
+
+// `%Y` also accepts two digits as a year of the first century, so the short form goes first
+const CSV_DATE_FORMATS: [&str; 3] = ["%d.%m.%y", "%d.%m.%Y", "%Y-%m-%d"];
+
+const CSV_DATE_COLUMNS: [&str; 4] = ["buchungstag", "buchungsdatum", "datum", "date"];
+const CSV_AMOUNT_COLUMNS: [&str; 4] = ["betrag", "betrag (eur)", "umsatz", "amount"];
+const CSV_COUNTERPARTY_COLUMNS: [&str; 6] = [
+    "name zahlungsbeteiligter",
+    "begünstigter/zahlungspflichtiger",
+    "beguenstigter/zahlungspflichtiger",
+    "zahlungspflichtige*r",
+    "auftraggeber",
+    "name",
+];
+const CSV_REFERENCE_COLUMNS: [&str; 3] = ["verwendungszweck", "reference", "purpose"];
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum StatementError {
+    #[error("Die Datei ist kein gültiges CAMT.053 XML")]
+    InvalidXml,
+    #[error("Die Datei ist keine lesbare CSV Datei")]
+    InvalidCsv,
+    #[error("Keine Kopfzeile mit Datum und Betrag gefunden")]
+    MissingColumns,
+    #[error("Ungültiger Eintrag in Zeile {0}")]
+    InvalidRow(usize),
+}
+
+/// Reads the incoming transfers of a CAMT.053 or CSV statement, the format is detected from the content.
+/// Outgoing transfers, pending entries and foreign currencies are left out.
+pub fn parse_statement(content: &[u8]) -> Result<Vec<BankTransaction>, StatementError> {
+    let content = decode(content);
+    let content = content.trim_start_matches('\u{feff}').trim_start();
+
+    let transactions = if content.starts_with('<') {
+        parse_camt(content)?
+    } else {
+        parse_csv(content)?
+    };
+
+    Ok(with_import_keys(transactions))
+}
+
+/// German bank exports are often Latin-1 instead of UTF-8.
+fn decode(content: &[u8]) -> String {
+    match std::str::from_utf8(content) {
+        Ok(content) => content.to_string(),
+        Err(_) => content.iter().map(|byte| *byte as char).collect(),
+    }
+}
+
+fn parse_camt(content: &str) -> Result<Vec<BankTransaction>, StatementError> {
+    let document = Document::parse(content).map_err(|_| StatementError::InvalidXml)?;
+
+    if !document.descendants().any(|node| node.has_tag_name("BkToCstmrStmt")) {
+        return Err(StatementError::InvalidXml);
+    }
+
+    Ok(document
+        .descendants()
+        .filter(|node| node.has_tag_name("Ntry"))
+        .filter_map(|entry| {
+            let credit = child_text(entry, &["CdtDbtInd"]) == Some("CRDT");
+            let booked = matches!(
+                child_text(entry, &["Sts", "Cd"]).or(child_text(entry, &["Sts"])),
+                Some("BOOK") | None
+            );
+            let amount = child(entry, &["Amt"])?;
+
+            if !credit || !booked || amount.attribute("Ccy").is_some_and(|currency| currency != "EUR") {
+                return None;
+            }
+
+            let booked_on = child_text(entry, &["BookgDt", "Dt"])
+                .or(child_text(entry, &["BookgDt", "DtTm"]).and_then(|value| value.get(..10)))
+                .and_then(|value| NaiveDate::parse_from_str(value, "%Y-%m-%d").ok())?;
+
+            let details = child(entry, &["NtryDtls", "TxDtls"]);
+            let counterparty = details
+                .and_then(|details| {
+                    child_text(details, &["RltdPties", "Dbtr", "Nm"])
+                        .or(child_text(details, &["RltdPties", "Dbtr", "Pty", "Nm"]))
+                })
+                .unwrap_or_default();
+            let reference = details
+                .and_then(|details| child(details, &["RmtInf"]))
+                .map(|remittance| {
+                    remittance
+                        .children()
+                        .filter(|node| node.has_tag_name("Ustrd"))
+                        .filter_map(|node| node.text())
+                        .map(str::trim)
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                })
+                .unwrap_or_default();
+
+            Some(BankTransaction {
+                booked_on,
+                amount_cents: parse_cents(amount.text()?)?,
+                counterparty: counterparty.trim().to_string(),
+                reference,
+                ..Default::default()
+            })
+        })
+        .collect())
+}
+
+/// Follows the path of element names below the node, ignoring namespaces.
+fn child<'a, 'input>(node: Node<'a, 'input>, path: &[&str]) -> Option<Node<'a, 'input>> {
+    path.iter().try_fold(node, |node, name| node.children().find(|child| child.has_tag_name(*name)))
+}
+
+fn child_text<'a>(node: Node<'a, '_>, path: &[&str]) -> Option<&'a str> {
+    child(node, path).and_then(|node| node.text()).map(str::trim)
+}
+
+fn parse_csv(content: &str) -> Result<Vec<BankTransaction>, StatementError> {
+    let first_line = content.lines().next().unwrap_or_default();
+    let delimiter = if first_line.matches(';').count() >= first_line.matches(',').count() { b';' } else { b',' };
+
+    let records: Vec<csv::StringRecord> = csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(content.as_bytes())
+        .records()
+        .collect::<Result<_, _>>()
+        .map_err(|_| StatementError::InvalidCsv)?;
+
+    // exports often start with account details before the actual header row
+    let (header_index, columns) = records
+        .iter()
+        .enumerate()
+        .find_map(|(index, record)| {
+            let headers: HashMap<String, usize> = record
+                .iter()
+                .enumerate()
+                .map(|(column, header)| (header.trim().to_lowercase(), column))
+                .collect();
+            let column = |aliases: &[&str]| aliases.iter().find_map(|alias| headers.get(*alias).copied());
+
+            Some((index, CsvColumns {
+                date: column(&CSV_DATE_COLUMNS)?,
+                amount: column(&CSV_AMOUNT_COLUMNS)?,
+                counterparty: column(&CSV_COUNTERPARTY_COLUMNS),
+                reference: column(&CSV_REFERENCE_COLUMNS),
+            }))
+        })
+        .ok_or(StatementError::MissingColumns)?;
+
+    let mut transactions = Vec::new();
+
+    for (index, record) in records.iter().enumerate().skip(header_index + 1) {
+        let line = index + 1;
+
+        if record.iter().all(|value| value.trim().is_empty()) {
+            continue;
+        }
+
+        let field = |column: Option<usize>| column
+            .and_then(|column| record.get(column))
+            .map(|value| value.trim().to_string())
+            .unwrap_or_default();
+
+        let amount = field(Some(columns.amount));
+        let amount = amount.trim_end_matches("EUR").trim_end_matches('€').trim();
+
+        // debits are outgoing transfers and never pay a charge
+        if amount.starts_with('-') {
+            continue;
+        }
+
+        let amount = amount.trim_start_matches('+');
+        let amount = if amount.contains(',') { amount.replace('.', "") } else { amount.to_string() };
+
+        let booked_on = CSV_DATE_FORMATS
+            .iter()
+            .find_map(|format| NaiveDate::parse_from_str(&field(Some(columns.date)), format).ok())
+            .ok_or(StatementError::InvalidRow(line))?;
+
+        transactions.push(BankTransaction {
+            booked_on,
+            amount_cents: parse_cents(&amount).ok_or(StatementError::InvalidRow(line))?,
+            counterparty: field(columns.counterparty),
+            reference: field(columns.reference),
+            ..Default::default()
+        });
+    }
+
+    Ok(transactions
+        .into_iter()
+        .filter(|transaction| transaction.amount_cents > 0)
+        .collect())
+}
+
+struct CsvColumns {
+    date: usize,
+    amount: usize,
+    counterparty: Option<usize>,
+    reference: Option<usize>,
+}
+
+/// Builds the key from the transfer's content. Identical transfers on the same day are
+/// told apart by their position, so re-importing an overlapping statement keeps them once.
+fn with_import_keys(transactions: Vec<BankTransaction>) -> Vec<BankTransaction> {
+    let mut seen: HashMap<String, usize> = HashMap::new();
+
+    transactions
+        .into_iter()
+        .map(|transaction| {
+            let key = format!(
+                "{}|{}|{}|{}",
+                transaction.booked_on, transaction.amount_cents, transaction.counterparty, transaction.reference,
+            );
+            let occurrence = seen.entry(key.clone()).or_default();
+            *occurrence += 1;
+
+            BankTransaction {
+                import_key: format!("{}|{}", key, occurrence),
+                ..transaction
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use super::*;
+    use crate::domain::{suggest_match, Charge, MatchReason, OpenCharge, Payer};
+
+    const CAMT_STATEMENT: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Document xmlns="urn:iso:std:iso:20022:tech:xsd:camt.053.001.08">
+  <BkToCstmrStmt>
+    <Stmt>
+      <Ntry>
+        <Amt Ccy="EUR">25.00</Amt>
+        <CdtDbtInd>CRDT</CdtDbtInd>
+        <Sts><Cd>BOOK</Cd></Sts>
+        <BookgDt><Dt>2026-03-02</Dt></BookgDt>
+        <NtryDtls><TxDtls>
+          <RltdPties><Dbtr><Nm>Max Mustermann</Nm></Dbtr></RltdPties>
+          <RmtInf><Ustrd>AS-12 Mitgliedsbeitrag</Ustrd></RmtInf>
+        </TxDtls></NtryDtls>
+      </Ntry>
+      <Ntry>
+        <Amt Ccy="EUR">45.00</Amt>
+        <CdtDbtInd>DBIT</CdtDbtInd>
+        <Sts><Cd>BOOK</Cd></Sts>
+        <BookgDt><Dt>2026-03-03</Dt></BookgDt>
+      </Ntry>
+      <Ntry>
+        <Amt Ccy="USD">30.00</Amt>
+        <CdtDbtInd>CRDT</CdtDbtInd>
+        <Sts><Cd>BOOK</Cd></Sts>
+        <BookgDt><Dt>2026-03-03</Dt></BookgDt>
+      </Ntry>
+      <Ntry>
+        <Amt Ccy="EUR">10.00</Amt>
+        <CdtDbtInd>CRDT</CdtDbtInd>
+        <Sts><Cd>PDNG</Cd></Sts>
+        <BookgDt><Dt>2026-03-03</Dt></BookgDt>
+      </Ntry>
+      <Ntry>
+        <Amt Ccy="EUR">7.5</Amt>
+        <CdtDbtInd>CRDT</CdtDbtInd>
+        <Sts>BOOK</Sts>
+        <BookgDt><DtTm>2026-03-04T09:15:00</DtTm></BookgDt>
+        <NtryDtls><TxDtls>
+          <RltdPties><Dbtr><Pty><Nm> Erika Musterfrau </Nm></Pty></Dbtr></RltdPties>
+          <RmtInf><Ustrd>Leihwaffe</Ustrd><Ustrd>Spieltag Maerz</Ustrd></RmtInf>
+        </TxDtls></NtryDtls>
+      </Ntry>
+    </Stmt>
+  </BkToCstmrStmt>
+</Document>
+"#;
+
+    const CSV_STATEMENT: &str = "\
+Kontonummer;DE12 3456 7890 1234 5678 90
+Zeitraum;01.03.2026 - 31.03.2026
+Buchungstag;Valutadatum;Name Zahlungsbeteiligter;Verwendungszweck;Betrag
+02.03.2026;02.03.2026;Max Mustermann;AS-12 Mitgliedsbeitrag;1.234,56
+03.03.2026;03.03.2026;Stromanbieter GmbH;Abschlag;-45,00
+04.03.26;04.03.26;Erika Musterfrau;Leihwaffe;+15,5 EUR
+;;;;
+";
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn transaction(amount_cents: i64, counterparty: &str, reference: &str) -> BankTransaction {
+        BankTransaction {
+            booked_on: date(2026, 3, 2),
+            amount_cents,
+            counterparty: counterparty.to_string(),
+            reference: reference.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn open_charge(id: i64, player_id: i64, outstanding_cents: i64, created_day: u32) -> OpenCharge {
+        OpenCharge {
+            charge: Charge {
+                id: Some(id),
+                player_id,
+                description: format!("Beitrag {}", id),
+                amount_cents: outstanding_cents,
+                created_at: Utc.with_ymd_and_hms(2026, 2, created_day, 12, 0, 0).unwrap(),
+                ..Default::default()
+            },
+            tag_name: format!("Spieler{}", player_id),
+            outstanding_cents,
+        }
+    }
+
+    fn payer(player_id: i64, tag_name: &str, real_name: Option<&str>) -> Payer {
+        Payer {
+            player_id,
+            tag_name: tag_name.to_string(),
+            real_name: real_name.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn camt_keeps_only_booked_euro_credits() {
+        let transactions = parse_statement(CAMT_STATEMENT.as_bytes()).unwrap();
+
+        assert_eq!(transactions.len(), 2);
+
+        assert_eq!(transactions[0].booked_on, date(2026, 3, 2));
+        assert_eq!(transactions[0].amount_cents, 2500);
+        assert_eq!(transactions[0].counterparty, "Max Mustermann");
+        assert_eq!(transactions[0].reference, "AS-12 Mitgliedsbeitrag");
+
+        assert_eq!(transactions[1].booked_on, date(2026, 3, 4));
+        assert_eq!(transactions[1].amount_cents, 750);
+        assert_eq!(transactions[1].counterparty, "Erika Musterfrau");
+        assert_eq!(transactions[1].reference, "Leihwaffe Spieltag Maerz");
+    }
+
+    #[test]
+    fn camt_needs_a_statement() {
+        assert_eq!(parse_statement(b"<Document><Foo/></Document>"), Err(StatementError::InvalidXml));
+        assert_eq!(parse_statement(b"<Document><BkToCstmrStmt>"), Err(StatementError::InvalidXml));
+    }
+
+    #[test]
+    fn csv_finds_the_header_below_the_account_details() {
+        let transactions = parse_statement(CSV_STATEMENT.as_bytes()).unwrap();
+
+        assert_eq!(transactions.len(), 2);
+
+        assert_eq!(transactions[0].booked_on, date(2026, 3, 2));
+        assert_eq!(transactions[0].amount_cents, 123456);
+        assert_eq!(transactions[0].counterparty, "Max Mustermann");
+        assert_eq!(transactions[0].reference, "AS-12 Mitgliedsbeitrag");
+
+        assert_eq!(transactions[1].booked_on, date(2026, 3, 4));
+        assert_eq!(transactions[1].amount_cents, 1550);
+        assert_eq!(transactions[1].counterparty, "Erika Musterfrau");
+    }
+
+    #[test]
+    fn csv_reads_comma_separated_exports_and_latin1() {
+        let transactions = parse_statement(b"date,amount,name,reference\n2026-03-02,25.00,Max Mustermann,AS-12\n").unwrap();
+
+        assert_eq!(transactions, vec![BankTransaction {
+            import_key: "2026-03-02|2500|Max Mustermann|AS-12|1".to_string(),
+            ..transaction(2500, "Max Mustermann", "AS-12")
+        }]);
+
+        let latin1 = b"Datum;Betrag;Beg\xfcnstigter/Zahlungspflichtiger\n02.03.2026;12,00;J\xfcrgen\n";
+        let transactions = parse_statement(latin1).unwrap();
+
+        assert_eq!(transactions[0].counterparty, "Jürgen");
+        assert_eq!(transactions[0].amount_cents, 1200);
+    }
+
+    #[test]
+    fn csv_reports_missing_columns_and_invalid_rows() {
+        assert_eq!(parse_statement(b"Name;Verwendungszweck\nMax;AS-12\n"), Err(StatementError::MissingColumns));
+        assert_eq!(
+            parse_statement(b"Datum;Betrag\n02.03.2026;12,00\nmorgen;12,00\n"),
+            Err(StatementError::InvalidRow(3)),
+        );
+        assert_eq!(
+            parse_statement(b"Datum;Betrag\n02.03.2026;zwoelf\n"),
+            Err(StatementError::InvalidRow(2)),
+        );
+    }
+
+    #[test]
+    fn import_keys_tell_identical_transfers_apart() {
+        let content = b"Datum;Betrag;Name\n02.03.2026;10,00;Max\n02.03.2026;10,00;Max\n03.03.2026;10,00;Max\n";
+        let transactions = parse_statement(content).unwrap();
+        let keys: Vec<&str> = transactions.iter().map(|transaction| transaction.import_key.as_str()).collect();
+
+        assert_eq!(keys, vec![
+            "2026-03-02|1000|Max||1",
+            "2026-03-02|1000|Max||2",
+            "2026-03-03|1000|Max||1",
+        ]);
+        assert_eq!(parse_statement(content).unwrap(), transactions);
+    }
+
+    #[test]
+    fn match_prefers_the_charge_reference() {
+        let open_charges = vec![open_charge(11, 1, 2500, 1), open_charge(12, 2, 2500, 2)];
+        let payers = vec![payer(1, "Maxe", Some("Max Mustermann")), payer(2, "Erika", None)];
+
+        let suggestion = suggest_match(&transaction(2500, "Max Mustermann", "as 12 danke"), &open_charges, &payers).unwrap();
+
+        assert_eq!((suggestion.player_id, suggestion.charge_id), (2, Some(12)));
+        assert_eq!(suggestion.reason, MatchReason::Reference);
+    }
+
+    #[test]
+    fn match_by_name_picks_the_charge_with_the_same_amount() {
+        let open_charges = vec![open_charge(11, 1, 1000, 1), open_charge(12, 1, 2500, 2), open_charge(13, 2, 2500, 3)];
+        let payers = vec![payer(1, "Maxe", Some("Max Mustermann")), payer(2, "Erika", Some("Erika Musterfrau"))];
+
+        let suggestion = suggest_match(&transaction(2500, "MUSTERMANN, MAX", "Beitrag"), &open_charges, &payers).unwrap();
+        assert_eq!((suggestion.player_id, suggestion.charge_id, suggestion.reason), (1, Some(12), MatchReason::Name));
+
+        let suggestion = suggest_match(&transaction(700, "Max Mustermann", ""), &open_charges, &payers).unwrap();
+        assert_eq!((suggestion.player_id, suggestion.charge_id), (1, Some(11)));
+
+        let suggestion = suggest_match(&transaction(700, "Bank", "fuer Erika Musterfrau"), &[], &payers).unwrap();
+        assert_eq!((suggestion.player_id, suggestion.charge_id, suggestion.reason), (2, None, MatchReason::Name));
+    }
+
+    #[test]
+    fn match_by_amount_only_when_it_is_unique() {
+        let open_charges = vec![open_charge(11, 1, 1000, 1), open_charge(12, 2, 2500, 2), open_charge(13, 3, 2500, 3)];
+
+        let suggestion = suggest_match(&transaction(1000, "Unbekannt", ""), &open_charges, &[]).unwrap();
+        assert_eq!((suggestion.player_id, suggestion.charge_id, suggestion.reason), (1, Some(11), MatchReason::Amount));
+
+        assert_eq!(suggest_match(&transaction(2500, "Unbekannt", ""), &open_charges, &[]), None);
+        assert_eq!(suggest_match(&transaction(999, "Unbekannt", ""), &open_charges, &[]), None);
+    }
+
+    #[test]
+    fn short_tag_names_do_not_match() {
+        let open_charges = vec![open_charge(11, 1, 1000, 1), open_charge(12, 2, 1000, 2)];
+        let payers = vec![payer(1, "Max", None), payer(2, "Erika", None)];
+
+        assert_eq!(suggest_match(&transaction(1000, "Bank", "Max Beitrag"), &open_charges, &payers), None);
+
+        let suggestion = suggest_match(&transaction(1000, "Bank", "Beitrag von erika"), &open_charges, &payers).unwrap();
+        assert_eq!(suggestion.player_id, 2);
+    }
+}
@@ -0,0 +1,199 @@
+use axum::{
+    extract::Path,
+    http::{header, StatusCode},
+    response::IntoResponse,
+    routing::get,
+    Extension, Router,
+};
+use chrono::{DateTime, Utc};
+
+use crate::auth::AuthSession;
+use crate::database::{self, DieselPool};
+use crate::domain::PlayEvent;
+
+const TOKEN_LENGTH: usize = 32;
+const LINE_LENGTH: usize = 75;
+
+pub fn generate_calendar_token() -> String {
+    use rand::{distributions::Alphanumeric, Rng};
+
+    rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(TOKEN_LENGTH)
+        .map(char::from)
+        .collect()
+}
+
+/// Routes serving the ICS feeds. Player feeds are addressed by their secret token,
+/// team and platoon feeds are public and single event downloads need a login.
+pub fn router<S>(pool: DieselPool) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    Router::new()
+        .route("/calendar/players/{token}", get(player_feed))
+        .route("/calendar/teams/{id}", get(team_feed))
+        .route("/calendar/platoons/{id}", get(platoon_feed))
+        .route("/calendar/events/{id}", get(play_event_download))
+        .layer(Extension(pool))
+}
+
+/// Renders the events as a VCALENDAR. Event times are wall-clock times, so they are written
+/// as floating times. The UID stays the same for an event and SEQUENCE grows with every change,
+/// which lets calendar clients replace updated and cancelled events instead of duplicating them.
+pub fn render_calendar(name: &str, events: &[PlayEvent], now: DateTime<Utc>) -> String {
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//AS-Manager//Events//DE".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+        "METHOD:PUBLISH".to_string(),
+        format!("X-WR-CALNAME:{}", escape_text(name)),
+    ];
+
+    for event in events {
+        let Some(id) = event.id else {
+            continue;
+        };
+
+        lines.extend([
+            "BEGIN:VEVENT".to_string(),
+            format!("UID:play-event-{}@as-manager", id),
+            format!("SEQUENCE:{}", event.sequence),
+            format!("DTSTAMP:{}", now.format("%Y%m%dT%H%M%SZ")),
+            format!("DTSTART:{}", format_floating(&event.starts_at)),
+            format!("DTEND:{}", format_floating(&event.ends_at)),
+            format!("SUMMARY:{}", escape_text(&event.name)),
+            format!("DESCRIPTION:{}", escape_text(&event.description)),
+            format!("STATUS:{}", if event.is_cancelled() { "CANCELLED" } else { "CONFIRMED" }),
+            "END:VEVENT".to_string(),
+        ]);
+    }
+
+    lines.push("END:VCALENDAR".to_string());
+
+    lines
+        .iter()
+        .map(|line| fold_line(line))
+        .collect::<Vec<_>>()
+        .join("")
+}
+
+fn format_floating(value: &DateTime<Utc>) -> String {
+    value.naive_utc().format("%Y%m%dT%H%M%S").to_string()
+}
+
+fn escape_text(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+/// Splits content lines after 75 octets without cutting through a UTF-8 character.
+fn fold_line(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len() + 2);
+    let mut length = 0;
+
+    for character in line.chars() {
+        if length + character.len_utf8() > LINE_LENGTH {
+            folded.push_str("\r\n ");
+            length = 1;
+        }
+
+        folded.push(character);
+        length += character.len_utf8();
+    }
+
+    folded.push_str("\r\n");
+    folded
+}
+
+fn calendar_response(body: String, filename: Option<String>) -> axum::response::Response {
+    let disposition = match filename {
+        Some(filename) => format!("attachment; filename=\"{}\"", filename),
+        None => "inline".to_string(),
+    };
+
+    (
+        [
+            (header::CONTENT_TYPE, "text/calendar; charset=utf-8".to_string()),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        body,
+    )
+        .into_response()
+}
+
+fn events_for_players(player_ids: Vec<i64>, pool: &DieselPool) -> Result<Vec<PlayEvent>, StatusCode> {
+    database::get_play_events_for_players(player_ids, pool)
+        .map(|events| events.into_iter().map(Into::into).collect())
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+}
+
+async fn player_feed(
+    Extension(pool): Extension<DieselPool>,
+    Path(token): Path<String>,
+) -> Result<axum::response::Response, StatusCode> {
+    let player_id = database::find_player_id_for_calendar_token(&token, &pool)
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    let events = events_for_players(vec![player_id], &pool)?;
+
+    Ok(calendar_response(render_calendar("Meine Events", &events, Utc::now()), None))
+}
+
+async fn team_feed(
+    Extension(pool): Extension<DieselPool>,
+    Path(id): Path<i64>,
+) -> Result<axum::response::Response, StatusCode> {
+    let team = database::find_team_for_id(id, &pool)
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+    let member_ids = database::get_players_for_team(id, &pool)
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .into_iter()
+        .map(|player| player.id)
+        .collect();
+
+    let events = events_for_players(member_ids, &pool)?;
+
+    Ok(calendar_response(render_calendar(&team.name, &events, Utc::now()), None))
+}
+
+async fn platoon_feed(
+    Extension(pool): Extension<DieselPool>,
+    Path(id): Path<i64>,
+) -> Result<axum::response::Response, StatusCode> {
+    let platoon = database::find_platoon_for_id(id, &pool)
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+    let member_ids = database::get_player_ids_for_platoon(id, &pool)
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let events = events_for_players(member_ids, &pool)?;
+
+    Ok(calendar_response(render_calendar(&platoon.name, &events, Utc::now()), None))
+}
+
+async fn play_event_download(
+    auth_session: AuthSession,
+    Extension(pool): Extension<DieselPool>,
+    Path(id): Path<i64>,
+) -> Result<axum::response::Response, StatusCode> {
+    if auth_session.user.is_none() {
+        return Err(StatusCode::UNAUTHORIZED);
+    }
+
+    let play_event: PlayEvent = database::find_play_event_for_id(id, &pool)
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?
+        .into();
+
+    let body = render_calendar(&play_event.name, std::slice::from_ref(&play_event), Utc::now());
+
+    Ok(calendar_response(body, Some(format!("event-{}.ics", id))))
+}
@@ -1,6 +1,27 @@
+pub mod announcement;
+pub mod api_token;
+pub mod attendance;
 pub mod auth;
+pub mod bank_import;
+pub mod calendar;
+pub mod carpool;
+pub mod check_in;
+pub mod consent;
+pub mod discipline;
+pub mod faction;
+pub mod gear;
+pub mod ledger;
+pub mod legal;
+pub mod location;
+pub mod membership;
+pub mod notification;
 pub mod play_event;
 pub mod player;
 pub mod protected;
+pub mod rental;
+pub mod scoreboard;
+pub mod scoring;
+pub mod survey;
 pub mod team;
 pub mod util;
+pub mod webhook;
@@ -0,0 +1,805 @@
+use leptos::prelude::*;
+use leptos::Params;
+use leptos_router::{components::A, hooks::use_params, params::Params};
+use serde::{Deserialize, Serialize};
+
+use crate::app::AppError;
+use crate::components::util::{datetime_input_value, format_datetime, BackButton};
+use crate::domain::{Announcement, AnnouncementFeedEntry, AnnouncementReader, AudienceOption};
+use crate::markdown::render_markdown;
+
+#[component]
+pub fn AnnouncementFeed() -> impl IntoView {
+
+    let mark_read = use_context::<ServerAction<MarkAnnouncementRead>>()
+        .unwrap_or_default();
+    let entries = Resource::new(move || mark_read.version().get(), |_| get_announcement_feed());
+
+    view! {
+        <div class="p-8 max-w-4xl mx-auto">
+            <h1 class="text-2xl font-semibold mb-6">
+                "Ankündigungen"
+            </h1>
+
+            <Suspense fallback=move || view! { <p>"Lade Daten..."</p> }>
+                {move || {
+                    entries.get().map(|result| match result {
+                        Ok(entries) if entries.is_empty() => view! {
+                            <p class="text-gray-700">"Keine aktuellen Ankündigungen"</p>
+                        }.into_any(),
+                        Ok(entries) => entries
+                            .into_iter()
+                            .map(|AnnouncementFeedEntry { announcement, read }| view! {
+                                <article class="border border-gray-200 rounded p-4 bg-white text-left mb-4">
+                                    <div class="flex items-center justify-between">
+                                        <h2 class="text-xl font-semibold">
+                                            { announcement.title }
+                                            <Show when=move || !read>
+                                                <span class="ml-2 px-2 py-0.5 rounded bg-blue-600 text-white text-xs align-middle">"Neu"</span>
+                                            </Show>
+                                        </h2>
+                                        <Show when=move || !read>
+                                            <ActionForm action=mark_read>
+                                                <input type="hidden" name="announcement_id" value=announcement.id/>
+                                                <button
+                                                    type="submit"
+                                                    class="px-4 py-2 rounded bg-gray-200 hover:bg-gray-300 text-gray-700"
+                                                >
+                                                    "Als gelesen markieren"
+                                                </button>
+                                            </ActionForm>
+                                        </Show>
+                                    </div>
+                                    <p class="text-sm text-gray-500 pb-2">
+                                        { format_datetime(&announcement.publish_at) } " · " { announcement.audience.to_string() }
+                                    </p>
+                                    <div class="prose max-w-none" inner_html=render_markdown(&announcement.content)></div>
+                                </article>
+                            })
+                            .collect_view()
+                            .into_any(),
+                        Err(e) => view! { <p>{ e.to_string() }</p> }.into_any(),
+                    })
+                }}
+            </Suspense>
+        </div>
+    }
+}
+
+#[component]
+pub fn Announcements() -> impl IntoView {
+    use crate::components::protected::Protected;
+    use leptos_router::components::Outlet;
+
+    view! {
+        <Protected>
+            <Outlet/>
+        </Protected>
+    }
+}
+
+#[component]
+pub fn AnnouncementsTable() -> impl IntoView {
+
+    let announcements = Resource::new(|| {}, |_| get_announcements());
+
+    view! {
+        <BackButton/>
+        <div class="p-8 max-w-4xl mx-auto">
+            <div class="flex items-center justify-between mb-6">
+                <h1 class="text-2xl font-semibold">
+                    "Ankündigungen"
+                </h1>
+                <A href="/announcements/new"
+                    attr:class="bg-blue-600 hover:bg-blue-700 text-white font-medium py-2 px-4 rounded transition"
+                >
+                    "Neue Ankündigung"
+                </A>
+            </div>
+
+            <Suspense fallback=move || view! { <p>"Lade Daten..."</p> }>
+                {
+                    move || {
+                        announcements.get().map(|result| match result {
+                            Ok(announcements) => {
+                                let now = chrono::Utc::now();
+
+                                view! {
+                                    <div class="overflow-x-auto">
+                                        <table class="min-w-full border border-gray-200 shadow-sm rounded-md bg-white">
+                                            <thead class="bg-gray-100 text-gray-700">
+                                                <tr>
+                                                    <th class="text-left py-2 px-4 border-b">Titel</th>
+                                                    <th class="text-left py-2 px-4 border-b">Status</th>
+                                                    <th class="text-left py-2 px-4 border-b">Zielgruppe</th>
+                                                    <th class="text-left py-2 px-4 border-b">Veröffentlichung</th>
+                                                    <th class="text-left py-2 px-4 border-b">Ablauf</th>
+                                                    <th class="text-left py-2 px-4 border-b">Gelesen</th>
+                                                </tr>
+                                            </thead>
+                                            <tbody>
+                                                {
+                                                    announcements.into_iter().map(|announcement: Announcement| {
+                                                        let status = announcement.status(now);
+
+                                                        view! {
+                                                            <tr class="hover:bg-gray-50">
+                                                                <th class="text-left py-2 px-4 border-b">
+                                                                    <A href=format!("/announcements/{}/edit", announcement.id.unwrap()) attr:class="hover:underline">{announcement.title}</A>
+                                                                </th>
+                                                                <th class="text-left py-2 px-4 border-b">
+                                                                    { status.to_string() }
+                                                                </th>
+                                                                <th class="text-left py-2 px-4 border-b">
+                                                                    { announcement.audience.to_string() }
+                                                                </th>
+                                                                <th class="text-left py-2 px-4 border-b">
+                                                                    { format_datetime(&announcement.publish_at) }
+                                                                </th>
+                                                                <th class="text-left py-2 px-4 border-b">
+                                                                    { announcement.expires_at.as_ref().map(format_datetime).unwrap_or("Kein Ablauf".to_string()) }
+                                                                </th>
+                                                                <th class="text-left py-2 px-4 border-b">
+                                                                    <A href=format!("/announcements/{}/reads", announcement.id.unwrap()) attr:class="hover:underline">"Lesestatus"</A>
+                                                                </th>
+                                                            </tr>
+                                                        }
+                                                    }).collect_view()
+                                                }
+                                            </tbody>
+                                        </table>
+                                    </div>
+                                }.into_any()
+                            },
+                            Err(e) => view! {
+                                <p>{ e.to_string() }</p>
+                            }.into_any(),
+                        })
+                    }
+                }
+            </Suspense>
+        </div>
+    }
+}
+
+#[component]
+pub fn AnnouncementNew() -> impl IntoView {
+
+    let create_new_announcement = ServerAction::<CreateNewAnnouncement>::new();
+
+    view! {
+        <div class="p-8 max-w-4xl mx-auto">
+            <div class="flex items-center justify-between mb-6">
+                <h1 class="text-2xl font-semibold">
+                    "Neue Ankündigung anlegen"
+                </h1>
+            </div>
+
+            <ActionForm action=create_new_announcement>
+                <AnnouncementFormFields form_name="create_new_announcement" announcement=Announcement::default()/>
+
+                <div class="flex justify-end gap-2 mt-6">
+                    <A href="/announcements"
+                        attr:class="px-4 py-2 rounded bg-gray-200 hover:bg-gray-300 text-gray-700"
+                    >
+                        "Abbrechen"
+                    </A>
+                    <button
+                        type="submit"
+                        class="px-4 py-2 rounded bg-blue-600 hover:bg-blue-700 text-white"
+                    >
+                        "Erstellen"
+                    </button>
+                </div>
+            </ActionForm>
+
+            {move || create_new_announcement.value().get().and_then(|result| result.err()).map(|e| view! {
+                <p class="text-red-600">{ e.to_string() }</p>
+            })}
+        </div>
+    }
+}
+
+#[component]
+pub fn AnnouncementEdit() -> impl IntoView {
+
+    let announcement_id = use_params::<AnnouncementIdParameter>();
+    let announcement = Resource::new(
+        move || announcement_id.read().clone(),
+        move |params_result| load_announcement_by_id(params_result.unwrap().id.unwrap()),
+    );
+
+    let save_announcement = ServerAction::<SaveAnnouncement>::new();
+    let delete_announcement = ServerAction::<DeleteAnnouncement>::new();
+
+    view! {
+        <div class="p-8 max-w-4xl mx-auto">
+
+            <Suspense fallback=move || view! { <p>"Lade Daten..."</p> }>
+                {
+                    move || {
+                        announcement.get().map(|result| match result {
+                            Ok(announcement) => view! {
+                                <div class="flex items-center justify-between mb-6">
+                                    <h1 class="text-2xl font-semibold">
+                                        "Ankündigung bearbeiten"
+                                    </h1>
+                                    <ActionForm action=delete_announcement>
+                                        <input type="hidden" name="id" value=announcement.id.unwrap()/>
+                                        <button
+                                            type="submit"
+                                            class="bg-red-600 hover:bg-red-700 text-white font-medium py-2 px-4 rounded transition"
+                                        >
+                                            "Löschen"
+                                        </button>
+                                    </ActionForm>
+                                </div>
+
+                                <ActionForm action=save_announcement>
+                                    <input
+                                        type="hidden"
+                                        name="announcement_form[id]"
+                                        value=announcement.id.unwrap()/>
+
+                                    <AnnouncementFormFields form_name="announcement_form" announcement=announcement.clone()/>
+
+                                    <div class="flex justify-end gap-2 mt-6">
+                                        <A href="/announcements"
+                                            attr:class="px-4 py-2 rounded bg-gray-200 hover:bg-gray-300 text-gray-700"
+                                        >
+                                            "Abbrechen"
+                                        </A>
+                                        <button
+                                            type="submit"
+                                            class="px-4 py-2 rounded bg-blue-600 hover:bg-blue-700 text-white"
+                                        >
+                                            "Speichern"
+                                        </button>
+                                    </div>
+                                </ActionForm>
+                            }.into_any(),
+                            Err(e) => view! {
+                                <p>{ e.to_string() }</p>
+                            }.into_any(),
+                        })
+                    }
+                }
+            </Suspense>
+
+            {move || {
+                let save_error = save_announcement.value().get().and_then(|result| result.err());
+                let delete_error = delete_announcement.value().get().and_then(|result| result.err());
+
+                save_error.or(delete_error).map(|e| view! {
+                    <p class="text-red-600">{ e.to_string() }</p>
+                })
+            }}
+        </div>
+    }
+}
+
+#[component]
+pub fn AnnouncementReads() -> impl IntoView {
+
+    let announcement_id = use_params::<AnnouncementIdParameter>();
+    let readers = Resource::new(
+        move || announcement_id.read().clone(),
+        move |params_result| get_announcement_readers(params_result.unwrap().id.unwrap()),
+    );
+
+    view! {
+        <BackButton/>
+        <div class="p-8 max-w-4xl mx-auto">
+            <div class="flex items-center justify-between mb-6">
+                <h1 class="text-2xl font-semibold">
+                    "Lesestatus"
+                </h1>
+            </div>
+
+            <Suspense fallback=move || view! { <p>"Lade Daten..."</p> }>
+                {move || {
+                    readers.get().map(|result| match result {
+                        Ok(readers) => view! { <AnnouncementReadersTable readers=readers/> }.into_any(),
+                        Err(e) => view! { <p>{ e.to_string() }</p> }.into_any(),
+                    })
+                }}
+            </Suspense>
+        </div>
+    }
+}
+
+#[component]
+fn AnnouncementReadersTable(readers: Vec<AnnouncementReader>) -> impl IntoView {
+    let total = readers.len();
+    let (read, unread): (Vec<AnnouncementReader>, Vec<AnnouncementReader>) = readers
+        .into_iter()
+        .partition(|reader| reader.read_at.is_some());
+
+    view! {
+        <h2 class="text-left text-xl font-semibold py-2">
+            "Gelesen: " { read.len() } " / " { total }
+        </h2>
+
+        <div class="overflow-x-auto">
+            <table class="min-w-full border border-gray-200 shadow-sm rounded-md bg-white">
+                <thead class="bg-gray-100 text-gray-700">
+                    <tr>
+                        <th class="text-left py-2 px-4 border-b">Spielername</th>
+                        <th class="text-left py-2 px-4 border-b">Gelesen am</th>
+                    </tr>
+                </thead>
+                <tbody>
+                    {
+                        unread
+                            .into_iter()
+                            .chain(read)
+                            .map(|reader| view! {
+                                <tr class="hover:bg-gray-50">
+                                    <th class="text-left py-2 px-4 border-b">
+                                        <A href=format!("/players/{}", reader.player_id) attr:class="hover:underline">{reader.tag_name}</A>
+                                    </th>
+                                    <th class="text-left py-2 px-4 border-b">
+                                        { reader.read_at.as_ref().map(format_datetime).unwrap_or("Ungelesen".to_string()) }
+                                    </th>
+                                </tr>
+                            })
+                            .collect_view()
+                    }
+                </tbody>
+            </table>
+        </div>
+    }
+}
+
+#[component]
+fn AnnouncementFormFields(form_name: &'static str, announcement: Announcement) -> impl IntoView {
+    let is_new = announcement.id.is_none();
+    let field = move |field: &str| format!("{}[{}]", form_name, field);
+
+    let content = RwSignal::new(announcement.content.clone());
+    let selected_audience = announcement.audience.form_value();
+    let audiences = Resource::new(|| {}, |_| get_announcement_audiences());
+
+    view! {
+        <div class="space-y-4">
+
+            <div class="grid grid-cols-[auto_1fr] items-center gap-4">
+                <label for=field("title") class="text-left text-gray-700">
+                    "Titel:"
+                </label>
+                <input
+                    type="text"
+                    name=field("title")
+                    class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+                    value=announcement.title
+                    required
+                />
+
+                <label for=field("content") class="text-left text-gray-700">
+                    "Inhalt (Markdown):"
+                </label>
+                <textarea
+                    name=field("content")
+                    rows="10"
+                    class="w-full border border-gray-300 rounded px-3 py-2 font-mono focus:outline-none focus:ring focus:border-blue-300"
+                    on:input=move |ev| content.set(event_target_value(&ev))
+                    required
+                >
+                    { announcement.content }
+                </textarea>
+
+                <span class="text-left text-gray-700">
+                    "Vorschau:"
+                </span>
+                <div
+                    class="prose max-w-none border border-gray-200 rounded px-3 py-2 bg-white text-left min-h-16"
+                    inner_html=move || render_markdown(&content.get())
+                ></div>
+
+                <label for=field("audience") class="text-left text-gray-700">
+                    "Zielgruppe:"
+                </label>
+                <Suspense fallback=move || view! { <p>"Lade Daten..."</p> }>
+                    {move || {
+                        let selected_audience = selected_audience.clone();
+
+                        audiences.get().map(|result| match result {
+                            Ok(options) => view! {
+                                <select
+                                    name=field("audience")
+                                    class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+                                    required
+                                >
+                                    {
+                                        options
+                                            .into_iter()
+                                            .map(|option: AudienceOption| {
+                                                let value = option.audience.form_value();
+                                                let selected = value == selected_audience;
+
+                                                view! {
+                                                    <option value=value selected=selected>{ option.label }</option>
+                                                }
+                                            })
+                                            .collect_view()
+                                    }
+                                </select>
+                            }.into_any(),
+                            Err(e) => view! { <p>{ e.to_string() }</p> }.into_any(),
+                        })
+                    }}
+                </Suspense>
+
+                <label for=field("hidden") class="text-left text-gray-700">
+                    "Versteckt:"
+                </label>
+                <input
+                    type="checkbox"
+                    name=field("hidden")
+                    class="w-4 h-4 accent-green-600 border-2 border-gray-300 rounded"
+                    value="true"
+                    checked=announcement.hidden/>
+
+                <label for=field("publish_at") class="text-left text-gray-700">
+                    "Veröffentlichen ab:"
+                </label>
+                <input
+                    type="datetime-local"
+                    name=field("publish_at")
+                    class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+                    value=(!is_new).then(|| datetime_input_value(&announcement.publish_at))
+                />
+
+                <label for=field("expires_at") class="text-left text-gray-700">
+                    "Ablauf:"
+                </label>
+                <input
+                    type="datetime-local"
+                    name=field("expires_at")
+                    class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+                    value=announcement.expires_at.as_ref().map(datetime_input_value)
+                />
+            </div>
+        </div>
+    }
+}
+
+#[derive(Params, PartialEq, Clone)]
+struct AnnouncementIdParameter {
+    id: Option<i64>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct AnnouncementForm {
+    #[serde(default)]
+    id: Option<i64>,
+    title: String,
+    content: String,
+    audience: String,
+    #[serde(default)]
+    hidden: bool,
+    #[serde(default)]
+    publish_at: String,
+    #[serde(default)]
+    expires_at: String,
+}
+
+#[cfg(feature = "ssr")]
+impl AnnouncementForm {
+    /// Without a publish time the announcement is published right away.
+    fn into_announcement(self, author_id: Option<i64>) -> Result<Announcement, AppError> {
+        use crate::components::util::parse_datetime_input;
+
+        let publish_at = if self.publish_at.is_empty() {
+            chrono::Utc::now()
+        } else {
+            parse_datetime_input(&self.publish_at)
+                .ok_or(AppError::InvalidInput("Veröffentlichen ab".to_string()))?
+        };
+
+        let expires_at = if self.expires_at.is_empty() {
+            None
+        } else {
+            Some(parse_datetime_input(&self.expires_at)
+                .ok_or(AppError::InvalidInput("Ablauf".to_string()))?)
+        };
+
+        if expires_at.is_some_and(|expires_at| expires_at <= publish_at) {
+            return Err(AppError::InvalidInput("Ablauf".to_string()));
+        }
+
+        let audience = self.audience
+            .parse()
+            .map_err(|_| AppError::InvalidInput("Zielgruppe".to_string()))?;
+
+        Ok(Announcement {
+            id: self.id,
+            title: self.title,
+            content: self.content,
+            hidden: self.hidden,
+            publish_at,
+            expires_at,
+            author_id,
+            audience,
+        })
+    }
+}
+
+/// Admins may address everyone and every group, leaders only their own team, platoon or event.
+#[cfg(feature = "ssr")]
+pub(crate) fn allowed_audiences(
+    player: &crate::database::models::Player,
+    pool: &crate::database::DieselPool,
+) -> Result<Vec<AudienceOption>, AppError> {
+    use crate::database;
+    use crate::domain::AnnouncementAudience;
+
+    let (platoons, teams, play_events) = if player.admin {
+        (
+            database::get_platoons_for_name_filter(String::new(), pool)?,
+            database::get_all_teams(pool)?,
+            database::get_all_play_events(pool)?,
+        )
+    } else {
+        (
+            database::get_platoons_for_leader(player.id, pool)?,
+            database::get_teams_for_contact_person(player.id, pool)?,
+            database::get_play_events_for_organizer(player.id, pool)?,
+        )
+    };
+
+    let mut options = Vec::new();
+
+    if player.admin {
+        options.push(AudienceOption {
+            audience: AnnouncementAudience::Everyone,
+            label: AnnouncementAudience::Everyone.to_string(),
+        });
+    }
+
+    options.extend(platoons.into_iter().map(|platoon| AudienceOption {
+        audience: AnnouncementAudience::Platoon(platoon.id),
+        label: format!("Zug {}", platoon.name),
+    }));
+    options.extend(teams.into_iter().map(|team| AudienceOption {
+        audience: AnnouncementAudience::Team(team.id),
+        label: format!("Team {}", team.name),
+    }));
+    options.extend(play_events.into_iter().map(|play_event| AudienceOption {
+        audience: AnnouncementAudience::PlayEvent(play_event.id),
+        label: format!("Teilnehmer {}", play_event.name),
+    }));
+
+    Ok(options)
+}
+
+#[cfg(feature = "ssr")]
+fn require_allowed_audience(
+    player: &crate::database::models::Player,
+    announcement: &Announcement,
+    pool: &crate::database::DieselPool,
+) -> Result<(), AppError> {
+    use crate::auth::AuthError;
+
+    let allowed = allowed_audiences(player, pool)?
+        .iter()
+        .any(|option| option.audience == announcement.audience);
+
+    if allowed {
+        Ok(())
+    } else {
+        Err(AppError::AuthError(AuthError::Forbidden))
+    }
+}
+
+/// Loads the announcement if the current player wrote it or is an admin.
+#[cfg(feature = "ssr")]
+async fn require_author(
+    announcement_id: i64,
+    pool: &crate::database::DieselPool,
+) -> Result<(crate::database::models::Player, Announcement), AppError> {
+    use crate::auth::{current_player, AuthError};
+    use crate::database::{self, DatabaseError};
+
+    let player = current_player().await?;
+    let announcement: Announcement = database::find_announcement_for_id(announcement_id, pool)?
+        .ok_or(DatabaseError::EntityNotFound)?
+        .into();
+
+    if player.admin || announcement.author_id == Some(player.id) {
+        Ok((player, announcement))
+    } else {
+        Err(AppError::AuthError(AuthError::Forbidden))
+    }
+}
+
+#[server]
+async fn get_announcement_feed() -> Result<Vec<AnnouncementFeedEntry>, AppError> {
+    use std::collections::HashSet;
+
+    use crate::auth::current_player_if_logged_in;
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let player = current_player_if_logged_in().await?;
+
+    let read_ids: HashSet<i64> = match &player {
+        Some(player) => database::get_read_announcement_ids(player.id, &pool)?.into_iter().collect(),
+        None => HashSet::new(),
+    };
+
+    let entries = database::get_published_announcements(player.as_ref().map(|player| player.id), &pool)?
+        .into_iter()
+        .map(|announcement| AnnouncementFeedEntry {
+            read: player.is_none() || read_ids.contains(&announcement.id),
+            announcement: announcement.into(),
+        })
+        .collect();
+
+    Ok(entries)
+}
+
+#[server]
+pub async fn get_unread_announcement_count() -> Result<usize, AppError> {
+    use std::collections::HashSet;
+
+    use crate::auth::current_player_if_logged_in;
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let Some(player) = current_player_if_logged_in().await? else {
+        return Ok(0);
+    };
+
+    let read_ids: HashSet<i64> = database::get_read_announcement_ids(player.id, &pool)?
+        .into_iter()
+        .collect();
+
+    let unread = database::get_published_announcements(Some(player.id), &pool)?
+        .iter()
+        .filter(|announcement| !read_ids.contains(&announcement.id))
+        .count();
+
+    Ok(unread)
+}
+
+#[server]
+pub async fn mark_announcement_read(announcement_id: i64) -> Result<(), AppError> {
+    use crate::auth::current_player;
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let player = current_player().await?;
+
+    Ok(database::mark_announcement_read(announcement_id, player.id, &pool)?)
+}
+
+#[server]
+pub async fn can_write_announcements() -> Result<bool, AppError> {
+    use crate::auth::current_player_if_logged_in;
+    use crate::database::DieselPool;
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    match current_player_if_logged_in().await? {
+        Some(player) => Ok(!allowed_audiences(&player, &pool)?.is_empty()),
+        None => Ok(false),
+    }
+}
+
+#[server]
+async fn get_announcement_audiences() -> Result<Vec<AudienceOption>, AppError> {
+    use crate::auth::current_player;
+    use crate::database::DieselPool;
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let player = current_player().await?;
+
+    allowed_audiences(&player, &pool)
+}
+
+#[server]
+async fn get_announcements() -> Result<Vec<Announcement>, AppError> {
+    use crate::auth::current_player;
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let player = current_player().await?;
+
+    let announcements = if player.admin {
+        database::get_all_announcements(&pool)?
+    } else {
+        database::get_announcements_for_author(player.id, &pool)?
+    };
+
+    Ok(announcements.into_iter().map(Into::into).collect())
+}
+
+#[server]
+async fn load_announcement_by_id(id: i64) -> Result<Announcement, AppError> {
+    use crate::database::DieselPool;
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let (_, announcement) = require_author(id, &pool).await?;
+
+    Ok(announcement)
+}
+
+#[server]
+async fn get_announcement_readers(announcement_id: i64) -> Result<Vec<AnnouncementReader>, AppError> {
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let (_, announcement) = require_author(announcement_id, &pool).await?;
+
+    Ok(database::get_announcement_readers(announcement_id, announcement.audience, &pool)?)
+}
+
+#[server]
+async fn create_new_announcement(create_new_announcement: AnnouncementForm) -> Result<(), AppError> {
+    use crate::auth::current_player;
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let author = current_player().await?;
+    let announcement = create_new_announcement.into_announcement(Some(author.id))?;
+
+    require_allowed_audience(&author, &announcement, &pool)?;
+
+    database::create_announcement(announcement, &pool)?;
+
+    leptos_axum::redirect("/announcements");
+    Ok(())
+}
+
+#[server]
+async fn save_announcement(announcement_form: AnnouncementForm) -> Result<(), AppError> {
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let id = announcement_form.id.ok_or(AppError::InvalidInput("Id".to_string()))?;
+    let (player, _) = require_author(id, &pool).await?;
+
+    let announcement = announcement_form.into_announcement(None)?;
+
+    require_allowed_audience(&player, &announcement, &pool)?;
+
+    database::save_announcement(announcement, &pool)?;
+
+    leptos_axum::redirect("/announcements");
+    Ok(())
+}
+
+#[server]
+async fn delete_announcement(id: i64) -> Result<(), AppError> {
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    require_author(id, &pool).await?;
+
+    database::delete_announcement(id, &pool)?;
+
+    leptos_axum::redirect("/announcements");
+    Ok(())
+}
@@ -0,0 +1,185 @@
+use leptos::prelude::*;
+
+use crate::app::AppError;
+use crate::components::util::{format_datetime, BackButton};
+use crate::domain::ApiToken;
+
+#[component]
+pub fn ApiTokensPage() -> impl IntoView {
+    use crate::components::protected::Protected;
+
+    let create_token = ServerAction::<CreateApiToken>::new();
+    let delete_token = ServerAction::<DeleteApiToken>::new();
+    let tokens = Resource::new(
+        move || (create_token.version().get(), delete_token.version().get()),
+        |_| get_own_api_tokens(),
+    );
+
+    let action_error = move || {
+        let errors = [
+            create_token.value().get().and_then(|result| result.err()),
+            delete_token.value().get().and_then(|result| result.err()),
+        ];
+
+        errors.into_iter().flatten().next().map(|e| view! {
+            <p class="text-red-600">{ e.to_string() }</p>
+        })
+    };
+
+    let created_token = move || create_token.value().get().and_then(|result| result.ok()).map(|token| view! {
+        <div class="border border-green-600 rounded p-4 bg-white text-left mb-6">
+            <p class="text-gray-700 pb-2">
+                "Dein neues Token. Es wird nur jetzt angezeigt, kopiere es an einen sicheren Ort."
+            </p>
+            <input
+                type="text"
+                readonly
+                value=token
+                class="w-full border border-gray-300 rounded px-3 py-2 font-mono text-sm bg-gray-50"
+            />
+        </div>
+    });
+
+    view! {
+        <Protected>
+            <BackButton/>
+            <div class="p-8 max-w-4xl mx-auto">
+                <h1 class="text-2xl font-semibold mb-6">
+                    "API-Tokens"
+                </h1>
+                <p class="text-left text-gray-700 pb-6">
+                    "Mit einem Token können andere Programme die Spieler, Teams, Züge und Events über "
+                    <code>"/api/v1"</code>
+                    " abrufen und dich für Events anmelden. Es wird im Header "
+                    <code>"Authorization: Bearer <Token>"</code>
+                    " mitgeschickt und sieht dieselben Daten wie du."
+                </p>
+
+                { created_token }
+
+                <Suspense fallback=move || view! { <p>"Lade Daten..."</p> }>
+                    {move || {
+                        tokens.get().map(|result| match result {
+                            Ok(tokens) if tokens.is_empty() => view! {
+                                <p class="text-gray-700">"Keine Tokens"</p>
+                            }.into_any(),
+                            Ok(tokens) => view! {
+                                <div class="overflow-x-auto">
+                                    <table class="min-w-full border border-gray-200 shadow-sm rounded-md bg-white">
+                                        <thead class="bg-gray-100 text-gray-700">
+                                            <tr>
+                                                <th class="text-left py-2 px-4 border-b">Name</th>
+                                                <th class="text-left py-2 px-4 border-b">Erstellt</th>
+                                                <th class="text-left py-2 px-4 border-b">Zuletzt benutzt</th>
+                                                <th class="text-left py-2 px-4 border-b"></th>
+                                            </tr>
+                                        </thead>
+                                        <tbody>
+                                            {
+                                                tokens.into_iter().map(|token: ApiToken| view! {
+                                                    <tr class="hover:bg-gray-50">
+                                                        <th class="text-left py-2 px-4 border-b">{ token.name }</th>
+                                                        <th class="text-left py-2 px-4 border-b">{ format_datetime(&token.created_at) }</th>
+                                                        <th class="text-left py-2 px-4 border-b">
+                                                            { token.last_used_at.as_ref().map(format_datetime).unwrap_or("Nie".to_string()) }
+                                                        </th>
+                                                        <th class="text-left py-2 px-4 border-b">
+                                                            <ActionForm action=delete_token>
+                                                                <input type="hidden" name="id" value=token.id/>
+                                                                <button
+                                                                    type="submit"
+                                                                    class="px-4 py-2 rounded bg-gray-200 hover:bg-gray-300 text-gray-700"
+                                                                >
+                                                                    "Löschen"
+                                                                </button>
+                                                            </ActionForm>
+                                                        </th>
+                                                    </tr>
+                                                }).collect_view()
+                                            }
+                                        </tbody>
+                                    </table>
+                                </div>
+                            }.into_any(),
+                            Err(e) => view! { <p>{ e.to_string() }</p> }.into_any(),
+                        })
+                    }}
+                </Suspense>
+
+                { action_error }
+
+                <h2 class="text-left text-xl font-semibold py-2 mt-8">Neues Token</h2>
+                <ActionForm action=create_token attr:class="grid grid-cols-[auto_1fr] items-center gap-4">
+                    <label for="name" class="text-left text-gray-700">
+                        "Name:"
+                    </label>
+                    <input
+                        type="text"
+                        name="name"
+                        class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+                        placeholder="z. B. Discord-Bot"
+                        required
+                    />
+
+                    <div class="col-span-2 flex justify-end">
+                        <button
+                            type="submit"
+                            class="px-4 py-2 rounded bg-blue-600 hover:bg-blue-700 text-white"
+                        >
+                            "Token erzeugen"
+                        </button>
+                    </div>
+                </ActionForm>
+            </div>
+        </Protected>
+    }
+}
+
+#[server]
+async fn get_own_api_tokens() -> Result<Vec<ApiToken>, AppError> {
+    use crate::auth::current_player;
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let player = current_player().await?;
+
+    Ok(database::get_api_tokens_for_player(player.id, &pool)?)
+}
+
+/// Returns the new token, which can't be shown again afterwards.
+#[server]
+async fn create_api_token(name: String) -> Result<String, AppError> {
+    use crate::api::{generate_api_token, hash_api_token};
+    use crate::auth::current_player;
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let player = current_player().await?;
+
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(AppError::InvalidInput("Name".to_string()));
+    }
+
+    let token = generate_api_token();
+    database::create_api_token(player.id, name, &hash_api_token(&token), &pool)?;
+
+    Ok(token)
+}
+
+#[server]
+async fn delete_api_token(id: i64) -> Result<(), AppError> {
+    use crate::auth::current_player;
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let player = current_player().await?;
+
+    Ok(database::delete_api_token(player.id, id, &pool)?)
+}
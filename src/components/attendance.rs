@@ -0,0 +1,357 @@
+use leptos::prelude::*;
+use leptos_router::components::A;
+
+use crate::app::AppError;
+use crate::components::util::BackButton;
+use crate::domain::{AttendanceReport, AttendanceStats, PlayerAttendance, TeamAttendance};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SortKey {
+    Name,
+    Registrations,
+    Attended,
+    NoShows,
+    LateWithdrawals,
+    Reliability,
+    CurrentStreak,
+    LongestStreak,
+}
+
+impl SortKey {
+    fn compare(&self, a: (&str, &AttendanceStats), b: (&str, &AttendanceStats)) -> std::cmp::Ordering {
+        let (a_name, a) = a;
+        let (b_name, b) = b;
+
+        match self {
+            SortKey::Name => a_name.to_lowercase().cmp(&b_name.to_lowercase()),
+            SortKey::Registrations => b.registrations.cmp(&a.registrations),
+            SortKey::Attended => b.attended.cmp(&a.attended),
+            SortKey::NoShows => b.no_shows.cmp(&a.no_shows),
+            SortKey::LateWithdrawals => b.late_withdrawals.cmp(&a.late_withdrawals),
+            SortKey::Reliability => b.reliability().cmp(&a.reliability()),
+            SortKey::CurrentStreak => b.current_streak.cmp(&a.current_streak),
+            SortKey::LongestStreak => b.longest_streak.cmp(&a.longest_streak),
+        }
+    }
+}
+
+#[component]
+pub fn PlayerAttendanceSummary(player_id: i64) -> impl IntoView {
+
+    let stats = Resource::new(move || player_id, get_player_attendance);
+
+    view! {
+        <h2 class="text-left text-xl font-semibold py-2">Zuverlässigkeit</h2>
+
+        <Suspense fallback=move || view! { <p>"Lade Daten..."</p> }>
+            {move || {
+                stats.get().map(|result| match result {
+                    Ok(stats) => view! { <AttendanceStatsGrid stats=stats/> }.into_any(),
+                    Err(e) => view! { <p>{ e.to_string() }</p> }.into_any(),
+                })
+            }}
+        </Suspense>
+    }
+}
+
+#[component]
+pub fn TeamAttendanceSummary(team_id: i64) -> impl IntoView {
+
+    let team_attendance = Resource::new(move || team_id, get_team_attendance);
+
+    view! {
+        <h2 class="text-left text-xl font-semibold py-2">Zuverlässigkeit</h2>
+
+        <Suspense fallback=move || view! { <p>"Lade Daten..."</p> }>
+            {move || {
+                team_attendance.get().map(|result| match result {
+                    Ok(team_attendance) => view! {
+                        <AttendanceStatsGrid stats=team_attendance.stats/>
+                        <div class="pt-4">
+                            <PlayerAttendanceTable players=team_attendance.members/>
+                        </div>
+                    }.into_any(),
+                    Err(e) => view! { <p>{ e.to_string() }</p> }.into_any(),
+                })
+            }}
+        </Suspense>
+    }
+}
+
+#[component]
+fn AttendanceStatsGrid(stats: AttendanceStats) -> impl IntoView {
+    let entries = [
+        ("Anmeldungen", stats.registrations.to_string()),
+        ("Anwesend", stats.attended.to_string()),
+        ("Nicht erschienen", stats.no_shows.to_string()),
+        ("Späte Abmeldungen", stats.late_withdrawals.to_string()),
+        ("Zuverlässigkeit", format_reliability(&stats)),
+        ("Aktuelle Serie", stats.current_streak.to_string()),
+        ("Längste Serie", stats.longest_streak.to_string()),
+    ];
+
+    view! {
+        <div class="grid grid-cols-[auto_1fr] items-center gap-2">
+            {
+                entries
+                    .into_iter()
+                    .map(|(label, value)| view! {
+                        <span class="text-left text-gray-700">{ label } ":"</span>
+                        <span class="text-left px-3">{ value }</span>
+                    })
+                    .collect_view()
+            }
+        </div>
+    }
+}
+
+#[component]
+pub fn AttendanceReportPage() -> impl IntoView {
+    use crate::components::protected::Protected;
+
+    let report = Resource::new(|| {}, |_| get_attendance_report());
+
+    view! {
+        <Protected>
+            <BackButton/>
+            <div class="p-8 max-w-6xl mx-auto">
+                <div class="flex items-center justify-between mb-6">
+                    <h1 class="text-2xl font-semibold">
+                        "Anwesenheit"
+                    </h1>
+                </div>
+
+                <Suspense fallback=move || view! { <p>"Lade Daten..."</p> }>
+                    {move || {
+                        report.get().map(|result| match result {
+                            Ok(AttendanceReport { players, teams }) => view! {
+                                <h2 class="text-left text-xl font-semibold py-2">Spieler</h2>
+                                <PlayerAttendanceTable players=players/>
+
+                                <h2 class="text-left text-xl font-semibold py-2 pt-6">Teams</h2>
+                                <TeamAttendanceTable teams=teams/>
+                            }.into_any(),
+                            Err(e) => view! { <p>{ e.to_string() }</p> }.into_any(),
+                        })
+                    }}
+                </Suspense>
+            </div>
+        </Protected>
+    }
+}
+
+#[component]
+fn PlayerAttendanceTable(players: Vec<PlayerAttendance>) -> impl IntoView {
+    let sort_key = RwSignal::new(SortKey::Reliability);
+
+    let sorted = move || {
+        let mut players = players.clone();
+        let key = sort_key.get();
+        players.sort_by(|a, b| key.compare((&a.tag_name, &a.stats), (&b.tag_name, &b.stats)));
+        players
+    };
+
+    view! {
+        <div class="overflow-x-auto">
+            <table class="min-w-full border border-gray-200 shadow-sm rounded-md bg-white">
+                <AttendanceTableHead name_label="Spielername" sort_key=sort_key/>
+                <tbody>
+                    {move || {
+                        sorted()
+                            .into_iter()
+                            .map(|player| view! {
+                                <tr class="hover:bg-gray-50">
+                                    <th class="text-left py-2 px-4 border-b">
+                                        <A href=format!("/players/{}", player.player_id) attr:class="hover:underline">{player.tag_name}</A>
+                                    </th>
+                                    <AttendanceStatsCells stats=player.stats/>
+                                </tr>
+                            })
+                            .collect_view()
+                    }}
+                </tbody>
+            </table>
+        </div>
+    }
+}
+
+#[component]
+fn TeamAttendanceTable(teams: Vec<TeamAttendance>) -> impl IntoView {
+    let sort_key = RwSignal::new(SortKey::Reliability);
+
+    let sorted = move || {
+        let mut teams = teams.clone();
+        let key = sort_key.get();
+        teams.sort_by(|a, b| key.compare((&a.name, &a.stats), (&b.name, &b.stats)));
+        teams
+    };
+
+    view! {
+        <div class="overflow-x-auto">
+            <table class="min-w-full border border-gray-200 shadow-sm rounded-md bg-white">
+                <AttendanceTableHead name_label="Team" sort_key=sort_key/>
+                <tbody>
+                    {move || {
+                        sorted()
+                            .into_iter()
+                            .map(|team| view! {
+                                <tr class="hover:bg-gray-50">
+                                    <th class="text-left py-2 px-4 border-b">
+                                        <A href=format!("/teams/{}", team.team_id) attr:class="hover:underline">{team.name}</A>
+                                    </th>
+                                    <AttendanceStatsCells stats=team.stats/>
+                                </tr>
+                            })
+                            .collect_view()
+                    }}
+                </tbody>
+            </table>
+        </div>
+    }
+}
+
+#[component]
+fn AttendanceTableHead(name_label: &'static str, sort_key: RwSignal<SortKey>) -> impl IntoView {
+    let columns = [
+        (name_label, SortKey::Name),
+        ("Anmeldungen", SortKey::Registrations),
+        ("Anwesend", SortKey::Attended),
+        ("Nicht erschienen", SortKey::NoShows),
+        ("Späte Abmeldungen", SortKey::LateWithdrawals),
+        ("Zuverlässigkeit", SortKey::Reliability),
+        ("Serie", SortKey::CurrentStreak),
+        ("Längste Serie", SortKey::LongestStreak),
+    ];
+
+    view! {
+        <thead class="bg-gray-100 text-gray-700">
+            <tr>
+                {
+                    columns
+                        .into_iter()
+                        .map(|(label, key)| view! {
+                            <th
+                                class="text-left py-2 px-4 border-b cursor-pointer hover:underline"
+                                on:click=move |_| sort_key.set(key)
+                            >
+                                { label }
+                                { move || (sort_key.get() == key).then_some(" ▾") }
+                            </th>
+                        })
+                        .collect_view()
+                }
+            </tr>
+        </thead>
+    }
+}
+
+#[component]
+fn AttendanceStatsCells(stats: AttendanceStats) -> impl IntoView {
+
+    view! {
+        <th class="text-left py-2 px-4 border-b">{ stats.registrations }</th>
+        <th class="text-left py-2 px-4 border-b">{ stats.attended }</th>
+        <th class="text-left py-2 px-4 border-b">{ stats.no_shows }</th>
+        <th class="text-left py-2 px-4 border-b">{ stats.late_withdrawals }</th>
+        <th class="text-left py-2 px-4 border-b">{ format_reliability(&stats) }</th>
+        <th class="text-left py-2 px-4 border-b">{ stats.current_streak }</th>
+        <th class="text-left py-2 px-4 border-b">{ stats.longest_streak }</th>
+    }
+}
+
+fn format_reliability(stats: &AttendanceStats) -> String {
+    stats
+        .reliability()
+        .map(|reliability| format!("{} %", reliability))
+        .unwrap_or("-".to_string())
+}
+
+#[server]
+async fn get_player_attendance(player_id: i64) -> Result<AttendanceStats, AppError> {
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let records = database::get_attendance_records(Some(vec![player_id]), &pool)?;
+
+    Ok(AttendanceStats::from_records(&records, chrono::Utc::now()))
+}
+
+#[server]
+async fn get_team_attendance(team_id: i64) -> Result<TeamAttendance, AppError> {
+    use std::collections::HashSet;
+
+    use crate::database::{self, DatabaseError, DieselPool};
+    use crate::domain::{Player, Team};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let team: Team = database::find_team_for_id(team_id, &pool)?
+        .ok_or(DatabaseError::EntityNotFound)?
+        .into();
+
+    // former members count for the events they played with the team
+    let member_ids: HashSet<i64> = database::get_team_memberships_for_team(team_id, &pool)?
+        .into_iter()
+        .map(|membership| membership.player_id)
+        .chain(database::get_players_for_team(team_id, &pool)?.into_iter().map(|player| player.id))
+        .collect();
+    let members: Vec<Player> = database::get_all_players(&pool)?
+        .into_iter()
+        .filter(|player| member_ids.contains(&player.id))
+        .map(Into::into)
+        .collect();
+    let records = database::get_attendance_records(Some(member_ids.into_iter().collect()), &pool)?;
+    let event_teams = database::get_team_memberships_for_events(event_ids(&records), &pool)?;
+
+    let report = AttendanceReport::new(
+        members,
+        vec![team],
+        &records,
+        |play_event_id, player_id| event_teams.get(&(play_event_id, player_id)).copied(),
+        chrono::Utc::now(),
+    );
+
+    report.teams
+        .into_iter()
+        .next()
+        .ok_or(DatabaseError::EntityNotFound.into())
+}
+
+#[server]
+async fn get_attendance_report() -> Result<AttendanceReport, AppError> {
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let players = database::get_all_players(&pool)?
+        .into_iter()
+        .map(Into::into)
+        .collect();
+    let teams = database::get_all_teams(&pool)?
+        .into_iter()
+        .map(Into::into)
+        .collect();
+    let records = database::get_attendance_records(None, &pool)?;
+    let event_teams = database::get_team_memberships_for_events(event_ids(&records), &pool)?;
+
+    Ok(AttendanceReport::new(
+        players,
+        teams,
+        &records,
+        |play_event_id, player_id| event_teams.get(&(play_event_id, player_id)).copied(),
+        chrono::Utc::now(),
+    ))
+}
+
+#[cfg(feature = "ssr")]
+fn event_ids(records: &[crate::domain::AttendanceRecord]) -> Vec<i64> {
+    let mut play_event_ids: Vec<i64> = records.iter().map(|record| record.play_event_id).collect();
+    play_event_ids.sort_unstable();
+    play_event_ids.dedup();
+
+    play_event_ids
+}
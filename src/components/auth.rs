@@ -8,7 +8,7 @@ use serde::{Deserialize, Serialize};
 use crate::auth::AuthSession;
 #[cfg(feature = "ssr")]
 use crate::database::create_player;
-use crate::{app::AppError, auth::Credentials};
+use crate::{app::AppError, auth::Credentials, domain::Player};
 
 #[component]
 pub fn Login() -> impl IntoView {
@@ -145,3 +145,10 @@ async fn do_register(register_form: RegisterForm) -> Result<bool, AppError> {
         Err(err) => Err(AppError::Database(err)),
     }
 }
+
+#[server]
+pub async fn get_current_player() -> Result<Player, AppError> {
+    use crate::auth::current_player;
+
+    Ok(current_player().await?.into())
+}
@@ -0,0 +1,400 @@
+use leptos::prelude::*;
+use leptos::server_fn::codec::{MultipartData, MultipartFormData};
+use leptos::web_sys::{FormData, HtmlFormElement, SubmitEvent};
+use leptos::wasm_bindgen::JsCast;
+use serde::{Deserialize, Serialize};
+
+use crate::app::AppError;
+use crate::components::util::{format_cents, format_date, BackButton};
+use crate::domain::{BankReconciliation, BankTransaction, MatchSuggestion, OpenCharge, Payer};
+
+#[component]
+pub fn BankImportPage() -> impl IntoView {
+
+    let import_statement = Action::new_local(|data: &FormData| import_bank_statement(data.clone().into()));
+    let book_transaction = ServerAction::<BookBankTransaction>::new();
+    let book_suggestions = ServerAction::<BookSuggestedBankTransactions>::new();
+    let ignore_transaction = ServerAction::<IgnoreBankTransaction>::new();
+
+    let reconciliation = Resource::new(
+        move || (
+            import_statement.version().get(),
+            book_transaction.version().get(),
+            book_suggestions.version().get(),
+            ignore_transaction.version().get(),
+        ),
+        |_| get_bank_reconciliation(),
+    );
+
+    let on_submit = move |ev: SubmitEvent| {
+        ev.prevent_default();
+
+        let form = ev.target().unwrap().unchecked_into::<HtmlFormElement>();
+        let data = FormData::new_with_form(&form).unwrap();
+        import_statement.dispatch_local(data);
+    };
+
+    let action_error = move || {
+        let import_error = import_statement.value().get().and_then(|result| result.err());
+        let book_error = book_transaction.value().get().and_then(|result| result.err());
+        let suggestions_error = book_suggestions.value().get().and_then(|result| result.err());
+        let ignore_error = ignore_transaction.value().get().and_then(|result| result.err());
+
+        import_error
+            .or(book_error)
+            .or(suggestions_error)
+            .or(ignore_error)
+            .map(|e| view! { <p class="text-red-600">{ e.to_string() }</p> })
+    };
+
+    let import_result = move || import_statement.value().get().and_then(|result| result.ok()).map(|count| view! {
+        <p class="text-left text-green-700">{ format!("{} neue Umsätze importiert", count) }</p>
+    });
+
+    let suggestions_result = move || book_suggestions.value().get().and_then(|result| result.ok()).map(|count| view! {
+        <p class="text-left text-green-700">{ format!("{} Zahlungen gebucht", count) }</p>
+    });
+
+    view! {
+        <BackButton/>
+        <div class="p-8 max-w-6xl mx-auto">
+            <div class="flex items-center justify-between mb-6">
+                <h1 class="text-2xl font-semibold">
+                    "Bankimport"
+                </h1>
+            </div>
+
+            <form on:submit=on_submit class="flex items-center gap-4 mb-2">
+                <label for="statement" class="text-left text-gray-700">
+                    "Kontoauszug (CAMT.053 oder CSV):"
+                </label>
+                <input type="file" name="statement" accept=".xml,.csv,.txt" required/>
+                <button
+                    type="submit"
+                    class="px-4 py-2 rounded bg-blue-600 hover:bg-blue-700 text-white"
+                >
+                    "Importieren"
+                </button>
+            </form>
+
+            { import_result }
+            { suggestions_result }
+            { action_error }
+
+            <Suspense fallback=move || view! { <p>"Lade Daten..."</p> }>
+                {move || {
+                    reconciliation.get().map(|result| match result {
+                        Ok(BankReconciliation { transactions, open_charges, payers }) => {
+                            let suggested = transactions.iter().filter(|(_, suggestion)| suggestion.is_some()).count();
+
+                            view! {
+                                <div class="flex items-center justify-between py-2">
+                                    <h2 class="text-left text-xl font-semibold">Offene Umsätze</h2>
+                                    <Show when=move || { suggested > 0 }>
+                                        <ActionForm action=book_suggestions>
+                                            <button
+                                                type="submit"
+                                                class="px-4 py-2 rounded bg-blue-600 hover:bg-blue-700 text-white"
+                                            >
+                                                { format!("{} Vorschläge buchen", suggested) }
+                                            </button>
+                                        </ActionForm>
+                                    </Show>
+                                </div>
+
+                                <div class="overflow-x-auto">
+                                    <table class="min-w-full border border-gray-200 shadow-sm rounded-md bg-white">
+                                        <thead class="bg-gray-100 text-gray-700">
+                                            <tr>
+                                                <th class="text-left py-2 px-4 border-b">Datum</th>
+                                                <th class="text-left py-2 px-4 border-b">Auftraggeber</th>
+                                                <th class="text-left py-2 px-4 border-b">Verwendungszweck</th>
+                                                <th class="text-left py-2 px-4 border-b">Betrag</th>
+                                                <th class="text-left py-2 px-4 border-b">Zuordnung</th>
+                                                <th class="py-2 px-4 border-b"></th>
+                                            </tr>
+                                        </thead>
+                                        <tbody>
+                                            {
+                                                transactions.into_iter().map(|(transaction, suggestion)| view! {
+                                                    <BankTransactionRow
+                                                        transaction=transaction
+                                                        suggestion=suggestion
+                                                        open_charges=open_charges.clone()
+                                                        payers=payers.clone()
+                                                        book=book_transaction
+                                                        ignore=ignore_transaction
+                                                    />
+                                                }).collect_view()
+                                            }
+                                        </tbody>
+                                    </table>
+                                </div>
+                            }.into_any()
+                        },
+                        Err(e) => view! { <p>{ e.to_string() }</p> }.into_any(),
+                    })
+                }}
+            </Suspense>
+        </div>
+    }
+}
+
+#[component]
+fn BankTransactionRow(
+    transaction: BankTransaction,
+    suggestion: Option<MatchSuggestion>,
+    open_charges: Vec<OpenCharge>,
+    payers: Vec<Payer>,
+    book: ServerAction<BookBankTransaction>,
+    ignore: ServerAction<IgnoreBankTransaction>,
+) -> impl IntoView {
+    let field = |field: &str| format!("booking_form[{}]", field);
+
+    let suggestion_text = suggestion.as_ref().map(|suggestion| {
+        let target = match &suggestion.charge_description {
+            Some(description) => format!("{}: {}", suggestion.tag_name, description),
+            None => suggestion.tag_name.clone(),
+        };
+
+        format!("{} (über {})", target, suggestion.reason)
+    });
+
+    view! {
+        <tr class="hover:bg-gray-50 align-top">
+            <th class="text-left py-2 px-4 border-b">{ format_date(&transaction.booked_on) }</th>
+            <th class="text-left py-2 px-4 border-b">{ transaction.counterparty.clone() }</th>
+            <th class="text-left py-2 px-4 border-b font-normal">{ transaction.reference.clone() }</th>
+            <th class="text-left py-2 px-4 border-b">{ format_cents(transaction.amount_cents) }</th>
+            <th class="text-left py-2 px-4 border-b">
+                <ActionForm action=book attr:class="flex flex-col gap-2">
+                    <input type="hidden" name=field("id") value=transaction.id/>
+                    <select
+                        name=field("charge_id")
+                        class="border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+                    >
+                        <option value="">"Keine bestimmte Forderung"</option>
+                        {
+                            open_charges
+                                .into_iter()
+                                .map(|open_charge| {
+                                    let selected = suggestion
+                                        .as_ref()
+                                        .is_some_and(|suggestion| suggestion.charge_id.is_some() && suggestion.charge_id == open_charge.charge.id);
+
+                                    view! {
+                                        <option value=open_charge.charge.id selected=selected>
+                                            {
+                                                format!(
+                                                    "{}: {} ({} offen)",
+                                                    open_charge.tag_name,
+                                                    open_charge.charge.description,
+                                                    format_cents(open_charge.outstanding_cents),
+                                                )
+                                            }
+                                        </option>
+                                    }
+                                })
+                                .collect_view()
+                        }
+                    </select>
+                    <select
+                        name=field("player_id")
+                        class="border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+                    >
+                        <option value="">"Spieler der Forderung"</option>
+                        {
+                            payers
+                                .into_iter()
+                                .map(|payer| {
+                                    let selected = suggestion
+                                        .as_ref()
+                                        .is_some_and(|suggestion| suggestion.charge_id.is_none() && suggestion.player_id == payer.player_id);
+
+                                    view! {
+                                        <option value=payer.player_id selected=selected>{ payer.tag_name }</option>
+                                    }
+                                })
+                                .collect_view()
+                        }
+                    </select>
+                    {
+                        suggestion_text.map(|text| view! {
+                            <span class="text-sm text-gray-600 font-normal">{ format!("Vorschlag: {}", text) }</span>
+                        })
+                    }
+                    <button
+                        type="submit"
+                        class="px-4 py-2 rounded bg-blue-600 hover:bg-blue-700 text-white"
+                    >
+                        "Buchen"
+                    </button>
+                </ActionForm>
+            </th>
+            <th class="py-2 px-4 border-b">
+                <ActionForm action=ignore>
+                    <input type="hidden" name="id" value=transaction.id/>
+                    <button
+                        type="submit"
+                        class="px-4 py-2 rounded bg-gray-200 hover:bg-gray-300 text-gray-700"
+                    >
+                        "Ignorieren"
+                    </button>
+                </ActionForm>
+            </th>
+        </tr>
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct BookingForm {
+    id: i64,
+    #[serde(default)]
+    charge_id: String,
+    #[serde(default)]
+    player_id: String,
+}
+
+/// Imports the uploaded statement and returns the number of new transactions.
+#[server(input = MultipartFormData)]
+async fn import_bank_statement(data: MultipartData) -> Result<usize, AppError> {
+    use crate::auth::require_admin;
+    use crate::bank_statement::parse_statement;
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let admin = require_admin().await?;
+
+    let mut data = data.into_inner().ok_or(AppError::MissingContext)?;
+    let mut content = Vec::new();
+
+    while let Ok(Some(mut field)) = data.next_field().await {
+        if field.name() != Some("statement") {
+            continue;
+        }
+
+        while let Ok(Some(chunk)) = field.chunk().await {
+            content.extend_from_slice(&chunk);
+        }
+    }
+
+    let transactions = parse_statement(&content)
+        .map_err(|e| AppError::InvalidInput(e.to_string()))?;
+
+    Ok(database::create_bank_transactions(&transactions, admin.id, &pool)?)
+}
+
+#[server]
+async fn get_bank_reconciliation() -> Result<BankReconciliation, AppError> {
+    use crate::auth::require_admin;
+    use crate::database::{self, DieselPool};
+    use crate::domain::suggest_match;
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    require_admin().await?;
+
+    let open_charges = database::get_open_charges(&pool)?;
+    let payers = database::get_payers(&pool)?;
+
+    let transactions = database::get_open_bank_transactions(&pool)?
+        .into_iter()
+        .map(|transaction| {
+            let transaction: BankTransaction = transaction.into();
+            let suggestion = suggest_match(&transaction, &open_charges, &payers);
+
+            (transaction, suggestion)
+        })
+        .collect();
+
+    Ok(BankReconciliation { transactions, open_charges, payers })
+}
+
+/// A selected charge decides the player, otherwise the transfer is booked as credit of the selected player.
+#[server]
+async fn book_bank_transaction(booking_form: BookingForm) -> Result<(), AppError> {
+    use crate::auth::require_admin;
+    use crate::database::{self, DatabaseError, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let admin = require_admin().await?;
+
+    let (player_id, charge_id) = if booking_form.charge_id.is_empty() {
+        let player_id = booking_form.player_id
+            .parse()
+            .map_err(|_| AppError::InvalidInput("Spieler".to_string()))?;
+
+        (player_id, None)
+    } else {
+        let charge_id: i64 = booking_form.charge_id
+            .parse()
+            .map_err(|_| AppError::InvalidInput("Forderung".to_string()))?;
+        let charge = database::find_charge_for_id(charge_id, &pool)?
+            .ok_or(DatabaseError::EntityNotFound)?;
+
+        if !booking_form.player_id.is_empty() && booking_form.player_id != charge.player_id.to_string() {
+            return Err(AppError::InvalidInput("Spieler".to_string()));
+        }
+
+        (charge.player_id, Some(charge_id))
+    };
+
+    Ok(database::book_bank_transaction(booking_form.id, player_id, charge_id, admin.id, &pool)?)
+}
+
+/// Books every open transaction that has a suggestion and returns how many were booked.
+#[server]
+async fn book_suggested_bank_transactions() -> Result<usize, AppError> {
+    use crate::auth::require_admin;
+    use crate::database::{self, DieselPool};
+    use crate::domain::suggest_match;
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let admin = require_admin().await?;
+
+    let payers = database::get_payers(&pool)?;
+    let mut booked = 0;
+
+    for transaction in database::get_open_bank_transactions(&pool)? {
+        let transaction: BankTransaction = transaction.into();
+
+        // reload, earlier bookings of this run may have paid a charge off
+        let open_charges = database::get_open_charges(&pool)?;
+
+        if let Some(suggestion) = suggest_match(&transaction, &open_charges, &payers) {
+            database::book_bank_transaction(
+                transaction.id.unwrap(),
+                suggestion.player_id,
+                suggestion.charge_id,
+                admin.id,
+                &pool,
+            )?;
+            booked += 1;
+        }
+    }
+
+    Ok(booked)
+}
+
+#[server]
+async fn ignore_bank_transaction(id: i64) -> Result<(), AppError> {
+    use crate::auth::require_admin;
+    use crate::database::{self, DatabaseError, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    require_admin().await?;
+
+    database::find_bank_transaction_for_id(id, &pool)?
+        .ok_or(DatabaseError::EntityNotFound)?;
+
+    Ok(database::ignore_bank_transaction(id, &pool)?)
+}
@@ -0,0 +1,136 @@
+use leptos::prelude::*;
+
+use crate::app::AppError;
+
+#[component]
+pub fn CalendarSubscription() -> impl IntoView {
+
+    let reset_token = ServerAction::<ResetCalendarToken>::new();
+    let feed_url = Resource::new(move || reset_token.version().get(), |_| get_own_calendar_url());
+
+    view! {
+        <div class="border border-gray-200 rounded p-4 bg-white text-left mb-6">
+            <h2 class="text-lg font-semibold">"Kalender abonnieren"</h2>
+            <p class="text-gray-700 pb-2">
+                "Alle Events, für die du angemeldet bist. Der Link ist geheim, teile ihn nicht."
+            </p>
+            <Suspense fallback=move || view! { <p>"Lade Daten..."</p> }>
+                {move || {
+                    feed_url.get().map(|result| match result {
+                        Ok(feed_url) => view! { <CalendarUrl url=feed_url/> }.into_any(),
+                        Err(e) => view! { <p>{ e.to_string() }</p> }.into_any(),
+                    })
+                }}
+            </Suspense>
+            <ActionForm action=reset_token attr:class="flex justify-end pt-2">
+                <button
+                    type="submit"
+                    class="px-4 py-2 rounded bg-gray-200 hover:bg-gray-300 text-gray-700"
+                >
+                    "Neuen Link erzeugen"
+                </button>
+            </ActionForm>
+        </div>
+    }
+}
+
+#[component]
+pub fn TeamCalendarLinks(team_id: i64, platoon_id: Option<i64>) -> impl IntoView {
+
+    let base_url = Resource::new(|| {}, |_| get_calendar_base_url());
+
+    view! {
+        <h2 class="text-left text-xl font-semibold py-2">Kalender</h2>
+
+        <Suspense fallback=move || view! { <p>"Lade Daten..."</p> }>
+            {move || {
+                base_url.get().map(|result| match result {
+                    Ok(base_url) => view! {
+                        <div class="grid grid-cols-[auto_1fr] items-center gap-2 text-left">
+                            <span class="text-gray-700">"Team:"</span>
+                            <CalendarUrl url=format!("{}/teams/{}", base_url, team_id)/>
+                            {platoon_id.map(|platoon_id| view! {
+                                <span class="text-gray-700">"Zug:"</span>
+                                <CalendarUrl url=format!("{}/platoons/{}", base_url, platoon_id)/>
+                            })}
+                        </div>
+                    }.into_any(),
+                    Err(e) => view! { <p>{ e.to_string() }</p> }.into_any(),
+                })
+            }}
+        </Suspense>
+    }
+}
+
+#[component]
+fn CalendarUrl(url: String) -> impl IntoView {
+
+    view! {
+        <input
+            type="text"
+            readonly
+            value=url
+            class="w-full border border-gray-300 rounded px-3 py-2 font-mono text-sm bg-gray-50"
+        />
+    }
+}
+
+#[cfg(feature = "ssr")]
+async fn calendar_base_url() -> Result<String, AppError> {
+    use axum::http::{header, HeaderMap};
+
+    let headers: HeaderMap = leptos_axum::extract().await?;
+
+    let host = headers
+        .get(header::HOST)
+        .and_then(|host| host.to_str().ok())
+        .unwrap_or("localhost");
+    let scheme = headers
+        .get("x-forwarded-proto")
+        .and_then(|scheme| scheme.to_str().ok())
+        .unwrap_or("http");
+
+    Ok(format!("{}://{}/calendar", scheme, host))
+}
+
+#[server]
+async fn get_calendar_base_url() -> Result<String, AppError> {
+    calendar_base_url().await
+}
+
+#[server]
+async fn get_own_calendar_url() -> Result<String, AppError> {
+    use crate::auth::current_player;
+    use crate::calendar::generate_calendar_token;
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let player = current_player().await?;
+
+    let token = match database::find_calendar_token(player.id, &pool)? {
+        Some(token) => token,
+        None => {
+            let token = generate_calendar_token();
+            database::save_calendar_token(player.id, &token, &pool)?;
+            token
+        }
+    };
+
+    Ok(format!("{}/players/{}", calendar_base_url().await?, token))
+}
+
+#[server]
+async fn reset_calendar_token() -> Result<(), AppError> {
+    use crate::auth::current_player;
+    use crate::calendar::generate_calendar_token;
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let player = current_player().await?;
+
+    Ok(database::save_calendar_token(player.id, &generate_calendar_token(), &pool)?)
+}
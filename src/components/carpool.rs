@@ -0,0 +1,648 @@
+use leptos::prelude::*;
+
+use crate::app::AppError;
+use crate::components::util::format_datetime;
+use crate::domain::{BookingStatus, Carpool, CarpoolArea, RideOffer};
+
+/// Ride offers and seat requests of an event grouped by area. Only participants offer,
+/// request and book seats, the driver confirms every booking.
+#[component]
+pub fn CarpoolPanel(play_event_id: i64) -> impl IntoView {
+
+    let offer_ride = ServerAction::<OfferRide>::new();
+    let withdraw_offer = ServerAction::<WithdrawRideOffer>::new();
+    let request_ride = ServerAction::<RequestRide>::new();
+    let withdraw_request = ServerAction::<WithdrawRideRequest>::new();
+    let book_seat = ServerAction::<BookRideSeat>::new();
+    let decide_booking = ServerAction::<DecideRideBooking>::new();
+    let cancel_booking = ServerAction::<CancelRideBooking>::new();
+    let mark_seen = ServerAction::<MarkRideBookingsSeen>::new();
+
+    let carpool = Resource::new(
+        move || (
+            offer_ride.version().get(),
+            withdraw_offer.version().get(),
+            request_ride.version().get(),
+            withdraw_request.version().get(),
+            book_seat.version().get(),
+            decide_booking.version().get(),
+            cancel_booking.version().get(),
+            mark_seen.version().get(),
+        ),
+        move |_| get_carpool(play_event_id),
+    );
+
+    let action_error = move || {
+        let errors = [
+            offer_ride.value().get().and_then(|result| result.err()),
+            withdraw_offer.value().get().and_then(|result| result.err()),
+            request_ride.value().get().and_then(|result| result.err()),
+            withdraw_request.value().get().and_then(|result| result.err()),
+            book_seat.value().get().and_then(|result| result.err()),
+            decide_booking.value().get().and_then(|result| result.err()),
+            cancel_booking.value().get().and_then(|result| result.err()),
+            mark_seen.value().get().and_then(|result| result.err()),
+        ];
+
+        errors.into_iter().flatten().next().map(|e| view! {
+            <p class="text-red-600">{ e.to_string() }</p>
+        })
+    };
+
+    view! {
+        <h2 class="text-left text-xl font-semibold py-2">"Fahrgemeinschaften"</h2>
+
+        <Suspense fallback=move || view! { <p>"Lade Daten..."</p> }>
+            {move || {
+                carpool.get().map(|result| match result {
+                    Ok(Carpool { areas, own_offer, own_request, own_ride, participant }) => {
+                        let can_book = participant && own_offer.is_none() && own_ride.is_none();
+
+                        view! {
+                            {own_offer.map(|offer| view! {
+                                <DriverPanel offer=offer decide_booking=decide_booking mark_seen=mark_seen withdraw_offer=withdraw_offer/>
+                            })}
+
+                            {own_ride.map(|(offer, booking)| {
+                                view! {
+                                    <ActionForm action=cancel_booking attr:class="flex items-center justify-end gap-4 py-2">
+                                        <span class="text-gray-700">
+                                            {
+                                                format!(
+                                                    "Mitfahrt bei {} ab {} um {}: {}",
+                                                    offer.driver_name,
+                                                    offer.start_location,
+                                                    format_datetime(&offer.departs_at),
+                                                    booking.status,
+                                                )
+                                            }
+                                        </span>
+                                        <input type="hidden" name="id" value=booking.id/>
+                                        <button
+                                            type="submit"
+                                            class="px-4 py-2 rounded bg-gray-200 hover:bg-gray-300 text-gray-700"
+                                        >
+                                            "Absagen"
+                                        </button>
+                                    </ActionForm>
+                                }
+                            })}
+
+                            {own_request.map(|request| view! {
+                                <ActionForm action=withdraw_request attr:class="flex items-center justify-end gap-4 py-2">
+                                    <span class="text-gray-700">
+                                        { format!("Du suchst eine Mitfahrt aus {}", request.area) }
+                                    </span>
+                                    <input type="hidden" name="id" value=request.id/>
+                                    <button
+                                        type="submit"
+                                        class="px-4 py-2 rounded bg-gray-200 hover:bg-gray-300 text-gray-700"
+                                    >
+                                        "Gesuch zurückziehen"
+                                    </button>
+                                </ActionForm>
+                            })}
+
+                            <Show when=move || participant>
+                                <CarpoolForms play_event_id=play_event_id offer_ride=offer_ride request_ride=request_ride/>
+                            </Show>
+
+                            {
+                                if areas.is_empty() {
+                                    view! { <p class="text-gray-700">"Noch keine Angebote oder Gesuche"</p> }.into_any()
+                                } else {
+                                    areas
+                                        .into_iter()
+                                        .map(|area| view! { <AreaGroup area=area can_book=can_book book_seat=book_seat/> })
+                                        .collect_view()
+                                        .into_any()
+                                }
+                            }
+                        }.into_any()
+                    },
+                    Err(e) => view! { <p>{ e.to_string() }</p> }.into_any(),
+                })
+            }}
+        </Suspense>
+
+        { action_error }
+    }
+}
+
+#[component]
+fn CarpoolForms(
+    play_event_id: i64,
+    offer_ride: ServerAction<OfferRide>,
+    request_ride: ServerAction<RequestRide>,
+) -> impl IntoView {
+
+    view! {
+        <details class="py-2">
+            <summary class="text-left cursor-pointer text-gray-700">"Mitfahrt anbieten"</summary>
+            <ActionForm action=offer_ride attr:class="grid grid-cols-[auto_1fr] items-center gap-4 py-2">
+                <input type="hidden" name="play_event_id" value=play_event_id/>
+
+                <label for="area" class="text-left text-gray-700">"Gegend:"</label>
+                <input
+                    type="text"
+                    name="area"
+                    class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+                    required
+                />
+
+                <label for="start_location" class="text-left text-gray-700">"Startort:"</label>
+                <input
+                    type="text"
+                    name="start_location"
+                    class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+                    required
+                />
+
+                <label for="seats" class="text-left text-gray-700">"Freie Plätze:"</label>
+                <input
+                    type="number"
+                    min="1"
+                    name="seats"
+                    class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+                    required
+                />
+
+                <label for="departs_at" class="text-left text-gray-700">"Abfahrt:"</label>
+                <input
+                    type="datetime-local"
+                    name="departs_at"
+                    class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+                    required
+                />
+
+                <label for="notes" class="text-left text-gray-700">"Hinweise:"</label>
+                <input
+                    type="text"
+                    name="notes"
+                    class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+                />
+
+                <div class="col-span-2 flex justify-end">
+                    <button type="submit" class="px-4 py-2 rounded bg-blue-600 hover:bg-blue-700 text-white">
+                        "Anbieten"
+                    </button>
+                </div>
+            </ActionForm>
+        </details>
+
+        <details class="py-2">
+            <summary class="text-left cursor-pointer text-gray-700">"Mitfahrt suchen"</summary>
+            <ActionForm action=request_ride attr:class="grid grid-cols-[auto_1fr] items-center gap-4 py-2">
+                <input type="hidden" name="play_event_id" value=play_event_id/>
+
+                <label for="area" class="text-left text-gray-700">"Gegend:"</label>
+                <input
+                    type="text"
+                    name="area"
+                    class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+                    required
+                />
+
+                <label for="notes" class="text-left text-gray-700">"Hinweise:"</label>
+                <input
+                    type="text"
+                    name="notes"
+                    class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+                />
+
+                <div class="col-span-2 flex justify-end">
+                    <button type="submit" class="px-4 py-2 rounded bg-blue-600 hover:bg-blue-700 text-white">
+                        "Suchen"
+                    </button>
+                </div>
+            </ActionForm>
+        </details>
+    }
+}
+
+#[component]
+fn AreaGroup(area: CarpoolArea, can_book: bool, book_seat: ServerAction<BookRideSeat>) -> impl IntoView {
+
+    view! {
+        <h3 class="text-left text-lg font-semibold py-2">{ area.name }</h3>
+        <table class="w-full border-collapse">
+            <tbody>
+                {
+                    area.offers
+                        .into_iter()
+                        .map(|offer| {
+                            let free_seats = offer.free_seats();
+                            let bookable = can_book && free_seats > 0;
+
+                            view! {
+                                <tr>
+                                    <th class="text-left py-2 px-4 border-b">{ offer.driver_name }</th>
+                                    <th class="text-left py-2 px-4 border-b">{ offer.start_location }</th>
+                                    <th class="text-left py-2 px-4 border-b">{ format_datetime(&offer.departs_at) }</th>
+                                    <th class="text-left py-2 px-4 border-b">{ format!("{} von {} Plätzen frei", free_seats, offer.seats) }</th>
+                                    <th class="text-left py-2 px-4 border-b">{ offer.notes }</th>
+                                    <th class="text-left py-2 px-4 border-b">
+                                        {bookable.then(|| view! {
+                                            <ActionForm action=book_seat>
+                                                <input type="hidden" name="ride_offer_id" value=offer.id/>
+                                                <button
+                                                    type="submit"
+                                                    class="px-4 py-2 rounded bg-blue-600 hover:bg-blue-700 text-white"
+                                                >
+                                                    "Platz buchen"
+                                                </button>
+                                            </ActionForm>
+                                        })}
+                                    </th>
+                                </tr>
+                            }
+                        })
+                        .collect_view()
+                }
+                {
+                    area.requests
+                        .into_iter()
+                        .map(|request| view! {
+                            <tr>
+                                <th class="text-left py-2 px-4 border-b">{ request.passenger_name }</th>
+                                <th class="text-left py-2 px-4 border-b" colspan="3">"sucht eine Mitfahrt"</th>
+                                <th class="text-left py-2 px-4 border-b">{ request.notes }</th>
+                                <th class="text-left py-2 px-4 border-b"></th>
+                            </tr>
+                        })
+                        .collect_view()
+                }
+            </tbody>
+        </table>
+    }
+}
+
+/// The driver's own offer with bookings to confirm and the changes they have not seen yet.
+#[component]
+fn DriverPanel(
+    offer: RideOffer,
+    decide_booking: ServerAction<DecideRideBooking>,
+    mark_seen: ServerAction<MarkRideBookingsSeen>,
+    withdraw_offer: ServerAction<WithdrawRideOffer>,
+) -> impl IntoView {
+
+    let unseen_changes = offer.unseen_changes()
+        .into_iter()
+        .map(|booking| match booking.status {
+            BookingStatus::Cancelled => format!("{} hat den Platz freigegeben", booking.passenger_name),
+            _ => format!("{} möchte mitfahren", booking.passenger_name),
+        })
+        .collect::<Vec<_>>();
+
+    let bookings = offer.bookings
+        .iter()
+        .filter(|booking| booking.status.is_active())
+        .cloned()
+        .collect::<Vec<_>>();
+
+    view! {
+        <div class="border border-gray-300 rounded p-4 my-2">
+            <div class="flex items-center justify-between gap-4">
+                <span class="text-left font-semibold">
+                    {
+                        format!(
+                            "Dein Angebot ab {} um {}: {} von {} Plätzen frei",
+                            offer.start_location,
+                            format_datetime(&offer.departs_at),
+                            offer.free_seats(),
+                            offer.seats,
+                        )
+                    }
+                </span>
+                <ActionForm action=withdraw_offer>
+                    <input type="hidden" name="id" value=offer.id/>
+                    <button type="submit" class="px-4 py-2 rounded bg-red-600 hover:bg-red-700 text-white">
+                        "Angebot zurückziehen"
+                    </button>
+                </ActionForm>
+            </div>
+
+            {(!unseen_changes.is_empty()).then(|| view! {
+                <ActionForm action=mark_seen attr:class="flex items-center justify-between gap-4 py-2 bg-yellow-100 px-2 rounded">
+                    <ul class="text-left">
+                        { unseen_changes.into_iter().map(|change| view! { <li>{ change }</li> }).collect_view() }
+                    </ul>
+                    <input type="hidden" name="ride_offer_id" value=offer.id/>
+                    <button type="submit" class="px-4 py-2 rounded bg-gray-200 hover:bg-gray-300 text-gray-700">
+                        "Gesehen"
+                    </button>
+                </ActionForm>
+            })}
+
+            <table class="w-full border-collapse">
+                <tbody>
+                    {
+                        bookings
+                            .into_iter()
+                            .map(|booking| view! {
+                                <tr>
+                                    <th class="text-left py-2 px-4 border-b">{ booking.passenger_name }</th>
+                                    <th class="text-left py-2 px-4 border-b">{ booking.status.to_string() }</th>
+                                    <th class="text-left py-2 px-4 border-b">
+                                        {(booking.status == BookingStatus::Requested).then(|| view! {
+                                            <div class="flex gap-2 justify-end">
+                                                <ActionForm action=decide_booking>
+                                                    <input type="hidden" name="id" value=booking.id/>
+                                                    <input type="hidden" name="confirm" value="true"/>
+                                                    <button
+                                                        type="submit"
+                                                        class="px-4 py-2 rounded bg-blue-600 hover:bg-blue-700 text-white"
+                                                    >
+                                                        "Bestätigen"
+                                                    </button>
+                                                </ActionForm>
+                                                <ActionForm action=decide_booking>
+                                                    <input type="hidden" name="id" value=booking.id/>
+                                                    <input type="hidden" name="confirm" value="false"/>
+                                                    <button
+                                                        type="submit"
+                                                        class="px-4 py-2 rounded bg-gray-200 hover:bg-gray-300 text-gray-700"
+                                                    >
+                                                        "Ablehnen"
+                                                    </button>
+                                                </ActionForm>
+                                            </div>
+                                        })}
+                                    </th>
+                                </tr>
+                            })
+                            .collect_view()
+                    }
+                </tbody>
+            </table>
+        </div>
+    }
+}
+
+#[server]
+async fn get_carpool(play_event_id: i64) -> Result<Carpool, AppError> {
+    use crate::auth::current_player;
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let player = current_player().await?;
+    let (offers, requests) = database::get_carpool_for_event(play_event_id, &pool)?;
+
+    Ok(Carpool::new(offers, requests, player.id, is_participant(play_event_id, player.id, &pool)?))
+}
+
+#[cfg(feature = "ssr")]
+fn is_participant(play_event_id: i64, player_id: i64, pool: &crate::database::DieselPool) -> Result<bool, AppError> {
+    use crate::database;
+    use crate::domain::{Registration, RegistrationStatus};
+
+    let registration: Option<Registration> = database::find_registration(play_event_id, player_id, pool)?
+        .map(Into::into);
+
+    Ok(registration.is_some_and(|registration| registration.status != RegistrationStatus::Withdrawn))
+}
+
+#[cfg(feature = "ssr")]
+fn require_participant(play_event_id: i64, player_id: i64, pool: &crate::database::DieselPool) -> Result<(), AppError> {
+    use crate::auth::AuthError;
+
+    if is_participant(play_event_id, player_id, pool)? {
+        Ok(())
+    } else {
+        Err(AppError::AuthError(AuthError::Forbidden))
+    }
+}
+
+#[server]
+async fn offer_ride(
+    play_event_id: i64,
+    area: String,
+    start_location: String,
+    seats: i32,
+    departs_at: String,
+    notes: String,
+) -> Result<(), AppError> {
+    use crate::auth::current_player;
+    use crate::components::util::parse_datetime_input;
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let player = current_player().await?;
+    require_participant(play_event_id, player.id, &pool)?;
+
+    if area.trim().is_empty() {
+        return Err(AppError::InvalidInput("Gegend".to_string()));
+    }
+
+    if seats < 1 {
+        return Err(AppError::InvalidInput("Freie Plätze".to_string()));
+    }
+
+    let departs_at = parse_datetime_input(&departs_at)
+        .ok_or(AppError::InvalidInput("Abfahrt".to_string()))?;
+
+    database::create_ride_offer(RideOffer {
+        id: None,
+        play_event_id,
+        driver_id: player.id,
+        driver_name: String::new(),
+        area: area.trim().to_string(),
+        start_location: start_location.trim().to_string(),
+        seats,
+        departs_at,
+        notes: notes.trim().to_string(),
+        bookings: Vec::new(),
+    }, &pool)?;
+
+    Ok(())
+}
+
+#[server]
+async fn withdraw_ride_offer(id: i64) -> Result<(), AppError> {
+    use crate::auth::{current_player, AuthError};
+    use crate::database::{self, DatabaseError, DieselPool};
+    use crate::notification::notify_ride_cancelled;
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let player = current_player().await?;
+    let offer = database::find_ride_offer_for_id(id, &pool)?
+        .ok_or(DatabaseError::EntityNotFound)?;
+
+    if offer.driver_id != player.id {
+        return Err(AppError::AuthError(AuthError::Forbidden));
+    }
+
+    let passenger_ids = database::cancel_ride_offer(id, &pool)?;
+
+    Ok(notify_ride_cancelled(offer.play_event_id, &player.tag_name, &passenger_ids, &pool)?)
+}
+
+#[server]
+async fn request_ride(play_event_id: i64, area: String, notes: String) -> Result<(), AppError> {
+    use crate::auth::current_player;
+    use crate::database::{self, DieselPool};
+    use crate::domain::RideRequest;
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let player = current_player().await?;
+    require_participant(play_event_id, player.id, &pool)?;
+
+    if area.trim().is_empty() {
+        return Err(AppError::InvalidInput("Gegend".to_string()));
+    }
+
+    database::create_ride_request(RideRequest {
+        id: None,
+        play_event_id,
+        passenger_id: player.id,
+        passenger_name: String::new(),
+        area: area.trim().to_string(),
+        notes: notes.trim().to_string(),
+    }, &pool)?;
+
+    Ok(())
+}
+
+#[server]
+async fn withdraw_ride_request(id: i64) -> Result<(), AppError> {
+    use crate::auth::{current_player, AuthError};
+    use crate::database::{self, DatabaseError, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let player = current_player().await?;
+    let request = database::find_ride_request_for_id(id, &pool)?
+        .ok_or(DatabaseError::EntityNotFound)?;
+
+    if request.passenger_id != player.id {
+        return Err(AppError::AuthError(AuthError::Forbidden));
+    }
+
+    Ok(database::delete_ride_request(id, &pool)?)
+}
+
+#[server]
+async fn book_ride_seat(ride_offer_id: i64) -> Result<(), AppError> {
+    use crate::auth::current_player;
+    use crate::database::{self, DatabaseError, DieselPool};
+    use crate::domain::{Notification, NotificationKind};
+    use crate::notification::notify;
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let player = current_player().await?;
+    let offer = database::find_ride_offer_for_id(ride_offer_id, &pool)?
+        .ok_or(DatabaseError::EntityNotFound)?;
+
+    require_participant(offer.play_event_id, player.id, &pool)?;
+
+    if offer.driver_id == player.id {
+        return Err(DatabaseError::RideAlreadyOffered.into());
+    }
+
+    database::book_ride_seat(ride_offer_id, player.id, &pool)?;
+
+    Ok(notify(&[offer.driver_id], Notification::new(
+        NotificationKind::Carpool,
+        format!("{} möchte bei dir mitfahren", player.tag_name),
+        "Bitte bestätige oder lehne die Anfrage ab.",
+        format!("/events/{}", offer.play_event_id),
+    ), &pool)?)
+}
+
+/// Only the driver confirms or declines seats in their car.
+#[server]
+async fn decide_ride_booking(id: i64, confirm: bool) -> Result<(), AppError> {
+    use crate::auth::{current_player, AuthError};
+    use crate::database::{self, DatabaseError, DieselPool};
+    use crate::domain::{Notification, NotificationKind};
+    use crate::notification::notify;
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let player = current_player().await?;
+    let booking = database::find_ride_booking_for_id(id, &pool)?
+        .ok_or(DatabaseError::EntityNotFound)?;
+    let offer = database::find_ride_offer_for_id(booking.ride_offer_id, &pool)?
+        .ok_or(DatabaseError::EntityNotFound)?;
+
+    if offer.driver_id != player.id {
+        return Err(AppError::AuthError(AuthError::Forbidden));
+    }
+
+    database::decide_ride_booking(id, confirm, &pool)?;
+
+    let title = if confirm {
+        format!("{} nimmt dich mit", player.tag_name)
+    } else {
+        format!("{} kann dich leider nicht mitnehmen", player.tag_name)
+    };
+
+    Ok(notify(&[booking.passenger_id], Notification::new(
+        NotificationKind::Carpool,
+        title,
+        format!("Abfahrt ab {}", offer.start_location),
+        format!("/events/{}", offer.play_event_id),
+    ), &pool)?)
+}
+
+#[server]
+async fn cancel_ride_booking(id: i64) -> Result<(), AppError> {
+    use crate::auth::{current_player, AuthError};
+    use crate::database::{self, DatabaseError, DieselPool};
+    use crate::domain::{Notification, NotificationKind};
+    use crate::notification::notify;
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let player = current_player().await?;
+    let booking = database::find_ride_booking_for_id(id, &pool)?
+        .ok_or(DatabaseError::EntityNotFound)?;
+
+    if booking.passenger_id != player.id {
+        return Err(AppError::AuthError(AuthError::Forbidden));
+    }
+
+    database::cancel_ride_booking(id, &pool)?;
+
+    let offer = database::find_ride_offer_for_id(booking.ride_offer_id, &pool)?
+        .ok_or(DatabaseError::EntityNotFound)?;
+
+    Ok(notify(&[offer.driver_id], Notification::new(
+        NotificationKind::Carpool,
+        format!("{} hat den Platz in deinem Auto freigegeben", player.tag_name),
+        "",
+        format!("/events/{}", offer.play_event_id),
+    ), &pool)?)
+}
+
+#[server]
+async fn mark_ride_bookings_seen(ride_offer_id: i64) -> Result<(), AppError> {
+    use crate::auth::{current_player, AuthError};
+    use crate::database::{self, DatabaseError, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let player = current_player().await?;
+    let offer = database::find_ride_offer_for_id(ride_offer_id, &pool)?
+        .ok_or(DatabaseError::EntityNotFound)?;
+
+    if offer.driver_id != player.id {
+        return Err(AppError::AuthError(AuthError::Forbidden));
+    }
+
+    Ok(database::mark_ride_bookings_seen(ride_offer_id, &pool)?)
+}
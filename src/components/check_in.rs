@@ -0,0 +1,259 @@
+use leptos::prelude::*;
+use leptos::Params;
+use leptos_router::{components::A, hooks::use_params, params::Params};
+
+use crate::app::AppError;
+use crate::components::gear::ChronoList;
+use crate::components::util::{format_datetime, BackButton, BoolSymbol};
+use crate::domain::{CheckInList, CheckInResult, Participant, RegistrationStatus, Ticket};
+
+#[component]
+pub fn TicketView(ticket: Resource<Result<Option<Ticket>, AppError>>) -> impl IntoView {
+
+    view! {
+        <Suspense fallback=move || view! { <p>"Lade Daten..."</p> }>
+            {move || {
+                ticket.get().map(|result| match result {
+                    Ok(Some(ticket)) => view! {
+                        <div class="flex flex-col items-center py-4">
+                            <div class="w-52 h-52" inner_html=ticket.qr_svg></div>
+                            <p class="font-mono text-sm text-gray-700">{ ticket.token }</p>
+                        </div>
+                    }.into_any(),
+                    Ok(None) => ().into_any(),
+                    Err(e) => view! { <p>{ e.to_string() }</p> }.into_any(),
+                })
+            }}
+        </Suspense>
+    }
+}
+
+#[component]
+pub fn PlayEventCheckIn() -> impl IntoView {
+
+    let play_event_id = use_params::<CheckInPlayEventIdParameter>();
+
+    let check_in = ServerAction::<CheckInTicket>::new();
+    let results = RwSignal::new(Vec::<CheckInResult>::new());
+
+    Effect::new(move |_| {
+        if let Some(Ok(result)) = check_in.value().get() {
+            results.update(|results| results.insert(0, result));
+        }
+    });
+
+    let check_in_list = Resource::new(
+        move || (play_event_id.read().clone(), check_in.version().get()),
+        move |(params_result, _)| get_check_in_list(params_result.unwrap().id.unwrap()),
+    );
+
+    view! {
+        <BackButton/>
+        <div class="p-8 max-w-4xl mx-auto">
+            <div class="flex items-center justify-between mb-6">
+                <h1 class="text-2xl font-semibold">
+                    "Check-in"
+                </h1>
+            </div>
+
+            <ActionForm action=check_in attr:class="flex gap-2 mb-6">
+                <input type="hidden" name="play_event_id" value=move || play_event_id.get().ok().and_then(|params| params.id)/>
+                <input
+                    type="text"
+                    name="token"
+                    placeholder="Ticket scannen oder eingeben"
+                    autofocus
+                    autocomplete="off"
+                    class="flex-grow border border-gray-300 rounded px-3 py-2 font-mono focus:outline-none focus:ring focus:border-blue-300"
+                    prop:value=move || { check_in.version().get(); String::new() }
+                    required
+                />
+                <button
+                    type="submit"
+                    class="px-4 py-2 rounded bg-blue-600 hover:bg-blue-700 text-white"
+                >
+                    "Einchecken"
+                </button>
+            </ActionForm>
+
+            {move || check_in.value().get().and_then(|result| result.err()).map(|e| view! {
+                <p class="text-red-600">{ e.to_string() }</p>
+            })}
+
+            <ul class="space-y-2 mb-6">
+                {move || {
+                    results
+                        .get()
+                        .into_iter()
+                        .map(|result| {
+                            let class = if result.outcome.is_ok() {
+                                "text-left px-4 py-2 rounded bg-green-100 text-green-800"
+                            } else {
+                                "text-left px-4 py-2 rounded bg-red-100 text-red-800"
+                            };
+
+                            view! {
+                                <li class=class>
+                                    { result.tag_name.unwrap_or("Unbekannt".to_string()) } ": " { result.outcome.to_string() }
+                                </li>
+                            }
+                        })
+                        .collect_view()
+                }}
+            </ul>
+
+            <Suspense fallback=move || view! { <p>"Lade Daten..."</p> }>
+                {move || {
+                    check_in_list.get().map(|result| match result {
+                        Ok(list) => view! { <CheckInTable list=list/> }.into_any(),
+                        Err(e) => view! { <p>{ e.to_string() }</p> }.into_any(),
+                    })
+                }}
+            </Suspense>
+
+            {move || play_event_id.get().ok().and_then(|params| params.id).map(|play_event_id| view! {
+                <ChronoList play_event_id=play_event_id/>
+            })}
+        </div>
+    }
+}
+
+#[component]
+fn CheckInTable(list: CheckInList) -> impl IntoView {
+    let CheckInList { participants, accepted_player_ids } = list;
+    let registered: Vec<Participant> = participants
+        .into_iter()
+        .filter(|participant| participant.status == RegistrationStatus::Registered)
+        .collect();
+    let checked_in = registered
+        .iter()
+        .filter(|participant| participant.checked_in_at.is_some())
+        .count();
+    let total = registered.len();
+
+    view! {
+        <h2 class="text-left text-xl font-semibold py-2">
+            "Anwesend: " { checked_in } " / " { total }
+        </h2>
+
+        <div class="overflow-x-auto">
+            <table class="min-w-full border border-gray-200 shadow-sm rounded-md bg-white">
+                <thead class="bg-gray-100 text-gray-700">
+                    <tr>
+                        <th class="text-left py-2 px-4 border-b">Spielername</th>
+                        <th class="text-left py-2 px-4 border-b">Team</th>
+                        <th class="py-2 px-4 border-b">Anwesend</th>
+                        <th class="text-left py-2 px-4 border-b">Eingecheckt um</th>
+                        <th class="py-2 px-4 border-b">Regeln akzeptiert</th>
+                    </tr>
+                </thead>
+                <tbody>
+                    {
+                        registered
+                            .into_iter()
+                            .map(|participant| view! {
+                                <tr class="hover:bg-gray-50">
+                                    <th class="text-left py-2 px-4 border-b">
+                                        <A href=format!("/players/{}", participant.player_id) attr:class="hover:underline">{participant.tag_name}</A>
+                                    </th>
+                                    <th class="text-left py-2 px-4 border-b">
+                                        { participant.team_name.unwrap_or("Ohne Team".to_string()) }
+                                    </th>
+                                    <th class="py-2 px-4 border-b">
+                                        <div class="flex justify-center">
+                                            <BoolSymbol value=participant.checked_in_at.is_some()/>
+                                        </div>
+                                    </th>
+                                    <th class="text-left py-2 px-4 border-b">
+                                        { participant.checked_in_at.as_ref().map(format_datetime) }
+                                    </th>
+                                    <th class="py-2 px-4 border-b">
+                                        <div class="flex justify-center">
+                                            <BoolSymbol value=accepted_player_ids.contains(&participant.player_id)/>
+                                        </div>
+                                    </th>
+                                </tr>
+                            })
+                            .collect_view()
+                    }
+                </tbody>
+            </table>
+        </div>
+    }
+}
+
+#[derive(Params, PartialEq, Clone)]
+struct CheckInPlayEventIdParameter {
+    id: Option<i64>,
+}
+
+#[server]
+pub async fn get_own_ticket(play_event_id: i64) -> Result<Option<Ticket>, AppError> {
+    use crate::auth::current_player;
+    use crate::database::{self, DieselPool};
+    use crate::domain::Registration;
+    use crate::ticket::{render_ticket_svg, sign_ticket, TicketSecret};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+    let secret = use_context::<TicketSecret>()
+        .ok_or(AppError::MissingContext)?;
+
+    let player = current_player().await?;
+    let registration: Option<Registration> = database::find_registration(play_event_id, player.id, &pool)?
+        .map(Into::into);
+
+    let ticket = registration
+        .filter(|registration| registration.status == RegistrationStatus::Registered)
+        .map(|registration| {
+            let token = sign_ticket(registration.id, &secret);
+            let qr_svg = render_ticket_svg(&token);
+
+            Ticket { token, qr_svg }
+        });
+
+    Ok(ticket)
+}
+
+#[server]
+async fn check_in_ticket(play_event_id: i64, token: String) -> Result<CheckInResult, AppError> {
+    use crate::auth::require_organizer;
+    use crate::database::{self, DieselPool};
+    use crate::domain::CheckInOutcome;
+    use crate::ticket::{verify_ticket, TicketSecret};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+    let secret = use_context::<TicketSecret>()
+        .ok_or(AppError::MissingContext)?;
+
+    let organizer = require_organizer(play_event_id, &pool).await?;
+
+    let Some(registration_id) = verify_ticket(&token, &secret) else {
+        return Ok(CheckInResult::invalid(CheckInOutcome::InvalidTicket));
+    };
+
+    Ok(database::check_in_registration(registration_id, play_event_id, organizer.id, &pool)?)
+}
+
+#[server]
+async fn get_check_in_list(play_event_id: i64) -> Result<CheckInList, AppError> {
+    use crate::auth::require_organizer;
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    require_organizer(play_event_id, &pool).await?;
+
+    let participants = database::get_participants_for_event(play_event_id, &pool)?;
+    let player_ids = participants
+        .iter()
+        .map(|participant| participant.player_id)
+        .collect();
+
+    Ok(CheckInList {
+        accepted_player_ids: database::get_player_ids_with_current_acceptance(player_ids, &pool)?,
+        participants,
+    })
+}
@@ -0,0 +1,383 @@
+use leptos::prelude::*;
+use leptos::server_fn::codec::{MultipartData, MultipartFormData};
+use leptos::wasm_bindgen::JsCast;
+use leptos::web_sys::{FormData, HtmlFormElement, SubmitEvent};
+use serde::{Deserialize, Serialize};
+
+use crate::app::AppError;
+use crate::components::util::{format_date, BoolSymbol};
+use crate::domain::{ParentalConsent, PlayerConsents};
+
+#[component]
+pub fn ParentalConsents(player_id: i64) -> impl IntoView {
+
+    let create_consent = ServerAction::<CreateParentalConsent>::new();
+    let delete_consent = ServerAction::<DeleteParentalConsent>::new();
+    let upload_consent = Action::new_local(|data: &FormData| upload_parental_consent(data.clone().into()));
+
+    let consents = Resource::new(
+        move || (create_consent.version().get(), delete_consent.version().get(), upload_consent.version().get()),
+        move |_| get_player_consents(player_id),
+    );
+
+    let on_upload = move |ev: SubmitEvent| {
+        ev.prevent_default();
+
+        let form = ev.target().unwrap().unchecked_into::<HtmlFormElement>();
+        let data = FormData::new_with_form(&form).unwrap();
+        upload_consent.dispatch_local(data);
+    };
+
+    let action_error = move || {
+        let create_error = create_consent.value().get().and_then(|result| result.err());
+        let delete_error = delete_consent.value().get().and_then(|result| result.err());
+        let upload_error = upload_consent.value().get().and_then(|result| result.err());
+
+        create_error.or(delete_error).or(upload_error).map(|e| view! {
+            <p class="text-red-600">{ e.to_string() }</p>
+        })
+    };
+
+    view! {
+        <Suspense fallback=move || view! { <p>"Lade Daten..."</p> }>
+            {move || {
+                consents.get().map(|result| match result {
+                    Ok(Some(PlayerConsents { consents, minor, can_manage })) => view! {
+                        <h2 class="text-left text-xl font-semibold py-2">Einverständniserklärungen</h2>
+
+                        <p class="text-left text-gray-700 pb-2 flex items-center gap-2">
+                            "Minderjährig:" <BoolSymbol value=minor/>
+                        </p>
+
+                        {
+                            if consents.is_empty() {
+                                view! { <p class="text-left text-gray-700">"Keine Einverständniserklärung hinterlegt"</p> }.into_any()
+                            } else {
+                                view! {
+                                    <div class="overflow-x-auto">
+                                        <table class="min-w-full border border-gray-200 shadow-sm rounded-md bg-white">
+                                            <thead class="bg-gray-100 text-gray-700">
+                                                <tr>
+                                                    <th class="text-left py-2 px-4 border-b">Erziehungsberechtigte(r)</th>
+                                                    <th class="text-left py-2 px-4 border-b">Art</th>
+                                                    <th class="text-left py-2 px-4 border-b">Gültig bis</th>
+                                                    <th class="text-left py-2 px-4 border-b">Notiz</th>
+                                                    <th class="py-2 px-4 border-b"></th>
+                                                </tr>
+                                            </thead>
+                                            <tbody>
+                                                {
+                                                    consents.into_iter().map(|consent: ParentalConsent| view! {
+                                                        <tr class="hover:bg-gray-50">
+                                                            <th class="text-left py-2 px-4 border-b">{ consent.guardian_name.clone() }</th>
+                                                            <th class="text-left py-2 px-4 border-b">{ consent.kind.to_string() }</th>
+                                                            <th class="text-left py-2 px-4 border-b">{ format_date(&consent.valid_until) }</th>
+                                                            <th class="text-left py-2 px-4 border-b">
+                                                                { consent.note.clone() }
+                                                                {consent.has_document.then(|| view! {
+                                                                    <a href=format!("/consents/{}/document", consent.id.unwrap())
+                                                                        rel="external"
+                                                                        target="_blank"
+                                                                        class="pl-2 text-blue-600 hover:underline"
+                                                                    >
+                                                                        "Dokument anzeigen"
+                                                                    </a>
+                                                                })}
+                                                            </th>
+                                                            <th class="py-2 px-4 border-b">
+                                                                <Show when=move || can_manage>
+                                                                    <ActionForm action=delete_consent>
+                                                                        <input type="hidden" name="id" value=consent.id/>
+                                                                        <button
+                                                                            type="submit"
+                                                                            class="px-4 py-2 rounded bg-gray-200 hover:bg-gray-300 text-gray-700"
+                                                                        >
+                                                                            "Entfernen"
+                                                                        </button>
+                                                                    </ActionForm>
+                                                                </Show>
+                                                            </th>
+                                                        </tr>
+                                                    }).collect_view()
+                                                }
+                                            </tbody>
+                                        </table>
+                                    </div>
+                                }.into_any()
+                            }
+                        }
+
+                        <Show when=move || can_manage>
+                            <SignedConsentForm player_id=player_id action=create_consent/>
+                            <UploadConsentForm player_id=player_id on_upload=on_upload/>
+                        </Show>
+                    }.into_any(),
+                    Ok(None) => ().into_any(),
+                    Err(e) => view! { <p>{ e.to_string() }</p> }.into_any(),
+                })
+            }}
+        </Suspense>
+
+        { action_error }
+    }
+}
+
+#[component]
+fn ConsentFields(field: fn(&str) -> String) -> impl IntoView {
+    view! {
+        <label for=field("guardian_name") class="text-left text-gray-700">
+            "Erziehungsberechtigte(r):"
+        </label>
+        <input
+            type="text"
+            name=field("guardian_name")
+            class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+            required
+        />
+
+        <label for=field("valid_until") class="text-left text-gray-700">
+            "Gültig bis:"
+        </label>
+        <input
+            type="date"
+            name=field("valid_until")
+            class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+            required
+        />
+    }
+}
+
+/// The guardian signs in person on the admin's device.
+#[component]
+fn SignedConsentForm(player_id: i64, action: ServerAction<CreateParentalConsent>) -> impl IntoView {
+    let field = |field: &str| format!("consent_form[{}]", field);
+
+    view! {
+        <ActionForm action=action attr:class="grid grid-cols-[auto_1fr] items-center gap-4 pt-4">
+            <input type="hidden" name=field("player_id") value=player_id/>
+
+            <h3 class="col-span-2 text-left font-semibold">"Digital unterschreiben"</h3>
+
+            <ConsentFields field=field/>
+
+            <label class="col-span-2 flex items-center gap-2 text-left text-gray-700">
+                <input type="checkbox" name=field("confirmed") value="true" required/>
+                "Die/der Erziehungsberechtigte bestätigt vor Ort, erziehungsberechtigt zu sein, und stimmt der Teilnahme an Spielen zu."
+            </label>
+
+            <div class="col-span-2 flex justify-end">
+                <button
+                    type="submit"
+                    class="px-4 py-2 rounded bg-blue-600 hover:bg-blue-700 text-white"
+                >
+                    "Einverständnis speichern"
+                </button>
+            </div>
+        </ActionForm>
+    }
+}
+
+#[component]
+fn UploadConsentForm<F>(player_id: i64, on_upload: F) -> impl IntoView
+where
+    F: Fn(SubmitEvent) + 'static,
+{
+    let field = |field: &str| field.to_string();
+
+    view! {
+        <form on:submit=on_upload class="grid grid-cols-[auto_1fr] items-center gap-4 pt-4">
+            <input type="hidden" name="player_id" value=player_id/>
+
+            <h3 class="col-span-2 text-left font-semibold">"Papierformular hochladen"</h3>
+
+            <ConsentFields field=field/>
+
+            <label for="note" class="text-left text-gray-700">
+                "Notiz:"
+            </label>
+            <input
+                type="text"
+                name="note"
+                class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+            />
+
+            <label for="document" class="text-left text-gray-700">
+                "Scan (PDF, PNG oder JPEG, max. 5 MB):"
+            </label>
+            <input type="file" name="document" accept="application/pdf,image/png,image/jpeg" required/>
+
+            <div class="col-span-2 flex justify-end">
+                <button
+                    type="submit"
+                    class="px-4 py-2 rounded bg-blue-600 hover:bg-blue-700 text-white"
+                >
+                    "Hochladen"
+                </button>
+            </div>
+        </form>
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct ConsentForm {
+    player_id: i64,
+    guardian_name: String,
+    valid_until: String,
+    #[serde(default)]
+    confirmed: bool,
+}
+
+/// The guardian and expiry entered for both kinds of consent.
+#[cfg(feature = "ssr")]
+fn parse_consent_details(guardian_name: &str, valid_until: &str) -> Result<(String, chrono::NaiveDate), AppError> {
+    use crate::components::util::parse_date_input;
+
+    let guardian_name = guardian_name.trim().to_string();
+
+    if guardian_name.is_empty() {
+        return Err(AppError::InvalidInput("Erziehungsberechtigte(r)".to_string()));
+    }
+
+    let valid_until = parse_date_input(valid_until)
+        .filter(|valid_until| *valid_until >= chrono::Utc::now().date_naive())
+        .ok_or(AppError::InvalidInput("Gültig bis".to_string()))?;
+
+    Ok((guardian_name, valid_until))
+}
+
+#[server]
+async fn get_player_consents(player_id: i64) -> Result<Option<PlayerConsents>, AppError> {
+    use crate::auth::{can_see_private_fields, current_player};
+    use crate::database::{self, DatabaseError, DieselPool};
+    use crate::domain::{age_on, Player, ADULT_AGE};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    if !can_see_private_fields(player_id, &pool).await? {
+        return Ok(None);
+    }
+
+    let viewer = current_player().await?;
+    let player: Player = database::find_player_for_id(player_id, &pool)?
+        .ok_or(DatabaseError::EntityNotFound)?
+        .into();
+
+    let today = chrono::Utc::now().date_naive();
+
+    Ok(Some(PlayerConsents {
+        consents: database::get_parental_consents_for_player(player_id, &pool)?,
+        minor: player.birth_date.is_some_and(|birth_date| age_on(birth_date, today) < ADULT_AGE),
+        can_manage: viewer.admin,
+    }))
+}
+
+/// Only admins record consents, a minor could otherwise consent for themselves.
+#[server]
+async fn create_parental_consent(consent_form: ConsentForm) -> Result<(), AppError> {
+    use crate::auth::require_admin;
+    use crate::database::{self, DieselPool};
+    use crate::domain::ConsentKind;
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let admin = require_admin().await?;
+
+    if !consent_form.confirmed {
+        return Err(AppError::InvalidInput("Bestätigung".to_string()));
+    }
+
+    let (guardian_name, valid_until) = parse_consent_details(&consent_form.guardian_name, &consent_form.valid_until)?;
+
+    database::create_parental_consent(ParentalConsent {
+        id: None,
+        player_id: consent_form.player_id,
+        kind: ConsentKind::Signed,
+        guardian_name,
+        note: String::new(),
+        recorded_by_id: Some(admin.id),
+        valid_until,
+        has_document: false,
+    }, None, &pool)?;
+
+    Ok(())
+}
+
+#[server(input = MultipartFormData)]
+async fn upload_parental_consent(data: MultipartData) -> Result<(), AppError> {
+    use std::collections::HashMap;
+
+    use crate::auth::require_admin;
+    use crate::consent::{detect_document_content_type, MAX_DOCUMENT_SIZE};
+    use crate::database::{self, DieselPool};
+    use crate::domain::ConsentKind;
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let admin = require_admin().await?;
+
+    let mut data = data.into_inner().ok_or(AppError::InvalidInput("Dokument".to_string()))?;
+    let mut fields = HashMap::new();
+    let mut content = Vec::new();
+
+    while let Ok(Some(mut field)) = data.next_field().await {
+        match field.name().map(str::to_string) {
+            Some(name) if name == "document" => {
+                while let Ok(Some(chunk)) = field.chunk().await {
+                    content.extend_from_slice(&chunk);
+
+                    if content.len() > MAX_DOCUMENT_SIZE {
+                        return Err(AppError::InvalidInput("Das Dokument ist größer als 5 MB".to_string()));
+                    }
+                }
+            },
+            Some(name) => {
+                let mut value = String::new();
+                while let Ok(Some(chunk)) = field.chunk().await {
+                    value.push_str(&String::from_utf8_lossy(&chunk));
+                }
+                fields.insert(name, value);
+            },
+            None => continue,
+        }
+    }
+
+    let field = |name: &str| fields.get(name).map(String::as_str).unwrap_or_default();
+
+    let player_id = field("player_id").trim().parse::<i64>()
+        .map_err(|_| AppError::InvalidInput("Id".to_string()))?;
+    let (guardian_name, valid_until) = parse_consent_details(field("guardian_name"), field("valid_until"))?;
+    let content_type = detect_document_content_type(&content)
+        .ok_or(AppError::InvalidInput("Das Dokument muss ein PDF, PNG oder JPEG sein".to_string()))?;
+
+    database::create_parental_consent(ParentalConsent {
+        id: None,
+        player_id,
+        kind: ConsentKind::Document,
+        guardian_name,
+        note: field("note").trim().to_string(),
+        recorded_by_id: Some(admin.id),
+        valid_until,
+        has_document: true,
+    }, Some((content_type, &content)), &pool)?;
+
+    Ok(())
+}
+
+#[server]
+async fn delete_parental_consent(id: i64) -> Result<(), AppError> {
+    use crate::auth::require_admin;
+    use crate::database::{self, DatabaseError, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    require_admin().await?;
+
+    database::find_parental_consent_for_id(id, &pool)?
+        .ok_or(DatabaseError::EntityNotFound)?;
+
+    Ok(database::delete_parental_consent(id, &pool)?)
+}
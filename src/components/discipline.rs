@@ -0,0 +1,544 @@
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::app::AppError;
+use crate::components::util::{date_input_value, format_date, format_datetime};
+use crate::domain::{DisciplinaryRecord, IncidentCategory, IncidentReport, PlayEvent, Suspension};
+
+#[component]
+pub fn SuspensionBadge(player_id: i64) -> impl IntoView {
+
+    let suspension = Resource::new(|| (), move |_| get_active_suspension(player_id));
+
+    view! {
+        <Suspense>
+            {move || {
+                suspension.get().and_then(|result| result.ok()).flatten().map(|suspension| view! {
+                    <span class="px-3 py-1 rounded bg-red-100 text-red-700 text-sm font-medium">
+                        <SuspensionText suspension=suspension/>
+                    </span>
+                })
+            }}
+        </Suspense>
+    }
+}
+
+#[component]
+fn SuspensionText(suspension: Suspension) -> impl IntoView {
+    match suspension.ends_on {
+        Some(ends_on) => format!("Gesperrt bis {}", format_date(&ends_on)),
+        None => "Gesperrt bis auf Weiteres".to_string(),
+    }
+}
+
+#[component]
+pub fn DisciplinaryRecords(player_id: i64) -> impl IntoView {
+
+    let create_incident = ServerAction::<CreateIncidentReport>::new();
+    let delete_incident = ServerAction::<DeleteIncidentReport>::new();
+    let create_suspension = ServerAction::<CreateSuspension>::new();
+    let lift_suspension = ServerAction::<LiftSuspension>::new();
+
+    let record = Resource::new(
+        move || (
+            create_incident.version().get(),
+            delete_incident.version().get(),
+            create_suspension.version().get(),
+            lift_suspension.version().get(),
+        ),
+        move |_| get_disciplinary_record(player_id),
+    );
+
+    let action_error = move || {
+        let errors = [
+            create_incident.value().get().and_then(|result| result.err()),
+            delete_incident.value().get().and_then(|result| result.err()),
+            create_suspension.value().get().and_then(|result| result.err()),
+            lift_suspension.value().get().and_then(|result| result.err()),
+        ];
+
+        errors.into_iter().flatten().next().map(|e| view! {
+            <p class="text-red-600">{ e.to_string() }</p>
+        })
+    };
+
+    view! {
+        <Suspense fallback=move || view! { <p>"Lade Daten..."</p> }>
+            {move || {
+                record.get().map(|result| match result {
+                    Ok(Some(DisciplinaryRecord { incidents, suspensions, active_suspension, play_events, can_manage })) => {
+                        let incident_options: Vec<IncidentReport> = incidents.clone();
+                        let today = chrono::Utc::now().date_naive();
+
+                        view! {
+                            <h2 class="text-left text-xl font-semibold py-2">Disziplinarakte</h2>
+
+                            {active_suspension.map(|suspension| view! {
+                                <p class="text-left text-red-700 font-medium pb-2">
+                                    <SuspensionText suspension=suspension.clone()/>
+                                    { format!(": {}", suspension.reason) }
+                                </p>
+                            })}
+
+                            <h3 class="text-left font-semibold py-2">Vorfälle</h3>
+                            {
+                                if incidents.is_empty() {
+                                    view! { <p class="text-left text-gray-700">"Keine Vorfälle gemeldet"</p> }.into_any()
+                                } else {
+                                    view! {
+                                        <div class="overflow-x-auto">
+                                            <table class="min-w-full border border-gray-200 shadow-sm rounded-md bg-white">
+                                                <thead class="bg-gray-100 text-gray-700">
+                                                    <tr>
+                                                        <th class="text-left py-2 px-4 border-b">Gemeldet am</th>
+                                                        <th class="text-left py-2 px-4 border-b">Art</th>
+                                                        <th class="text-left py-2 px-4 border-b">Event</th>
+                                                        <th class="text-left py-2 px-4 border-b">Gemeldet von</th>
+                                                        <th class="text-left py-2 px-4 border-b">Beschreibung</th>
+                                                        <th class="py-2 px-4 border-b"></th>
+                                                    </tr>
+                                                </thead>
+                                                <tbody>
+                                                    {
+                                                        incidents.into_iter().map(|incident: IncidentReport| view! {
+                                                            <tr class="hover:bg-gray-50">
+                                                                <th class="text-left py-2 px-4 border-b">{ format_datetime(&incident.reported_at) }</th>
+                                                                <th class="text-left py-2 px-4 border-b">{ incident.category.to_string() }</th>
+                                                                <th class="text-left py-2 px-4 border-b">{ incident.play_event_name.clone() }</th>
+                                                                <th class="text-left py-2 px-4 border-b">{ incident.reported_by_name.clone() }</th>
+                                                                <th class="text-left py-2 px-4 border-b whitespace-pre-line">{ incident.description.clone() }</th>
+                                                                <th class="py-2 px-4 border-b">
+                                                                    <Show when=move || can_manage>
+                                                                        <ActionForm action=delete_incident>
+                                                                            <input type="hidden" name="id" value=incident.id/>
+                                                                            <button
+                                                                                type="submit"
+                                                                                class="px-4 py-2 rounded bg-gray-200 hover:bg-gray-300 text-gray-700"
+                                                                            >
+                                                                                "Entfernen"
+                                                                            </button>
+                                                                        </ActionForm>
+                                                                    </Show>
+                                                                </th>
+                                                            </tr>
+                                                        }).collect_view()
+                                                    }
+                                                </tbody>
+                                            </table>
+                                        </div>
+                                    }.into_any()
+                                }
+                            }
+
+                            <NewIncidentForm player_id=player_id play_events=play_events action=create_incident/>
+
+                            <h3 class="text-left font-semibold py-2">Sperren</h3>
+                            {
+                                if suspensions.is_empty() {
+                                    view! { <p class="text-left text-gray-700">"Keine Sperren"</p> }.into_any()
+                                } else {
+                                    view! {
+                                        <div class="overflow-x-auto">
+                                            <table class="min-w-full border border-gray-200 shadow-sm rounded-md bg-white">
+                                                <thead class="bg-gray-100 text-gray-700">
+                                                    <tr>
+                                                        <th class="text-left py-2 px-4 border-b">Von</th>
+                                                        <th class="text-left py-2 px-4 border-b">Bis</th>
+                                                        <th class="text-left py-2 px-4 border-b">Grund</th>
+                                                        <th class="py-2 px-4 border-b"></th>
+                                                    </tr>
+                                                </thead>
+                                                <tbody>
+                                                    {
+                                                        suspensions.into_iter().map(|suspension: Suspension| {
+                                                            let liftable = can_manage && suspension.ends_on.is_none_or(|ends_on| ends_on >= today);
+
+                                                            view! {
+                                                                <tr class="hover:bg-gray-50">
+                                                                    <th class="text-left py-2 px-4 border-b">{ format_date(&suspension.starts_on) }</th>
+                                                                    <th class="text-left py-2 px-4 border-b">
+                                                                        { suspension.ends_on.map(|ends_on| format_date(&ends_on)).unwrap_or("Auf Weiteres".to_string()) }
+                                                                    </th>
+                                                                    <th class="text-left py-2 px-4 border-b">{ suspension.reason.clone() }</th>
+                                                                    <th class="py-2 px-4 border-b">
+                                                                        <Show when=move || liftable>
+                                                                            <ActionForm action=lift_suspension>
+                                                                                <input type="hidden" name="id" value=suspension.id/>
+                                                                                <button
+                                                                                    type="submit"
+                                                                                    class="px-4 py-2 rounded bg-gray-200 hover:bg-gray-300 text-gray-700"
+                                                                                >
+                                                                                    "Aufheben"
+                                                                                </button>
+                                                                            </ActionForm>
+                                                                        </Show>
+                                                                    </th>
+                                                                </tr>
+                                                            }
+                                                        }).collect_view()
+                                                    }
+                                                </tbody>
+                                            </table>
+                                        </div>
+                                    }.into_any()
+                                }
+                            }
+
+                            <Show when=move || can_manage>
+                                <NewSuspensionForm player_id=player_id incidents=incident_options.clone() action=create_suspension/>
+                            </Show>
+                        }.into_any()
+                    },
+                    Ok(None) => ().into_any(),
+                    Err(e) => view! { <p>{ e.to_string() }</p> }.into_any(),
+                })
+            }}
+        </Suspense>
+
+        { action_error }
+    }
+}
+
+#[component]
+fn NewIncidentForm(
+    player_id: i64,
+    play_events: Vec<PlayEvent>,
+    action: ServerAction<CreateIncidentReport>,
+) -> impl IntoView {
+    let field = |field: &str| format!("incident_form[{}]", field);
+
+    view! {
+        <ActionForm action=action attr:class="grid grid-cols-[auto_1fr] items-center gap-4 pt-4">
+            <input type="hidden" name=field("player_id") value=player_id/>
+
+            <h3 class="col-span-2 text-left font-semibold">"Vorfall melden"</h3>
+
+            <label for=field("category") class="text-left text-gray-700">
+                "Art:"
+            </label>
+            <select
+                name=field("category")
+                class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+            >
+                {
+                    IncidentCategory::ALL.into_iter().map(|category| view! {
+                        <option value=category.as_str()>{ category.to_string() }</option>
+                    }).collect_view()
+                }
+            </select>
+
+            <label for=field("play_event_id") class="text-left text-gray-700">
+                "Event:"
+            </label>
+            <select
+                name=field("play_event_id")
+                class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+            >
+                <option value="">"Kein Event"</option>
+                {
+                    play_events.into_iter().rev().map(|play_event| view! {
+                        <option value=play_event.id>
+                            { format!("{} ({})", play_event.name, format_date(&play_event.starts_at.date_naive())) }
+                        </option>
+                    }).collect_view()
+                }
+            </select>
+
+            <label for=field("description") class="text-left text-gray-700">
+                "Beschreibung:"
+            </label>
+            <textarea
+                name=field("description")
+                rows="3"
+                class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+                required
+            ></textarea>
+
+            <div class="col-span-2 flex justify-end">
+                <button
+                    type="submit"
+                    class="px-4 py-2 rounded bg-blue-600 hover:bg-blue-700 text-white"
+                >
+                    "Vorfall speichern"
+                </button>
+            </div>
+        </ActionForm>
+    }
+}
+
+#[component]
+fn NewSuspensionForm(
+    player_id: i64,
+    incidents: Vec<IncidentReport>,
+    action: ServerAction<CreateSuspension>,
+) -> impl IntoView {
+    let field = |field: &str| format!("suspension_form[{}]", field);
+    let today = date_input_value(&chrono::Utc::now().date_naive());
+
+    view! {
+        <ActionForm action=action attr:class="grid grid-cols-[auto_1fr] items-center gap-4 pt-4">
+            <input type="hidden" name=field("player_id") value=player_id/>
+
+            <h3 class="col-span-2 text-left font-semibold">"Spieler sperren"</h3>
+
+            <label for=field("starts_on") class="text-left text-gray-700">
+                "Von:"
+            </label>
+            <input
+                type="date"
+                name=field("starts_on")
+                value=today
+                class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+                required
+            />
+
+            <label for=field("ends_on") class="text-left text-gray-700">
+                "Bis (leer für unbefristet):"
+            </label>
+            <input
+                type="date"
+                name=field("ends_on")
+                class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+            />
+
+            <label for=field("incident_report_id") class="text-left text-gray-700">
+                "Vorfall:"
+            </label>
+            <select
+                name=field("incident_report_id")
+                class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+            >
+                <option value="">"Kein Vorfall"</option>
+                {
+                    incidents.into_iter().map(|incident| view! {
+                        <option value=incident.id>
+                            { format!("{}: {}", format_datetime(&incident.reported_at), incident.category) }
+                        </option>
+                    }).collect_view()
+                }
+            </select>
+
+            <label for=field("reason") class="text-left text-gray-700">
+                "Grund:"
+            </label>
+            <input
+                type="text"
+                name=field("reason")
+                class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+                required
+            />
+
+            <div class="col-span-2 flex justify-end">
+                <button
+                    type="submit"
+                    class="px-4 py-2 rounded bg-red-600 hover:bg-red-700 text-white"
+                >
+                    "Sperre speichern"
+                </button>
+            </div>
+        </ActionForm>
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct IncidentForm {
+    player_id: i64,
+    category: String,
+    #[serde(default)]
+    play_event_id: String,
+    description: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct SuspensionForm {
+    player_id: i64,
+    starts_on: String,
+    #[serde(default)]
+    ends_on: String,
+    #[serde(default)]
+    incident_report_id: String,
+    reason: String,
+}
+
+#[server]
+async fn get_active_suspension(player_id: i64) -> Result<Option<Suspension>, AppError> {
+    use crate::auth::can_see_disciplinary_records;
+    use crate::database::{self, DieselPool};
+    use crate::domain::active_suspension;
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    if !can_see_disciplinary_records(player_id, &pool).await? {
+        return Ok(None);
+    }
+
+    let suspensions = database::get_suspensions_for_player(player_id, &pool)?;
+
+    Ok(active_suspension(&suspensions, chrono::Utc::now().date_naive()).cloned())
+}
+
+#[server]
+async fn get_disciplinary_record(player_id: i64) -> Result<Option<DisciplinaryRecord>, AppError> {
+    use crate::auth::{can_see_disciplinary_records, current_player};
+    use crate::database::{self, DieselPool};
+    use crate::domain::active_suspension;
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    if !can_see_disciplinary_records(player_id, &pool).await? {
+        return Ok(None);
+    }
+
+    let viewer = current_player().await?;
+    let suspensions = database::get_suspensions_for_player(player_id, &pool)?;
+    let active_suspension = active_suspension(&suspensions, chrono::Utc::now().date_naive()).cloned();
+
+    Ok(Some(DisciplinaryRecord {
+        incidents: database::get_incident_reports_for_player(player_id, &pool)?,
+        suspensions,
+        active_suspension,
+        play_events: database::get_play_events_for_players(vec![player_id], &pool)?
+            .into_iter()
+            .map(Into::into)
+            .collect(),
+        can_manage: viewer.admin,
+    }))
+}
+
+/// Team contact persons, platoon leaders and admins report incidents, they are not shown to the player.
+#[server]
+async fn create_incident_report(incident_form: IncidentForm) -> Result<(), AppError> {
+    use crate::auth::{can_see_disciplinary_records, current_player, AuthError};
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    if !can_see_disciplinary_records(incident_form.player_id, &pool).await? {
+        return Err(AppError::AuthError(AuthError::Forbidden));
+    }
+
+    let reporter = current_player().await?;
+
+    let category: IncidentCategory = incident_form.category
+        .parse()
+        .map_err(|_| AppError::InvalidInput("Art".to_string()))?;
+
+    let play_event_id = match incident_form.play_event_id.trim() {
+        "" => None,
+        value => Some(value.parse::<i64>().map_err(|_| AppError::InvalidInput("Event".to_string()))?),
+    };
+
+    let description = incident_form.description.trim().to_string();
+
+    if description.is_empty() {
+        return Err(AppError::InvalidInput("Beschreibung".to_string()));
+    }
+
+    database::create_incident_report(IncidentReport {
+        id: None,
+        player_id: incident_form.player_id,
+        play_event_id,
+        play_event_name: None,
+        reported_by_id: Some(reporter.id),
+        reported_by_name: None,
+        category,
+        description,
+        reported_at: chrono::Utc::now(),
+    }, &pool)?;
+
+    Ok(())
+}
+
+#[server]
+async fn delete_incident_report(id: i64) -> Result<(), AppError> {
+    use crate::auth::require_admin;
+    use crate::database::{self, DatabaseError, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    require_admin().await?;
+
+    database::find_incident_report_for_id(id, &pool)?
+        .ok_or(DatabaseError::EntityNotFound)?;
+
+    Ok(database::delete_incident_report(id, &pool)?)
+}
+
+#[server]
+async fn create_suspension(suspension_form: SuspensionForm) -> Result<(), AppError> {
+    use crate::auth::require_admin;
+    use crate::components::util::parse_date_input;
+    use crate::database::{self, DatabaseError, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let admin = require_admin().await?;
+
+    let starts_on = parse_date_input(&suspension_form.starts_on)
+        .ok_or(AppError::InvalidInput("Von".to_string()))?;
+
+    let ends_on = match suspension_form.ends_on.trim() {
+        "" => None,
+        value => Some(
+            parse_date_input(value)
+                .filter(|ends_on| *ends_on >= starts_on)
+                .ok_or(AppError::InvalidInput("Bis".to_string()))?
+        ),
+    };
+
+    let incident_report_id = match suspension_form.incident_report_id.trim() {
+        "" => None,
+        value => {
+            let report = value.parse::<i64>()
+                .ok()
+                .and_then(|id| database::find_incident_report_for_id(id, &pool).transpose())
+                .transpose()?
+                .ok_or(DatabaseError::EntityNotFound)?;
+
+            if report.player_id != suspension_form.player_id {
+                return Err(AppError::InvalidInput("Vorfall".to_string()));
+            }
+
+            Some(report.id)
+        },
+    };
+
+    let reason = suspension_form.reason.trim().to_string();
+
+    if reason.is_empty() {
+        return Err(AppError::InvalidInput("Grund".to_string()));
+    }
+
+    database::create_suspension(Suspension {
+        id: None,
+        player_id: suspension_form.player_id,
+        incident_report_id,
+        issued_by_id: Some(admin.id),
+        reason,
+        starts_on,
+        ends_on,
+    }, &pool)?;
+
+    Ok(())
+}
+
+#[server]
+async fn lift_suspension(id: i64) -> Result<(), AppError> {
+    use crate::auth::require_admin;
+    use crate::database::{self, DatabaseError, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    require_admin().await?;
+
+    database::find_suspension_for_id(id, &pool)?
+        .ok_or(DatabaseError::EntityNotFound)?;
+
+    Ok(database::lift_suspension(id, chrono::Utc::now().date_naive(), &pool)?)
+}
@@ -0,0 +1,402 @@
+use std::collections::BTreeMap;
+
+use leptos::prelude::*;
+use leptos::Params;
+use leptos_router::{components::A, hooks::use_params, params::Params};
+
+use crate::app::AppError;
+use crate::components::util::BackButton;
+use crate::domain::{FactionOverview, Participant};
+
+#[component]
+pub fn FactionList(play_event_id: i64) -> impl IntoView {
+
+    let overview = Resource::new(move || play_event_id, get_faction_overview);
+
+    view! {
+        <h2 class="text-left text-xl font-semibold py-2">Fraktionen</h2>
+
+        <Suspense fallback=move || view! { <p>"Lade Daten..."</p> }>
+            {move || {
+                overview.get().map(|result| match result {
+                    Ok(overview) => view! {
+                        <Show when=move || overview.editable>
+                            <div class="flex justify-end">
+                                <A href=format!("/events/{}/factions", play_event_id)
+                                    attr:class="bg-blue-600 hover:bg-blue-700 text-white font-medium py-2 px-4 rounded transition"
+                                >
+                                    "Fraktionen bearbeiten"
+                                </A>
+                            </div>
+                        </Show>
+
+                        <div class="grid grid-cols-2 gap-4 pt-2">
+                            {
+                                overview.factions
+                                    .into_iter()
+                                    .map(|faction| view! {
+                                        <div class="border border-gray-200 rounded p-4 bg-white text-left">
+                                            <h3 class="text-lg font-semibold">
+                                                { faction.faction.name } " (" { faction.members.len() } ")"
+                                            </h3>
+                                            <ul>
+                                                {
+                                                    faction.members
+                                                        .into_iter()
+                                                        .map(|member| view! {
+                                                            <li>
+                                                                <A href=format!("/players/{}", member.player_id) attr:class="hover:underline">
+                                                                    { member.tag_name }
+                                                                </A>
+                                                                { member.team_name.map(|team_name| format!(" ({})", team_name)) }
+                                                            </li>
+                                                        })
+                                                        .collect_view()
+                                                }
+                                            </ul>
+                                        </div>
+                                    })
+                                    .collect_view()
+                            }
+                        </div>
+                    }.into_any(),
+                    Err(e) => view! { <p>{ e.to_string() }</p> }.into_any(),
+                })
+            }}
+        </Suspense>
+    }
+}
+
+#[component]
+pub fn PlayEventFactions() -> impl IntoView {
+
+    let play_event_id = use_params::<FactionPlayEventIdParameter>();
+
+    let create_faction = ServerAction::<CreateFaction>::new();
+    let delete_faction = ServerAction::<DeleteFaction>::new();
+    let assign_players = ServerAction::<AssignPlayersToFaction>::new();
+    let balance_factions = ServerAction::<BalancePlayEventFactions>::new();
+
+    let overview = Resource::new(
+        move || (
+            play_event_id.read().clone(),
+            create_faction.version().get(),
+            delete_faction.version().get(),
+            assign_players.version().get(),
+            balance_factions.version().get(),
+        ),
+        move |(params_result, ..)| get_faction_overview(params_result.unwrap().id.unwrap()),
+    );
+
+    let dragged = RwSignal::new(Vec::<i64>::new());
+
+    let drop_on = move |faction_id: Option<i64>| {
+        let player_ids = dragged.get_untracked();
+
+        if player_ids.is_empty() {
+            return;
+        }
+
+        let player_ids = player_ids
+            .iter()
+            .map(|id| id.to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+
+        assign_players.dispatch(AssignPlayersToFaction {
+            play_event_id: play_event_id.get_untracked().unwrap().id.unwrap(),
+            player_ids,
+            faction_id,
+        });
+        dragged.set(Vec::new());
+    };
+
+    let action_error = move || {
+        let errors = [
+            create_faction.value().get().and_then(|result| result.err()),
+            delete_faction.value().get().and_then(|result| result.err()),
+            assign_players.value().get().and_then(|result| result.err()),
+            balance_factions.value().get().and_then(|result| result.err()),
+        ];
+
+        errors
+            .into_iter()
+            .flatten()
+            .next()
+            .map(|e| view! { <p class="text-red-600">{ e.to_string() }</p> })
+    };
+
+    view! {
+        <BackButton/>
+        <div class="p-8 max-w-6xl mx-auto">
+            <div class="flex items-center justify-between mb-6">
+                <h1 class="text-2xl font-semibold">
+                    "Fraktionen bearbeiten"
+                </h1>
+                <ActionForm action=balance_factions>
+                    <input type="hidden" name="play_event_id" value=move || play_event_id.get().ok().and_then(|params| params.id)/>
+                    <button
+                        type="submit"
+                        class="bg-blue-600 hover:bg-blue-700 text-white font-medium py-2 px-4 rounded transition"
+                    >
+                        "Automatisch aufteilen"
+                    </button>
+                </ActionForm>
+            </div>
+
+            <ActionForm action=create_faction attr:class="flex gap-2 mb-6">
+                <input type="hidden" name="play_event_id" value=move || play_event_id.get().ok().and_then(|params| params.id)/>
+                <input
+                    type="text"
+                    name="name"
+                    placeholder="Name der Fraktion"
+                    class="flex-grow border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+                    required
+                />
+                <button
+                    type="submit"
+                    class="px-4 py-2 rounded bg-blue-600 hover:bg-blue-700 text-white"
+                >
+                    "Fraktion hinzufügen"
+                </button>
+            </ActionForm>
+
+            { action_error }
+
+            <Suspense fallback=move || view! { <p>"Lade Daten..."</p> }>
+                {move || {
+                    overview.get().map(|result| match result {
+                        Ok(overview) => view! {
+                            <div class="grid grid-cols-3 gap-4">
+                                <FactionColumn
+                                    title="Nicht zugeordnet".to_string()
+                                    members=overview.unassigned
+                                    dragged=dragged
+                                    on_drop=move || drop_on(None)
+                                />
+                                {
+                                    overview.factions
+                                        .into_iter()
+                                        .map(|faction| {
+                                            let faction_id = faction.faction.id.unwrap();
+
+                                            view! {
+                                                <div>
+                                                    <FactionColumn
+                                                        title=faction.faction.name
+                                                        members=faction.members
+                                                        dragged=dragged
+                                                        on_drop=move || drop_on(Some(faction_id))
+                                                    />
+                                                    <ActionForm action=delete_faction attr:class="flex justify-end mt-2">
+                                                        <input type="hidden" name="faction_id" value=faction_id/>
+                                                        <button
+                                                            type="submit"
+                                                            class="px-4 py-2 rounded bg-gray-200 hover:bg-gray-300 text-gray-700"
+                                                        >
+                                                            "Entfernen"
+                                                        </button>
+                                                    </ActionForm>
+                                                </div>
+                                            }
+                                        })
+                                        .collect_view()
+                                }
+                            </div>
+                        }.into_any(),
+                        Err(e) => view! { <p>{ e.to_string() }</p> }.into_any(),
+                    })
+                }}
+            </Suspense>
+        </div>
+    }
+}
+
+#[component]
+fn FactionColumn<F>(
+    title: String,
+    members: Vec<Participant>,
+    dragged: RwSignal<Vec<i64>>,
+    on_drop: F,
+) -> impl IntoView
+where
+    F: Fn() + Clone + Send + Sync + 'static,
+{
+    let headcount = members.len();
+
+    let mut teams: BTreeMap<Option<String>, Vec<Participant>> = BTreeMap::new();
+    for member in members {
+        teams.entry(member.team_name.clone()).or_default().push(member);
+    }
+
+    view! {
+        <div
+            class="border border-gray-200 rounded p-4 bg-white text-left min-h-40"
+            on:dragover=move |ev| ev.prevent_default()
+            on:drop=move |ev| {
+                ev.prevent_default();
+                on_drop();
+            }
+        >
+            <h3 class="text-lg font-semibold">{ title } " (" { headcount } ")"</h3>
+            {
+                teams
+                    .into_iter()
+                    .map(|(team_name, members)| {
+                        let team_player_ids: Vec<i64> = members.iter().map(|member| member.player_id).collect();
+
+                        view! {
+                            <div class="pt-2">
+                                <p
+                                    class="font-medium cursor-move"
+                                    draggable="true"
+                                    on:dragstart=move |_| dragged.set(team_player_ids.clone())
+                                >
+                                    { team_name.unwrap_or("Ohne Team".to_string()) }
+                                </p>
+                                <ul>
+                                    {
+                                        members
+                                            .into_iter()
+                                            .map(|member| {
+                                                let player_id = member.player_id;
+
+                                                view! {
+                                                    <li
+                                                        class="px-2 py-1 rounded hover:bg-blue-100 cursor-move"
+                                                        draggable="true"
+                                                        on:dragstart=move |_| dragged.set(vec![player_id])
+                                                    >
+                                                        { member.tag_name }
+                                                    </li>
+                                                }
+                                            })
+                                            .collect_view()
+                                    }
+                                </ul>
+                            </div>
+                        }
+                    })
+                    .collect_view()
+            }
+        </div>
+    }
+}
+
+#[derive(Params, PartialEq, Clone)]
+struct FactionPlayEventIdParameter {
+    id: Option<i64>,
+}
+
+#[server]
+async fn get_faction_overview(play_event_id: i64) -> Result<FactionOverview, AppError> {
+    use std::collections::HashMap;
+
+    use crate::auth::current_player;
+    use crate::database::{self, DatabaseError, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let player = current_player().await?;
+    let play_event = database::find_play_event_for_id(play_event_id, &pool)?
+        .ok_or(DatabaseError::EntityNotFound)?;
+
+    let factions = database::get_factions_for_event(play_event_id, &pool)?
+        .into_iter()
+        .map(Into::into)
+        .collect();
+    let assignments: HashMap<i64, i64> = database::get_faction_assignments_for_event(play_event_id, &pool)?
+        .into_iter()
+        .map(|assignment| (assignment.player_id, assignment.faction_id))
+        .collect();
+    let participants = database::get_participants_for_event(play_event_id, &pool)?;
+
+    Ok(FactionOverview::new(
+        factions,
+        &assignments,
+        participants,
+        play_event.organizer_id == Some(player.id),
+    ))
+}
+
+#[server]
+async fn create_faction(play_event_id: i64, name: String) -> Result<(), AppError> {
+    use crate::auth::require_organizer;
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    require_organizer(play_event_id, &pool).await?;
+
+    Ok(database::create_faction(play_event_id, name, &pool)?)
+}
+
+#[server]
+async fn delete_faction(faction_id: i64) -> Result<(), AppError> {
+    use crate::auth::require_organizer;
+    use crate::database::{self, DatabaseError, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let faction = database::find_faction_for_id(faction_id, &pool)?
+        .ok_or(DatabaseError::EntityNotFound)?;
+    require_organizer(faction.play_event_id, &pool).await?;
+
+    Ok(database::delete_faction(faction_id, &pool)?)
+}
+
+#[server]
+async fn assign_players_to_faction(
+    play_event_id: i64,
+    player_ids: String,
+    faction_id: Option<i64>,
+) -> Result<(), AppError> {
+    use crate::auth::require_organizer;
+    use crate::database::{self, DatabaseError, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    require_organizer(play_event_id, &pool).await?;
+
+    if let Some(faction_id) = faction_id {
+        let faction = database::find_faction_for_id(faction_id, &pool)?
+            .ok_or(DatabaseError::EntityNotFound)?;
+
+        if faction.play_event_id != play_event_id {
+            return Err(DatabaseError::EntityNotFound.into());
+        }
+    }
+
+    let player_ids: Vec<i64> = player_ids
+        .split(",")
+        .flat_map(|id| id.parse().ok())
+        .collect();
+
+    Ok(database::assign_players_to_faction(play_event_id, player_ids, faction_id, &pool)?)
+}
+
+#[server]
+async fn balance_play_event_factions(play_event_id: i64) -> Result<(), AppError> {
+    use crate::auth::require_organizer;
+    use crate::database::{self, DieselPool};
+    use crate::domain::balance_factions;
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    require_organizer(play_event_id, &pool).await?;
+
+    let faction_ids: Vec<i64> = database::get_factions_for_event(play_event_id, &pool)?
+        .into_iter()
+        .map(|faction| faction.id)
+        .collect();
+    let participants = database::get_participants_for_event(play_event_id, &pool)?;
+
+    let assignments = balance_factions(&participants, &faction_ids);
+
+    Ok(database::replace_faction_assignments(play_event_id, assignments, &pool)?)
+}
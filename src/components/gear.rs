@@ -0,0 +1,600 @@
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::app::AppError;
+use crate::components::util::BoolSymbol;
+use crate::domain::{ChronoEntry, ChronoStatus, ClassLimit, EventLimits, PlayerGear, Replica, ReplicaClass};
+
+#[component]
+pub fn PlayerReplicas(player_id: i64) -> impl IntoView {
+
+    let create_replica = ServerAction::<CreateReplica>::new();
+    let delete_replica = ServerAction::<DeleteReplica>::new();
+
+    let gear = Resource::new(
+        move || (create_replica.version().get(), delete_replica.version().get()),
+        move |_| get_player_gear(player_id),
+    );
+
+    let action_error = move || {
+        let create_error = create_replica.value().get().and_then(|result| result.err());
+        let delete_error = delete_replica.value().get().and_then(|result| result.err());
+
+        create_error.or(delete_error).map(|e| view! {
+            <p class="text-red-600">{ e.to_string() }</p>
+        })
+    };
+
+    view! {
+        <h2 class="text-left text-xl font-semibold py-2">Replikas</h2>
+
+        <Suspense fallback=move || view! { <p>"Lade Daten..."</p> }>
+            {move || {
+                gear.get().map(|result| match result {
+                    Ok(PlayerGear { replicas, editable }) => view! {
+                        <div class="overflow-x-auto">
+                            <table class="min-w-full border border-gray-200 shadow-sm rounded-md bg-white">
+                                <thead class="bg-gray-100 text-gray-700">
+                                    <tr>
+                                        <th class="text-left py-2 px-4 border-b">Name</th>
+                                        <th class="text-left py-2 px-4 border-b">Klasse</th>
+                                        <th class="text-left py-2 px-4 border-b">Messung</th>
+                                        <th class="text-left py-2 px-4 border-b">FPS (0,20 g)</th>
+                                        <th class="text-left py-2 px-4 border-b">Joule</th>
+                                        <th class="py-2 px-4 border-b"></th>
+                                    </tr>
+                                </thead>
+                                <tbody>
+                                    {
+                                        replicas.into_iter().map(|replica: Replica| view! {
+                                            <tr class="hover:bg-gray-50">
+                                                <th class="text-left py-2 px-4 border-b">{ replica.name.clone() }</th>
+                                                <th class="text-left py-2 px-4 border-b">{ replica.class.to_string() }</th>
+                                                <th class="text-left py-2 px-4 border-b">
+                                                    { format!("{:.0} FPS @ {:.2} g", replica.fps, replica.bb_weight) }
+                                                </th>
+                                                <th class="text-left py-2 px-4 border-b">{ format!("{:.0}", replica.reference_fps()) }</th>
+                                                <th class="text-left py-2 px-4 border-b">{ format!("{:.2}", replica.joules()) }</th>
+                                                <th class="py-2 px-4 border-b">
+                                                    <Show when=move || editable>
+                                                        <ActionForm action=delete_replica>
+                                                            <input type="hidden" name="id" value=replica.id/>
+                                                            <button
+                                                                type="submit"
+                                                                class="px-4 py-2 rounded bg-gray-200 hover:bg-gray-300 text-gray-700"
+                                                            >
+                                                                "Entfernen"
+                                                            </button>
+                                                        </ActionForm>
+                                                    </Show>
+                                                </th>
+                                            </tr>
+                                        }).collect_view()
+                                    }
+                                </tbody>
+                            </table>
+                        </div>
+
+                        <Show when=move || editable>
+                            <NewReplicaForm player_id=player_id action=create_replica/>
+                        </Show>
+                    }.into_any(),
+                    Err(e) => view! { <p>{ e.to_string() }</p> }.into_any(),
+                })
+            }}
+        </Suspense>
+
+        { action_error }
+    }
+}
+
+#[component]
+fn NewReplicaForm(player_id: i64, action: ServerAction<CreateReplica>) -> impl IntoView {
+    let field = |field: &str| format!("replica_form[{}]", field);
+
+    view! {
+        <ActionForm action=action attr:class="grid grid-cols-[auto_1fr] items-center gap-4 pt-4">
+            <input type="hidden" name=field("player_id") value=player_id/>
+
+            <label for=field("name") class="text-left text-gray-700">
+                "Name:"
+            </label>
+            <input
+                type="text"
+                name=field("name")
+                class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+                required
+            />
+
+            <label for=field("class") class="text-left text-gray-700">
+                "Klasse:"
+            </label>
+            <ReplicaClassSelect name=field("class")/>
+
+            <label for=field("value") class="text-left text-gray-700">
+                "Messwert:"
+            </label>
+            <div class="flex gap-2">
+                <input
+                    type="text"
+                    inputmode="decimal"
+                    name=field("value")
+                    class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+                    required
+                />
+                <select
+                    name=field("unit")
+                    class="border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+                >
+                    <option value="fps">"FPS"</option>
+                    <option value="joule">"Joule"</option>
+                </select>
+            </div>
+
+            <label for=field("bb_weight") class="text-left text-gray-700">
+                "BB-Gewicht (g):"
+            </label>
+            <input
+                type="text"
+                inputmode="decimal"
+                name=field("bb_weight")
+                class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+                value="0.20"
+                required
+            />
+
+            <div class="col-span-2 flex justify-end">
+                <button
+                    type="submit"
+                    class="px-4 py-2 rounded bg-blue-600 hover:bg-blue-700 text-white"
+                >
+                    "Replika hinzufügen"
+                </button>
+            </div>
+        </ActionForm>
+    }
+}
+
+#[component]
+fn ReplicaClassSelect(name: String) -> impl IntoView {
+
+    view! {
+        <select
+            name=name
+            class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+        >
+            {
+                ReplicaClass::ALL
+                    .into_iter()
+                    .map(|class| view! { <option value=class.as_str()>{ class.to_string() }</option> })
+                    .collect_view()
+            }
+        </select>
+    }
+}
+
+#[component]
+pub fn ClassLimitPanel(play_event_id: i64) -> impl IntoView {
+
+    let save_limit = ServerAction::<SaveClassLimit>::new();
+    let delete_limit = ServerAction::<DeleteClassLimit>::new();
+
+    let limits = Resource::new(
+        move || (save_limit.version().get(), delete_limit.version().get()),
+        move |_| get_event_limits(play_event_id),
+    );
+
+    let action_error = move || {
+        let save_error = save_limit.value().get().and_then(|result| result.err());
+        let delete_error = delete_limit.value().get().and_then(|result| result.err());
+
+        save_error.or(delete_error).map(|e| view! {
+            <p class="text-red-600">{ e.to_string() }</p>
+        })
+    };
+
+    view! {
+        <h2 class="text-left text-xl font-semibold py-2">Energielimits</h2>
+
+        <Suspense fallback=move || view! { <p>"Lade Daten..."</p> }>
+            {move || {
+                limits.get().map(|result| match result {
+                    Ok(EventLimits { limits, editable }) => view! {
+                        {
+                            if limits.is_empty() {
+                                view! { <p class="text-left text-gray-700">"Keine Limits festgelegt"</p> }.into_any()
+                            } else {
+                                view! {
+                                    <div class="overflow-x-auto">
+                                        <table class="min-w-full border border-gray-200 shadow-sm rounded-md bg-white">
+                                            <thead class="bg-gray-100 text-gray-700">
+                                                <tr>
+                                                    <th class="text-left py-2 px-4 border-b">Klasse</th>
+                                                    <th class="text-left py-2 px-4 border-b">Max. Joule</th>
+                                                    <th class="text-left py-2 px-4 border-b">Max. FPS (0,20 g)</th>
+                                                    <th class="text-left py-2 px-4 border-b">Mindestabstand</th>
+                                                    <th class="py-2 px-4 border-b"></th>
+                                                </tr>
+                                            </thead>
+                                            <tbody>
+                                                {
+                                                    limits.into_iter().map(|limit: ClassLimit| view! {
+                                                        <tr class="hover:bg-gray-50">
+                                                            <th class="text-left py-2 px-4 border-b">{ limit.class.to_string() }</th>
+                                                            <th class="text-left py-2 px-4 border-b">{ format!("{:.2}", limit.max_joules) }</th>
+                                                            <th class="text-left py-2 px-4 border-b">{ format!("{:.0}", limit.max_reference_fps()) }</th>
+                                                            <th class="text-left py-2 px-4 border-b">{ format!("{} m", limit.min_distance_meters) }</th>
+                                                            <th class="py-2 px-4 border-b">
+                                                                <Show when=move || editable>
+                                                                    <ActionForm action=delete_limit>
+                                                                        <input type="hidden" name="play_event_id" value=play_event_id/>
+                                                                        <input type="hidden" name="class" value=limit.class.as_str()/>
+                                                                        <button
+                                                                            type="submit"
+                                                                            class="px-4 py-2 rounded bg-gray-200 hover:bg-gray-300 text-gray-700"
+                                                                        >
+                                                                            "Entfernen"
+                                                                        </button>
+                                                                    </ActionForm>
+                                                                </Show>
+                                                            </th>
+                                                        </tr>
+                                                    }).collect_view()
+                                                }
+                                            </tbody>
+                                        </table>
+                                    </div>
+                                }.into_any()
+                            }
+                        }
+
+                        <Show when=move || editable>
+                            <ActionForm action=save_limit attr:class="grid grid-cols-[auto_1fr] items-center gap-4 pt-4">
+                                <input type="hidden" name="play_event_id" value=play_event_id/>
+
+                                <label for="class" class="text-left text-gray-700">
+                                    "Klasse:"
+                                </label>
+                                <ReplicaClassSelect name="class".to_string()/>
+
+                                <label for="max_joules" class="text-left text-gray-700">
+                                    "Max. Joule:"
+                                </label>
+                                <input
+                                    type="text"
+                                    inputmode="decimal"
+                                    name="max_joules"
+                                    class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+                                    required
+                                />
+
+                                <label for="min_distance_meters" class="text-left text-gray-700">
+                                    "Mindestabstand (m):"
+                                </label>
+                                <input
+                                    type="number"
+                                    min="0"
+                                    name="min_distance_meters"
+                                    class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+                                    value="0"
+                                />
+
+                                <div class="col-span-2 flex justify-end">
+                                    <button
+                                        type="submit"
+                                        class="px-4 py-2 rounded bg-blue-600 hover:bg-blue-700 text-white"
+                                    >
+                                        "Limit speichern"
+                                    </button>
+                                </div>
+                            </ActionForm>
+                        </Show>
+                    }.into_any(),
+                    Err(e) => view! { <p>{ e.to_string() }</p> }.into_any(),
+                })
+            }}
+        </Suspense>
+
+        { action_error }
+    }
+}
+
+#[component]
+pub fn ChronoList(play_event_id: i64) -> impl IntoView {
+
+    let entries = Resource::new(|| {}, move |_| get_chrono_list(play_event_id));
+
+    view! {
+        <h2 class="text-left text-xl font-semibold py-2">Chrono</h2>
+
+        <Suspense fallback=move || view! { <p>"Lade Daten..."</p> }>
+            {move || {
+                entries.get().map(|result| match result {
+                    Ok(entries) => {
+                        let failed = entries
+                            .iter()
+                            .filter(|entry| entry.status == ChronoStatus::Failed)
+                            .count();
+
+                        view! {
+                            <p class="text-left text-gray-700 pb-2">
+                                "Nicht bestanden: " { failed } " / " { entries.len() }
+                            </p>
+                            <div class="overflow-x-auto">
+                                <table class="min-w-full border border-gray-200 shadow-sm rounded-md bg-white">
+                                    <thead class="bg-gray-100 text-gray-700">
+                                        <tr>
+                                            <th class="text-left py-2 px-4 border-b">Spielername</th>
+                                            <th class="py-2 px-4 border-b">Anwesend</th>
+                                            <th class="text-left py-2 px-4 border-b">Replika</th>
+                                            <th class="text-left py-2 px-4 border-b">Klasse</th>
+                                            <th class="text-left py-2 px-4 border-b">Joule</th>
+                                            <th class="text-left py-2 px-4 border-b">Ergebnis</th>
+                                        </tr>
+                                    </thead>
+                                    <tbody>
+                                        {
+                                            entries.into_iter().map(|entry: ChronoEntry| {
+                                                let class = match entry.status {
+                                                    ChronoStatus::Passed => "text-left py-2 px-4 border-b text-green-700",
+                                                    ChronoStatus::Failed => "text-left py-2 px-4 border-b text-red-600",
+                                                    ChronoStatus::NoLimit => "text-left py-2 px-4 border-b text-gray-500",
+                                                };
+
+                                                view! {
+                                                    <tr class="hover:bg-gray-50">
+                                                        <th class="text-left py-2 px-4 border-b">{ entry.tag_name }</th>
+                                                        <th class="py-2 px-4 border-b">
+                                                            <div class="flex justify-center">
+                                                                <BoolSymbol value=entry.checked_in/>
+                                                            </div>
+                                                        </th>
+                                                        <th class="text-left py-2 px-4 border-b">{ entry.replica.name.clone() }</th>
+                                                        <th class="text-left py-2 px-4 border-b">{ entry.replica.class.to_string() }</th>
+                                                        <th class="text-left py-2 px-4 border-b">{ format!("{:.2}", entry.replica.joules()) }</th>
+                                                        <th class=class>{ entry.status.to_string() }</th>
+                                                    </tr>
+                                                }
+                                            }).collect_view()
+                                        }
+                                    </tbody>
+                                </table>
+                            </div>
+                        }.into_any()
+                    },
+                    Err(e) => view! { <p>{ e.to_string() }</p> }.into_any(),
+                })
+            }}
+        </Suspense>
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct ReplicaForm {
+    player_id: i64,
+    name: String,
+    class: String,
+    value: String,
+    unit: String,
+    bb_weight: String,
+}
+
+#[cfg(feature = "ssr")]
+impl ReplicaForm {
+    /// Energies are stored as the velocity they give with the entered BB weight.
+    fn into_replica(self) -> Result<Replica, AppError> {
+        use crate::domain::fps_from_joules;
+
+        let bb_weight = parse_positive(&self.bb_weight)
+            .filter(|bb_weight| *bb_weight <= 1.0)
+            .ok_or(AppError::InvalidInput("BB-Gewicht".to_string()))?;
+        let value = parse_positive(&self.value)
+            .ok_or(AppError::InvalidInput("Messwert".to_string()))?;
+
+        let fps = match self.unit.as_str() {
+            "fps" => value,
+            "joule" => fps_from_joules(value, bb_weight),
+            _ => return Err(AppError::InvalidInput("Einheit".to_string())),
+        };
+
+        Ok(Replica {
+            id: None,
+            player_id: self.player_id,
+            name: self.name.trim().to_string(),
+            class: self.class
+                .parse()
+                .map_err(|_| AppError::InvalidInput("Klasse".to_string()))?,
+            fps,
+            bb_weight,
+        })
+    }
+}
+
+/// Accepts both decimal separators, since German users usually type a comma.
+#[cfg(feature = "ssr")]
+fn parse_positive(value: &str) -> Option<f64> {
+    value
+        .trim()
+        .replace(',', ".")
+        .parse::<f64>()
+        .ok()
+        .filter(|value| value.is_finite() && *value > 0.0)
+}
+
+/// Players manage their own replicas, admins those of everyone.
+#[cfg(feature = "ssr")]
+async fn require_replica_owner(player_id: i64) -> Result<(), AppError> {
+    use crate::auth::{current_player, AuthError};
+
+    let player = current_player().await?;
+
+    if player.admin || player.id == player_id {
+        Ok(())
+    } else {
+        Err(AppError::AuthError(AuthError::Forbidden))
+    }
+}
+
+#[server]
+async fn get_player_gear(player_id: i64) -> Result<PlayerGear, AppError> {
+    use crate::auth::current_player;
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let player = current_player().await?;
+
+    let replicas = database::get_replicas_for_player(player_id, &pool)?
+        .into_iter()
+        .map(Into::into)
+        .collect();
+
+    Ok(PlayerGear {
+        replicas,
+        editable: player.admin || player.id == player_id,
+    })
+}
+
+#[server]
+async fn create_replica(replica_form: ReplicaForm) -> Result<(), AppError> {
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    require_replica_owner(replica_form.player_id).await?;
+
+    let replica = replica_form.into_replica()?;
+
+    if replica.name.is_empty() {
+        return Err(AppError::InvalidInput("Name".to_string()));
+    }
+
+    database::create_replica(replica, &pool)?;
+
+    Ok(())
+}
+
+#[server]
+async fn delete_replica(id: i64) -> Result<(), AppError> {
+    use crate::database::{self, DatabaseError, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let replica = database::find_replica_for_id(id, &pool)?
+        .ok_or(DatabaseError::EntityNotFound)?;
+
+    require_replica_owner(replica.player_id).await?;
+
+    Ok(database::delete_replica(id, &pool)?)
+}
+
+#[server]
+async fn get_event_limits(play_event_id: i64) -> Result<EventLimits, AppError> {
+    use crate::auth::current_player;
+    use crate::database::{self, DatabaseError, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let player = current_player().await?;
+    let play_event = database::find_play_event_for_id(play_event_id, &pool)?
+        .ok_or(DatabaseError::EntityNotFound)?;
+
+    let limits = database::get_class_limits_for_event(play_event_id, &pool)?
+        .into_iter()
+        .map(Into::into)
+        .collect();
+
+    Ok(EventLimits {
+        limits,
+        editable: play_event.organizer_id == Some(player.id),
+    })
+}
+
+#[server]
+async fn save_class_limit(
+    play_event_id: i64,
+    class: String,
+    max_joules: String,
+    #[server(default)]
+    min_distance_meters: String,
+) -> Result<(), AppError> {
+    use crate::auth::require_organizer;
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    require_organizer(play_event_id, &pool).await?;
+
+    let limit = ClassLimit {
+        class: class
+            .parse()
+            .map_err(|_| AppError::InvalidInput("Klasse".to_string()))?,
+        max_joules: parse_positive(&max_joules)
+            .ok_or(AppError::InvalidInput("Max. Joule".to_string()))?,
+        min_distance_meters: if min_distance_meters.is_empty() {
+            0
+        } else {
+            min_distance_meters
+                .parse()
+                .ok()
+                .filter(|distance| *distance >= 0)
+                .ok_or(AppError::InvalidInput("Mindestabstand".to_string()))?
+        },
+    };
+
+    Ok(database::save_class_limit(play_event_id, limit, &pool)?)
+}
+
+#[server]
+async fn delete_class_limit(play_event_id: i64, class: String) -> Result<(), AppError> {
+    use crate::auth::require_organizer;
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    require_organizer(play_event_id, &pool).await?;
+
+    let class = class
+        .parse()
+        .map_err(|_| AppError::InvalidInput("Klasse".to_string()))?;
+
+    Ok(database::delete_class_limit(play_event_id, class, &pool)?)
+}
+
+#[server]
+async fn get_chrono_list(play_event_id: i64) -> Result<Vec<ChronoEntry>, AppError> {
+    use crate::auth::require_organizer;
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    require_organizer(play_event_id, &pool).await?;
+
+    let limits: Vec<ClassLimit> = database::get_class_limits_for_event(play_event_id, &pool)?
+        .into_iter()
+        .map(Into::into)
+        .collect();
+
+    let entries = database::get_replicas_for_event(play_event_id, &pool)?
+        .into_iter()
+        .map(|(replica, tag_name, checked_in)| {
+            let replica = Replica::from(replica);
+
+            ChronoEntry {
+                tag_name,
+                checked_in,
+                status: replica.chrono_status(&limits),
+                replica,
+            }
+        })
+        .collect();
+
+    Ok(entries)
+}
@@ -0,0 +1,1026 @@
+use leptos::prelude::*;
+use leptos_router::components::A;
+use serde::{Deserialize, Serialize};
+
+use crate::app::AppError;
+use crate::components::util::{date_input_value, format_cents, format_date, format_datetime, BackButton};
+use crate::domain::{Charge, Fee, FeeKind, Payment, PaymentMethod, PlayEvent, PlayerBalance, PlayerLedger, TreasurerReport};
+
+#[component]
+pub fn Ledger() -> impl IntoView {
+    use crate::components::protected::Protected;
+    use leptos_router::components::Outlet;
+
+    view! {
+        <Protected>
+            <Outlet/>
+        </Protected>
+    }
+}
+
+#[component]
+pub fn TreasurerReportPage() -> impl IntoView {
+
+    let current_year = chrono::Datelike::year(&chrono::Utc::now());
+    let year = RwSignal::new(current_year);
+    let report = Resource::new(move || year.get(), get_treasurer_report);
+
+    view! {
+        <BackButton/>
+        <div class="p-8 max-w-4xl mx-auto">
+            <div class="flex items-center justify-between mb-6">
+                <h1 class="text-2xl font-semibold">
+                    "Kassenbericht"
+                </h1>
+                <div class="flex gap-2">
+                    <A href="/ledger/fees"
+                        attr:class="bg-blue-600 hover:bg-blue-700 text-white font-medium py-2 px-4 rounded transition"
+                    >
+                        "Gebühren"
+                    </A>
+                    <A href="/ledger/bank"
+                        attr:class="bg-blue-600 hover:bg-blue-700 text-white font-medium py-2 px-4 rounded transition"
+                    >
+                        "Bankimport"
+                    </A>
+                </div>
+            </div>
+
+            <div class="flex items-center gap-4 mb-6">
+                <label for="year" class="text-left text-gray-700">
+                    "Jahr:"
+                </label>
+                <input
+                    type="number"
+                    name="year"
+                    class="border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+                    prop:value=move || year.get()
+                    on:change=move |ev| {
+                        if let Ok(value) = event_target_value(&ev).parse() {
+                            year.set(value);
+                        }
+                    }
+                />
+            </div>
+
+            <Suspense fallback=move || view! { <p>"Lade Daten..."</p> }>
+                {move || {
+                    report.get().map(|result| match result {
+                        Ok(report) => view! { <TreasurerReportView report=report/> }.into_any(),
+                        Err(e) => view! { <p>{ e.to_string() }</p> }.into_any(),
+                    })
+                }}
+            </Suspense>
+        </div>
+    }
+}
+
+#[component]
+fn TreasurerReportView(report: TreasurerReport) -> impl IntoView {
+    let charged = report.charged_cents();
+    let paid = report.paid_cents();
+    let outstanding = report.outstanding_cents();
+
+    view! {
+        <div class="grid grid-cols-[auto_1fr] items-center gap-4 text-left mb-6">
+            <span class="text-gray-700">{ format!("Forderungen {}:", report.year) }</span>
+            <span>{ format_cents(charged) }</span>
+            <span class="text-gray-700">{ format!("Eingänge {}:", report.year) }</span>
+            <span>{ format_cents(paid) }</span>
+            <span class="text-gray-700">"Offen gesamt:"</span>
+            <span>{ format_cents(outstanding) }</span>
+        </div>
+
+        <h2 class="text-left text-xl font-semibold py-2">Forderungen nach Art</h2>
+        <div class="overflow-x-auto">
+            <table class="min-w-full border border-gray-200 shadow-sm rounded-md bg-white">
+                <thead class="bg-gray-100 text-gray-700">
+                    <tr>
+                        <th class="text-left py-2 px-4 border-b">Art</th>
+                        <th class="text-left py-2 px-4 border-b">Betrag</th>
+                    </tr>
+                </thead>
+                <tbody>
+                    {
+                        report.charged_by_kind.into_iter().map(|(kind, cents)| view! {
+                            <tr class="hover:bg-gray-50">
+                                <th class="text-left py-2 px-4 border-b">
+                                    { kind.map(|kind| kind.to_string()).unwrap_or("Sonstiges".to_string()) }
+                                </th>
+                                <th class="text-left py-2 px-4 border-b">{ format_cents(cents) }</th>
+                            </tr>
+                        }).collect_view()
+                    }
+                </tbody>
+            </table>
+        </div>
+
+        <h2 class="text-left text-xl font-semibold py-2">Eingänge nach Zahlungsart</h2>
+        <div class="overflow-x-auto">
+            <table class="min-w-full border border-gray-200 shadow-sm rounded-md bg-white">
+                <thead class="bg-gray-100 text-gray-700">
+                    <tr>
+                        <th class="text-left py-2 px-4 border-b">Zahlungsart</th>
+                        <th class="text-left py-2 px-4 border-b">Betrag</th>
+                    </tr>
+                </thead>
+                <tbody>
+                    {
+                        report.paid_by_method.into_iter().map(|(method, cents)| view! {
+                            <tr class="hover:bg-gray-50">
+                                <th class="text-left py-2 px-4 border-b">{ method.to_string() }</th>
+                                <th class="text-left py-2 px-4 border-b">{ format_cents(cents) }</th>
+                            </tr>
+                        }).collect_view()
+                    }
+                </tbody>
+            </table>
+        </div>
+
+        <h2 class="text-left text-xl font-semibold py-2">Offene Beträge</h2>
+        <BalancesTable balances=report.debtors/>
+    }
+}
+
+#[component]
+fn BalancesTable(balances: Vec<PlayerBalance>) -> impl IntoView {
+
+    view! {
+        <div class="overflow-x-auto">
+            <table class="min-w-full border border-gray-200 shadow-sm rounded-md bg-white">
+                <thead class="bg-gray-100 text-gray-700">
+                    <tr>
+                        <th class="text-left py-2 px-4 border-b">Spielername</th>
+                        <th class="text-left py-2 px-4 border-b">Forderungen</th>
+                        <th class="text-left py-2 px-4 border-b">Bezahlt</th>
+                        <th class="text-left py-2 px-4 border-b">Offen</th>
+                    </tr>
+                </thead>
+                <tbody>
+                    {
+                        balances.into_iter().map(|balance: PlayerBalance| {
+                            let outstanding = balance.outstanding_cents();
+                            let class = if outstanding > 0 {
+                                "text-left py-2 px-4 border-b text-red-600"
+                            } else {
+                                "text-left py-2 px-4 border-b"
+                            };
+
+                            view! {
+                                <tr class="hover:bg-gray-50">
+                                    <th class="text-left py-2 px-4 border-b">
+                                        <A href=format!("/players/{}", balance.player_id) attr:class="hover:underline">{ balance.tag_name }</A>
+                                    </th>
+                                    <th class="text-left py-2 px-4 border-b">{ format_cents(balance.charged_cents) }</th>
+                                    <th class="text-left py-2 px-4 border-b">{ format_cents(balance.paid_cents) }</th>
+                                    <th class=class>{ format_cents(outstanding) }</th>
+                                </tr>
+                            }
+                        }).collect_view()
+                    }
+                </tbody>
+            </table>
+        </div>
+    }
+}
+
+#[component]
+pub fn FeesPage() -> impl IntoView {
+
+    let create_fee = ServerAction::<CreateFee>::new();
+    let delete_fee = ServerAction::<DeleteFee>::new();
+    let charge_fee = ServerAction::<ChargeFee>::new();
+
+    let fees = Resource::new(
+        move || (create_fee.version().get(), delete_fee.version().get()),
+        |_| get_fees(),
+    );
+    let play_events = Resource::new(|| {}, |_| get_fee_play_events());
+
+    let action_error = move || {
+        let create_error = create_fee.value().get().and_then(|result| result.err());
+        let delete_error = delete_fee.value().get().and_then(|result| result.err());
+        let charge_error = charge_fee.value().get().and_then(|result| result.err());
+
+        create_error.or(delete_error).or(charge_error).map(|e| view! {
+            <p class="text-red-600">{ e.to_string() }</p>
+        })
+    };
+
+    let charge_result = move || charge_fee.value().get().and_then(|result| result.ok()).map(|count| view! {
+        <p class="text-left text-green-700">{ format!("{} Forderungen angelegt", count) }</p>
+    });
+
+    view! {
+        <BackButton/>
+        <div class="p-8 max-w-4xl mx-auto">
+            <div class="flex items-center justify-between mb-6">
+                <h1 class="text-2xl font-semibold">
+                    "Gebühren"
+                </h1>
+            </div>
+
+            <Suspense fallback=move || view! { <p>"Lade Daten..."</p> }>
+                {move || {
+                    fees.get().map(|result| match result {
+                        Ok(fees) => view! {
+                            <div class="overflow-x-auto">
+                                <table class="min-w-full border border-gray-200 shadow-sm rounded-md bg-white">
+                                    <thead class="bg-gray-100 text-gray-700">
+                                        <tr>
+                                            <th class="text-left py-2 px-4 border-b">Name</th>
+                                            <th class="text-left py-2 px-4 border-b">Art</th>
+                                            <th class="text-left py-2 px-4 border-b">Betrag</th>
+                                            <th class="py-2 px-4 border-b"></th>
+                                        </tr>
+                                    </thead>
+                                    <tbody>
+                                        {
+                                            fees.into_iter().map(|fee: Fee| {
+                                                let chargeable = fee.kind != FeeKind::Rental;
+
+                                                view! {
+                                                    <tr class="hover:bg-gray-50">
+                                                        <th class="text-left py-2 px-4 border-b">{ fee.name.clone() }</th>
+                                                        <th class="text-left py-2 px-4 border-b">{ fee.kind.to_string() }</th>
+                                                        <th class="text-left py-2 px-4 border-b">{ format_cents(fee.amount_cents) }</th>
+                                                        <th class="py-2 px-4 border-b">
+                                                            <div class="flex justify-end gap-2">
+                                                                <Show when=move || chargeable>
+                                                                    <ActionForm action=charge_fee>
+                                                                        <input type="hidden" name="id" value=fee.id/>
+                                                                        <button
+                                                                            type="submit"
+                                                                            class="px-4 py-2 rounded bg-blue-600 hover:bg-blue-700 text-white"
+                                                                        >
+                                                                            "Abrechnen"
+                                                                        </button>
+                                                                    </ActionForm>
+                                                                </Show>
+                                                                <ActionForm action=delete_fee>
+                                                                    <input type="hidden" name="id" value=fee.id/>
+                                                                    <button
+                                                                        type="submit"
+                                                                        class="px-4 py-2 rounded bg-gray-200 hover:bg-gray-300 text-gray-700"
+                                                                    >
+                                                                        "Löschen"
+                                                                    </button>
+                                                                </ActionForm>
+                                                            </div>
+                                                        </th>
+                                                    </tr>
+                                                }
+                                            }).collect_view()
+                                        }
+                                    </tbody>
+                                </table>
+                            </div>
+                        }.into_any(),
+                        Err(e) => view! { <p>{ e.to_string() }</p> }.into_any(),
+                    })
+                }}
+            </Suspense>
+
+            { charge_result }
+            { action_error }
+
+            <h2 class="text-left text-xl font-semibold py-2">Neue Gebühr</h2>
+            <ActionForm action=create_fee attr:class="grid grid-cols-[auto_1fr] items-center gap-4">
+                <label for="fee_form[name]" class="text-left text-gray-700">
+                    "Name:"
+                </label>
+                <input
+                    type="text"
+                    name="fee_form[name]"
+                    class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+                    required
+                />
+
+                <label for="fee_form[kind]" class="text-left text-gray-700">
+                    "Art:"
+                </label>
+                <select
+                    name="fee_form[kind]"
+                    class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+                >
+                    {
+                        FeeKind::ALL
+                            .into_iter()
+                            .map(|kind| view! { <option value=kind.as_str()>{ kind.to_string() }</option> })
+                            .collect_view()
+                    }
+                </select>
+
+                <label for="fee_form[amount]" class="text-left text-gray-700">
+                    "Betrag (€):"
+                </label>
+                <input
+                    type="text"
+                    inputmode="decimal"
+                    name="fee_form[amount]"
+                    class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+                    required
+                />
+
+                <label for="fee_form[play_event_id]" class="text-left text-gray-700">
+                    "Event:"
+                </label>
+                <Suspense fallback=move || view! { <p>"Lade Daten..."</p> }>
+                    {move || {
+                        play_events.get().map(|result| match result {
+                            Ok(play_events) => view! {
+                                <select
+                                    name="fee_form[play_event_id]"
+                                    class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+                                >
+                                    <option value="">"Kein Event"</option>
+                                    {
+                                        play_events
+                                            .into_iter()
+                                            .map(|play_event: PlayEvent| view! {
+                                                <option value=play_event.id>
+                                                    { format!("{} ({})", play_event.name, format_datetime(&play_event.starts_at)) }
+                                                </option>
+                                            })
+                                            .collect_view()
+                                    }
+                                </select>
+                            }.into_any(),
+                            Err(e) => view! { <p>{ e.to_string() }</p> }.into_any(),
+                        })
+                    }}
+                </Suspense>
+
+                <div class="col-span-2 flex justify-end">
+                    <button
+                        type="submit"
+                        class="px-4 py-2 rounded bg-blue-600 hover:bg-blue-700 text-white"
+                    >
+                        "Gebühr anlegen"
+                    </button>
+                </div>
+            </ActionForm>
+        </div>
+    }
+}
+
+#[component]
+pub fn PlayerAccount(player_id: i64) -> impl IntoView {
+
+    let add_charge = ServerAction::<AddCharge>::new();
+    let delete_charge = ServerAction::<DeleteCharge>::new();
+    let record_payment = ServerAction::<RecordPayment>::new();
+    let delete_payment = ServerAction::<DeletePayment>::new();
+
+    let ledger = Resource::new(
+        move || (
+            add_charge.version().get(),
+            delete_charge.version().get(),
+            record_payment.version().get(),
+            delete_payment.version().get(),
+        ),
+        move |_| get_player_ledger(player_id),
+    );
+
+    let action_error = move || {
+        let add_charge_error = add_charge.value().get().and_then(|result| result.err());
+        let delete_charge_error = delete_charge.value().get().and_then(|result| result.err());
+        let record_payment_error = record_payment.value().get().and_then(|result| result.err());
+        let delete_payment_error = delete_payment.value().get().and_then(|result| result.err());
+
+        add_charge_error
+            .or(delete_charge_error)
+            .or(record_payment_error)
+            .or(delete_payment_error)
+            .map(|e| view! { <p class="text-red-600">{ e.to_string() }</p> })
+    };
+
+    view! {
+        <Suspense fallback=move || view! { <p>"Lade Daten..."</p> }>
+            {move || {
+                ledger.get().map(|result| match result {
+                    Ok(Some(ledger)) => {
+                        let balance = ledger.balance();
+                        let paid_per_charge = ledger.paid_per_charge();
+                        let editable = ledger.editable;
+                        let open_charges: Vec<Charge> = ledger.charges
+                            .iter()
+                            .filter(|charge| paid_per_charge.get(&charge.id.unwrap()).copied().unwrap_or(0) < charge.amount_cents)
+                            .cloned()
+                            .collect();
+
+                        view! {
+                            <h2 class="text-left text-xl font-semibold py-2">Beiträge</h2>
+
+                            <p class="text-left text-gray-700 pb-2">
+                                "Offen: " { format_cents(balance.outstanding_cents()) }
+                            </p>
+
+                            <div class="overflow-x-auto">
+                                <table class="min-w-full border border-gray-200 shadow-sm rounded-md bg-white">
+                                    <thead class="bg-gray-100 text-gray-700">
+                                        <tr>
+                                            <th class="text-left py-2 px-4 border-b">Forderung</th>
+                                            <th class="text-left py-2 px-4 border-b">Verwendungszweck</th>
+                                            <th class="text-left py-2 px-4 border-b">Datum</th>
+                                            <th class="text-left py-2 px-4 border-b">Betrag</th>
+                                            <th class="text-left py-2 px-4 border-b">Bezahlt</th>
+                                            <th class="py-2 px-4 border-b"></th>
+                                        </tr>
+                                    </thead>
+                                    <tbody>
+                                        {
+                                            ledger.charges.into_iter().map(|charge: Charge| view! {
+                                                <tr class="hover:bg-gray-50">
+                                                    <th class="text-left py-2 px-4 border-b">{ charge.description.clone() }</th>
+                                                    <th class="text-left py-2 px-4 border-b">{ charge.reference() }</th>
+                                                    <th class="text-left py-2 px-4 border-b">{ format_datetime(&charge.created_at) }</th>
+                                                    <th class="text-left py-2 px-4 border-b">{ format_cents(charge.amount_cents) }</th>
+                                                    <th class="text-left py-2 px-4 border-b">
+                                                        { format_cents(paid_per_charge.get(&charge.id.unwrap()).copied().unwrap_or(0)) }
+                                                    </th>
+                                                    <th class="py-2 px-4 border-b">
+                                                        <Show when=move || editable>
+                                                            <ActionForm action=delete_charge>
+                                                                <input type="hidden" name="id" value=charge.id/>
+                                                                <button
+                                                                    type="submit"
+                                                                    class="px-4 py-2 rounded bg-gray-200 hover:bg-gray-300 text-gray-700"
+                                                                >
+                                                                    "Entfernen"
+                                                                </button>
+                                                            </ActionForm>
+                                                        </Show>
+                                                    </th>
+                                                </tr>
+                                            }).collect_view()
+                                        }
+                                    </tbody>
+                                </table>
+                            </div>
+
+                            <div class="overflow-x-auto pt-4">
+                                <table class="min-w-full border border-gray-200 shadow-sm rounded-md bg-white">
+                                    <thead class="bg-gray-100 text-gray-700">
+                                        <tr>
+                                            <th class="text-left py-2 px-4 border-b">Zahlung</th>
+                                            <th class="text-left py-2 px-4 border-b">Zahlungsart</th>
+                                            <th class="text-left py-2 px-4 border-b">Referenz</th>
+                                            <th class="text-left py-2 px-4 border-b">Betrag</th>
+                                            <th class="py-2 px-4 border-b"></th>
+                                        </tr>
+                                    </thead>
+                                    <tbody>
+                                        {
+                                            ledger.payments.into_iter().map(|payment: Payment| view! {
+                                                <tr class="hover:bg-gray-50">
+                                                    <th class="text-left py-2 px-4 border-b">{ format_date(&payment.paid_on) }</th>
+                                                    <th class="text-left py-2 px-4 border-b">{ payment.method.to_string() }</th>
+                                                    <th class="text-left py-2 px-4 border-b">{ payment.reference.clone() }</th>
+                                                    <th class="text-left py-2 px-4 border-b">{ format_cents(payment.amount_cents) }</th>
+                                                    <th class="py-2 px-4 border-b">
+                                                        <Show when=move || editable>
+                                                            <ActionForm action=delete_payment>
+                                                                <input type="hidden" name="id" value=payment.id/>
+                                                                <button
+                                                                    type="submit"
+                                                                    class="px-4 py-2 rounded bg-gray-200 hover:bg-gray-300 text-gray-700"
+                                                                >
+                                                                    "Entfernen"
+                                                                </button>
+                                                            </ActionForm>
+                                                        </Show>
+                                                    </th>
+                                                </tr>
+                                            }).collect_view()
+                                        }
+                                    </tbody>
+                                </table>
+                            </div>
+
+                            <Show when=move || editable>
+                                <ChargeForm player_id=player_id action=add_charge/>
+                                <PaymentForm player_id=player_id open_charges=open_charges.clone() action=record_payment/>
+                            </Show>
+                        }.into_any()
+                    },
+                    Ok(None) => ().into_any(),
+                    Err(e) => view! { <p>{ e.to_string() }</p> }.into_any(),
+                })
+            }}
+        </Suspense>
+
+        { action_error }
+    }
+}
+
+#[component]
+fn ChargeForm(player_id: i64, action: ServerAction<AddCharge>) -> impl IntoView {
+    let fees = Resource::new(|| {}, |_| get_fees());
+    let field = |field: &str| format!("charge_form[{}]", field);
+
+    view! {
+        <ActionForm action=action attr:class="grid grid-cols-[auto_1fr] items-center gap-4 pt-4">
+            <input type="hidden" name=field("player_id") value=player_id/>
+
+            <h3 class="col-span-2 text-left font-semibold">"Forderung anlegen"</h3>
+
+            <label for=field("fee_id") class="text-left text-gray-700">
+                "Gebühr:"
+            </label>
+            <Suspense fallback=move || view! { <p>"Lade Daten..."</p> }>
+                {move || {
+                    fees.get().map(|result| match result {
+                        Ok(fees) => view! {
+                            <select
+                                name=field("fee_id")
+                                class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+                            >
+                                <option value="">"Freie Forderung"</option>
+                                {
+                                    fees
+                                        .into_iter()
+                                        .map(|fee: Fee| view! {
+                                            <option value=fee.id>{ format!("{} ({})", fee.name, format_cents(fee.amount_cents)) }</option>
+                                        })
+                                        .collect_view()
+                                }
+                            </select>
+                        }.into_any(),
+                        Err(e) => view! { <p>{ e.to_string() }</p> }.into_any(),
+                    })
+                }}
+            </Suspense>
+
+            <label for=field("description") class="text-left text-gray-700">
+                "Beschreibung:"
+            </label>
+            <input
+                type="text"
+                name=field("description")
+                placeholder="Nur für freie Forderungen"
+                class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+            />
+
+            <label for=field("amount") class="text-left text-gray-700">
+                "Betrag (€):"
+            </label>
+            <input
+                type="text"
+                inputmode="decimal"
+                name=field("amount")
+                placeholder="Nur für freie Forderungen"
+                class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+            />
+
+            <div class="col-span-2 flex justify-end">
+                <button
+                    type="submit"
+                    class="px-4 py-2 rounded bg-blue-600 hover:bg-blue-700 text-white"
+                >
+                    "Forderung anlegen"
+                </button>
+            </div>
+        </ActionForm>
+    }
+}
+
+#[component]
+fn PaymentForm(player_id: i64, open_charges: Vec<Charge>, action: ServerAction<RecordPayment>) -> impl IntoView {
+    let field = |field: &str| format!("payment_form[{}]", field);
+    let today = date_input_value(&chrono::Utc::now().date_naive());
+
+    view! {
+        <ActionForm action=action attr:class="grid grid-cols-[auto_1fr] items-center gap-4 pt-4">
+            <input type="hidden" name=field("player_id") value=player_id/>
+
+            <h3 class="col-span-2 text-left font-semibold">"Zahlung erfassen"</h3>
+
+            <label for=field("charge_id") class="text-left text-gray-700">
+                "Für Forderung:"
+            </label>
+            <select
+                name=field("charge_id")
+                class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+            >
+                <option value="">"Keine bestimmte Forderung"</option>
+                {
+                    open_charges
+                        .into_iter()
+                        .map(|charge| view! {
+                            <option value=charge.id>{ format!("{} ({})", charge.description, format_cents(charge.amount_cents)) }</option>
+                        })
+                        .collect_view()
+                }
+            </select>
+
+            <label for=field("amount") class="text-left text-gray-700">
+                "Betrag (€):"
+            </label>
+            <input
+                type="text"
+                inputmode="decimal"
+                name=field("amount")
+                class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+                required
+            />
+
+            <label for=field("method") class="text-left text-gray-700">
+                "Zahlungsart:"
+            </label>
+            <select
+                name=field("method")
+                class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+            >
+                {
+                    PaymentMethod::ALL
+                        .into_iter()
+                        .map(|method| view! { <option value=method.as_str()>{ method.to_string() }</option> })
+                        .collect_view()
+                }
+            </select>
+
+            <label for=field("reference") class="text-left text-gray-700">
+                "Referenz:"
+            </label>
+            <input
+                type="text"
+                name=field("reference")
+                class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+            />
+
+            <label for=field("paid_on") class="text-left text-gray-700">
+                "Datum:"
+            </label>
+            <input
+                type="date"
+                name=field("paid_on")
+                class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+                value=today
+                required
+            />
+
+            <div class="col-span-2 flex justify-end">
+                <button
+                    type="submit"
+                    class="px-4 py-2 rounded bg-blue-600 hover:bg-blue-700 text-white"
+                >
+                    "Zahlung erfassen"
+                </button>
+            </div>
+        </ActionForm>
+    }
+}
+
+#[component]
+pub fn TeamBalances(team_id: i64) -> impl IntoView {
+
+    let balances = Resource::new(|| {}, move |_| get_team_balances(team_id));
+
+    view! {
+        <Suspense fallback=move || view! { <p>"Lade Daten..."</p> }>
+            {move || {
+                balances.get().map(|result| match result {
+                    Ok(Some(balances)) => {
+                        let outstanding: i64 = balances.iter().map(PlayerBalance::outstanding_cents).sum();
+
+                        view! {
+                            <h2 class="text-left text-xl font-semibold py-2">Beiträge</h2>
+                            <p class="text-left text-gray-700 pb-2">
+                                "Offen im Team: " { format_cents(outstanding) }
+                            </p>
+                            <BalancesTable balances=balances/>
+                        }.into_any()
+                    },
+                    Ok(None) => ().into_any(),
+                    Err(e) => view! { <p>{ e.to_string() }</p> }.into_any(),
+                })
+            }}
+        </Suspense>
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct FeeForm {
+    name: String,
+    kind: String,
+    amount: String,
+    #[serde(default)]
+    play_event_id: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct ChargeForm {
+    player_id: i64,
+    #[serde(default)]
+    fee_id: String,
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    amount: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct PaymentForm {
+    player_id: i64,
+    #[serde(default)]
+    charge_id: String,
+    amount: String,
+    method: String,
+    #[serde(default)]
+    reference: String,
+    paid_on: String,
+}
+
+/// Empty select values stand for no reference.
+#[cfg(feature = "ssr")]
+fn parse_optional_id(value: &str, field: &str) -> Result<Option<i64>, AppError> {
+    if value.is_empty() {
+        Ok(None)
+    } else {
+        value.parse().map(Some).map_err(|_| AppError::InvalidInput(field.to_string()))
+    }
+}
+
+#[cfg(feature = "ssr")]
+fn parse_amount(value: &str) -> Result<i64, AppError> {
+    use crate::domain::parse_cents;
+
+    parse_cents(value)
+        .filter(|cents| *cents > 0)
+        .ok_or(AppError::InvalidInput("Betrag".to_string()))
+}
+
+#[server]
+pub async fn is_treasurer() -> Result<bool, AppError> {
+    use crate::auth::current_player_if_logged_in;
+
+    Ok(current_player_if_logged_in().await?.is_some_and(|player| player.admin))
+}
+
+#[server]
+async fn get_treasurer_report(year: i32) -> Result<TreasurerReport, AppError> {
+    use crate::auth::require_admin;
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    require_admin().await?;
+
+    Ok(database::get_treasurer_report(year, &pool)?)
+}
+
+#[server]
+async fn get_fees() -> Result<Vec<Fee>, AppError> {
+    use crate::auth::require_admin;
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    require_admin().await?;
+
+    Ok(database::get_fees(&pool)?
+        .into_iter()
+        .map(Into::into)
+        .collect())
+}
+
+#[server]
+async fn get_fee_play_events() -> Result<Vec<PlayEvent>, AppError> {
+    use crate::auth::require_admin;
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    require_admin().await?;
+
+    Ok(database::get_all_play_events(&pool)?
+        .into_iter()
+        .map(Into::into)
+        .collect())
+}
+
+#[server]
+async fn create_fee(fee_form: FeeForm) -> Result<(), AppError> {
+    use crate::auth::require_admin;
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    require_admin().await?;
+
+    let fee = Fee {
+        id: None,
+        name: fee_form.name.trim().to_string(),
+        kind: fee_form.kind
+            .parse()
+            .map_err(|_| AppError::InvalidInput("Art".to_string()))?,
+        amount_cents: parse_amount(&fee_form.amount)?,
+        play_event_id: parse_optional_id(&fee_form.play_event_id, "Event")?,
+    };
+
+    if fee.name.is_empty() {
+        return Err(AppError::InvalidInput("Name".to_string()));
+    }
+
+    if fee.kind == FeeKind::Event && fee.play_event_id.is_none() {
+        return Err(AppError::InvalidInput("Event".to_string()));
+    }
+
+    database::create_fee(fee, &pool)?;
+
+    Ok(())
+}
+
+#[server]
+async fn delete_fee(id: i64) -> Result<(), AppError> {
+    use crate::auth::require_admin;
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    require_admin().await?;
+
+    Ok(database::delete_fee(id, &pool)?)
+}
+
+/// Charges the fee to everyone it applies to and returns the number of new charges.
+#[server]
+async fn charge_fee(id: i64) -> Result<usize, AppError> {
+    use crate::auth::require_admin;
+    use crate::database::{self, DatabaseError, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let admin = require_admin().await?;
+
+    let fee: Fee = database::find_fee_for_id(id, &pool)?
+        .ok_or(DatabaseError::EntityNotFound)?
+        .into();
+    let player_ids = database::get_fee_target_player_ids(&fee, &pool)?;
+
+    Ok(database::charge_fee(&fee, &player_ids, admin.id, &pool)?)
+}
+
+/// Ledgers are visible to the player and admins, only admins book entries.
+#[server]
+async fn get_player_ledger(player_id: i64) -> Result<Option<PlayerLedger>, AppError> {
+    use crate::auth::current_player;
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let player = current_player().await?;
+
+    if !player.admin && player.id != player_id {
+        return Ok(None);
+    }
+
+    Ok(Some(PlayerLedger {
+        charges: database::get_charges_for_player(player_id, &pool)?
+            .into_iter()
+            .map(Into::into)
+            .collect(),
+        payments: database::get_payments_for_player(player_id, &pool)?
+            .into_iter()
+            .map(Into::into)
+            .collect(),
+        editable: player.admin,
+    }))
+}
+
+/// A selected fee determines description and amount, otherwise both are required.
+#[server]
+async fn add_charge(charge_form: ChargeForm) -> Result<(), AppError> {
+    use crate::auth::require_admin;
+    use crate::database::{self, DatabaseError, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let admin = require_admin().await?;
+
+    let charge = match parse_optional_id(&charge_form.fee_id, "Gebühr")? {
+        Some(fee_id) => {
+            let fee: Fee = database::find_fee_for_id(fee_id, &pool)?
+                .ok_or(DatabaseError::EntityNotFound)?
+                .into();
+
+            Charge {
+                player_id: charge_form.player_id,
+                fee_id: fee.id,
+                description: fee.name,
+                amount_cents: fee.amount_cents,
+                created_by_id: Some(admin.id),
+                ..Default::default()
+            }
+        }
+        None => {
+            let description = charge_form.description.trim().to_string();
+
+            if description.is_empty() {
+                return Err(AppError::InvalidInput("Beschreibung".to_string()));
+            }
+
+            Charge {
+                player_id: charge_form.player_id,
+                description,
+                amount_cents: parse_amount(&charge_form.amount)?,
+                created_by_id: Some(admin.id),
+                ..Default::default()
+            }
+        }
+    };
+
+    database::create_charge(charge, &pool)?;
+
+    Ok(())
+}
+
+#[server]
+async fn delete_charge(id: i64) -> Result<(), AppError> {
+    use crate::auth::require_admin;
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    require_admin().await?;
+
+    Ok(database::delete_charge(id, &pool)?)
+}
+
+#[server]
+async fn record_payment(payment_form: PaymentForm) -> Result<(), AppError> {
+    use crate::auth::require_admin;
+    use crate::components::util::parse_date_input;
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let admin = require_admin().await?;
+
+    let payment = Payment {
+        id: None,
+        player_id: payment_form.player_id,
+        charge_id: parse_optional_id(&payment_form.charge_id, "Forderung")?,
+        amount_cents: parse_amount(&payment_form.amount)?,
+        method: payment_form.method
+            .parse()
+            .map_err(|_| AppError::InvalidInput("Zahlungsart".to_string()))?,
+        reference: payment_form.reference.trim().to_string(),
+        paid_on: parse_date_input(&payment_form.paid_on)
+            .ok_or(AppError::InvalidInput("Datum".to_string()))?,
+        recorded_by_id: Some(admin.id),
+    };
+
+    database::create_payment(payment, &pool)?;
+
+    Ok(())
+}
+
+#[server]
+async fn delete_payment(id: i64) -> Result<(), AppError> {
+    use crate::auth::require_admin;
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    require_admin().await?;
+
+    Ok(database::delete_payment(id, &pool)?)
+}
+
+/// Team balances are shown to admins and the team's contact person.
+#[server]
+async fn get_team_balances(team_id: i64) -> Result<Option<Vec<PlayerBalance>>, AppError> {
+    use crate::auth::current_player;
+    use crate::database::{self, DatabaseError, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let player = current_player().await?;
+    let team = database::find_team_for_id(team_id, &pool)?
+        .ok_or(DatabaseError::EntityNotFound)?;
+
+    if !player.admin && team.contact_person_id != Some(player.id) {
+        return Ok(None);
+    }
+
+    let member_ids: Vec<i64> = database::get_players_for_team(team_id, &pool)?
+        .into_iter()
+        .map(|member| member.id)
+        .collect();
+
+    Ok(Some(database::get_balances_for_players(&member_ids, &pool)?))
+}
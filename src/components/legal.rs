@@ -0,0 +1,596 @@
+use leptos::prelude::*;
+use leptos::Params;
+use leptos_router::{components::A, hooks::use_params, params::Params};
+use serde::{Deserialize, Serialize};
+
+use crate::app::AppError;
+use crate::components::util::{format_datetime, BackButton};
+use crate::domain::{LegalAcceptance, LegalDocument, LegalDocumentKind};
+use crate::markdown::render_markdown;
+
+#[component]
+pub fn LegalDocuments() -> impl IntoView {
+    use crate::components::protected::Protected;
+    use leptos_router::components::Outlet;
+
+    view! {
+        <Protected>
+            <Outlet/>
+        </Protected>
+    }
+}
+
+#[component]
+pub fn LegalDocumentsTable() -> impl IntoView {
+
+    let publish = ServerAction::<PublishLegalDocument>::new();
+    let delete = ServerAction::<DeleteLegalDocument>::new();
+
+    let documents = Resource::new(
+        move || (publish.version().get(), delete.version().get()),
+        |_| get_legal_documents(),
+    );
+    let is_admin = Resource::new(|| {}, |_| is_legal_admin());
+
+    let action_error = move || {
+        let publish_error = publish.value().get().and_then(|result| result.err());
+        let delete_error = delete.value().get().and_then(|result| result.err());
+
+        publish_error.or(delete_error).map(|e| view! {
+            <p class="text-red-600">{ e.to_string() }</p>
+        })
+    };
+
+    let editable = move || is_admin.get().and_then(|result| result.ok()).unwrap_or(false);
+
+    view! {
+        <BackButton/>
+        <div class="p-8 max-w-4xl mx-auto">
+            <div class="flex items-center justify-between mb-6">
+                <h1 class="text-2xl font-semibold">
+                    "Dokumente"
+                </h1>
+                <Transition>
+                    <Show when=editable>
+                        <A href="/legal/new"
+                            attr:class="bg-blue-600 hover:bg-blue-700 text-white font-medium py-2 px-4 rounded transition"
+                        >
+                            "Neue Version"
+                        </A>
+                    </Show>
+                </Transition>
+            </div>
+
+            <Suspense fallback=move || view! { <p>"Lade Daten..."</p> }>
+                {
+                    move || {
+                        documents.get().map(|result| match result {
+                            Ok(documents) => view! {
+                                <div class="overflow-x-auto">
+                                    <table class="min-w-full border border-gray-200 shadow-sm rounded-md bg-white">
+                                        <thead class="bg-gray-100 text-gray-700">
+                                            <tr>
+                                                <th class="text-left py-2 px-4 border-b">Art</th>
+                                                <th class="text-left py-2 px-4 border-b">Version</th>
+                                                <th class="text-left py-2 px-4 border-b">Titel</th>
+                                                <th class="text-left py-2 px-4 border-b">Veröffentlicht</th>
+                                                <th class="py-2 px-4 border-b"></th>
+                                            </tr>
+                                        </thead>
+                                        <tbody>
+                                            {
+                                                documents.into_iter().map(|document: LegalDocument| {
+                                                    let is_draft = !document.is_published();
+
+                                                    view! {
+                                                        <tr class="hover:bg-gray-50">
+                                                            <th class="text-left py-2 px-4 border-b">{ document.kind.to_string() }</th>
+                                                            <th class="text-left py-2 px-4 border-b">{ document.version }</th>
+                                                            <th class="text-left py-2 px-4 border-b">
+                                                                <A href=format!("/legal/{}", document.id.unwrap()) attr:class="hover:underline">{ document.title }</A>
+                                                            </th>
+                                                            <th class="text-left py-2 px-4 border-b">
+                                                                { document.published_at.as_ref().map(format_datetime).unwrap_or("Entwurf".to_string()) }
+                                                            </th>
+                                                            <th class="py-2 px-4 border-b">
+                                                                <Show when=move || is_draft>
+                                                                    <div class="flex justify-end gap-2">
+                                                                        <ActionForm action=publish>
+                                                                            <input type="hidden" name="id" value=document.id/>
+                                                                            <button
+                                                                                type="submit"
+                                                                                class="px-4 py-2 rounded bg-blue-600 hover:bg-blue-700 text-white"
+                                                                            >
+                                                                                "Veröffentlichen"
+                                                                            </button>
+                                                                        </ActionForm>
+                                                                        <ActionForm action=delete>
+                                                                            <input type="hidden" name="id" value=document.id/>
+                                                                            <button
+                                                                                type="submit"
+                                                                                class="px-4 py-2 rounded bg-gray-200 hover:bg-gray-300 text-gray-700"
+                                                                            >
+                                                                                "Löschen"
+                                                                            </button>
+                                                                        </ActionForm>
+                                                                    </div>
+                                                                </Show>
+                                                            </th>
+                                                        </tr>
+                                                    }
+                                                }).collect_view()
+                                            }
+                                        </tbody>
+                                    </table>
+                                </div>
+                            }.into_any(),
+                            Err(e) => view! { <p>{ e.to_string() }</p> }.into_any(),
+                        })
+                    }
+                }
+            </Suspense>
+
+            { action_error }
+        </div>
+    }
+}
+
+#[component]
+pub fn LegalDocumentNew() -> impl IntoView {
+
+    let create_legal_document = ServerAction::<CreateLegalDocument>::new();
+    let field = |field: &str| format!("legal_document_form[{}]", field);
+    let content = RwSignal::new(String::new());
+
+    view! {
+        <div class="p-8 max-w-4xl mx-auto">
+            <div class="flex items-center justify-between mb-6">
+                <h1 class="text-2xl font-semibold">
+                    "Neue Dokumentversion anlegen"
+                </h1>
+            </div>
+
+            <ActionForm action=create_legal_document>
+                <div class="grid grid-cols-[auto_1fr] items-center gap-4">
+                    <label for=field("kind") class="text-left text-gray-700">
+                        "Art:"
+                    </label>
+                    <select
+                        name=field("kind")
+                        class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+                    >
+                        {
+                            LegalDocumentKind::ALL
+                                .into_iter()
+                                .map(|kind| view! { <option value=kind.as_str()>{ kind.to_string() }</option> })
+                                .collect_view()
+                        }
+                    </select>
+
+                    <label for=field("title") class="text-left text-gray-700">
+                        "Titel:"
+                    </label>
+                    <input
+                        type="text"
+                        name=field("title")
+                        class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+                        required
+                    />
+
+                    <label for=field("content") class="text-left text-gray-700">
+                        "Inhalt (Markdown):"
+                    </label>
+                    <textarea
+                        name=field("content")
+                        rows="15"
+                        class="w-full border border-gray-300 rounded px-3 py-2 font-mono focus:outline-none focus:ring focus:border-blue-300"
+                        on:input=move |ev| content.set(event_target_value(&ev))
+                        required
+                    ></textarea>
+
+                    <span class="text-left text-gray-700">
+                        "Vorschau:"
+                    </span>
+                    <div
+                        class="prose max-w-none border border-gray-200 rounded px-3 py-2 bg-white text-left min-h-16"
+                        inner_html=move || render_markdown(&content.get())
+                    ></div>
+                </div>
+
+                <div class="flex justify-end gap-2 mt-6">
+                    <A href="/legal"
+                        attr:class="px-4 py-2 rounded bg-gray-200 hover:bg-gray-300 text-gray-700"
+                    >
+                        "Abbrechen"
+                    </A>
+                    <button
+                        type="submit"
+                        class="px-4 py-2 rounded bg-blue-600 hover:bg-blue-700 text-white"
+                    >
+                        "Als Entwurf speichern"
+                    </button>
+                </div>
+            </ActionForm>
+
+            {move || create_legal_document.value().get().and_then(|result| result.err()).map(|e| view! {
+                <p class="text-red-600">{ e.to_string() }</p>
+            })}
+        </div>
+    }
+}
+
+#[component]
+pub fn LegalDocumentPage() -> impl IntoView {
+
+    let document_id = use_params::<LegalDocumentIdParameter>();
+    let document = Resource::new(
+        move || document_id.read().clone(),
+        move |params_result| get_legal_document(params_result.unwrap().id.unwrap()),
+    );
+
+    view! {
+        <BackButton/>
+        <div class="p-8 max-w-4xl mx-auto">
+            <Suspense fallback=move || view! { <p>"Lade Daten..."</p> }>
+                {move || {
+                    document.get().map(|result| match result {
+                        Ok(document) => view! { <LegalDocumentView document=document/> }.into_any(),
+                        Err(e) => view! { <p>{ e.to_string() }</p> }.into_any(),
+                    })
+                }}
+            </Suspense>
+        </div>
+    }
+}
+
+#[component]
+fn LegalDocumentView(document: LegalDocument) -> impl IntoView {
+
+    view! {
+        <article class="text-left mb-6">
+            <h2 class="text-xl font-semibold">{ document.title.clone() }</h2>
+            <p class="text-sm text-gray-500 mb-2">
+                { document.kind.to_string() } " – Version " { document.version }
+            </p>
+            <div class="prose max-w-none" inner_html=render_markdown(&document.content)></div>
+        </article>
+    }
+}
+
+/// Shown instead of the app until the player accepted the current version of every document.
+#[component]
+pub fn LegalAcceptancePage() -> impl IntoView {
+    use crate::components::protected::Protected;
+
+    let accept = ServerAction::<AcceptLegalDocuments>::new();
+    let pending = Resource::new(|| {}, |_| get_pending_legal_documents());
+
+    view! {
+        <Protected>
+            <div class="p-8 max-w-4xl mx-auto">
+                <div class="flex items-center justify-between mb-6">
+                    <h1 class="text-2xl font-semibold">
+                        "Zustimmung erforderlich"
+                    </h1>
+                </div>
+
+                <p class="text-left text-gray-700 mb-6">
+                    "Bevor du weiterspielen kannst, musst du die aktuellen Fassungen der folgenden Dokumente akzeptieren."
+                </p>
+
+                <Suspense fallback=move || view! { <p>"Lade Daten..."</p> }>
+                    {move || {
+                        pending.get().map(|result| match result {
+                            Ok(documents) => {
+                                let document_ids = documents
+                                    .iter()
+                                    .filter_map(|document| document.id)
+                                    .map(|id| id.to_string())
+                                    .collect::<Vec<_>>()
+                                    .join(",");
+
+                                view! {
+                                    {
+                                        documents
+                                            .into_iter()
+                                            .map(|document| view! { <LegalDocumentView document=document/> })
+                                            .collect_view()
+                                    }
+
+                                    <ActionForm action=accept attr:class="flex flex-col gap-4">
+                                        <input type="hidden" name="document_ids" value=document_ids/>
+                                        <label class="flex items-center gap-2 text-left text-gray-700">
+                                            <input type="checkbox" name="confirmed" value="true" required/>
+                                            "Ich habe die Dokumente gelesen und akzeptiere sie."
+                                        </label>
+                                        <div class="flex justify-end">
+                                            <button
+                                                type="submit"
+                                                class="px-4 py-2 rounded bg-blue-600 hover:bg-blue-700 text-white"
+                                            >
+                                                "Akzeptieren"
+                                            </button>
+                                        </div>
+                                    </ActionForm>
+                                }.into_any()
+                            },
+                            Err(e) => view! { <p>{ e.to_string() }</p> }.into_any(),
+                        })
+                    }}
+                </Suspense>
+
+                {move || accept.value().get().and_then(|result| result.err()).map(|e| view! {
+                    <p class="text-red-600">{ e.to_string() }</p>
+                })}
+            </div>
+        </Protected>
+    }
+}
+
+#[component]
+pub fn PlayerLegalAcceptances(player_id: i64) -> impl IntoView {
+
+    let acceptances = Resource::new(|| {}, move |_| get_player_legal_acceptances(player_id));
+
+    view! {
+        <Suspense fallback=move || view! { <p>"Lade Daten..."</p> }>
+            {move || {
+                acceptances.get().map(|result| match result {
+                    Ok(Some(acceptances)) => view! {
+                        <h2 class="text-left text-xl font-semibold py-2">Akzeptierte Dokumente</h2>
+
+                        <div class="overflow-x-auto">
+                            <table class="min-w-full border border-gray-200 shadow-sm rounded-md bg-white">
+                                <thead class="bg-gray-100 text-gray-700">
+                                    <tr>
+                                        <th class="text-left py-2 px-4 border-b">Dokument</th>
+                                        <th class="text-left py-2 px-4 border-b">Version</th>
+                                        <th class="text-left py-2 px-4 border-b">Akzeptiert am</th>
+                                        <th class="text-left py-2 px-4 border-b">IP-Adresse</th>
+                                    </tr>
+                                </thead>
+                                <tbody>
+                                    {
+                                        acceptances.into_iter().map(|acceptance: LegalAcceptance| view! {
+                                            <tr class="hover:bg-gray-50">
+                                                <th class="text-left py-2 px-4 border-b">
+                                                    <A href=format!("/legal/{}", acceptance.document_id) attr:class="hover:underline">
+                                                        { acceptance.kind.to_string() }
+                                                    </A>
+                                                </th>
+                                                <th class="text-left py-2 px-4 border-b">{ acceptance.version }</th>
+                                                <th class="text-left py-2 px-4 border-b">{ format_datetime(&acceptance.accepted_at) }</th>
+                                                <th class="text-left py-2 px-4 border-b">{ acceptance.ip_address }</th>
+                                            </tr>
+                                        }).collect_view()
+                                    }
+                                </tbody>
+                            </table>
+                        </div>
+                    }.into_any(),
+                    Ok(None) => ().into_any(),
+                    Err(e) => view! { <p>{ e.to_string() }</p> }.into_any(),
+                })
+            }}
+        </Suspense>
+    }
+}
+
+#[derive(Params, PartialEq, Clone)]
+struct LegalDocumentIdParameter {
+    id: Option<i64>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct LegalDocumentForm {
+    kind: String,
+    title: String,
+    content: String,
+}
+
+/// Prefers the address a reverse proxy forwarded over the one of the connection.
+#[cfg(feature = "ssr")]
+async fn client_ip_address() -> Result<Option<String>, AppError> {
+    use std::net::SocketAddr;
+
+    use axum::extract::ConnectInfo;
+    use axum::http::{Extensions, HeaderMap};
+
+    let (headers, extensions): (HeaderMap, Extensions) = leptos_axum::extract().await?;
+
+    let forwarded = headers
+        .get("x-forwarded-for")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.split(',').next())
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty());
+
+    Ok(forwarded.or_else(|| extensions
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(address)| address.ip().to_string())))
+}
+
+#[server]
+async fn is_legal_admin() -> Result<bool, AppError> {
+    use crate::auth::current_player;
+
+    Ok(current_player().await?.admin)
+}
+
+/// Drafts are only listed for admins.
+#[server]
+async fn get_legal_documents() -> Result<Vec<LegalDocument>, AppError> {
+    use crate::auth::current_player;
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let player = current_player().await?;
+
+    Ok(database::get_all_legal_documents(&pool)?
+        .into_iter()
+        .map(LegalDocument::from)
+        .filter(|document| player.admin || document.is_published())
+        .collect())
+}
+
+#[server]
+async fn get_legal_document(id: i64) -> Result<LegalDocument, AppError> {
+    use crate::auth::current_player;
+    use crate::database::{self, DatabaseError, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let player = current_player().await?;
+
+    database::find_legal_document_for_id(id, &pool)?
+        .map(LegalDocument::from)
+        .filter(|document| player.admin || document.is_published())
+        .ok_or(DatabaseError::EntityNotFound.into())
+}
+
+#[server]
+async fn create_legal_document(legal_document_form: LegalDocumentForm) -> Result<(), AppError> {
+    use crate::auth::require_admin;
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let admin = require_admin().await?;
+
+    let kind = legal_document_form.kind
+        .parse()
+        .map_err(|_| AppError::InvalidInput("Art".to_string()))?;
+    let title = legal_document_form.title.trim().to_string();
+
+    if title.is_empty() {
+        return Err(AppError::InvalidInput("Titel".to_string()));
+    }
+
+    database::create_legal_document(LegalDocument {
+        kind,
+        title,
+        content: legal_document_form.content,
+        author_id: Some(admin.id),
+        ..Default::default()
+    }, &pool)?;
+
+    leptos_axum::redirect("/legal");
+    Ok(())
+}
+
+#[server]
+async fn publish_legal_document(id: i64) -> Result<(), AppError> {
+    use crate::auth::require_admin;
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    require_admin().await?;
+
+    Ok(database::publish_legal_document(id, &pool)?)
+}
+
+#[server]
+async fn delete_legal_document(id: i64) -> Result<(), AppError> {
+    use crate::auth::require_admin;
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    require_admin().await?;
+
+    Ok(database::delete_legal_document_draft(id, &pool)?)
+}
+
+/// Without a login nothing is pending, the login page handles that case.
+#[server]
+pub async fn has_pending_legal_documents() -> Result<bool, AppError> {
+    use crate::auth::current_player_if_logged_in;
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    match current_player_if_logged_in().await? {
+        Some(player) => Ok(!database::get_pending_legal_documents(player.id, &pool)?.is_empty()),
+        None => Ok(false),
+    }
+}
+
+#[server]
+async fn get_pending_legal_documents() -> Result<Vec<LegalDocument>, AppError> {
+    use crate::auth::current_player_pending_acceptance;
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let player = current_player_pending_acceptance().await?;
+
+    Ok(database::get_pending_legal_documents(player.id, &pool)?
+        .into_iter()
+        .map(Into::into)
+        .collect())
+}
+
+/// Fails if another version was published since the page was loaded, so it has to be read first.
+#[server]
+async fn accept_legal_documents(
+    document_ids: String,
+    #[server(default)] confirmed: bool,
+) -> Result<(), AppError> {
+    use crate::auth::current_player_pending_acceptance;
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let player = current_player_pending_acceptance().await?;
+
+    if !confirmed {
+        return Err(AppError::InvalidInput("Bestätigung".to_string()));
+    }
+
+    let shown_ids = document_ids
+        .split(',')
+        .filter(|id| !id.is_empty())
+        .map(|id| id.trim().parse::<i64>())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| AppError::InvalidInput("Dokumente".to_string()))?;
+
+    let pending_ids: Vec<i64> = database::get_pending_legal_documents(player.id, &pool)?
+        .into_iter()
+        .map(|document| document.id)
+        .collect();
+
+    if pending_ids.iter().any(|id| !shown_ids.contains(id)) {
+        return Err(AppError::InvalidInput("Dokumente".to_string()));
+    }
+
+    database::accept_legal_documents(&pending_ids, player.id, client_ip_address().await?, &pool)?;
+
+    leptos_axum::redirect("/");
+    Ok(())
+}
+
+/// Acceptance records are private, like the other personal details of the profile.
+#[server]
+async fn get_player_legal_acceptances(player_id: i64) -> Result<Option<Vec<LegalAcceptance>>, AppError> {
+    use crate::auth::can_see_private_fields;
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    if !can_see_private_fields(player_id, &pool).await? {
+        return Ok(None);
+    }
+
+    Ok(Some(database::get_legal_acceptances_for_player(player_id, &pool)?))
+}
@@ -0,0 +1,705 @@
+use leptos::prelude::*;
+use leptos::Params;
+use leptos::server_fn::codec::{MultipartData, MultipartFormData};
+use leptos::web_sys::{FormData, HtmlFormElement, SubmitEvent};
+use leptos::wasm_bindgen::JsCast;
+use leptos_router::{components::A, hooks::use_params, params::Params};
+use serde::{Deserialize, Serialize};
+
+use crate::app::AppError;
+use crate::components::auth::get_current_player;
+use crate::components::util::BackButton;
+use crate::domain::Location;
+
+#[component]
+pub fn Locations() -> impl IntoView {
+    use crate::components::protected::Protected;
+    use leptos_router::components::Outlet;
+
+    view! {
+        <Protected>
+            <Outlet/>
+        </Protected>
+    }
+}
+
+#[component]
+pub fn LocationsTable() -> impl IntoView {
+
+    let locations = Resource::new(|| {}, |_| get_filtered_locations(String::new()));
+    let current_player = Resource::new(|| {}, |_| get_current_player());
+
+    let is_admin = move || current_player.get().and_then(|result| result.ok()).is_some_and(|player| player.admin);
+
+    view! {
+        <BackButton/>
+        <div class="p-8 max-w-4xl mx-auto">
+            <div class="flex items-center justify-between mb-6">
+                <h1 class="text-2xl font-semibold">
+                    "Orte"
+                </h1>
+                <Transition>
+                    <Show when=is_admin>
+                        <A href="/locations/new"
+                            attr:class="bg-blue-600 hover:bg-blue-700 text-white font-medium py-2 px-4 rounded transition"
+                        >
+                            "Neuer Ort"
+                        </A>
+                    </Show>
+                </Transition>
+            </div>
+
+            <Suspense fallback=move || view! { <p>"Lade Daten..."</p> }>
+                {move || {
+                    locations.get().map(|result| match result {
+                        Ok(locations) => view! {
+                            <div class="overflow-x-auto">
+                                <table class="min-w-full border border-gray-200 shadow-sm rounded-md bg-white">
+                                    <thead class="bg-gray-100 text-gray-700">
+                                        <tr>
+                                            <th class="text-left py-2 px-4 border-b">Name</th>
+                                            <th class="text-left py-2 px-4 border-b">Adresse</th>
+                                            <th class="text-left py-2 px-4 border-b">Koordinaten</th>
+                                        </tr>
+                                    </thead>
+                                    <tbody>
+                                        {
+                                            locations.into_iter().map(|location: Location| {
+                                                let coordinates = format_coordinates(&location);
+
+                                                view! {
+                                                    <tr class="hover:bg-gray-50">
+                                                        <th class="text-left py-2 px-4 border-b">
+                                                            <A href=format!("/locations/{}", location.id.unwrap()) attr:class="hover:underline">{ location.name }</A>
+                                                        </th>
+                                                        <th class="text-left py-2 px-4 border-b">{ location.address }</th>
+                                                        <th class="text-left py-2 px-4 border-b">{ coordinates }</th>
+                                                    </tr>
+                                                }
+                                            }).collect_view()
+                                        }
+                                    </tbody>
+                                </table>
+                            </div>
+                        }.into_any(),
+                        Err(e) => view! { <p>{ e.to_string() }</p> }.into_any(),
+                    })
+                }}
+            </Suspense>
+        </div>
+    }
+}
+
+#[component]
+pub fn LocationPage() -> impl IntoView {
+
+    let location_id = use_params::<LocationIdParameter>();
+    let location = Resource::new(
+        move || location_id.read().clone(),
+        move |params_result| load_location_by_id(params_result.unwrap().id.unwrap()),
+    );
+    let current_player = Resource::new(|| {}, |_| get_current_player());
+
+    let is_admin = move || current_player.get().and_then(|result| result.ok()).is_some_and(|player| player.admin);
+
+    view! {
+        <BackButton/>
+        <div class="p-8 max-w-4xl mx-auto">
+            <Suspense fallback=move || view! { <p>"Lade Daten..."</p> }>
+                {move || {
+                    location.get().map(|result| match result {
+                        Ok(location) => view! {
+                            <div class="flex items-center justify-between mb-6">
+                                <h1 class="text-2xl font-semibold">
+                                    { location.name.clone() }
+                                </h1>
+                                <Show when=is_admin>
+                                    <A href=format!("/locations/{}/edit", location.id.unwrap())
+                                        attr:class="bg-blue-600 hover:bg-blue-700 text-white font-medium py-2 px-4 rounded transition"
+                                    >
+                                        "Bearbeiten"
+                                    </A>
+                                </Show>
+                            </div>
+
+                            <div class="grid grid-cols-[auto_1fr] items-start gap-4">
+                                <LocationDetails location=location.clone()/>
+
+                                <span class="text-left text-gray-700">"Spielfeldregeln:"</span>
+                                <p class="text-left whitespace-pre-line">{ location.field_rules.clone() }</p>
+
+                                <span class="text-left text-gray-700">"Parken:"</span>
+                                <p class="text-left whitespace-pre-line">{ location.parking_info.clone() }</p>
+
+                                <span class="text-left text-gray-700">"Safe Zone:"</span>
+                                <p class="text-left whitespace-pre-line">{ location.safe_zone_notes.clone() }</p>
+                            </div>
+
+                            {location.has_map.then(|| view! {
+                                <h2 class="text-left text-xl font-semibold py-2">Lageplan</h2>
+                                <img
+                                    src=format!("/locations/{}/map", location.id.unwrap())
+                                    alt=format!("Lageplan {}", location.name)
+                                    class="max-w-full border border-gray-200 rounded"
+                                />
+                            })}
+                        }.into_any(),
+                        Err(e) => view! { <p>{ e.to_string() }</p> }.into_any(),
+                    })
+                }}
+            </Suspense>
+        </div>
+    }
+}
+
+/// Address, coordinates and the links to map and route planner, as rows of a two column grid.
+#[component]
+fn LocationDetails(location: Location) -> impl IntoView {
+
+    view! {
+        <span class="text-left text-gray-700">"Adresse:"</span>
+        <p class="text-left whitespace-pre-line">{ location.address.clone() }</p>
+
+        <span class="text-left text-gray-700">"Koordinaten:"</span>
+        <div class="text-left flex gap-4">
+            <span>{ format_coordinates(&location) }</span>
+            {location.map_url().map(|url| view! {
+                <a href=url target="_blank" rel="noopener noreferrer" class="text-blue-600 hover:underline">"Karte"</a>
+            })}
+            {location.directions_url().map(|url| view! {
+                <a href=url target="_blank" rel="noopener noreferrer" class="text-blue-600 hover:underline">"Route planen"</a>
+            })}
+        </div>
+    }
+}
+
+/// The event's location, nothing if the event has none.
+#[component]
+pub fn LocationPanel(location_id: Option<i64>) -> impl IntoView {
+
+    let location = Resource::new(move || location_id, find_location_for_id);
+
+    view! {
+        <Suspense fallback=move || view! { <p>"Lade Daten..."</p> }>
+            {move || {
+                location.get().map(|result| match result {
+                    Ok(Some(location)) => view! {
+                        <h2 class="text-left text-xl font-semibold py-2">Ort</h2>
+                        <div class="grid grid-cols-[auto_1fr] items-start gap-4">
+                            <span class="text-left text-gray-700">"Name:"</span>
+                            {
+                                let name = location.name.clone();
+
+                                view! {
+                                    <A href=format!("/locations/{}", location.id.unwrap()) attr:class="text-left hover:underline">
+                                        { name }
+                                    </A>
+                                }
+                            }
+
+                            <LocationDetails location=location.clone()/>
+
+                            {location.has_map.then(|| view! {
+                                <span class="text-left text-gray-700">"Lageplan:"</span>
+                                <a href=format!("/locations/{}/map", location.id.unwrap())
+                                    rel="external"
+                                    target="_blank"
+                                    class="text-left text-blue-600 hover:underline"
+                                >
+                                    "Anzeigen"
+                                </a>
+                            })}
+                        </div>
+                    }.into_any(),
+                    Ok(None) => ().into_any(),
+                    Err(e) => view! { <p>{ e.to_string() }</p> }.into_any(),
+                })
+            }}
+        </Suspense>
+    }
+}
+
+#[component]
+pub fn LocationNew() -> impl IntoView {
+
+    let create_location = ServerAction::<CreateLocation>::new();
+
+    view! {
+        <div class="p-8 max-w-4xl mx-auto">
+            <div class="flex items-center justify-between mb-6">
+                <h1 class="text-2xl font-semibold">
+                    "Neuen Ort anlegen"
+                </h1>
+            </div>
+
+            <ActionForm action=create_location>
+                <LocationFormFields form_name="create_location" location=Location::default()/>
+
+                <div class="flex justify-end gap-2 mt-6">
+                    <A href="/locations"
+                        attr:class="px-4 py-2 rounded bg-gray-200 hover:bg-gray-300 text-gray-700"
+                    >
+                        "Abbrechen"
+                    </A>
+                    <button
+                        type="submit"
+                        class="px-4 py-2 rounded bg-blue-600 hover:bg-blue-700 text-white"
+                    >
+                        "Anlegen"
+                    </button>
+                </div>
+            </ActionForm>
+
+            {move || create_location.value().get().and_then(|result| result.err()).map(|e| view! {
+                <p class="text-red-600">{ e.to_string() }</p>
+            })}
+        </div>
+    }
+}
+
+#[component]
+pub fn LocationEdit() -> impl IntoView {
+
+    let location_id = use_params::<LocationIdParameter>();
+
+    let save_location = ServerAction::<SaveLocation>::new();
+    let delete_location = ServerAction::<DeleteLocation>::new();
+    let delete_map = ServerAction::<DeleteLocationMap>::new();
+    let upload_map = Action::new_local(|data: &FormData| upload_location_map(data.clone().into()));
+
+    let location = Resource::new(
+        move || (location_id.read().clone(), upload_map.version().get(), delete_map.version().get()),
+        move |(params_result, ..)| load_location_by_id(params_result.unwrap().id.unwrap()),
+    );
+
+    let on_upload = move |ev: SubmitEvent| {
+        ev.prevent_default();
+
+        let form = ev.target().unwrap().unchecked_into::<HtmlFormElement>();
+        let data = FormData::new_with_form(&form).unwrap();
+        upload_map.dispatch_local(data);
+    };
+
+    let action_error = move || {
+        let save_error = save_location.value().get().and_then(|result| result.err());
+        let delete_error = delete_location.value().get().and_then(|result| result.err());
+        let upload_error = upload_map.value().get().and_then(|result| result.err());
+        let delete_map_error = delete_map.value().get().and_then(|result| result.err());
+
+        save_error
+            .or(delete_error)
+            .or(upload_error)
+            .or(delete_map_error)
+            .map(|e| view! { <p class="text-red-600">{ e.to_string() }</p> })
+    };
+
+    view! {
+        <div class="p-8 max-w-4xl mx-auto">
+            <Suspense fallback=move || view! { <p>"Lade Daten..."</p> }>
+                {move || {
+                    location.get().map(|result| match result {
+                        Ok(location) => {
+                            let id = location.id.unwrap();
+                            let has_map = location.has_map;
+
+                            view! {
+                                <div class="flex items-center justify-between mb-6">
+                                    <h1 class="text-2xl font-semibold">
+                                        "Ort " { location.name.clone() } " bearbeiten"
+                                    </h1>
+                                    <ActionForm action=delete_location>
+                                        <input type="hidden" name="id" value=id/>
+                                        <button
+                                            type="submit"
+                                            class="px-4 py-2 rounded bg-gray-200 hover:bg-gray-300 text-gray-700"
+                                        >
+                                            "Löschen"
+                                        </button>
+                                    </ActionForm>
+                                </div>
+
+                                <ActionForm action=save_location>
+                                    <input
+                                        type="hidden"
+                                        name="location_form[id]"
+                                        value=id/>
+
+                                    <LocationFormFields form_name="location_form" location=location.clone()/>
+
+                                    <div class="flex justify-end gap-2 mt-6">
+                                        <A href=format!("/locations/{}", id)
+                                            attr:class="px-4 py-2 rounded bg-gray-200 hover:bg-gray-300 text-gray-700"
+                                        >
+                                            "Abbrechen"
+                                        </A>
+                                        <button
+                                            type="submit"
+                                            class="px-4 py-2 rounded bg-blue-600 hover:bg-blue-700 text-white"
+                                        >
+                                            "Speichern"
+                                        </button>
+                                    </div>
+                                </ActionForm>
+
+                                <h2 class="text-left text-xl font-semibold py-2">Lageplan</h2>
+                                {has_map.then(|| view! {
+                                    <div class="flex items-center justify-between mb-2">
+                                        <a href=format!("/locations/{}/map", id)
+                                            rel="external"
+                                            target="_blank"
+                                            class="text-blue-600 hover:underline"
+                                        >
+                                            "Aktuellen Lageplan anzeigen"
+                                        </a>
+                                        <ActionForm action=delete_map>
+                                            <input type="hidden" name="id" value=id/>
+                                            <button
+                                                type="submit"
+                                                class="px-4 py-2 rounded bg-gray-200 hover:bg-gray-300 text-gray-700"
+                                            >
+                                                "Lageplan entfernen"
+                                            </button>
+                                        </ActionForm>
+                                    </div>
+                                })}
+                                <form on:submit=on_upload class="flex items-center gap-4">
+                                    <input type="hidden" name="location_id" value=id/>
+                                    <label for="map" class="text-left text-gray-700">
+                                        "Bild (PNG, JPEG oder WebP, max. 2 MB):"
+                                    </label>
+                                    <input type="file" name="map" accept="image/png,image/jpeg,image/webp" required/>
+                                    <button
+                                        type="submit"
+                                        class="px-4 py-2 rounded bg-blue-600 hover:bg-blue-700 text-white"
+                                    >
+                                        "Hochladen"
+                                    </button>
+                                </form>
+                            }.into_any()
+                        },
+                        Err(e) => view! { <p>{ e.to_string() }</p> }.into_any(),
+                    })
+                }}
+            </Suspense>
+
+            { action_error }
+        </div>
+    }
+}
+
+#[component]
+fn LocationFormFields(form_name: &'static str, location: Location) -> impl IntoView {
+    let field = move |field: &str| format!("{}[{}]", form_name, field);
+
+    view! {
+        <div class="grid grid-cols-[auto_1fr] items-center gap-4">
+            <label for=field("name") class="text-left text-gray-700">
+                "Name:"
+            </label>
+            <input
+                type="text"
+                name=field("name")
+                class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+                value=location.name
+                required
+            />
+
+            <label for=field("address") class="text-left text-gray-700">
+                "Adresse:"
+            </label>
+            <textarea
+                name=field("address")
+                rows="2"
+                class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+            >
+                { location.address }
+            </textarea>
+
+            <label for=field("latitude") class="text-left text-gray-700">
+                "Breitengrad:"
+            </label>
+            <input
+                type="text"
+                inputmode="decimal"
+                name=field("latitude")
+                placeholder="52.520008"
+                class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+                value=location.latitude
+            />
+
+            <label for=field("longitude") class="text-left text-gray-700">
+                "Längengrad:"
+            </label>
+            <input
+                type="text"
+                inputmode="decimal"
+                name=field("longitude")
+                placeholder="13.404954"
+                class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+                value=location.longitude
+            />
+
+            <label for=field("field_rules") class="text-left text-gray-700">
+                "Spielfeldregeln:"
+            </label>
+            <textarea
+                name=field("field_rules")
+                rows="5"
+                class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+            >
+                { location.field_rules }
+            </textarea>
+
+            <label for=field("parking_info") class="text-left text-gray-700">
+                "Parken:"
+            </label>
+            <textarea
+                name=field("parking_info")
+                rows="3"
+                class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+            >
+                { location.parking_info }
+            </textarea>
+
+            <label for=field("safe_zone_notes") class="text-left text-gray-700">
+                "Safe Zone:"
+            </label>
+            <textarea
+                name=field("safe_zone_notes")
+                rows="3"
+                class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+            >
+                { location.safe_zone_notes }
+            </textarea>
+        </div>
+    }
+}
+
+fn format_coordinates(location: &Location) -> String {
+    location
+        .coordinates()
+        .map(|(latitude, longitude)| format!("{:.6}, {:.6}", latitude, longitude))
+        .unwrap_or("Keine".to_string())
+}
+
+#[derive(Params, PartialEq, Clone)]
+struct LocationIdParameter {
+    id: Option<i64>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct LocationForm {
+    #[serde(default)]
+    id: Option<i64>,
+    name: String,
+    #[serde(default)]
+    address: String,
+    #[serde(default)]
+    latitude: String,
+    #[serde(default)]
+    longitude: String,
+    #[serde(default)]
+    field_rules: String,
+    #[serde(default)]
+    parking_info: String,
+    #[serde(default)]
+    safe_zone_notes: String,
+}
+
+#[cfg(feature = "ssr")]
+impl LocationForm {
+    fn into_location(self) -> Result<Location, AppError> {
+        let latitude = parse_coordinate(&self.latitude, 90.0)
+            .ok_or(AppError::InvalidInput("Breitengrad".to_string()))?;
+        let longitude = parse_coordinate(&self.longitude, 180.0)
+            .ok_or(AppError::InvalidInput("Längengrad".to_string()))?;
+
+        if latitude.is_some() != longitude.is_some() {
+            return Err(AppError::InvalidInput("Koordinaten".to_string()));
+        }
+
+        Ok(Location {
+            id: self.id,
+            name: self.name.trim().to_string(),
+            address: self.address.trim().to_string(),
+            latitude,
+            longitude,
+            field_rules: self.field_rules,
+            parking_info: self.parking_info,
+            safe_zone_notes: self.safe_zone_notes,
+            ..Default::default()
+        })
+    }
+}
+
+/// Empty input is no coordinate, a decimal comma is accepted as well.
+#[cfg(feature = "ssr")]
+fn parse_coordinate(value: &str, limit: f64) -> Option<Option<f64>> {
+    let value = value.trim();
+
+    if value.is_empty() {
+        return Some(None);
+    }
+
+    value
+        .replace(',', ".")
+        .parse::<f64>()
+        .ok()
+        .filter(|coordinate| coordinate.abs() <= limit)
+        .map(Some)
+}
+
+#[server]
+pub async fn get_filtered_locations(filter: String) -> Result<Vec<Location>, AppError> {
+    use crate::auth::current_player;
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    current_player().await?;
+
+    let locations = database::get_locations_for_name_filter(filter, &pool)?;
+
+    Ok(locations.into_iter().map(Into::into).collect())
+}
+
+#[server]
+pub async fn find_location_for_id(id: Option<i64>) -> Result<Option<Location>, AppError> {
+    use crate::auth::current_player;
+    use crate::database::{self, DieselPool};
+
+    let Some(id) = id else {
+        return Ok(None);
+    };
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    current_player().await?;
+
+    Ok(database::find_location_for_id(id, &pool)?.map(Into::into))
+}
+
+#[server]
+async fn load_location_by_id(id: i64) -> Result<Location, AppError> {
+    use crate::auth::current_player;
+    use crate::database::{self, DatabaseError, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    current_player().await?;
+
+    database::find_location_for_id(id, &pool)?
+        .map(Into::into)
+        .ok_or(DatabaseError::EntityNotFound.into())
+}
+
+#[server]
+async fn create_location(create_location: LocationForm) -> Result<(), AppError> {
+    use crate::auth::require_admin;
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    require_admin().await?;
+
+    let location = create_location.into_location()?;
+    let id = database::create_location(location, &pool)?;
+
+    leptos_axum::redirect(&format!("/locations/{}", id));
+    Ok(())
+}
+
+#[server]
+async fn save_location(location_form: LocationForm) -> Result<(), AppError> {
+    use crate::auth::require_admin;
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    require_admin().await?;
+
+    let location = location_form.into_location()?;
+    let id = location.id.ok_or(AppError::InvalidInput("Id".to_string()))?;
+
+    database::save_location(location, &pool)?;
+
+    leptos_axum::redirect(&format!("/locations/{}", id));
+    Ok(())
+}
+
+#[server]
+async fn delete_location(id: i64) -> Result<(), AppError> {
+    use crate::auth::require_admin;
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    require_admin().await?;
+
+    database::delete_location(id, &pool)?;
+
+    leptos_axum::redirect("/locations");
+    Ok(())
+}
+
+#[server(input = MultipartFormData)]
+async fn upload_location_map(data: MultipartData) -> Result<(), AppError> {
+    use crate::auth::require_admin;
+    use crate::database::{self, DieselPool};
+    use crate::location::{detect_map_content_type, MAX_MAP_SIZE};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    require_admin().await?;
+
+    let mut data = data.into_inner().ok_or(AppError::InvalidInput("Karte".to_string()))?;
+    let mut location_id = String::new();
+    let mut content = Vec::new();
+
+    while let Ok(Some(mut field)) = data.next_field().await {
+        match field.name() {
+            Some("location_id") => {
+                while let Ok(Some(chunk)) = field.chunk().await {
+                    location_id.push_str(&String::from_utf8_lossy(&chunk));
+                }
+            },
+            Some("map") => {
+                while let Ok(Some(chunk)) = field.chunk().await {
+                    content.extend_from_slice(&chunk);
+
+                    if content.len() > MAX_MAP_SIZE {
+                        return Err(AppError::InvalidInput("Der Lageplan ist größer als 2 MB".to_string()));
+                    }
+                }
+            },
+            _ => continue,
+        }
+    }
+
+    let location_id = location_id.trim().parse::<i64>()
+        .map_err(|_| AppError::InvalidInput("Id".to_string()))?;
+    let content_type = detect_map_content_type(&content)
+        .ok_or(AppError::InvalidInput("Der Lageplan muss ein PNG, JPEG oder WebP Bild sein".to_string()))?;
+
+    Ok(database::save_location_map(location_id, content_type, &content, &pool)?)
+}
+
+#[server]
+async fn delete_location_map(id: i64) -> Result<(), AppError> {
+    use crate::auth::require_admin;
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    require_admin().await?;
+
+    Ok(database::delete_location_map(id, &pool)?)
+}
@@ -0,0 +1,518 @@
+use leptos::prelude::*;
+
+use crate::app::AppError;
+use crate::components::util::{format_date, format_datetime};
+use crate::domain::{PlayEvent, TeamHistory, TeamJoinRequest, TeamMembership, TeamMembershipStatus};
+
+/// Join, leave and decide on join requests. Actions that change the roster are passed in
+/// so the team page can reload its members.
+#[component]
+pub fn TeamJoinPanel(
+    team_id: i64,
+    decide_request: ServerAction<DecideTeamJoinRequest>,
+    leave_team: ServerAction<LeaveTeam>,
+) -> impl IntoView {
+
+    let request_join = ServerAction::<RequestTeamJoin>::new();
+    let withdraw_request = ServerAction::<WithdrawTeamJoinRequest>::new();
+
+    let status = Resource::new(
+        move || (
+            request_join.version().get(),
+            withdraw_request.version().get(),
+            decide_request.version().get(),
+            leave_team.version().get(),
+        ),
+        move |_| get_team_membership_status(team_id),
+    );
+
+    let action_error = move || {
+        let errors = [
+            request_join.value().get().and_then(|result| result.err()),
+            withdraw_request.value().get().and_then(|result| result.err()),
+            decide_request.value().get().and_then(|result| result.err()),
+            leave_team.value().get().and_then(|result| result.err()),
+        ];
+
+        errors.into_iter().flatten().next().map(|e| view! {
+            <p class="text-red-600">{ e.to_string() }</p>
+        })
+    };
+
+    view! {
+        <Suspense fallback=move || view! { <p>"Lade Daten..."</p> }>
+            {move || {
+                status.get().map(|result| match result {
+                    Ok(TeamMembershipStatus { member, own_request, pending_requests, can_decide }) => view! {
+                        {
+                            match (member, own_request) {
+                                (true, _) => view! {
+                                    <ActionForm action=leave_team attr:class="flex justify-end">
+                                        <input type="hidden" name="team_id" value=team_id/>
+                                        <button
+                                            type="submit"
+                                            class="px-4 py-2 rounded bg-red-600 hover:bg-red-700 text-white"
+                                        >
+                                            "Team verlassen"
+                                        </button>
+                                    </ActionForm>
+                                }.into_any(),
+                                (false, Some(own_request)) => view! {
+                                    <ActionForm action=withdraw_request attr:class="flex items-center justify-end gap-4">
+                                        <span class="text-gray-700">
+                                            { format!("Beitrittsanfrage vom {} ist offen", format_datetime(&own_request.requested_at)) }
+                                        </span>
+                                        <input type="hidden" name="id" value=own_request.id/>
+                                        <button
+                                            type="submit"
+                                            class="px-4 py-2 rounded bg-gray-200 hover:bg-gray-300 text-gray-700"
+                                        >
+                                            "Zurückziehen"
+                                        </button>
+                                    </ActionForm>
+                                }.into_any(),
+                                (false, None) => view! {
+                                    <ActionForm action=request_join attr:class="grid grid-cols-[auto_1fr] items-center gap-4">
+                                        <input type="hidden" name="team_id" value=team_id/>
+
+                                        <label for="message" class="text-left text-gray-700">
+                                            "Nachricht:"
+                                        </label>
+                                        <input
+                                            type="text"
+                                            name="message"
+                                            class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+                                        />
+
+                                        <div class="col-span-2 flex justify-end">
+                                            <button
+                                                type="submit"
+                                                class="px-4 py-2 rounded bg-blue-600 hover:bg-blue-700 text-white"
+                                            >
+                                                "Beitritt anfragen"
+                                            </button>
+                                        </div>
+                                    </ActionForm>
+                                }.into_any(),
+                            }
+                        }
+
+                        <Show when=move || can_decide>
+                            <JoinRequestsTable requests=pending_requests.clone() decide_request=decide_request/>
+                        </Show>
+                    }.into_any(),
+                    Err(e) => view! { <p>{ e.to_string() }</p> }.into_any(),
+                })
+            }}
+        </Suspense>
+
+        { action_error }
+    }
+}
+
+#[component]
+fn JoinRequestsTable(
+    requests: Vec<TeamJoinRequest>,
+    decide_request: ServerAction<DecideTeamJoinRequest>,
+) -> impl IntoView {
+    view! {
+        <h2 class="text-left text-xl font-semibold py-2">Beitrittsanfragen</h2>
+
+        {
+            if requests.is_empty() {
+                view! { <p class="text-left text-gray-700">"Keine offenen Anfragen"</p> }.into_any()
+            } else {
+                view! {
+                    <div class="overflow-x-auto">
+                        <table class="min-w-full border border-gray-200 shadow-sm rounded-md bg-white">
+                            <thead class="bg-gray-100 text-gray-700">
+                                <tr>
+                                    <th class="text-left py-2 px-4 border-b">Spieler</th>
+                                    <th class="text-left py-2 px-4 border-b">Angefragt am</th>
+                                    <th class="text-left py-2 px-4 border-b">Nachricht</th>
+                                    <th class="py-2 px-4 border-b"></th>
+                                </tr>
+                            </thead>
+                            <tbody>
+                                {
+                                    requests.into_iter().map(|request| view! {
+                                        <tr class="hover:bg-gray-50">
+                                            <th class="text-left py-2 px-4 border-b">
+                                                <a href=format!("/players/{}", request.player_id) class="hover:underline">{ request.tag_name }</a>
+                                            </th>
+                                            <th class="text-left py-2 px-4 border-b">{ format_datetime(&request.requested_at) }</th>
+                                            <th class="text-left py-2 px-4 border-b">{ request.message }</th>
+                                            <th class="py-2 px-4 border-b">
+                                                <div class="flex gap-2 justify-end">
+                                                    <ActionForm action=decide_request>
+                                                        <input type="hidden" name="id" value=request.id/>
+                                                        <input type="hidden" name="approve" value="true"/>
+                                                        <button
+                                                            type="submit"
+                                                            class="px-4 py-2 rounded bg-blue-600 hover:bg-blue-700 text-white"
+                                                        >
+                                                            "Annehmen"
+                                                        </button>
+                                                    </ActionForm>
+                                                    <ActionForm action=decide_request>
+                                                        <input type="hidden" name="id" value=request.id/>
+                                                        <input type="hidden" name="approve" value="false"/>
+                                                        <button
+                                                            type="submit"
+                                                            class="px-4 py-2 rounded bg-gray-200 hover:bg-gray-300 text-gray-700"
+                                                        >
+                                                            "Ablehnen"
+                                                        </button>
+                                                    </ActionForm>
+                                                </div>
+                                            </th>
+                                        </tr>
+                                    }).collect_view()
+                                }
+                            </tbody>
+                        </table>
+                    </div>
+                }.into_any()
+            }
+        }
+    }
+}
+
+#[component]
+pub fn PlayerTeamHistory(player_id: i64) -> impl IntoView {
+
+    let memberships = Resource::new(|| (), move |_| get_player_team_history(player_id));
+
+    view! {
+        <h2 class="text-left text-xl font-semibold py-2">Teamhistorie</h2>
+
+        <Suspense fallback=move || view! { <p>"Lade Daten..."</p> }>
+            {move || {
+                memberships.get().map(|result| match result {
+                    Ok(memberships) if memberships.is_empty() => view! {
+                        <p class="text-left text-gray-700">"Noch in keinem Team"</p>
+                    }.into_any(),
+                    Ok(memberships) => view! {
+                        <MembershipTable memberships=memberships show_team=true/>
+                    }.into_any(),
+                    Err(e) => view! { <p>{ e.to_string() }</p> }.into_any(),
+                })
+            }}
+        </Suspense>
+    }
+}
+
+/// Membership timeline of a team and its roster at a past event. Reloads when the roster
+/// changes through the given actions.
+#[component]
+pub fn TeamHistoryPanel(
+    team_id: i64,
+    decide_request: ServerAction<DecideTeamJoinRequest>,
+    leave_team: ServerAction<LeaveTeam>,
+) -> impl IntoView {
+
+    let selected_event = RwSignal::new(None::<i64>);
+
+    let history = Resource::new(
+        move || (decide_request.version().get(), leave_team.version().get()),
+        move |_| get_team_history(team_id),
+    );
+
+    let roster = Resource::new(
+        move || selected_event.get(),
+        move |play_event_id| get_team_roster_at_event(team_id, play_event_id),
+    );
+
+    view! {
+        <h2 class="text-left text-xl font-semibold py-2">Teamhistorie</h2>
+
+        <Suspense fallback=move || view! { <p>"Lade Daten..."</p> }>
+            {move || {
+                history.get().map(|result| match result {
+                    Ok(TeamHistory { memberships, past_events }) => view! {
+                        {
+                            if memberships.is_empty() {
+                                view! { <p class="text-left text-gray-700">"Keine Mitglieder"</p> }.into_any()
+                            } else {
+                                view! { <MembershipTable memberships=memberships show_team=false/> }.into_any()
+                            }
+                        }
+
+                        <div class="grid grid-cols-[auto_1fr] items-center gap-4 pt-4">
+                            <label for="roster_event" class="text-left text-gray-700">
+                                "Kader bei Event:"
+                            </label>
+                            <select
+                                name="roster_event"
+                                class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+                                on:change=move |ev| selected_event.set(event_target_value(&ev).parse().ok())
+                            >
+                                <option value="">"Event auswählen"</option>
+                                {
+                                    past_events.into_iter().rev().map(|play_event: PlayEvent| view! {
+                                        <option value=play_event.id>
+                                            { format!("{} ({})", play_event.name, format_date(&play_event.starts_at.date_naive())) }
+                                        </option>
+                                    }).collect_view()
+                                }
+                            </select>
+                        </div>
+                    }.into_any(),
+                    Err(e) => view! { <p>{ e.to_string() }</p> }.into_any(),
+                })
+            }}
+        </Suspense>
+
+        <Suspense>
+            {move || {
+                roster.get().map(|result| match result {
+                    Ok(Some(roster)) if roster.is_empty() => view! {
+                        <p class="text-left text-gray-700 pt-2">"Zu diesem Event hatte das Team keine Mitglieder"</p>
+                    }.into_any(),
+                    Ok(Some(roster)) => view! {
+                        <ul class="text-left list-disc pl-6 pt-2">
+                            {
+                                roster.into_iter().map(|membership| view! {
+                                    <li>
+                                        <a href=format!("/players/{}", membership.player_id) class="hover:underline">
+                                            { membership.tag_name }
+                                        </a>
+                                    </li>
+                                }).collect_view()
+                            }
+                        </ul>
+                    }.into_any(),
+                    Ok(None) => ().into_any(),
+                    Err(e) => view! { <p>{ e.to_string() }</p> }.into_any(),
+                })
+            }}
+        </Suspense>
+    }
+}
+
+#[component]
+fn MembershipTable(memberships: Vec<TeamMembership>, show_team: bool) -> impl IntoView {
+    view! {
+        <div class="overflow-x-auto">
+            <table class="min-w-full border border-gray-200 shadow-sm rounded-md bg-white">
+                <thead class="bg-gray-100 text-gray-700">
+                    <tr>
+                        <th class="text-left py-2 px-4 border-b">{ if show_team { "Team" } else { "Spieler" } }</th>
+                        <th class="text-left py-2 px-4 border-b">Beigetreten</th>
+                        <th class="text-left py-2 px-4 border-b">Ausgetreten</th>
+                    </tr>
+                </thead>
+                <tbody>
+                    {
+                        memberships.into_iter().map(|membership| {
+                            let (href, text) = if show_team {
+                                (format!("/teams/{}", membership.team_id), membership.team_name)
+                            } else {
+                                (format!("/players/{}", membership.player_id), membership.tag_name)
+                            };
+
+                            view! {
+                                <tr class="hover:bg-gray-50">
+                                    <th class="text-left py-2 px-4 border-b">
+                                        <a href=href class="hover:underline">{ text }</a>
+                                    </th>
+                                    <th class="text-left py-2 px-4 border-b">{ format_date(&membership.joined_on) }</th>
+                                    <th class="text-left py-2 px-4 border-b">
+                                        { membership.left_on.map(|left_on| format_date(&left_on)).unwrap_or("Aktuell".to_string()) }
+                                    </th>
+                                </tr>
+                            }
+                        }).collect_view()
+                    }
+                </tbody>
+            </table>
+        </div>
+    }
+}
+
+#[server]
+async fn get_team_membership_status(team_id: i64) -> Result<TeamMembershipStatus, AppError> {
+    use crate::auth::current_player;
+    use crate::database::{self, DatabaseError, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let player = current_player().await?;
+    let team = database::find_team_for_id(team_id, &pool)?
+        .ok_or(DatabaseError::EntityNotFound)?;
+
+    let can_decide = player.admin || team.contact_person_id == Some(player.id);
+
+    let pending_requests = if can_decide {
+        database::get_pending_team_join_requests(team_id, None, &pool)?
+    } else {
+        Vec::new()
+    };
+
+    Ok(TeamMembershipStatus {
+        member: player.team_id == Some(team_id),
+        own_request: database::get_pending_team_join_requests(team_id, Some(player.id), &pool)?
+            .into_iter()
+            .next(),
+        pending_requests,
+        can_decide,
+    })
+}
+
+#[server]
+async fn request_team_join(team_id: i64, message: String) -> Result<(), AppError> {
+    use crate::auth::current_player;
+    use crate::database::{self, DatabaseError, DieselPool};
+    use crate::domain::{Notification, NotificationKind};
+    use crate::notification::notify;
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let player = current_player().await?;
+    let team = database::find_team_for_id(team_id, &pool)?
+        .ok_or(DatabaseError::EntityNotFound)?;
+
+    database::create_team_join_request(team_id, player.id, message.trim(), &pool)?;
+
+    let contact_person_ids: Vec<i64> = team.contact_person_id.into_iter().collect();
+
+    Ok(notify(&contact_person_ids, Notification::new(
+        NotificationKind::TeamJoinRequest,
+        format!("{} möchte {} beitreten", player.tag_name, team.name),
+        message.trim(),
+        format!("/teams/{}", team_id),
+    ), &pool)?)
+}
+
+#[server]
+async fn withdraw_team_join_request(id: i64) -> Result<(), AppError> {
+    use crate::auth::{current_player, AuthError};
+    use crate::database::{self, DatabaseError, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let player = current_player().await?;
+    let request = database::find_team_join_request_for_id(id, &pool)?
+        .ok_or(DatabaseError::EntityNotFound)?;
+
+    if request.player_id != player.id {
+        return Err(AppError::AuthError(AuthError::Forbidden));
+    }
+
+    Ok(database::withdraw_team_join_request(id, &pool)?)
+}
+
+/// Only the team's contact person and admins decide on join requests.
+#[server]
+pub async fn decide_team_join_request(id: i64, approve: bool) -> Result<(), AppError> {
+    use crate::auth::{current_player, AuthError};
+    use crate::database::{self, DatabaseError, DieselPool};
+    use crate::domain::{Notification, NotificationKind};
+    use crate::notification::notify;
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let player = current_player().await?;
+    let request = database::find_team_join_request_for_id(id, &pool)?
+        .ok_or(DatabaseError::EntityNotFound)?;
+    let team = database::find_team_for_id(request.team_id, &pool)?
+        .ok_or(DatabaseError::EntityNotFound)?;
+
+    if !player.admin && team.contact_person_id != Some(player.id) {
+        return Err(AppError::AuthError(AuthError::Forbidden));
+    }
+
+    database::decide_team_join_request(id, approve, player.id, &pool)?;
+
+    let title = if approve {
+        format!("Deine Anfrage für {} wurde angenommen", team.name)
+    } else {
+        format!("Deine Anfrage für {} wurde abgelehnt", team.name)
+    };
+
+    Ok(notify(&[request.player_id], Notification::new(
+        NotificationKind::TeamJoinRequest,
+        title,
+        "",
+        format!("/teams/{}", team.id),
+    ), &pool)?)
+}
+
+#[server]
+pub async fn leave_team(team_id: i64) -> Result<(), AppError> {
+    use crate::auth::current_player;
+    use crate::database::{self, DatabaseError, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let player = current_player().await?;
+
+    if player.team_id != Some(team_id) {
+        return Err(DatabaseError::NotTeamMember.into());
+    }
+
+    Ok(database::leave_team(player.id, &pool)?)
+}
+
+#[server]
+async fn get_player_team_history(player_id: i64) -> Result<Vec<TeamMembership>, AppError> {
+    use crate::auth::current_player;
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    current_player().await?;
+
+    Ok(database::get_team_memberships_for_player(player_id, &pool)?)
+}
+
+/// Past events are only those the team took part in.
+#[server]
+async fn get_team_history(team_id: i64) -> Result<TeamHistory, AppError> {
+    use crate::auth::current_player;
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    current_player().await?;
+
+    let now = chrono::Utc::now();
+
+    Ok(TeamHistory {
+        memberships: database::get_team_memberships_for_team(team_id, &pool)?,
+        past_events: database::get_play_events_for_team(team_id, &pool)?
+            .into_iter()
+            .map(PlayEvent::from)
+            .filter(|play_event| play_event.starts_at < now)
+            .collect(),
+    })
+}
+
+/// The team's members on the day the event started.
+#[server]
+async fn get_team_roster_at_event(team_id: i64, play_event_id: Option<i64>) -> Result<Option<Vec<TeamMembership>>, AppError> {
+    use crate::auth::current_player;
+    use crate::database::{self, DatabaseError, DieselPool};
+
+    current_player().await?;
+
+    let Some(play_event_id) = play_event_id else {
+        return Ok(None);
+    };
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let play_event: PlayEvent = database::find_play_event_for_id(play_event_id, &pool)?
+        .ok_or(DatabaseError::EntityNotFound)?
+        .into();
+
+    Ok(Some(database::get_team_roster_as_of(team_id, play_event.starts_at.date_naive(), &pool)?))
+}
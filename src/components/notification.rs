@@ -0,0 +1,287 @@
+use std::collections::HashMap;
+
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::app::AppError;
+use crate::components::util::{format_datetime, BackButton};
+use crate::domain::{Notification, NotificationKind, NotificationSettings};
+
+#[cfg(feature = "ssr")]
+const INBOX_SIZE: i64 = 100;
+
+#[component]
+pub fn NotificationsPage() -> impl IntoView {
+    use crate::components::protected::Protected;
+
+    let mark_read = use_context::<ServerAction<MarkNotificationsRead>>()
+        .unwrap_or_default();
+    let notifications = Resource::new(move || mark_read.version().get(), |_| get_notifications());
+
+    view! {
+        <Protected>
+            <BackButton/>
+            <div class="p-8 max-w-4xl mx-auto">
+                <div class="flex items-center justify-between mb-6">
+                    <h1 class="text-2xl font-semibold">
+                        "Nachrichten"
+                    </h1>
+                    <button
+                        class="px-4 py-2 rounded bg-gray-200 hover:bg-gray-300 text-gray-700"
+                        on:click=move |_| { mark_read.dispatch(MarkNotificationsRead { id: None }); }
+                    >
+                        "Alle als gelesen markieren"
+                    </button>
+                </div>
+
+                <Suspense fallback=move || view! { <p>"Lade Daten..."</p> }>
+                    {move || {
+                        notifications.get().map(|result| match result {
+                            Ok(notifications) if notifications.is_empty() => view! {
+                                <p class="text-gray-700">"Keine Nachrichten"</p>
+                            }.into_any(),
+                            Ok(notifications) => notifications
+                                .into_iter()
+                                .map(|notification| view! { <NotificationEntry notification=notification mark_read=mark_read/> })
+                                .collect_view()
+                                .into_any(),
+                            Err(e) => view! { <p>{ e.to_string() }</p> }.into_any(),
+                        })
+                    }}
+                </Suspense>
+
+                <h2 class="text-left text-xl font-semibold py-2 mt-8">"Benachrichtigungen"</h2>
+                <NotificationSettingsPanel/>
+            </div>
+        </Protected>
+    }
+}
+
+#[component]
+fn NotificationEntry(notification: Notification, mark_read: ServerAction<MarkNotificationsRead>) -> impl IntoView {
+
+    let id = notification.id;
+    let unread = notification.is_unread();
+    let read_on_open = move |_| {
+        if unread {
+            mark_read.dispatch(MarkNotificationsRead { id });
+        }
+    };
+
+    view! {
+        <div class="border-b py-4 text-left" class:font-semibold=unread>
+            <div class="flex items-center justify-between gap-4">
+                <span>{ notification.title }</span>
+                <span class="text-sm text-gray-500">
+                    { format!("{} · {}", notification.kind, format_datetime(&notification.created_at)) }
+                </span>
+            </div>
+            <p class="text-gray-700 font-normal whitespace-pre-line">{ notification.body }</p>
+            <div class="flex justify-end gap-4 font-normal">
+                {(!notification.link.is_empty()).then(|| view! {
+                    <a href=notification.link class="text-blue-600 hover:underline" on:click=read_on_open>"Öffnen"</a>
+                })}
+                {unread.then(|| view! {
+                    <button
+                        class="text-gray-700 hover:underline"
+                        on:click=move |_| { mark_read.dispatch(MarkNotificationsRead { id }); }
+                    >
+                        "Gelesen"
+                    </button>
+                })}
+            </div>
+        </div>
+    }
+}
+
+/// Which kinds additionally go out by email or to the player's own webhook.
+#[component]
+fn NotificationSettingsPanel() -> impl IntoView {
+
+    let save_settings = ServerAction::<SaveNotificationSettings>::new();
+    let settings = Resource::new(move || save_settings.version().get(), |_| get_notification_settings());
+
+    let field = |name: &str| format!("notification_settings[{}]", name);
+
+    view! {
+        <Suspense fallback=move || view! { <p>"Lade Daten..."</p> }>
+            {move || {
+                settings.get().map(|result| match result {
+                    Ok(settings) => view! {
+                        <ActionForm action=save_settings>
+                            <table class="w-full border-collapse">
+                                <thead>
+                                    <tr>
+                                        <th class="text-left py-2 px-4 border-b">"Art"</th>
+                                        <th class="text-left py-2 px-4 border-b">"E-Mail"</th>
+                                        <th class="text-left py-2 px-4 border-b">"Webhook"</th>
+                                    </tr>
+                                </thead>
+                                <tbody>
+                                    {
+                                        NotificationKind::ALL
+                                            .into_iter()
+                                            .map(|kind| {
+                                                let preference = settings.preference(kind);
+
+                                                view! {
+                                                    <tr>
+                                                        <th class="text-left py-2 px-4 border-b">{ kind.to_string() }</th>
+                                                        <th class="text-left py-2 px-4 border-b">
+                                                            <input
+                                                                type="checkbox"
+                                                                name=format!("{}[{}]", field("email"), kind.as_str())
+                                                                class="w-4 h-4 accent-green-600 border-2 border-gray-300 rounded"
+                                                                value="true"
+                                                                checked=preference.email/>
+                                                        </th>
+                                                        <th class="text-left py-2 px-4 border-b">
+                                                            <input
+                                                                type="checkbox"
+                                                                name=format!("{}[{}]", field("webhook"), kind.as_str())
+                                                                class="w-4 h-4 accent-green-600 border-2 border-gray-300 rounded"
+                                                                value="true"
+                                                                checked=preference.webhook/>
+                                                        </th>
+                                                    </tr>
+                                                }
+                                            })
+                                            .collect_view()
+                                    }
+                                </tbody>
+                            </table>
+
+                            <div class="grid grid-cols-[auto_1fr] items-center gap-4 py-4">
+                                <label for=field("webhook_url") class="text-left text-gray-700">
+                                    "Webhook-URL:"
+                                </label>
+                                <input
+                                    type="url"
+                                    name=field("webhook_url")
+                                    class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+                                    placeholder="https://"
+                                    value=settings.webhook_url.clone()
+                                />
+                            </div>
+
+                            <div class="flex justify-end">
+                                <button type="submit" class="px-4 py-2 rounded bg-blue-600 hover:bg-blue-700 text-white">
+                                    "Speichern"
+                                </button>
+                            </div>
+                        </ActionForm>
+                    }.into_any(),
+                    Err(e) => view! { <p>{ e.to_string() }</p> }.into_any(),
+                })
+            }}
+        </Suspense>
+
+        {move || save_settings.value().get().and_then(|result| result.err()).map(|e| view! {
+            <p class="text-red-600">{ e.to_string() }</p>
+        })}
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct NotificationSettingsForm {
+    #[serde(default)]
+    webhook_url: String,
+    #[serde(default)]
+    email: HashMap<String, String>,
+    #[serde(default)]
+    webhook: HashMap<String, String>,
+}
+
+#[cfg(feature = "ssr")]
+impl NotificationSettingsForm {
+    fn into_settings(self) -> Result<NotificationSettings, AppError> {
+        use crate::domain::NotificationPreference;
+
+        use crate::notification::check_public_webhook_url;
+
+        let webhook_url = self.webhook_url.trim().to_string();
+
+        if !webhook_url.is_empty() && check_public_webhook_url(&webhook_url).is_err() {
+            return Err(AppError::InvalidInput("Webhook-URL".to_string()));
+        }
+
+        let preferences = NotificationKind::ALL
+            .into_iter()
+            .map(|kind| NotificationPreference {
+                kind,
+                email: self.email.contains_key(kind.as_str()),
+                webhook: self.webhook.contains_key(kind.as_str()),
+            })
+            .collect();
+
+        Ok(NotificationSettings { webhook_url, preferences })
+    }
+}
+
+#[server]
+pub async fn get_unread_notification_count() -> Result<i64, AppError> {
+    use crate::auth::current_player_if_logged_in;
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let Some(player) = current_player_if_logged_in().await? else {
+        return Ok(0);
+    };
+
+    Ok(database::count_unread_notifications(player.id, &pool)?)
+}
+
+#[server]
+async fn get_notifications() -> Result<Vec<Notification>, AppError> {
+    use crate::auth::current_player;
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let player = current_player().await?;
+
+    Ok(database::get_notifications_for_player(player.id, INBOX_SIZE, &pool)?)
+}
+
+/// Marks one notification or, without an id, all of them as read.
+#[server]
+pub async fn mark_notifications_read(id: Option<i64>) -> Result<(), AppError> {
+    use crate::auth::current_player;
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let player = current_player().await?;
+
+    Ok(database::mark_notifications_read(player.id, id, &pool)?)
+}
+
+#[server]
+async fn get_notification_settings() -> Result<NotificationSettings, AppError> {
+    use crate::auth::current_player;
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let player = current_player().await?;
+
+    Ok(database::get_notification_settings(player.id, &pool)?)
+}
+
+#[server]
+async fn save_notification_settings(notification_settings: NotificationSettingsForm) -> Result<(), AppError> {
+    use crate::auth::current_player;
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let player = current_player().await?;
+
+    Ok(database::save_notification_settings(player.id, notification_settings.into_settings()?, &pool)?)
+}
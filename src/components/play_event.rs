@@ -1,4 +1,21 @@
 use leptos::prelude::*;
+use leptos::Params;
+use leptos_router::{components::A, hooks::use_params, params::Params};
+use serde::{Deserialize, Serialize};
+
+use crate::app::AppError;
+use crate::components::auth::get_current_player;
+use crate::components::calendar::CalendarSubscription;
+use crate::components::carpool::CarpoolPanel;
+use crate::components::check_in::{get_own_ticket, TicketView};
+use crate::components::faction::FactionList;
+use crate::components::gear::ClassLimitPanel;
+use crate::components::location::{find_location_for_id, get_filtered_locations, LocationPanel};
+use crate::components::rental::EventRentalPanel;
+use crate::components::scoring::GameResults;
+use crate::components::util::{datetime_input_value, format_datetime, BackButton, OptionalLink, SelectFromServer};
+use crate::database::DatabaseError;
+use crate::domain::{group_participants, Location, Participant, PlayEvent, Registration, RegistrationStatus, Team, Ticket};
 
 #[component]
 pub fn PlayEvents() -> impl IntoView {
@@ -7,7 +24,6 @@ pub fn PlayEvents() -> impl IntoView {
 
     view! {
         <Protected>
-            <h1>Events</h1>
             <Outlet/>
         </Protected>
     }
@@ -15,10 +31,1033 @@ pub fn PlayEvents() -> impl IntoView {
 
 #[component]
 pub fn PlayEventsTable() -> impl IntoView {
-    view! {}
+
+    let play_events = Resource::new(|| {}, |_| get_play_events());
+
+    view! {
+        <BackButton/>
+        <div class="p-8 max-w-4xl mx-auto">
+            <div class="flex items-center justify-between mb-6">
+                <h1 class="text-2xl font-semibold">
+                    "Events"
+                </h1>
+                <A href="/events/new"
+                    attr:class="bg-blue-600 hover:bg-blue-700 text-white font-medium py-2 px-4 rounded transition"
+                >
+                    "Neues Event"
+                </A>
+            </div>
+
+            <CalendarSubscription/>
+
+            <Suspense fallback=move || view! { <p>"Lade Daten..."</p> }>
+                {
+                    move || {
+                        play_events.get().map(|result| match result {
+                            Ok(play_events) => view! {
+                                <div class="overflow-x-auto">
+                                    <table class="min-w-full border border-gray-200 shadow-sm rounded-md bg-white">
+                                        <thead class="bg-gray-100 text-gray-700">
+                                            <tr>
+                                                <th class="text-left py-2 px-4 border-b">Name</th>
+                                                <th class="text-left py-2 px-4 border-b">Beginn</th>
+                                                <th class="text-left py-2 px-4 border-b">Ende</th>
+                                                <th class="text-left py-2 px-4 border-b">Plätze</th>
+                                                <th class="text-left py-2 px-4 border-b">Anmeldeschluss</th>
+                                            </tr>
+                                        </thead>
+                                        <tbody>
+                                            {
+                                                play_events.into_iter().map(|play_event: PlayEvent| view! {
+                                                    <tr class="hover:bg-gray-50">
+                                                        <th class="text-left py-2 px-4 border-b">
+                                                            <A href=format!("/events/{}", play_event.id.unwrap()) attr:class="hover:underline">{play_event.name}</A>
+                                                        </th>
+                                                        <th class="text-left py-2 px-4 border-b">
+                                                            { format_datetime(&play_event.starts_at) }
+                                                        </th>
+                                                        <th class="text-left py-2 px-4 border-b">
+                                                            { format_datetime(&play_event.ends_at) }
+                                                        </th>
+                                                        <th class="text-left py-2 px-4 border-b">
+                                                            { play_event.capacity.map(|capacity| capacity.to_string()).unwrap_or("Unbegrenzt".to_string()) }
+                                                        </th>
+                                                        <th class="text-left py-2 px-4 border-b">
+                                                            { play_event.registration_deadline.as_ref().map(format_datetime).unwrap_or("Kein Anmeldeschluss".to_string()) }
+                                                        </th>
+                                                    </tr>
+                                                }).collect_view()
+                                            }
+                                        </tbody>
+                                    </table>
+                                </div>
+                            }.into_any(),
+                            Err(e) => view! {
+                                <p>{ e.to_string() }</p>
+                            }.into_any(),
+                        })
+                    }
+                }
+            </Suspense>
+        </div>
+    }
 }
 
 #[component]
 pub fn PlayEventDetails() -> impl IntoView {
-    view! {}
+
+    let play_event_id = use_params::<PlayEventIdParameter>();
+
+    let register = ServerAction::<RegisterForPlayEvent>::new();
+    let register_team = ServerAction::<RegisterTeamForPlayEvent>::new();
+    let withdraw = ServerAction::<WithdrawFromPlayEvent>::new();
+    let cancel = ServerAction::<CancelPlayEvent>::new();
+
+    let play_event = Resource::new(
+        move || (play_event_id.read().clone(), cancel.version().get()),
+        move |(params_result, _)| load_play_event_by_id(params_result.unwrap().id.unwrap()),
+    );
+
+    let registration_changes = move || (
+        play_event_id.read().clone(),
+        register.version().get(),
+        register_team.version().get(),
+        withdraw.version().get(),
+    );
+
+    let own_registration = Resource::new(
+        registration_changes,
+        move |(params_result, ..)| get_own_registration(params_result.unwrap().id.unwrap()),
+    );
+
+    let participants = Resource::new(
+        registration_changes,
+        move |(params_result, ..)| get_participants(params_result.unwrap().id.unwrap()),
+    );
+
+    let own_ticket = Resource::new(
+        registration_changes,
+        move |(params_result, ..)| get_own_ticket(params_result.unwrap().id.unwrap()),
+    );
+
+    let contact_teams = Resource::new(|| {}, |_| get_own_contact_teams());
+    let current_player = Resource::new(|| {}, |_| get_current_player());
+
+    view! {
+        <BackButton/>
+        <div class="p-8 max-w-4xl mx-auto">
+
+            <Suspense fallback=move || view! { <p>"Lade Daten..."</p> }>
+                {
+                    move || {
+                        play_event.get().map(|result| match result {
+                            Ok(play_event) => view! {
+                                <div class="flex items-center justify-between mb-6">
+                                    <h1 class="text-2xl font-semibold">
+                                        "Event " { play_event.name.clone() }
+                                    </h1>
+                                    <div class="flex gap-2">
+                                        {
+                                            let organizer_id = play_event.organizer_id;
+                                            let is_organizer = move || current_player
+                                                .get()
+                                                .and_then(|result| result.ok())
+                                                .is_some_and(|player| player.id.is_some() && player.id == organizer_id);
+
+                                            let cancellable = !play_event.is_cancelled();
+
+                                            view! {
+                                                <Show when=is_organizer>
+                                                    <A href=format!("/events/{}/checkin", play_event.id.unwrap())
+                                                        attr:class="bg-blue-600 hover:bg-blue-700 text-white font-medium py-2 px-4 rounded transition"
+                                                    >
+                                                        "Check-in"
+                                                    </A>
+                                                    <A href=format!("/events/{}/referee", play_event.id.unwrap())
+                                                        attr:class="bg-blue-600 hover:bg-blue-700 text-white font-medium py-2 px-4 rounded transition"
+                                                    >
+                                                        "Schiedsrichter"
+                                                    </A>
+                                                    <Show when=move || cancellable>
+                                                        <ActionForm action=cancel>
+                                                            <input type="hidden" name="play_event_id" value=play_event.id/>
+                                                            <button
+                                                                type="submit"
+                                                                class="bg-red-600 hover:bg-red-700 text-white font-medium py-2 px-4 rounded transition"
+                                                            >
+                                                                "Absagen"
+                                                            </button>
+                                                        </ActionForm>
+                                                    </Show>
+                                                </Show>
+                                            }
+                                        }
+                                        <a href=format!("/scoreboard/{}", play_event.id.unwrap())
+                                            target="_blank"
+                                            class="bg-gray-200 hover:bg-gray-300 text-gray-700 font-medium py-2 px-4 rounded transition"
+                                        >
+                                            "Anzeigetafel"
+                                        </a>
+                                        <a href=format!("/calendar/events/{}", play_event.id.unwrap())
+                                            rel="external"
+                                            download
+                                            class="bg-gray-200 hover:bg-gray-300 text-gray-700 font-medium py-2 px-4 rounded transition"
+                                        >
+                                            "Kalender (.ics)"
+                                        </a>
+                                        <A href=format!("/events/{}/edit", play_event.id.unwrap())
+                                            attr:class="bg-blue-600 hover:bg-blue-700 text-white font-medium py-2 px-4 rounded transition"
+                                        >
+                                            "Bearbeiten"
+                                        </A>
+                                    </div>
+                                </div>
+
+                                {play_event.cancelled_at.as_ref().map(|cancelled_at| view! {
+                                    <p class="text-left px-4 py-2 mb-6 rounded bg-red-100 text-red-800">
+                                        "Abgesagt am " { format_datetime(cancelled_at) }
+                                    </p>
+                                })}
+
+                                <div class="space-y-4">
+
+                                    <div class="grid grid-cols-[auto_1fr] items-center gap-4">
+                                        <label for="view_play_event[description]" class="text-left text-gray-700">
+                                            "Beschreibung:"
+                                        </label>
+                                        <output
+                                            name="view_play_event[description]"
+                                            class="text-left w-full px-3 py-2 focus:outline-none focus:ring focus:border-blue-300">
+                                            { play_event.description.clone() }
+                                        </output>
+
+                                        <label for="view_play_event[starts_at]" class="text-left text-gray-700">
+                                            "Beginn:"
+                                        </label>
+                                        <output
+                                            name="view_play_event[starts_at]"
+                                            class="text-left w-full px-3 py-2 focus:outline-none focus:ring focus:border-blue-300">
+                                            { format_datetime(&play_event.starts_at) }
+                                        </output>
+
+                                        <label for="view_play_event[ends_at]" class="text-left text-gray-700">
+                                            "Ende:"
+                                        </label>
+                                        <output
+                                            name="view_play_event[ends_at]"
+                                            class="text-left w-full px-3 py-2 focus:outline-none focus:ring focus:border-blue-300">
+                                            { format_datetime(&play_event.ends_at) }
+                                        </output>
+
+                                        <label for="view_play_event[organizer]" class="text-left text-gray-700">
+                                            "Organisator:"
+                                        </label>
+                                        <output
+                                            name="view_play_event[organizer]"
+                                            class="text-left w-full px-3 py-2 focus:outline-none focus:ring focus:border-blue-300">
+                                            <OptionalLink value=play_event.organizer_id
+                                                text=|id| format!("Spieler Id: {}", id)
+                                                href=|id| format!("/players/{}", id)
+                                                fallback=move || view! { "Kein Organisator" }
+                                            />
+                                        </output>
+
+                                        <label for="view_play_event[capacity]" class="text-left text-gray-700">
+                                            "Plätze:"
+                                        </label>
+                                        <output
+                                            name="view_play_event[capacity]"
+                                            class="text-left w-full px-3 py-2 focus:outline-none focus:ring focus:border-blue-300">
+                                            { play_event.capacity.map(|capacity| capacity.to_string()).unwrap_or("Unbegrenzt".to_string()) }
+                                        </output>
+
+                                        <label for="view_play_event[registration_deadline]" class="text-left text-gray-700">
+                                            "Anmeldeschluss:"
+                                        </label>
+                                        <output
+                                            name="view_play_event[registration_deadline]"
+                                            class="text-left w-full px-3 py-2 focus:outline-none focus:ring focus:border-blue-300">
+                                            { play_event.registration_deadline.as_ref().map(format_datetime).unwrap_or("Kein Anmeldeschluss".to_string()) }
+                                        </output>
+
+                                        <label for="view_play_event[min_age]" class="text-left text-gray-700">
+                                            "Mindestalter:"
+                                        </label>
+                                        <output
+                                            name="view_play_event[min_age]"
+                                            class="text-left w-full px-3 py-2 focus:outline-none focus:ring focus:border-blue-300">
+                                            { play_event.min_age.map(|min_age| format!("{} Jahre", min_age)).unwrap_or("Keines".to_string()) }
+                                        </output>
+
+                                        <div class="col-span-2">
+                                            <LocationPanel location_id=play_event.location_id/>
+                                        </div>
+
+                                        <div class="col-span-2">
+                                            <RegistrationPanel
+                                                play_event_id=play_event.id.unwrap()
+                                                own_registration=own_registration
+                                                own_ticket=own_ticket
+                                                contact_teams=contact_teams
+                                                register=register
+                                                register_team=register_team
+                                                withdraw=withdraw
+                                            />
+                                        </div>
+
+                                        <div class="col-span-2">
+                                            <ParticipantList participants=participants/>
+                                        </div>
+
+                                        <div class="col-span-2">
+                                            <CarpoolPanel play_event_id=play_event.id.unwrap()/>
+                                        </div>
+
+                                        <div class="col-span-2">
+                                            <FactionList play_event_id=play_event.id.unwrap()/>
+                                        </div>
+
+                                        <div class="col-span-2">
+                                            <GameResults play_event_id=play_event.id.unwrap()/>
+                                        </div>
+
+                                        <div class="col-span-2">
+                                            <ClassLimitPanel play_event_id=play_event.id.unwrap()/>
+                                        </div>
+                                    </div>
+                                </div>
+                            }.into_any(),
+                            Err(e) => view! {
+                                <p>{ e.to_string() }</p>
+                            }.into_any(),
+                        })
+                    }
+                }
+            </Suspense>
+        </div>
+    }
+}
+
+#[component]
+fn RegistrationPanel(
+    play_event_id: i64,
+    own_registration: Resource<Result<Option<Registration>, AppError>>,
+    own_ticket: Resource<Result<Option<Ticket>, AppError>>,
+    contact_teams: Resource<Result<Vec<Team>, AppError>>,
+    register: ServerAction<RegisterForPlayEvent>,
+    register_team: ServerAction<RegisterTeamForPlayEvent>,
+    withdraw: ServerAction<WithdrawFromPlayEvent>,
+) -> impl IntoView {
+
+    let action_error = move || {
+        let register_error = register.value().get().and_then(|result| result.err());
+        let register_team_error = register_team.value().get().and_then(|result| result.err());
+        let withdraw_error = withdraw.value().get().and_then(|result| result.err());
+
+        register_error
+            .or(register_team_error)
+            .or(withdraw_error)
+            .map(|e| match e {
+                AppError::Database(DatabaseError::NotEligible(block)) => block.to_string(),
+                e => e.to_string(),
+            })
+            .map(|message| view! { <p class="text-red-600">{ message }</p> })
+    };
+
+    view! {
+        <h2 class="text-left text-xl font-semibold py-2">Anmeldung</h2>
+
+        <Suspense fallback=move || view! { <p>"Lade Daten..."</p> }>
+            {move || {
+                own_registration.get().map(|result| match result {
+                    Ok(Some(registration)) if registration.status != RegistrationStatus::Withdrawn => view! {
+                        <div class="flex items-center justify-between">
+                            <p>"Status: " { registration.status.to_string() }</p>
+                            <ActionForm action=withdraw>
+                                <input type="hidden" name="play_event_id" value=play_event_id/>
+                                <button
+                                    type="submit"
+                                    class="px-4 py-2 rounded bg-gray-200 hover:bg-gray-300 text-gray-700"
+                                >
+                                    "Abmelden"
+                                </button>
+                            </ActionForm>
+                        </div>
+                        <TicketView ticket=own_ticket/>
+                        <EventRentalPanel play_event_id=play_event_id/>
+                    }.into_any(),
+                    Ok(_) => view! {
+                        <div class="flex items-center justify-between">
+                            <p>"Du bist nicht angemeldet."</p>
+                            <ActionForm action=register>
+                                <input type="hidden" name="play_event_id" value=play_event_id/>
+                                <button
+                                    type="submit"
+                                    class="px-4 py-2 rounded bg-blue-600 hover:bg-blue-700 text-white"
+                                >
+                                    "Anmelden"
+                                </button>
+                            </ActionForm>
+                        </div>
+                    }.into_any(),
+                    Err(e) => view! { <p>{ e.to_string() }</p> }.into_any(),
+                })
+            }}
+
+            {move || {
+                contact_teams.get().map(|result| match result {
+                    Ok(teams) => teams
+                        .into_iter()
+                        .map(|team| view! {
+                            <div class="flex items-center justify-between mt-2">
+                                <p>"Team " { team.name.clone() }</p>
+                                <ActionForm action=register_team>
+                                    <input type="hidden" name="play_event_id" value=play_event_id/>
+                                    <input type="hidden" name="team_id" value=team.id.unwrap()/>
+                                    <button
+                                        type="submit"
+                                        class="px-4 py-2 rounded bg-blue-600 hover:bg-blue-700 text-white"
+                                    >
+                                        "Team anmelden"
+                                    </button>
+                                </ActionForm>
+                            </div>
+                        })
+                        .collect_view()
+                        .into_any(),
+                    Err(e) => view! { <p>{ e.to_string() }</p> }.into_any(),
+                })
+            }}
+        </Suspense>
+
+        { action_error }
+    }
+}
+
+#[component]
+fn ParticipantList(participants: Resource<Result<Vec<Participant>, AppError>>) -> impl IntoView {
+
+    view! {
+        <h2 class="text-left text-xl font-semibold py-2">Teilnehmer</h2>
+
+        <Suspense fallback=move || view! { <p>"Lade Daten..."</p> }>
+            {move || {
+                participants.get().map(|result| match result {
+                    Ok(participants) => {
+                        let (registered, waitlisted): (Vec<_>, Vec<_>) = participants
+                            .into_iter()
+                            .partition(|participant| participant.status == RegistrationStatus::Registered);
+
+                        view! {
+                            <p class="text-left text-gray-700">
+                                { registered.len() } " angemeldet, " { waitlisted.len() } " auf der Warteliste"
+                            </p>
+
+                            {
+                                group_participants(registered)
+                                    .into_iter()
+                                    .map(|(platoon_name, teams)| view! {
+                                        <h3 class="text-left text-lg font-semibold pt-4">
+                                            { platoon_name.unwrap_or("Ohne Zug".to_string()) }
+                                        </h3>
+                                        {
+                                            teams
+                                                .into_iter()
+                                                .map(|(team_name, members)| view! {
+                                                    <h4 class="text-left font-medium pt-2">
+                                                        { team_name.unwrap_or("Ohne Team".to_string()) }
+                                                    </h4>
+                                                    <ParticipantTable participants=members/>
+                                                })
+                                                .collect_view()
+                                        }
+                                    })
+                                    .collect_view()
+                            }
+
+                            <h3 class="text-left text-lg font-semibold pt-4">Warteliste</h3>
+                            <ParticipantTable participants=waitlisted/>
+                        }.into_any()
+                    },
+                    Err(e) => view! { <p>{ e.to_string() }</p> }.into_any(),
+                })
+            }}
+        </Suspense>
+    }
+}
+
+#[component]
+fn ParticipantTable(participants: Vec<Participant>) -> impl IntoView {
+
+    view! {
+        <div class="overflow-x-auto">
+            <table class="min-w-full border border-gray-200 shadow-sm rounded-md bg-white">
+                <thead class="bg-gray-100 text-gray-700">
+                    <tr>
+                        <th class="text-left py-2 px-4 border-b">Spielername</th>
+                        <th class="text-left py-2 px-4 border-b">Angemeldet am</th>
+                    </tr>
+                </thead>
+                <tbody>
+                    {
+                        participants
+                            .into_iter()
+                            .map(|participant| view! {
+                                <tr class="hover:bg-gray-50">
+                                    <th class="text-left py-2 px-4 border-b">
+                                        <A href=format!("/players/{}", participant.player_id) attr:class="hover:underline">{participant.tag_name}</A>
+                                    </th>
+                                    <th class="text-left py-2 px-4 border-b">
+                                        { format_datetime(&participant.registered_at) }
+                                    </th>
+                                </tr>
+                            })
+                            .collect_view()
+                    }
+                </tbody>
+            </table>
+        </div>
+    }
+}
+
+#[component]
+pub fn PlayEventNew() -> impl IntoView {
+
+    let create_new_play_event = ServerAction::<CreateNewPlayEvent>::new();
+
+    view! {
+        <div class="p-8 max-w-4xl mx-auto">
+            <div class="flex items-center justify-between mb-6">
+                <h1 class="text-2xl font-semibold">
+                    "Neues Event anlegen"
+                </h1>
+            </div>
+
+            <ActionForm action=create_new_play_event>
+                <PlayEventFormFields form_name="create_new_play_event" play_event=PlayEvent::default()/>
+
+                <div class="flex justify-end gap-2 mt-6">
+                    <A href="/events"
+                        attr:class="px-4 py-2 rounded bg-gray-200 hover:bg-gray-300 text-gray-700"
+                    >
+                        "Abbrechen"
+                    </A>
+                    <button
+                        type="submit"
+                        class="px-4 py-2 rounded bg-blue-600 hover:bg-blue-700 text-white"
+                    >
+                        "Erstellen"
+                    </button>
+                </div>
+            </ActionForm>
+
+            {move || create_new_play_event.value().get().and_then(|result| result.err()).map(|e| view! {
+                <p class="text-red-600">{ e.to_string() }</p>
+            })}
+        </div>
+    }
+}
+
+#[component]
+pub fn PlayEventEdit() -> impl IntoView {
+
+    let play_event_id = use_params::<PlayEventIdParameter>();
+    let play_event = Resource::new(
+        move || play_event_id.read().clone(),
+        move |params_result| load_play_event_by_id(params_result.unwrap().id.unwrap()),
+    );
+
+    let save_play_event = ServerAction::<SavePlayEvent>::new();
+
+    view! {
+        <div class="p-8 max-w-4xl mx-auto">
+
+            <Suspense fallback=move || view! { <p>"Lade Daten..."</p> }>
+                {
+                    move || {
+                        play_event.get().map(|result| match result {
+                            Ok(play_event) => view! {
+                                <ActionForm action=save_play_event>
+                                    <div class="flex items-center justify-between mb-6">
+                                        <h1 class="text-2xl font-semibold">
+                                            "Event " { play_event.name.clone() } " bearbeiten"
+                                        </h1>
+                                    </div>
+
+                                    <input
+                                        type="hidden"
+                                        name="play_event_form[id]"
+                                        value=play_event.id.unwrap()/>
+
+                                    <PlayEventFormFields form_name="play_event_form" play_event=play_event.clone()/>
+
+                                    <div class="flex justify-end gap-2 mt-6">
+                                        <A href=format!("/events/{}", play_event.id.unwrap())
+                                            attr:class="px-4 py-2 rounded bg-gray-200 hover:bg-gray-300 text-gray-700"
+                                        >
+                                            "Abbrechen"
+                                        </A>
+                                        <button
+                                            type="submit"
+                                            class="px-4 py-2 rounded bg-blue-600 hover:bg-blue-700 text-white"
+                                        >
+                                            "Speichern"
+                                        </button>
+                                    </div>
+                                </ActionForm>
+                            }.into_any(),
+                            Err(e) => view! {
+                                <p>{ e.to_string() }</p>
+                            }.into_any(),
+                        })
+                    }
+                }
+            </Suspense>
+
+            {move || save_play_event.value().get().and_then(|result| result.err()).map(|e| view! {
+                <p class="text-red-600">{ e.to_string() }</p>
+            })}
+        </div>
+    }
+}
+
+#[component]
+fn PlayEventFormFields(form_name: &'static str, play_event: PlayEvent) -> impl IntoView {
+    let is_new = play_event.id.is_none();
+    let field = move |field: &str| format!("{}[{}]", form_name, field);
+
+    let location_id = play_event.location_id;
+    let location = Resource::new(move || location_id, find_location_for_id);
+    let get_filtered_locations = Action::new(|filter: &String| {
+        get_filtered_locations(filter.to_string())
+    });
+
+    view! {
+        <div class="space-y-4">
+
+            <div class="grid grid-cols-[auto_1fr] items-center gap-4">
+                <label for=field("name") class="text-left text-gray-700">
+                    "Name:"
+                </label>
+                <input
+                    type="text"
+                    name=field("name")
+                    class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+                    value=play_event.name
+                    required
+                />
+
+                <label for=field("description") class="text-left text-gray-700">
+                    "Beschreibung:"
+                </label>
+                <textarea
+                    name=field("description")
+                    class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+                >
+                    { play_event.description }
+                </textarea>
+
+                <label for=field("starts_at") class="text-left text-gray-700">
+                    "Beginn:"
+                </label>
+                <input
+                    type="datetime-local"
+                    name=field("starts_at")
+                    class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+                    value=(!is_new).then(|| datetime_input_value(&play_event.starts_at))
+                    required
+                />
+
+                <label for=field("ends_at") class="text-left text-gray-700">
+                    "Ende:"
+                </label>
+                <input
+                    type="datetime-local"
+                    name=field("ends_at")
+                    class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+                    value=(!is_new).then(|| datetime_input_value(&play_event.ends_at))
+                    required
+                />
+
+                <label for=field("capacity") class="text-left text-gray-700">
+                    "Plätze:"
+                </label>
+                <input
+                    type="number"
+                    min="1"
+                    name=field("capacity")
+                    class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+                    value=play_event.capacity
+                />
+
+                <label for=field("registration_deadline") class="text-left text-gray-700">
+                    "Anmeldeschluss:"
+                </label>
+                <input
+                    type="datetime-local"
+                    name=field("registration_deadline")
+                    class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+                    value=play_event.registration_deadline.as_ref().map(datetime_input_value)
+                />
+
+                <label for=field("min_age") class="text-left text-gray-700">
+                    "Mindestalter:"
+                </label>
+                <input
+                    type="number"
+                    min="1"
+                    name=field("min_age")
+                    class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+                    value=play_event.min_age
+                />
+
+                <label for=field("location_id") class="text-left text-gray-700">
+                    "Ort:"
+                </label>
+                <Suspense fallback=move || view! { <p>"Lade Daten..."</p> }>
+                    { move || {
+                        location.get().map(|result| match result {
+                            Ok(location) => view! {
+                                <SelectFromServer
+                                    name=field("location_id")
+                                    current_value=location
+                                    options_action=get_filtered_locations
+                                    option_text=move |location: &Location| location.name.clone()
+                                    default_text="Kein Ort"
+                                />
+                            }.into_any(),
+                            Err(e) => view! { <p>{ e.to_string() }</p> }.into_any(),
+                        })
+                    }}
+                </Suspense>
+            </div>
+        </div>
+    }
+}
+
+#[derive(Params, PartialEq, Clone)]
+struct PlayEventIdParameter {
+    id: Option<i64>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct PlayEventForm {
+    #[serde(default)]
+    id: Option<i64>,
+    name: String,
+    #[serde(default)]
+    description: String,
+    starts_at: String,
+    ends_at: String,
+    #[serde(default)]
+    capacity: Option<i32>,
+    #[serde(default)]
+    registration_deadline: String,
+    #[serde(default)]
+    min_age: String,
+    #[serde(default)]
+    location_id: String,
+}
+
+#[cfg(feature = "ssr")]
+impl PlayEventForm {
+    fn into_play_event(self, organizer_id: Option<i64>) -> Result<PlayEvent, AppError> {
+        use crate::components::util::parse_datetime_input;
+
+        let starts_at = parse_datetime_input(&self.starts_at)
+            .ok_or(AppError::InvalidInput("Beginn".to_string()))?;
+        let ends_at = parse_datetime_input(&self.ends_at)
+            .ok_or(AppError::InvalidInput("Ende".to_string()))?;
+
+        if ends_at < starts_at {
+            return Err(AppError::InvalidInput("Ende".to_string()));
+        }
+
+        let registration_deadline = if self.registration_deadline.is_empty() {
+            None
+        } else {
+            Some(parse_datetime_input(&self.registration_deadline)
+                .ok_or(AppError::InvalidInput("Anmeldeschluss".to_string()))?)
+        };
+
+        let min_age = if self.min_age.is_empty() {
+            None
+        } else {
+            Some(self.min_age.parse::<i32>()
+                .ok()
+                .filter(|min_age| *min_age > 0)
+                .ok_or(AppError::InvalidInput("Mindestalter".to_string()))?)
+        };
+
+        let location_id = if self.location_id.is_empty() {
+            None
+        } else {
+            Some(self.location_id.parse::<i64>()
+                .map_err(|_| AppError::InvalidInput("Ort".to_string()))?)
+        };
+
+        Ok(PlayEvent {
+            id: self.id,
+            name: self.name,
+            description: self.description,
+            starts_at,
+            ends_at,
+            organizer_id,
+            capacity: self.capacity,
+            registration_deadline,
+            min_age,
+            location_id,
+            ..Default::default()
+        })
+    }
+}
+
+#[server]
+async fn get_play_events() -> Result<Vec<PlayEvent>, AppError> {
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let database_play_events = database::get_all_play_events(&pool)?;
+    let domain_play_events = database_play_events
+        .into_iter()
+        .map(|db_play_event| db_play_event.into())
+        .collect();
+
+    Ok(domain_play_events)
+}
+
+#[server]
+async fn load_play_event_by_id(id: i64) -> Result<PlayEvent, AppError> {
+    use crate::database::{self, DatabaseError, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let result = database::find_play_event_for_id(id, &pool);
+
+    match result {
+        Ok(Some(play_event)) => Ok(play_event.into()),
+        Ok(None) => Err(DatabaseError::EntityNotFound.into()),
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[server]
+async fn create_new_play_event(create_new_play_event: PlayEventForm) -> Result<(), AppError> {
+    use crate::auth::current_player;
+    use crate::database::{self, DieselPool};
+    use crate::domain::WebhookEventType;
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let organizer = current_player().await?;
+    let mut play_event = create_new_play_event.into_play_event(Some(organizer.id))?;
+
+    let id = database::create_play_event(play_event.clone(), &pool)?;
+    play_event.id = Some(id);
+
+    // registration is open from creation, so subscribers of either type hear about new events
+    publish_play_event(WebhookEventType::EventPublished, &play_event, &pool)?;
+    if play_event.registration_open(chrono::Utc::now()) {
+        publish_play_event(WebhookEventType::RegistrationOpened, &play_event, &pool)?;
+    }
+
+    leptos_axum::redirect(&format!("/events/{}", id));
+    Ok(())
+}
+
+#[server]
+async fn save_play_event(play_event_form: PlayEventForm) -> Result<(), AppError> {
+    use crate::auth::require_organizer;
+    use crate::database::{self, DieselPool};
+    use crate::domain::{Notification, NotificationKind, WebhookEventType};
+    use crate::notification::{notify, notify_promoted};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let play_event = play_event_form.into_play_event(None)?;
+    let id = play_event.id.ok_or(AppError::InvalidInput("Id".to_string()))?;
+    let name = play_event.name.clone();
+
+    require_organizer(id, &pool).await?;
+
+    let now = chrono::Utc::now();
+    let was_open = database::find_play_event_for_id(id, &pool)?
+        .map(|previous| PlayEvent::from(previous).registration_open(now))
+        .unwrap_or(false);
+
+    let promoted_ids = database::save_play_event(play_event, &pool)?;
+
+    // the form carries no cancellation, so compare against the stored event
+    let saved: PlayEvent = database::find_play_event_for_id(id, &pool)?
+        .ok_or(DatabaseError::EntityNotFound)?
+        .into();
+    if !was_open && saved.registration_open(now) {
+        publish_play_event(WebhookEventType::RegistrationOpened, &saved, &pool)?;
+    }
+
+    let participant_ids: Vec<i64> = database::get_participants_for_event(id, &pool)?
+        .into_iter()
+        .map(|participant| participant.player_id)
+        .filter(|player_id| !promoted_ids.contains(player_id))
+        .collect();
+
+    notify(&participant_ids, Notification::new(
+        NotificationKind::Event,
+        format!("{} wurde geändert", name),
+        "Die Angaben zum Event haben sich geändert.",
+        format!("/events/{}", id),
+    ), &pool)?;
+    notify_promoted(id, &promoted_ids, &pool)?;
+
+    leptos_axum::redirect(&format!("/events/{}", id));
+    Ok(())
+}
+
+#[server]
+async fn get_own_registration(play_event_id: i64) -> Result<Option<Registration>, AppError> {
+    use crate::auth::current_player;
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let player = current_player().await?;
+    let registration = database::find_registration(play_event_id, player.id, &pool)?;
+
+    Ok(registration.map(Into::into))
+}
+
+#[server]
+async fn get_own_contact_teams() -> Result<Vec<Team>, AppError> {
+    use crate::auth::current_player;
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let player = current_player().await?;
+    let database_teams = database::get_teams_for_contact_person(player.id, &pool)?;
+    let domain_teams = database_teams
+        .into_iter()
+        .map(|db_team| db_team.into())
+        .collect();
+
+    Ok(domain_teams)
+}
+
+#[server]
+async fn get_participants(play_event_id: i64) -> Result<Vec<Participant>, AppError> {
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    Ok(database::get_participants_for_event(play_event_id, &pool)?)
+}
+
+#[server]
+async fn register_for_play_event(play_event_id: i64) -> Result<RegistrationStatus, AppError> {
+    use crate::auth::current_player;
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let player = current_player().await?;
+
+    Ok(database::register_player_for_event(play_event_id, player.id, player.id, &pool)?)
+}
+
+#[server]
+async fn register_team_for_play_event(play_event_id: i64, team_id: i64) -> Result<usize, AppError> {
+    use crate::auth::{current_player, AuthError};
+    use crate::database::{self, DatabaseError, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let player = current_player().await?;
+    let team = database::find_team_for_id(team_id, &pool)?
+        .ok_or(DatabaseError::EntityNotFound)?;
+
+    if team.contact_person_id != Some(player.id) {
+        return Err(AppError::AuthError(AuthError::Forbidden));
+    }
+
+    let registrations = database::register_team_for_event(play_event_id, team_id, player.id, &pool)?;
+
+    Ok(registrations.len())
+}
+
+#[server]
+async fn cancel_play_event(play_event_id: i64) -> Result<(), AppError> {
+    use crate::auth::require_organizer;
+    use crate::database::{self, DieselPool};
+    use crate::domain::{Notification, NotificationKind, WebhookEventType};
+    use crate::notification::notify;
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let organizer = require_organizer(play_event_id, &pool).await?;
+
+    database::cancel_play_event(play_event_id, &pool)?;
+
+    let play_event: PlayEvent = database::find_play_event_for_id(play_event_id, &pool)?
+        .ok_or(DatabaseError::EntityNotFound)?
+        .into();
+    publish_play_event(WebhookEventType::EventCancelled, &play_event, &pool)?;
+
+    let participant_ids: Vec<i64> = database::get_participants_for_event(play_event_id, &pool)?
+        .into_iter()
+        .map(|participant| participant.player_id)
+        .filter(|player_id| *player_id != organizer.id)
+        .collect();
+
+    Ok(notify(&participant_ids, Notification::new(
+        NotificationKind::Event,
+        format!("{} wurde abgesagt", play_event.name),
+        "Das Event findet nicht statt.",
+        format!("/events/{}", play_event_id),
+    ), &pool)?)
+}
+
+#[server]
+async fn withdraw_from_play_event(play_event_id: i64) -> Result<(), AppError> {
+    use crate::auth::current_player;
+    use crate::database::{self, DieselPool};
+    use crate::notification::{notify_promoted, notify_ride_cancelled};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let player = current_player().await?;
+    let withdrawal = database::withdraw_player_from_event(play_event_id, player.id, &pool)?;
+
+    notify_ride_cancelled(play_event_id, &player.tag_name, &withdrawal.stranded_passenger_ids, &pool)?;
+    Ok(notify_promoted(play_event_id, &withdrawal.promoted_ids, &pool)?)
+}
+
+/// Posts the event to the club webhooks subscribed to `event_type`.
+#[cfg(feature = "ssr")]
+fn publish_play_event(
+    event_type: crate::domain::WebhookEventType,
+    play_event: &PlayEvent,
+    pool: &crate::database::DieselPool,
+) -> Result<(), AppError> {
+    use crate::notification::DeliveryChannels;
+    use crate::webhook::{self, PlayEventPayload};
+
+    let public_url = use_context::<DeliveryChannels>()
+        .map(|channels| channels.public_url)
+        .unwrap_or_default();
+
+    webhook::publish(event_type, PlayEventPayload::new(play_event, &public_url), pool)?;
+
+    Ok(())
 }
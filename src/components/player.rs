@@ -3,9 +3,16 @@ use leptos::Params;
 use leptos_router::{components::A, hooks::use_params, params::Params};
 use serde::{Deserialize, Serialize};
 
-use crate::domain::Team;
+use crate::domain::{PlayerRole, Team};
 use crate::{app::AppError, domain::Player};
-use crate::components::util::{BackButton, BoolSymbol, OptionalLink, SelectFromServer};
+use crate::components::attendance::PlayerAttendanceSummary;
+use crate::components::consent::ParentalConsents;
+use crate::components::discipline::{DisciplinaryRecords, SuspensionBadge};
+use crate::components::gear::PlayerReplicas;
+use crate::components::ledger::PlayerAccount;
+use crate::components::membership::PlayerTeamHistory;
+use crate::components::legal::PlayerLegalAcceptances;
+use crate::components::util::{date_input_value, format_date, BackButton, BoolSymbol, OptionalLink, SelectFromServer};
 
 #[component]
 pub fn Players() -> impl IntoView {
@@ -121,9 +128,12 @@ pub fn PlayerProfile() -> impl IntoView {
                         player.get().map(|result| match result {
                             Ok(player) => view! {
                                 <div class="flex items-center justify-between mb-6">
-                                    <h1 class="text-2xl font-semibold">
-                                        "Spieler " { player.tag_name.clone() }
-                                    </h1>
+                                    <div class="flex items-center gap-4">
+                                        <h1 class="text-2xl font-semibold">
+                                            "Spieler " { player.tag_name.clone() }
+                                        </h1>
+                                        <SuspensionBadge player_id=player.id.unwrap()/>
+                                    </div>
                                     <A href=format!("/players/{}/edit", player.id.unwrap())
                                         attr:class="bg-blue-600 hover:bg-blue-700 text-white font-medium py-2 px-4 rounded transition"
                                     >
@@ -152,6 +162,57 @@ pub fn PlayerProfile() -> impl IntoView {
                                             { player.tag_name }
                                         </output>
 
+                                        <label for="view_player[real_name]" class="text-left text-gray-700">
+                                            "Name:"
+                                        </label>
+                                        <output
+                                            name="view_player[real_name]"
+                                            class="text-left w-full px-3 py-2 focus:outline-none focus:ring focus:border-blue-300">
+                                            { player.real_name.unwrap_or("Keine Angabe".to_string()) }
+                                        </output>
+
+                                        <label for="view_player[role]" class="text-left text-gray-700">
+                                            "Rolle:"
+                                        </label>
+                                        <output
+                                            name="view_player[role]"
+                                            class="text-left w-full px-3 py-2 focus:outline-none focus:ring focus:border-blue-300">
+                                            { player.role.map(|role| role.to_string()).unwrap_or("Keine Angabe".to_string()) }
+                                        </output>
+
+                                        {player.birth_date.map(|birth_date| view! {
+                                            <label for="view_player[birth_date]" class="text-left text-gray-700">
+                                                "Geburtsdatum:"
+                                            </label>
+                                            <output
+                                                name="view_player[birth_date]"
+                                                class="text-left w-full px-3 py-2 focus:outline-none focus:ring focus:border-blue-300">
+                                                { format_date(&birth_date) }
+                                            </output>
+                                        })}
+
+                                        {player.phone.map(|phone| view! {
+                                            <label for="view_player[phone]" class="text-left text-gray-700">
+                                                "Telefon:"
+                                            </label>
+                                            <output
+                                                name="view_player[phone]"
+                                                class="text-left w-full px-3 py-2 focus:outline-none focus:ring focus:border-blue-300">
+                                                { phone }
+                                            </output>
+                                        })}
+
+                                        {player.emergency_contact.map(|emergency_contact| view! {
+                                            <label for="view_player[emergency_contact]" class="text-left text-gray-700">
+                                                "Notfallkontakt:"
+                                            </label>
+                                            <output
+                                                name="view_player[emergency_contact]"
+                                                class="text-left w-full px-3 py-2 whitespace-pre-line focus:outline-none focus:ring focus:border-blue-300">
+                                                { emergency_contact }
+                                            </output>
+                                        })}
+
                                         <label for="view_player[active]" class="text-left text-gray-700">
                                             "Aktiv:"
                                         </label>
@@ -181,6 +242,34 @@ pub fn PlayerProfile() -> impl IntoView {
                                                 })
                                             }}
                                         </output>
+
+                                        <div class="col-span-2">
+                                            <PlayerTeamHistory player_id=player.id.unwrap()/>
+                                        </div>
+
+                                        <div class="col-span-2">
+                                            <PlayerAttendanceSummary player_id=player.id.unwrap()/>
+                                        </div>
+
+                                        <div class="col-span-2">
+                                            <PlayerReplicas player_id=player.id.unwrap()/>
+                                        </div>
+
+                                        <div class="col-span-2">
+                                            <ParentalConsents player_id=player.id.unwrap()/>
+                                        </div>
+
+                                        <div class="col-span-2">
+                                            <DisciplinaryRecords player_id=player.id.unwrap()/>
+                                        </div>
+
+                                        <div class="col-span-2">
+                                            <PlayerLegalAcceptances player_id=player.id.unwrap()/>
+                                        </div>
+
+                                        <div class="col-span-2">
+                                            <PlayerAccount player_id=player.id.unwrap()/>
+                                        </div>
                                     </div>
                                 </div>
                             }.into_any(),
@@ -268,6 +357,11 @@ pub fn PlayerEdit() -> impl IntoView {
         |id| find_team_for_id(id),
     );
 
+    let private_access = Resource::new(
+        move || player_id.read().clone(),
+        move |params_result| can_see_private_profile(params_result.unwrap().id.unwrap()),
+    );
+
     let save_team = ServerAction::<SavePlayer>::new();
     let get_filtered_teams = Action::new(|filter: &String| {
         get_filtered_teams(filter.to_string())
@@ -335,6 +429,65 @@ pub fn PlayerEdit() -> impl IntoView {
                                                 })
                                             }}
 
+                                            <label for="player_form[real_name]" class="text-left text-gray-700">
+                                                "Name:"
+                                            </label>
+                                            <input
+                                                name="player_form[real_name]"
+                                                class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+                                                value=player.real_name/>
+
+                                            <label for="player_form[role]" class="text-left text-gray-700">
+                                                "Rolle:"
+                                            </label>
+                                            <select
+                                                name="player_form[role]"
+                                                class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+                                            >
+                                                <option value="" selected=player.role.is_none()>"Keine Angabe"</option>
+                                                {
+                                                    PlayerRole::ALL
+                                                        .into_iter()
+                                                        .map(|role| view! {
+                                                            <option value=role.as_str() selected=player.role == Some(role)>{ role.to_string() }</option>
+                                                        })
+                                                        .collect_view()
+                                                }
+                                            </select>
+
+                                            <Transition>
+                                                <Show when=move || private_access.get().and_then(|result| result.ok()).unwrap_or(false)>
+                                                    <label for="player_form[birth_date]" class="text-left text-gray-700">
+                                                        "Geburtsdatum:"
+                                                    </label>
+                                                    <input
+                                                        type="date"
+                                                        name="player_form[birth_date]"
+                                                        class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+                                                        value=player.birth_date.as_ref().map(date_input_value)/>
+
+                                                    <label for="player_form[phone]" class="text-left text-gray-700">
+                                                        "Telefon:"
+                                                    </label>
+                                                    <input
+                                                        type="tel"
+                                                        name="player_form[phone]"
+                                                        class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+                                                        value=player.phone.clone()/>
+
+                                                    <label for="player_form[emergency_contact]" class="text-left text-gray-700">
+                                                        "Notfallkontakt:"
+                                                    </label>
+                                                    <textarea
+                                                        name="player_form[emergency_contact]"
+                                                        rows="2"
+                                                        class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+                                                    >
+                                                        { player.emergency_contact.clone() }
+                                                    </textarea>
+                                                </Show>
+                                            </Transition>
+
                                             <input
                                                 type="hidden"
                                                 name="player_form[id]"
@@ -387,26 +540,42 @@ struct EditPlayerForm {
     #[serde(default)]
     active: bool,
     team_id: Option<i64>,
+    #[serde(default)]
+    real_name: String,
+    #[serde(default)]
+    role: String,
+    #[serde(default)]
+    birth_date: String,
+    #[serde(default)]
+    phone: String,
+    #[serde(default)]
+    emergency_contact: String,
+}
+
+#[cfg(feature = "ssr")]
+fn optional_text(value: String) -> Option<String> {
+    let value = value.trim();
+
+    (!value.is_empty()).then(|| value.to_string())
 }
 
 #[server]
 async fn get_players() -> Result<Vec<Player>, AppError> {
+    use crate::auth::visible_players;
     use crate::database::{self, DieselPool};
 
     let pool = use_context::<DieselPool>()
         .ok_or_else(|| AppError::MissingContext)?;
 
     let database_players = database::get_all_players(&pool)?;
-    let domain_players = database_players
-        .into_iter()
-        .map(|db_player| db_player.into())
-        .collect();
+    let domain_players = visible_players(database_players, &pool).await?;
 
     Ok(domain_players)
 }
 
 #[server]
 async fn load_player_by_id(id: i64) -> Result<Player, AppError> {
+    use crate::auth::visible_players;
     use crate::database::{self, DatabaseError, DieselPool};
 
     let pool = use_context::<DieselPool>()
@@ -415,25 +584,82 @@ async fn load_player_by_id(id: i64) -> Result<Player, AppError> {
     let result = database::find_player_for_id(id, &pool);
 
     match result {
-        Ok(Some(player)) => Ok(player.into()),
+        Ok(Some(player)) => Ok(visible_players(vec![player], &pool).await?.remove(0)),
         Ok(None) => Err(DatabaseError::EntityNotFound.into()),
         Err(err) => Err(err.into()),
     }
 }
 
+#[server]
+async fn can_see_private_profile(id: i64) -> Result<bool, AppError> {
+    use crate::auth::can_see_private_fields;
+    use crate::database::DieselPool;
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    can_see_private_fields(id, &pool).await
+}
+
 #[server]
 async fn save_player(player_form: EditPlayerForm) -> Result<(), AppError> {
-    use crate::database::{self, DieselPool};
+    use crate::auth::{can_see_private_fields, current_player, AuthError};
+    use crate::components::util::parse_date_input;
+    use crate::database::{self, DatabaseError, DieselPool};
 
     let pool = use_context::<DieselPool>()
         .ok_or_else(|| AppError::MissingContext)?;
 
+    let editor = current_player().await?;
+
+    if !editor.admin && editor.id != player_form.id {
+        return Err(AppError::AuthError(AuthError::Forbidden));
+    }
+
+    let current: Player = database::find_player_for_id(player_form.id, &pool)?
+        .ok_or(DatabaseError::EntityNotFound)?
+        .into();
+
+    // The team decides what a player may see of others, so only admins move players or change the login.
+    if !editor.admin
+        && (player_form.email != current.email
+            || player_form.active != current.active
+            || player_form.team_id != current.team_id)
+    {
+        return Err(AppError::AuthError(AuthError::Forbidden));
+    }
+
+    let role = match player_form.role.as_str() {
+        "" => None,
+        role => Some(role.parse().map_err(|_| AppError::InvalidInput("Rolle".to_string()))?),
+    };
+
+    // Private fields the editor cannot see are not part of the form and stay as they are.
+    let (birth_date, phone, emergency_contact) = if can_see_private_fields(player_form.id, &pool).await? {
+        let birth_date = match player_form.birth_date.as_str() {
+            "" => None,
+            birth_date => Some(parse_date_input(birth_date)
+                .filter(|birth_date| *birth_date <= chrono::Utc::now().date_naive())
+                .ok_or(AppError::InvalidInput("Geburtsdatum".to_string()))?),
+        };
+
+        (birth_date, optional_text(player_form.phone), optional_text(player_form.emergency_contact))
+    } else {
+        (current.birth_date, current.phone, current.emergency_contact)
+    };
+
     let player = Player {
         id: Some(player_form.id),
         email: player_form.email,
         tag_name: player_form.tag_name,
         active: player_form.active,
         team_id: player_form.team_id,
+        real_name: optional_text(player_form.real_name),
+        role,
+        birth_date,
+        phone,
+        emergency_contact,
+        ..Default::default()
     };
 
     let result = database::save_player(player, &pool);
@@ -0,0 +1,937 @@
+use leptos::prelude::*;
+use leptos::Params;
+use leptos_router::{components::A, hooks::use_params, params::Params};
+use serde::{Deserialize, Serialize};
+
+use crate::app::AppError;
+use crate::components::util::{format_datetime, BackButton};
+use crate::database::DatabaseError;
+use crate::domain::{
+    EventRentals, ItemCondition, LoanState, RentalItem, RentalItemDetails, RentalItemStatus, RentalKind, RentalLoan,
+    RentalOverview,
+};
+
+#[component]
+pub fn Rentals() -> impl IntoView {
+    use crate::components::protected::Protected;
+    use leptos_router::components::Outlet;
+
+    view! {
+        <Protected>
+            <Outlet/>
+        </Protected>
+    }
+}
+
+#[component]
+pub fn RentalInventory() -> impl IntoView {
+
+    let create_item = ServerAction::<CreateRentalItem>::new();
+    let items = Resource::new(move || create_item.version().get(), |_| get_rental_items());
+
+    let action_error = move || create_item.value().get().and_then(|result| result.err()).map(|e| view! {
+        <p class="text-red-600">{ e.to_string() }</p>
+    });
+
+    view! {
+        <BackButton/>
+        <div class="p-8 max-w-4xl mx-auto">
+            <div class="flex items-center justify-between mb-6">
+                <h1 class="text-2xl font-semibold">
+                    "Leihausrüstung"
+                </h1>
+                <A href="/rentals/loans"
+                    attr:class="bg-blue-600 hover:bg-blue-700 text-white font-medium py-2 px-4 rounded transition"
+                >
+                    "Ausleihen"
+                </A>
+            </div>
+
+            <Suspense fallback=move || view! { <p>"Lade Daten..."</p> }>
+                {move || {
+                    items.get().map(|result| match result {
+                        Ok(items) => view! {
+                            <div class="overflow-x-auto">
+                                <table class="min-w-full border border-gray-200 shadow-sm rounded-md bg-white">
+                                    <thead class="bg-gray-100 text-gray-700">
+                                        <tr>
+                                            <th class="text-left py-2 px-4 border-b">Art</th>
+                                            <th class="text-left py-2 px-4 border-b">Name</th>
+                                            <th class="text-left py-2 px-4 border-b">Seriennummer</th>
+                                            <th class="text-left py-2 px-4 border-b">Zustand</th>
+                                            <th class="text-left py-2 px-4 border-b">Status</th>
+                                        </tr>
+                                    </thead>
+                                    <tbody>
+                                        {
+                                            items.into_iter().map(|item: RentalItem| view! {
+                                                <tr class="hover:bg-gray-50">
+                                                    <th class="text-left py-2 px-4 border-b">{ item.kind.to_string() }</th>
+                                                    <th class="text-left py-2 px-4 border-b">
+                                                        <A href=format!("/rentals/{}", item.id.unwrap()) attr:class="hover:underline">{ item.name }</A>
+                                                    </th>
+                                                    <th class="text-left py-2 px-4 border-b">{ item.serial_number }</th>
+                                                    <th class="text-left py-2 px-4 border-b">{ item.condition.to_string() }</th>
+                                                    <th class="text-left py-2 px-4 border-b">{ item.status.to_string() }</th>
+                                                </tr>
+                                            }).collect_view()
+                                        }
+                                    </tbody>
+                                </table>
+                            </div>
+                        }.into_any(),
+                        Err(e) => view! { <p>{ e.to_string() }</p> }.into_any(),
+                    })
+                }}
+            </Suspense>
+
+            { action_error }
+
+            <h2 class="text-left text-xl font-semibold py-2">Neuer Gegenstand</h2>
+            <ActionForm action=create_item attr:class="grid grid-cols-[auto_1fr] items-center gap-4">
+                <label for="item_form[kind]" class="text-left text-gray-700">
+                    "Art:"
+                </label>
+                <select
+                    name="item_form[kind]"
+                    class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+                >
+                    {
+                        RentalKind::ALL
+                            .into_iter()
+                            .map(|kind| view! { <option value=kind.as_str()>{ kind.to_string() }</option> })
+                            .collect_view()
+                    }
+                </select>
+
+                <label for="item_form[name]" class="text-left text-gray-700">
+                    "Name:"
+                </label>
+                <input
+                    type="text"
+                    name="item_form[name]"
+                    class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+                    required
+                />
+
+                <label for="item_form[serial_number]" class="text-left text-gray-700">
+                    "Seriennummer:"
+                </label>
+                <input
+                    type="text"
+                    name="item_form[serial_number]"
+                    class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+                />
+
+                <label for="item_form[condition]" class="text-left text-gray-700">
+                    "Zustand:"
+                </label>
+                <ConditionSelect name="item_form[condition]" selected=ItemCondition::Good/>
+
+                <div class="col-span-2 flex justify-end">
+                    <button
+                        type="submit"
+                        class="px-4 py-2 rounded bg-blue-600 hover:bg-blue-700 text-white"
+                    >
+                        "Gegenstand anlegen"
+                    </button>
+                </div>
+            </ActionForm>
+        </div>
+    }
+}
+
+#[component]
+fn ConditionSelect(#[prop(into)] name: String, selected: ItemCondition) -> impl IntoView {
+
+    view! {
+        <select
+            name=name
+            class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+        >
+            {
+                ItemCondition::ALL
+                    .into_iter()
+                    .map(|condition| view! {
+                        <option value=condition.as_str() selected=condition == selected>{ condition.to_string() }</option>
+                    })
+                    .collect_view()
+            }
+        </select>
+    }
+}
+
+#[component]
+pub fn RentalItemPage() -> impl IntoView {
+
+    let item_id = use_params::<RentalItemIdParameter>();
+
+    let update_item = ServerAction::<UpdateRentalItem>::new();
+    let delete_item = ServerAction::<DeleteRentalItem>::new();
+
+    let details = Resource::new(
+        move || (item_id.read().clone(), update_item.version().get()),
+        move |(params_result, _)| get_rental_item_details(params_result.unwrap().id.unwrap()),
+    );
+
+    let action_error = move || {
+        let update_error = update_item.value().get().and_then(|result| result.err());
+        let delete_error = delete_item.value().get().and_then(|result| result.err());
+
+        update_error.or(delete_error).map(|e| view! {
+            <p class="text-red-600">{ e.to_string() }</p>
+        })
+    };
+
+    view! {
+        <BackButton/>
+        <div class="p-8 max-w-4xl mx-auto">
+            <Suspense fallback=move || view! { <p>"Lade Daten..."</p> }>
+                {move || {
+                    details.get().map(|result| match result {
+                        Ok(RentalItemDetails { item, loans }) => view! {
+                            <div class="flex items-center justify-between mb-6">
+                                <h1 class="text-2xl font-semibold">
+                                    { format!("{} ({})", item.name, item.kind) }
+                                </h1>
+                                <ActionForm action=delete_item>
+                                    <input type="hidden" name="id" value=item.id/>
+                                    <button
+                                        type="submit"
+                                        class="px-4 py-2 rounded bg-gray-200 hover:bg-gray-300 text-gray-700"
+                                    >
+                                        "Löschen"
+                                    </button>
+                                </ActionForm>
+                            </div>
+
+                            <ActionForm action=update_item attr:class="grid grid-cols-[auto_1fr] items-center gap-4">
+                                <input type="hidden" name="id" value=item.id/>
+
+                                <span class="text-left text-gray-700">"Seriennummer:"</span>
+                                <span class="text-left px-3 py-2">{ item.serial_number.clone() }</span>
+
+                                <label for="condition" class="text-left text-gray-700">
+                                    "Zustand:"
+                                </label>
+                                <ConditionSelect name="condition" selected=item.condition/>
+
+                                <label for="status" class="text-left text-gray-700">
+                                    "Status:"
+                                </label>
+                                <select
+                                    name="status"
+                                    class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+                                >
+                                    {
+                                        RentalItemStatus::ALL
+                                            .into_iter()
+                                            .map(|status| view! {
+                                                <option value=status.as_str() selected=status == item.status>{ status.to_string() }</option>
+                                            })
+                                            .collect_view()
+                                    }
+                                </select>
+
+                                <div class="col-span-2 flex justify-end">
+                                    <button
+                                        type="submit"
+                                        class="px-4 py-2 rounded bg-blue-600 hover:bg-blue-700 text-white"
+                                    >
+                                        "Speichern"
+                                    </button>
+                                </div>
+                            </ActionForm>
+
+                            <h2 class="text-left text-xl font-semibold py-2">Ausleihen</h2>
+                            <LoanHistoryTable loans=loans/>
+                        }.into_any(),
+                        Err(e) => view! { <p>{ e.to_string() }</p> }.into_any(),
+                    })
+                }}
+            </Suspense>
+
+            { action_error }
+        </div>
+    }
+}
+
+#[component]
+fn LoanHistoryTable(loans: Vec<RentalLoan>) -> impl IntoView {
+
+    view! {
+        <div class="overflow-x-auto">
+            <table class="min-w-full border border-gray-200 shadow-sm rounded-md bg-white">
+                <thead class="bg-gray-100 text-gray-700">
+                    <tr>
+                        <th class="text-left py-2 px-4 border-b">Spielername</th>
+                        <th class="text-left py-2 px-4 border-b">Event</th>
+                        <th class="text-left py-2 px-4 border-b">Ausgegeben</th>
+                        <th class="text-left py-2 px-4 border-b">Zurück</th>
+                        <th class="text-left py-2 px-4 border-b">Schäden</th>
+                    </tr>
+                </thead>
+                <tbody>
+                    {
+                        loans.into_iter().map(|loan: RentalLoan| view! {
+                            <tr class="hover:bg-gray-50">
+                                <th class="text-left py-2 px-4 border-b">
+                                    <A href=format!("/players/{}", loan.player_id) attr:class="hover:underline">{ loan.tag_name }</A>
+                                </th>
+                                <th class="text-left py-2 px-4 border-b">{ loan.play_event_name }</th>
+                                <th class="text-left py-2 px-4 border-b">
+                                    { loan.checked_out_at.as_ref().map(format_datetime).unwrap_or("Reserviert".to_string()) }
+                                </th>
+                                <th class="text-left py-2 px-4 border-b">
+                                    { loan.returned_at.as_ref().map(format_datetime) }
+                                </th>
+                                <th class="text-left py-2 px-4 border-b font-normal">{ loan.damage_note }</th>
+                            </tr>
+                        }).collect_view()
+                    }
+                </tbody>
+            </table>
+        </div>
+    }
+}
+
+#[component]
+pub fn RentalLoansPage() -> impl IntoView {
+
+    let issue_item = ServerAction::<IssueRentalItem>::new();
+    let check_out = ServerAction::<CheckOutRentalLoan>::new();
+    let cancel_reservation = ServerAction::<CancelRentalReservation>::new();
+    let return_loan = ServerAction::<ReturnRentalLoan>::new();
+
+    let overview = Resource::new(
+        move || (
+            issue_item.version().get(),
+            check_out.version().get(),
+            cancel_reservation.version().get(),
+            return_loan.version().get(),
+        ),
+        |_| get_rental_overview(),
+    );
+
+    let action_error = move || {
+        let issue_error = issue_item.value().get().and_then(|result| result.err());
+        let check_out_error = check_out.value().get().and_then(|result| result.err());
+        let cancel_error = cancel_reservation.value().get().and_then(|result| result.err());
+        let return_error = return_loan.value().get().and_then(|result| result.err());
+
+        issue_error
+            .or(check_out_error)
+            .or(cancel_error)
+            .or(return_error)
+            .map(|e| view! { <p class="text-red-600">{ rental_error_text(e) }</p> })
+    };
+
+    view! {
+        <BackButton/>
+        <div class="p-8 max-w-6xl mx-auto">
+            <div class="flex items-center justify-between mb-6">
+                <h1 class="text-2xl font-semibold">
+                    "Ausleihen"
+                </h1>
+            </div>
+
+            { action_error }
+
+            <Suspense fallback=move || view! { <p>"Lade Daten..."</p> }>
+                {move || {
+                    overview.get().map(|result| match result {
+                        Ok(RentalOverview { loans, issuable_items, players, play_events }) => {
+                            let now = chrono::Utc::now();
+                            let (issued, reserved): (Vec<RentalLoan>, Vec<RentalLoan>) = loans
+                                .into_iter()
+                                .partition(|loan| loan.state() == LoanState::Issued);
+                            let (overdue, issued): (Vec<RentalLoan>, Vec<RentalLoan>) = issued
+                                .into_iter()
+                                .partition(|loan| loan.is_overdue(now));
+
+                            view! {
+                                <h2 class="text-left text-xl font-semibold py-2 text-red-600">Überfällig</h2>
+                                <OpenLoansTable loans=overdue check_out=check_out cancel_reservation=cancel_reservation return_loan=return_loan/>
+
+                                <h2 class="text-left text-xl font-semibold py-2">Ausgegeben</h2>
+                                <OpenLoansTable loans=issued check_out=check_out cancel_reservation=cancel_reservation return_loan=return_loan/>
+
+                                <h2 class="text-left text-xl font-semibold py-2">Reserviert</h2>
+                                <OpenLoansTable loans=reserved check_out=check_out cancel_reservation=cancel_reservation return_loan=return_loan/>
+
+                                <h2 class="text-left text-xl font-semibold py-2">Ohne Reservierung ausgeben</h2>
+                                <ActionForm action=issue_item attr:class="grid grid-cols-[auto_1fr] items-center gap-4">
+                                    <label for="issue_form[item_id]" class="text-left text-gray-700">
+                                        "Gegenstand:"
+                                    </label>
+                                    <select
+                                        name="issue_form[item_id]"
+                                        class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+                                    >
+                                        {
+                                            issuable_items
+                                                .into_iter()
+                                                .map(|item| view! {
+                                                    <option value=item.id>{ format!("{}: {} {}", item.kind, item.name, item.serial_number) }</option>
+                                                })
+                                                .collect_view()
+                                        }
+                                    </select>
+
+                                    <label for="issue_form[player_id]" class="text-left text-gray-700">
+                                        "Spieler:"
+                                    </label>
+                                    <select
+                                        name="issue_form[player_id]"
+                                        class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+                                    >
+                                        {
+                                            players
+                                                .into_iter()
+                                                .map(|(player_id, tag_name)| view! { <option value=player_id>{ tag_name }</option> })
+                                                .collect_view()
+                                        }
+                                    </select>
+
+                                    <label for="issue_form[play_event_id]" class="text-left text-gray-700">
+                                        "Event:"
+                                    </label>
+                                    <select
+                                        name="issue_form[play_event_id]"
+                                        class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+                                    >
+                                        {
+                                            play_events
+                                                .into_iter()
+                                                .map(|(play_event_id, name)| view! { <option value=play_event_id>{ name }</option> })
+                                                .collect_view()
+                                        }
+                                    </select>
+
+                                    <div class="col-span-2 flex justify-end">
+                                        <button
+                                            type="submit"
+                                            class="px-4 py-2 rounded bg-blue-600 hover:bg-blue-700 text-white"
+                                        >
+                                            "Ausgeben"
+                                        </button>
+                                    </div>
+                                </ActionForm>
+                            }.into_any()
+                        },
+                        Err(e) => view! { <p>{ e.to_string() }</p> }.into_any(),
+                    })
+                }}
+            </Suspense>
+        </div>
+    }
+}
+
+#[component]
+fn OpenLoansTable(
+    loans: Vec<RentalLoan>,
+    check_out: ServerAction<CheckOutRentalLoan>,
+    cancel_reservation: ServerAction<CancelRentalReservation>,
+    return_loan: ServerAction<ReturnRentalLoan>,
+) -> impl IntoView {
+
+    if loans.is_empty() {
+        return view! { <p class="text-left text-gray-700">"Keine Einträge"</p> }.into_any();
+    }
+
+    view! {
+        <div class="overflow-x-auto">
+            <table class="min-w-full border border-gray-200 shadow-sm rounded-md bg-white">
+                <thead class="bg-gray-100 text-gray-700">
+                    <tr>
+                        <th class="text-left py-2 px-4 border-b">Gegenstand</th>
+                        <th class="text-left py-2 px-4 border-b">Spielername</th>
+                        <th class="text-left py-2 px-4 border-b">Event</th>
+                        <th class="text-left py-2 px-4 border-b">Ausgegeben</th>
+                        <th class="py-2 px-4 border-b"></th>
+                    </tr>
+                </thead>
+                <tbody>
+                    {
+                        loans.into_iter().map(|loan: RentalLoan| {
+                            let state = loan.state();
+
+                            view! {
+                                <tr class="hover:bg-gray-50 align-top">
+                                    <th class="text-left py-2 px-4 border-b">
+                                        <A href=format!("/rentals/{}", loan.item.id.unwrap()) attr:class="hover:underline">
+                                            { format!("{}: {}", loan.item.kind, loan.item.name) }
+                                        </A>
+                                    </th>
+                                    <th class="text-left py-2 px-4 border-b">
+                                        <A href=format!("/players/{}", loan.player_id) attr:class="hover:underline">{ loan.tag_name }</A>
+                                    </th>
+                                    <th class="text-left py-2 px-4 border-b">{ loan.play_event_name }</th>
+                                    <th class="text-left py-2 px-4 border-b">{ loan.checked_out_at.as_ref().map(format_datetime) }</th>
+                                    <th class="py-2 px-4 border-b">
+                                        {
+                                            match state {
+                                                LoanState::Reserved => view! {
+                                                    <div class="flex justify-end gap-2">
+                                                        <ActionForm action=check_out>
+                                                            <input type="hidden" name="id" value=loan.id/>
+                                                            <button
+                                                                type="submit"
+                                                                class="px-4 py-2 rounded bg-blue-600 hover:bg-blue-700 text-white"
+                                                            >
+                                                                "Ausgeben"
+                                                            </button>
+                                                        </ActionForm>
+                                                        <ActionForm action=cancel_reservation>
+                                                            <input type="hidden" name="id" value=loan.id/>
+                                                            <button
+                                                                type="submit"
+                                                                class="px-4 py-2 rounded bg-gray-200 hover:bg-gray-300 text-gray-700"
+                                                            >
+                                                                "Stornieren"
+                                                            </button>
+                                                        </ActionForm>
+                                                    </div>
+                                                }.into_any(),
+                                                _ => view! {
+                                                    <ActionForm action=return_loan attr:class="flex flex-col gap-2">
+                                                        <input type="hidden" name="return_form[id]" value=loan.id/>
+                                                        <ConditionSelect name="return_form[condition]" selected=loan.item.condition/>
+                                                        <input
+                                                            type="text"
+                                                            name="return_form[damage_note]"
+                                                            placeholder="Schäden"
+                                                            class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+                                                        />
+                                                        <button
+                                                            type="submit"
+                                                            class="px-4 py-2 rounded bg-blue-600 hover:bg-blue-700 text-white"
+                                                        >
+                                                            "Zurücknehmen"
+                                                        </button>
+                                                    </ActionForm>
+                                                }.into_any(),
+                                            }
+                                        }
+                                    </th>
+                                </tr>
+                            }
+                        }).collect_view()
+                    }
+                </tbody>
+            </table>
+        </div>
+    }.into_any()
+}
+
+/// Rental reservations of the current player, shown while registered for the event.
+#[component]
+pub fn EventRentalPanel(play_event_id: i64) -> impl IntoView {
+
+    let reserve = ServerAction::<ReserveRental>::new();
+    let cancel_reservation = ServerAction::<CancelOwnRentalReservation>::new();
+
+    let rentals = Resource::new(
+        move || (reserve.version().get(), cancel_reservation.version().get()),
+        move |_| get_event_rentals(play_event_id),
+    );
+
+    let action_error = move || {
+        let reserve_error = reserve.value().get().and_then(|result| result.err());
+        let cancel_error = cancel_reservation.value().get().and_then(|result| result.err());
+
+        reserve_error.or(cancel_error).map(|e| view! {
+            <p class="text-red-600">{ rental_error_text(e) }</p>
+        })
+    };
+
+    view! {
+        <Suspense fallback=move || view! { <p>"Lade Daten..."</p> }>
+            {move || {
+                rentals.get().map(|result| match result {
+                    Ok(Some(EventRentals { reservations, available })) => view! {
+                        <h3 class="text-left font-semibold pt-4">Leihausrüstung</h3>
+
+                        {
+                            reservations.into_iter().map(|loan: RentalLoan| {
+                                let reserved = loan.state() == LoanState::Reserved;
+
+                                view! {
+                                    <div class="flex items-center justify-between mt-2">
+                                        <p>{ format!("{}: {} ({})", loan.item.kind, loan.item.name, loan.state()) }</p>
+                                        <Show when=move || reserved>
+                                            <ActionForm action=cancel_reservation>
+                                                <input type="hidden" name="id" value=loan.id/>
+                                                <button
+                                                    type="submit"
+                                                    class="px-4 py-2 rounded bg-gray-200 hover:bg-gray-300 text-gray-700"
+                                                >
+                                                    "Stornieren"
+                                                </button>
+                                            </ActionForm>
+                                        </Show>
+                                    </div>
+                                }
+                            }).collect_view()
+                        }
+
+                        <div class="flex flex-wrap gap-2 mt-2">
+                            {
+                                available.into_iter().map(|(kind, count)| view! {
+                                    <ActionForm action=reserve>
+                                        <input type="hidden" name="play_event_id" value=play_event_id/>
+                                        <input type="hidden" name="kind" value=kind.as_str()/>
+                                        <button
+                                            type="submit"
+                                            class="px-4 py-2 rounded bg-blue-600 hover:bg-blue-700 text-white disabled:opacity-50"
+                                            disabled=count == 0
+                                        >
+                                            { format!("{} leihen ({} frei)", kind, count) }
+                                        </button>
+                                    </ActionForm>
+                                }).collect_view()
+                            }
+                        </div>
+                    }.into_any(),
+                    Ok(None) => ().into_any(),
+                    Err(e) => view! { <p>{ e.to_string() }</p> }.into_any(),
+                })
+            }}
+        </Suspense>
+
+        { action_error }
+    }
+}
+
+fn rental_error_text(error: AppError) -> String {
+    match error {
+        AppError::Database(DatabaseError::RentalUnavailable) => "Kein passender Gegenstand frei".to_string(),
+        AppError::Database(DatabaseError::AlreadyReserved) => "Du hast bereits einen Gegenstand dieser Art reserviert".to_string(),
+        e => e.to_string(),
+    }
+}
+
+#[derive(Params, PartialEq, Clone)]
+struct RentalItemIdParameter {
+    id: Option<i64>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct ItemForm {
+    kind: String,
+    name: String,
+    #[serde(default)]
+    serial_number: String,
+    condition: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct IssueForm {
+    item_id: i64,
+    player_id: i64,
+    play_event_id: i64,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct ReturnForm {
+    id: i64,
+    condition: String,
+    #[serde(default)]
+    damage_note: String,
+}
+
+#[server]
+pub async fn can_manage_rentals() -> Result<bool, AppError> {
+    use crate::auth::current_player_if_logged_in;
+
+    Ok(current_player_if_logged_in().await?.is_some_and(|player| player.admin))
+}
+
+#[server]
+async fn get_rental_items() -> Result<Vec<RentalItem>, AppError> {
+    use crate::auth::require_admin;
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    require_admin().await?;
+
+    Ok(database::get_rental_items(&pool)?
+        .into_iter()
+        .map(Into::into)
+        .collect())
+}
+
+#[server]
+async fn create_rental_item(item_form: ItemForm) -> Result<(), AppError> {
+    use crate::auth::require_admin;
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    require_admin().await?;
+
+    let item = RentalItem {
+        id: None,
+        kind: item_form.kind
+            .parse()
+            .map_err(|_| AppError::InvalidInput("Art".to_string()))?,
+        name: item_form.name.trim().to_string(),
+        serial_number: item_form.serial_number.trim().to_string(),
+        condition: item_form.condition
+            .parse()
+            .map_err(|_| AppError::InvalidInput("Zustand".to_string()))?,
+        status: RentalItemStatus::Available,
+    };
+
+    if item.name.is_empty() {
+        return Err(AppError::InvalidInput("Name".to_string()));
+    }
+
+    database::create_rental_item(item, &pool)?;
+
+    Ok(())
+}
+
+#[server]
+async fn get_rental_item_details(id: i64) -> Result<RentalItemDetails, AppError> {
+    use crate::auth::require_admin;
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    require_admin().await?;
+
+    let item = database::find_rental_item_for_id(id, &pool)?
+        .ok_or(DatabaseError::EntityNotFound)?
+        .into();
+
+    Ok(RentalItemDetails {
+        item,
+        loans: database::get_rental_loans_for_item(id, &pool)?,
+    })
+}
+
+#[server]
+async fn update_rental_item(id: i64, condition: String, status: String) -> Result<(), AppError> {
+    use crate::auth::require_admin;
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    require_admin().await?;
+
+    let condition = condition
+        .parse()
+        .map_err(|_| AppError::InvalidInput("Zustand".to_string()))?;
+    let status = status
+        .parse()
+        .map_err(|_| AppError::InvalidInput("Status".to_string()))?;
+
+    Ok(database::update_rental_item_state(id, condition, status, &pool)?)
+}
+
+#[server]
+async fn delete_rental_item(id: i64) -> Result<(), AppError> {
+    use crate::auth::require_admin;
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    require_admin().await?;
+
+    database::delete_rental_item(id, &pool)?;
+
+    leptos_axum::redirect("/rentals");
+    Ok(())
+}
+
+#[server]
+async fn get_rental_overview() -> Result<RentalOverview, AppError> {
+    use std::collections::HashSet;
+
+    use crate::auth::require_admin;
+    use crate::database::{self, DieselPool};
+    use crate::domain::PlayEvent;
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    require_admin().await?;
+
+    let loans = database::get_open_rental_loans(&pool)?;
+
+    let out_item_ids: HashSet<i64> = loans
+        .iter()
+        .filter(|loan| loan.state() == LoanState::Issued)
+        .filter_map(|loan| loan.item.id)
+        .collect();
+
+    let issuable_items = database::get_rental_items(&pool)?
+        .into_iter()
+        .map(RentalItem::from)
+        .filter(|item| item.is_rentable() && !out_item_ids.contains(&item.id.unwrap()))
+        .collect();
+
+    let mut players: Vec<(i64, String)> = database::get_all_players(&pool)?
+        .into_iter()
+        .filter(|player| player.active)
+        .map(|player| (player.id, player.tag_name))
+        .collect();
+    players.sort_by(|a, b| a.1.cmp(&b.1));
+
+    let now = chrono::Utc::now();
+    let play_events = database::get_all_play_events(&pool)?
+        .into_iter()
+        .map(PlayEvent::from)
+        .filter(|play_event| play_event.cancelled_at.is_none() && play_event.ends_at > now)
+        .map(|play_event| (play_event.id.unwrap(), play_event.name))
+        .collect();
+
+    Ok(RentalOverview { loans, issuable_items, players, play_events })
+}
+
+#[server]
+async fn issue_rental_item(issue_form: IssueForm) -> Result<(), AppError> {
+    use crate::auth::require_admin;
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let admin = require_admin().await?;
+
+    database::issue_rental_item(issue_form.item_id, issue_form.player_id, issue_form.play_event_id, admin.id, &pool)?;
+
+    Ok(())
+}
+
+#[server]
+async fn check_out_rental_loan(id: i64) -> Result<(), AppError> {
+    use crate::auth::require_admin;
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let admin = require_admin().await?;
+
+    Ok(database::check_out_rental_loan(id, admin.id, &pool)?)
+}
+
+#[server]
+async fn cancel_rental_reservation(id: i64) -> Result<(), AppError> {
+    use crate::auth::require_admin;
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    require_admin().await?;
+
+    Ok(database::cancel_rental_reservation(id, &pool)?)
+}
+
+/// A reported damage always marks the item as damaged, so it isn't handed out again unchecked.
+#[server]
+async fn return_rental_loan(return_form: ReturnForm) -> Result<(), AppError> {
+    use crate::auth::require_admin;
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    require_admin().await?;
+
+    let damage_note = return_form.damage_note.trim();
+    let condition = if damage_note.is_empty() {
+        return_form.condition
+            .parse()
+            .map_err(|_| AppError::InvalidInput("Zustand".to_string()))?
+    } else {
+        ItemCondition::Damaged
+    };
+
+    Ok(database::return_rental_loan(return_form.id, damage_note, condition, &pool)?)
+}
+
+/// Only players with an active registration see and reserve rentals.
+#[server]
+async fn get_event_rentals(play_event_id: i64) -> Result<Option<EventRentals>, AppError> {
+    use crate::auth::current_player;
+    use crate::database::{self, DieselPool};
+    use crate::domain::{Registration, RegistrationStatus};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let player = current_player().await?;
+
+    let registration: Option<Registration> = database::find_registration(play_event_id, player.id, &pool)?
+        .map(Into::into);
+
+    if !registration.is_some_and(|registration| registration.status != RegistrationStatus::Withdrawn) {
+        return Ok(None);
+    }
+
+    Ok(Some(EventRentals {
+        reservations: database::get_rental_loans_for_player_event(player.id, play_event_id, &pool)?
+            .into_iter()
+            .filter(|loan| loan.state() != LoanState::Returned)
+            .collect(),
+        available: database::get_available_rental_kinds(play_event_id, &pool)?,
+    }))
+}
+
+#[server]
+async fn reserve_rental(play_event_id: i64, kind: String) -> Result<(), AppError> {
+    use crate::auth::current_player;
+    use crate::database::{self, DieselPool};
+    use crate::domain::{Registration, RegistrationStatus};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let player = current_player().await?;
+
+    let registration: Registration = database::find_registration(play_event_id, player.id, &pool)?
+        .ok_or(DatabaseError::NotRegistered)?
+        .into();
+
+    if registration.status == RegistrationStatus::Withdrawn {
+        return Err(AppError::Database(DatabaseError::NotRegistered));
+    }
+
+    let kind = kind
+        .parse()
+        .map_err(|_| AppError::InvalidInput("Art".to_string()))?;
+
+    database::reserve_rental(kind, player.id, play_event_id, &pool)?;
+
+    Ok(())
+}
+
+#[server]
+async fn cancel_own_rental_reservation(id: i64) -> Result<(), AppError> {
+    use crate::auth::{current_player, AuthError};
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let player = current_player().await?;
+
+    let loan = database::find_rental_loan_for_id(id, &pool)?
+        .ok_or(DatabaseError::EntityNotFound)?;
+
+    if loan.player_id != player.id {
+        return Err(AppError::AuthError(AuthError::Forbidden));
+    }
+
+    Ok(database::cancel_rental_reservation(id, &pool)?)
+}
@@ -0,0 +1,311 @@
+use std::time::Duration;
+
+use leptos::prelude::*;
+use leptos::Params;
+use leptos_router::{hooks::use_params, params::Params};
+
+use crate::app::AppError;
+use crate::components::scoring::{AdjustRoundScore, CreateGameRound};
+use crate::components::util::BackButton;
+use crate::domain::{FactionStanding, Scoreboard};
+
+const RECONNECT_DELAY: Duration = Duration::from_secs(3);
+
+#[component]
+pub fn ScoreboardScreen() -> impl IntoView {
+    use crate::components::protected::Protected;
+
+    view! {
+        <Protected>
+            <ScoreboardPage/>
+        </Protected>
+    }
+}
+
+/// Read-only scoreboard for a big screen, updated live from the event's stream.
+#[component]
+fn ScoreboardPage() -> impl IntoView {
+
+    let play_event_id = use_params::<ScoreboardPlayEventIdParameter>()
+        .get_untracked()
+        .ok()
+        .and_then(|params| params.id)
+        .unwrap_or_default();
+
+    let (scoreboard, connected) = use_scoreboard_stream(play_event_id);
+
+    let enter_fullscreen = move |_| {
+        if let Some(element) = document().document_element() {
+            let _ = element.request_fullscreen();
+        }
+    };
+
+    view! {
+        <div class="min-h-screen bg-gray-900 text-white p-8 flex flex-col gap-8">
+            <div class="flex items-center justify-between">
+                <h1 class="text-4xl font-bold">
+                    { move || scoreboard.get().map(|scoreboard| scoreboard.play_event_name) }
+                </h1>
+                <div class="flex items-center gap-4">
+                    <Show when=move || !connected.get()>
+                        <span class="px-3 py-1 rounded bg-red-700 text-sm">"Verbindung getrennt, verbinde neu..."</span>
+                    </Show>
+                    <button
+                        class="px-4 py-2 rounded bg-gray-700 hover:bg-gray-600 text-sm"
+                        on:click=enter_fullscreen
+                    >
+                        "Vollbild"
+                    </button>
+                </div>
+            </div>
+
+            {move || match scoreboard.get() {
+                Some(scoreboard) => view! { <ScoreboardView scoreboard=scoreboard/> }.into_any(),
+                None => view! { <p class="text-2xl text-gray-400">"Lade Daten..."</p> }.into_any(),
+            }}
+        </div>
+    }
+}
+
+#[component]
+fn ScoreboardView(scoreboard: Scoreboard) -> impl IntoView {
+
+    let round_title = scoreboard.current_round
+        .as_ref()
+        .map(|round| format!("Runde {}: {}", round.number, round.name))
+        .unwrap_or("Noch keine Runde".to_string());
+
+    view! {
+        <h2 class="text-3xl text-gray-300">{ round_title }</h2>
+
+        <div class="grid grid-cols-2 gap-8 flex-1">
+            {
+                scoreboard.standings
+                    .into_iter()
+                    .map(|standing: FactionStanding| {
+                        let round_points = standing.faction.id
+                            .zip(scoreboard.current_round.as_ref())
+                            .and_then(|(faction_id, round)| round.points(faction_id))
+                            .unwrap_or(0);
+
+                        view! {
+                            <div class="rounded-lg bg-gray-800 p-8 flex flex-col items-center justify-center gap-4">
+                                <h3 class="text-5xl font-semibold">{ standing.faction.name }</h3>
+                                <span class="text-9xl font-bold tabular-nums">{ round_points }</span>
+                                <span class="text-2xl text-gray-400">
+                                    { format!("{} Siege · {} Punkte gesamt", standing.record.wins, standing.points) }
+                                </span>
+                            </div>
+                        }
+                    })
+                    .collect_view()
+            }
+        </div>
+    }
+}
+
+/// Phone friendly controls for referees to count the current round's points.
+#[component]
+pub fn RefereeControl() -> impl IntoView {
+
+    let play_event_id = use_params::<ScoreboardPlayEventIdParameter>();
+
+    let adjust_score = ServerAction::<AdjustRoundScore>::new();
+    let create_round = ServerAction::<CreateGameRound>::new();
+
+    let scoreboard = Resource::new(
+        move || (play_event_id.read().clone(), adjust_score.version().get(), create_round.version().get()),
+        move |(params_result, ..)| get_scoreboard(params_result.unwrap().id.unwrap()),
+    );
+
+    let action_error = move || {
+        let adjust_error = adjust_score.value().get().and_then(|result| result.err());
+        let create_error = create_round.value().get().and_then(|result| result.err());
+
+        adjust_error.or(create_error).map(|e| view! {
+            <p class="text-red-600">{ e.to_string() }</p>
+        })
+    };
+
+    view! {
+        <BackButton/>
+        <div class="p-4 max-w-xl mx-auto">
+            <Transition fallback=move || view! { <p>"Lade Daten..."</p> }>
+                {move || {
+                    scoreboard.get().map(|result| match result {
+                        Ok(scoreboard) => {
+                            let play_event_id = scoreboard.play_event_id;
+                            let next_round = scoreboard.rounds_played + 1;
+                            let round_id = scoreboard.current_round.as_ref().map(|round| round.id);
+
+                            view! {
+                                <div class="flex items-center justify-between mb-4">
+                                    <h1 class="text-2xl font-semibold">
+                                        { scoreboard.play_event_name.clone() }
+                                    </h1>
+                                    <a href=format!("/scoreboard/{}", play_event_id)
+                                        target="_blank"
+                                        class="bg-gray-200 hover:bg-gray-300 text-gray-700 font-medium py-2 px-4 rounded transition"
+                                    >
+                                        "Anzeigetafel"
+                                    </a>
+                                </div>
+
+                                <h2 class="text-left text-xl font-semibold py-2">
+                                    {
+                                        scoreboard.current_round
+                                            .as_ref()
+                                            .map(|round| format!("Runde {}: {}", round.number, round.name))
+                                            .unwrap_or("Noch keine Runde".to_string())
+                                    }
+                                </h2>
+
+                                {round_id.map(|round_id| {
+                                    scoreboard.standings
+                                        .iter()
+                                        .filter_map(|standing| {
+                                            let faction_id = standing.faction.id?;
+                                            let points = scoreboard.current_round
+                                                .as_ref()
+                                                .and_then(|round| round.points(faction_id))
+                                                .unwrap_or(0);
+
+                                            let adjust = move |delta: i32| {
+                                                adjust_score.dispatch(AdjustRoundScore { round_id, faction_id, delta });
+                                            };
+
+                                            Some(view! {
+                                                <div class="flex items-center justify-between gap-2 py-3 border-b">
+                                                    <span class="text-lg font-semibold flex-1 text-left">{ standing.faction.name.clone() }</span>
+                                                    <button
+                                                        class="w-14 h-14 rounded bg-gray-200 hover:bg-gray-300 text-2xl"
+                                                        on:click=move |_| adjust(-1)
+                                                    >
+                                                        "−"
+                                                    </button>
+                                                    <span class="w-16 text-3xl font-bold tabular-nums">{ points }</span>
+                                                    <button
+                                                        class="w-14 h-14 rounded bg-blue-600 hover:bg-blue-700 text-white text-2xl"
+                                                        on:click=move |_| adjust(1)
+                                                    >
+                                                        "+1"
+                                                    </button>
+                                                    <button
+                                                        class="w-14 h-14 rounded bg-blue-600 hover:bg-blue-700 text-white text-2xl"
+                                                        on:click=move |_| adjust(5)
+                                                    >
+                                                        "+5"
+                                                    </button>
+                                                </div>
+                                            })
+                                        })
+                                        .collect_view()
+                                })}
+
+                                <div class="flex justify-end pt-4">
+                                    <button
+                                        class="px-4 py-3 rounded bg-blue-600 hover:bg-blue-700 text-white"
+                                        on:click=move |_| {
+                                            create_round.dispatch(CreateGameRound {
+                                                play_event_id,
+                                                name: format!("Runde {}", next_round),
+                                                game_mode_id: String::new(),
+                                            });
+                                        }
+                                    >
+                                        "Neue Runde starten"
+                                    </button>
+                                </div>
+                            }.into_any()
+                        },
+                        Err(e) => view! { <p>{ e.to_string() }</p> }.into_any(),
+                    })
+                }}
+            </Transition>
+
+            { action_error }
+        </div>
+    }
+}
+
+/// Follows the event's scoreboard stream. The browser retries a dropped stream on its own,
+/// a stream it gave up on (e.g. while the server restarted) is opened again after a delay.
+fn use_scoreboard_stream(play_event_id: i64) -> (ReadSignal<Option<Scoreboard>>, ReadSignal<bool>) {
+    use leptos::wasm_bindgen::{closure::Closure, JsCast};
+    use leptos::web_sys::{Event, EventSource, MessageEvent};
+
+    let (scoreboard, set_scoreboard) = signal(None::<Scoreboard>);
+    let (connected, set_connected) = signal(true);
+    let attempt = RwSignal::new(0_u32);
+    let connection = StoredValue::new_local(None::<(EventSource, Vec<Closure<dyn Fn(Event)>>)>);
+
+    let close = move || connection.update_value(|connection| {
+        if let Some((source, _)) = connection.take() {
+            source.close();
+        }
+    });
+
+    Effect::new(move |_| {
+        attempt.track();
+        close();
+
+        let Ok(source) = EventSource::new(&format!("/scoreboard/events/{}/stream", play_event_id)) else {
+            set_connected.set(false);
+            return;
+        };
+
+        let on_open = Closure::<dyn Fn(Event)>::new(move |_: Event| set_connected.set(true));
+        let on_message = Closure::<dyn Fn(Event)>::new(move |event: Event| {
+            let update = event
+                .unchecked_into::<MessageEvent>()
+                .data()
+                .as_string()
+                .and_then(|data| serde_json::from_str::<Scoreboard>(&data).ok());
+
+            if let Some(update) = update {
+                set_scoreboard.set(Some(update));
+            }
+        });
+        let on_error = Closure::<dyn Fn(Event)>::new(move |event: Event| {
+            set_connected.set(false);
+
+            let closed = event
+                .target()
+                .is_some_and(|target| target.unchecked_into::<EventSource>().ready_state() == EventSource::CLOSED);
+
+            if closed {
+                set_timeout(move || attempt.update(|attempt| *attempt += 1), RECONNECT_DELAY);
+            }
+        });
+
+        source.set_onopen(Some(on_open.as_ref().unchecked_ref()));
+        source.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+        source.set_onerror(Some(on_error.as_ref().unchecked_ref()));
+
+        connection.set_value(Some((source, vec![on_open, on_message, on_error])));
+    });
+
+    on_cleanup(close);
+
+    (scoreboard, connected)
+}
+
+#[derive(Params, PartialEq, Clone)]
+struct ScoreboardPlayEventIdParameter {
+    id: Option<i64>,
+}
+
+#[server]
+async fn get_scoreboard(play_event_id: i64) -> Result<Scoreboard, AppError> {
+    use crate::auth::current_player;
+    use crate::database::{DatabaseError, DieselPool};
+    use crate::scoreboard::load_scoreboard;
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    current_player().await?;
+
+    load_scoreboard(play_event_id, &pool)?
+        .ok_or(DatabaseError::EntityNotFound.into())
+}
@@ -0,0 +1,958 @@
+use leptos::prelude::*;
+use leptos_router::components::A;
+use serde::{Deserialize, Serialize};
+
+use crate::app::AppError;
+use crate::components::auth::get_current_player;
+use crate::components::util::BackButton;
+use crate::domain::{
+    EventResults, Faction, FactionScore, FactionStanding, GameMode, GameRound, RoundObjective, RoundOutcome,
+    SeasonRecord, SeasonStandings,
+};
+
+#[component]
+pub fn Standings() -> impl IntoView {
+    use crate::components::protected::Protected;
+    use leptos_router::components::Outlet;
+
+    view! {
+        <Protected>
+            <Outlet/>
+        </Protected>
+    }
+}
+
+#[component]
+pub fn GameResults(play_event_id: i64) -> impl IntoView {
+
+    let create_round = ServerAction::<CreateGameRound>::new();
+    let delete_round = ServerAction::<DeleteGameRound>::new();
+    let save_result = ServerAction::<SaveRoundResult>::new();
+    let add_objective = ServerAction::<AddRoundObjective>::new();
+    let set_objective = ServerAction::<SetRoundObjectiveFaction>::new();
+    let delete_objective = ServerAction::<DeleteRoundObjective>::new();
+
+    let results = Resource::new(
+        move || (
+            create_round.version().get(),
+            delete_round.version().get(),
+            save_result.version().get(),
+            add_objective.version().get(),
+            set_objective.version().get(),
+            delete_objective.version().get(),
+        ),
+        move |_| get_event_results(play_event_id),
+    );
+
+    let action_error = move || {
+        let errors = [
+            create_round.value().get().and_then(|result| result.err()),
+            delete_round.value().get().and_then(|result| result.err()),
+            save_result.value().get().and_then(|result| result.err()),
+            add_objective.value().get().and_then(|result| result.err()),
+            set_objective.value().get().and_then(|result| result.err()),
+            delete_objective.value().get().and_then(|result| result.err()),
+        ];
+
+        errors.into_iter().flatten().next().map(|e| view! {
+            <p class="text-red-600">{ e.to_string() }</p>
+        })
+    };
+
+    view! {
+        <h2 class="text-left text-xl font-semibold py-2">Ergebnisse</h2>
+
+        <Suspense fallback=move || view! { <p>"Lade Daten..."</p> }>
+            {move || {
+                results.get().map(|result| match result {
+                    Ok(EventResults { standings, rounds, game_modes, editable }) => {
+                        let factions: Vec<Faction> = standings.iter().map(|standing| standing.faction.clone()).collect();
+
+                        view! {
+                            {
+                                if rounds.is_empty() {
+                                    view! { <p class="text-left text-gray-700">"Noch keine Runden gespielt"</p> }.into_any()
+                                } else {
+                                    view! { <FactionStandingsTable standings=standings/> }.into_any()
+                                }
+                            }
+
+                            {
+                                rounds.into_iter().map(|round| view! {
+                                    <GameRoundView
+                                        round=round
+                                        factions=factions.clone()
+                                        editable=editable
+                                        delete_round=delete_round
+                                        save_result=save_result
+                                        add_objective=add_objective
+                                        set_objective=set_objective
+                                        delete_objective=delete_objective
+                                    />
+                                }).collect_view()
+                            }
+
+                            {editable.then(|| view! {
+                                <ActionForm action=create_round attr:class="grid grid-cols-[auto_1fr] items-center gap-4 pt-4">
+                                    <input type="hidden" name="play_event_id" value=play_event_id/>
+
+                                    <label for="game_mode_id" class="text-left text-gray-700">
+                                        "Spielmodus:"
+                                    </label>
+                                    <select
+                                        name="game_mode_id"
+                                        class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+                                    >
+                                        <option value="">"Kein Spielmodus"</option>
+                                        {
+                                            game_modes
+                                                .into_iter()
+                                                .map(|game_mode: GameMode| view! {
+                                                    <option value=game_mode.id>{ game_mode.name }</option>
+                                                })
+                                                .collect_view()
+                                        }
+                                    </select>
+
+                                    <label for="name" class="text-left text-gray-700">
+                                        "Name:"
+                                    </label>
+                                    <input
+                                        type="text"
+                                        name="name"
+                                        placeholder="Leer lassen für den Namen des Spielmodus"
+                                        class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+                                    />
+
+                                    <div class="col-span-2 flex justify-end">
+                                        <button
+                                            type="submit"
+                                            class="px-4 py-2 rounded bg-blue-600 hover:bg-blue-700 text-white"
+                                        >
+                                            "Runde anlegen"
+                                        </button>
+                                    </div>
+                                </ActionForm>
+                            })}
+                        }.into_any()
+                    },
+                    Err(e) => view! { <p>{ e.to_string() }</p> }.into_any(),
+                })
+            }}
+        </Suspense>
+
+        { action_error }
+    }
+}
+
+#[component]
+fn FactionStandingsTable(standings: Vec<FactionStanding>) -> impl IntoView {
+
+    view! {
+        <div class="overflow-x-auto">
+            <table class="min-w-full border border-gray-200 shadow-sm rounded-md bg-white">
+                <thead class="bg-gray-100 text-gray-700">
+                    <tr>
+                        <th class="text-left py-2 px-4 border-b">Fraktion</th>
+                        <th class="text-left py-2 px-4 border-b">Siege</th>
+                        <th class="text-left py-2 px-4 border-b">Unentschieden</th>
+                        <th class="text-left py-2 px-4 border-b">Niederlagen</th>
+                        <th class="text-left py-2 px-4 border-b">Punkte</th>
+                    </tr>
+                </thead>
+                <tbody>
+                    {
+                        standings.into_iter().map(|standing: FactionStanding| view! {
+                            <tr class="hover:bg-gray-50">
+                                <th class="text-left py-2 px-4 border-b">{ standing.faction.name }</th>
+                                <th class="text-left py-2 px-4 border-b">{ standing.record.wins }</th>
+                                <th class="text-left py-2 px-4 border-b">{ standing.record.draws }</th>
+                                <th class="text-left py-2 px-4 border-b">{ standing.record.losses }</th>
+                                <th class="text-left py-2 px-4 border-b">{ standing.points }</th>
+                            </tr>
+                        }).collect_view()
+                    }
+                </tbody>
+            </table>
+        </div>
+    }
+}
+
+#[component]
+fn GameRoundView(
+    round: GameRound,
+    factions: Vec<Faction>,
+    editable: bool,
+    delete_round: ServerAction<DeleteGameRound>,
+    save_result: ServerAction<SaveRoundResult>,
+    add_objective: ServerAction<AddRoundObjective>,
+    set_objective: ServerAction<SetRoundObjectiveFaction>,
+    delete_objective: ServerAction<DeleteRoundObjective>,
+) -> impl IntoView {
+
+    let round_id = round.id;
+    let faction_name = {
+        let factions = factions.clone();
+        move |faction_id: i64| factions
+            .iter()
+            .find(|faction| faction.id == Some(faction_id))
+            .map(|faction| faction.name.clone())
+            .unwrap_or_default()
+    };
+
+    let outcome = match round.outcome() {
+        RoundOutcome::Open => "Offen".to_string(),
+        RoundOutcome::Draw => "Unentschieden".to_string(),
+        RoundOutcome::Won(faction_id) => format!("Sieg {}", faction_name(faction_id)),
+    };
+
+    let title = match &round.game_mode_name {
+        Some(game_mode_name) if *game_mode_name != round.name => format!("Runde {}: {} ({})", round.number, round.name, game_mode_name),
+        _ => format!("Runde {}: {}", round.number, round.name),
+    };
+
+    let scores = RwSignal::new(
+        factions
+            .iter()
+            .flat_map(|faction| faction.id)
+            .map(|faction_id| FactionScore { faction_id, points: round.points(faction_id).unwrap_or(0) })
+            .collect::<Vec<_>>(),
+    );
+    let notes = RwSignal::new(round.notes.clone());
+
+    let submit = move |_| {
+        save_result.dispatch(SaveRoundResult {
+            round_id,
+            scores: scores.get_untracked(),
+            notes: notes.get_untracked(),
+        });
+    };
+
+    view! {
+        <div class="border border-gray-200 rounded p-4 bg-white text-left mt-4">
+            <div class="flex items-center justify-between">
+                <h3 class="text-lg font-semibold">{ title }</h3>
+                <div class="flex items-center gap-4">
+                    <span class="text-gray-700">{ outcome }</span>
+                    {editable.then(|| view! {
+                        <ActionForm action=delete_round>
+                            <input type="hidden" name="round_id" value=round_id/>
+                            <button
+                                type="submit"
+                                class="px-4 py-2 rounded bg-gray-200 hover:bg-gray-300 text-gray-700"
+                            >
+                                "Löschen"
+                            </button>
+                        </ActionForm>
+                    })}
+                </div>
+            </div>
+
+            {
+                if editable {
+                    view! {
+                        <div class="grid grid-cols-[auto_1fr] items-center gap-4 pt-2">
+                            {
+                                factions.iter().filter_map(|faction| {
+                                    let faction_id = faction.id?;
+                                    let points = round.points(faction_id).unwrap_or(0);
+
+                                    Some(view! {
+                                        <label class="text-gray-700">{ faction.name.clone() } ":"</label>
+                                        <input
+                                            type="number"
+                                            min="0"
+                                            class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+                                            value=points
+                                            on:input=move |ev| {
+                                                let points = event_target_value(&ev).parse::<i32>().unwrap_or(0);
+
+                                                scores.update(|scores| {
+                                                    if let Some(score) = scores.iter_mut().find(|score| score.faction_id == faction_id) {
+                                                        score.points = points;
+                                                    }
+                                                });
+                                            }
+                                        />
+                                    })
+                                }).collect_view()
+                            }
+
+                            <label class="text-gray-700">"Notizen:"</label>
+                            <textarea
+                                rows="2"
+                                class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+                                on:input=move |ev| notes.set(event_target_value(&ev))
+                            >
+                                { round.notes.clone() }
+                            </textarea>
+
+                            <div class="col-span-2 flex justify-end">
+                                <button
+                                    type="button"
+                                    class="px-4 py-2 rounded bg-blue-600 hover:bg-blue-700 text-white"
+                                    on:click=submit
+                                >
+                                    "Ergebnis speichern"
+                                </button>
+                            </div>
+                        </div>
+                    }.into_any()
+                } else {
+                    view! {
+                        <ul class="pt-2">
+                            {
+                                round.scores.iter().map(|score| view! {
+                                    <li>{ faction_name(score.faction_id) } ": " { score.points } " Punkte"</li>
+                                }).collect_view()
+                            }
+                        </ul>
+                        <p class="text-gray-700 whitespace-pre-line">{ round.notes.clone() }</p>
+                    }.into_any()
+                }
+            }
+
+            {(editable || !round.objectives.is_empty()).then(|| view! {
+                <h4 class="font-semibold pt-2">"Ziele"</h4>
+            })}
+            <ul>
+                {
+                    round.objectives.into_iter().map(|objective: RoundObjective| {
+                        let objective_id = objective.id;
+                        let taken_by = objective.faction_id.map(&faction_name);
+
+                        if editable {
+                            view! {
+                                <li class="flex items-center gap-4 py-1">
+                                    <span class="flex-1">{ objective.name }</span>
+                                    <select
+                                        class="border border-gray-300 rounded px-3 py-1 focus:outline-none focus:ring focus:border-blue-300"
+                                        on:change=move |ev| {
+                                            set_objective.dispatch(SetRoundObjectiveFaction {
+                                                objective_id,
+                                                faction_id: event_target_value(&ev).parse().ok(),
+                                            });
+                                        }
+                                    >
+                                        <option value="" selected=objective.faction_id.is_none()>"Nicht eingenommen"</option>
+                                        {
+                                            factions.iter().map(|faction| view! {
+                                                <option value=faction.id selected=faction.id.is_some() && faction.id == objective.faction_id>
+                                                    { faction.name.clone() }
+                                                </option>
+                                            }).collect_view()
+                                        }
+                                    </select>
+                                    <ActionForm action=delete_objective>
+                                        <input type="hidden" name="objective_id" value=objective_id/>
+                                        <button
+                                            type="submit"
+                                            class="px-2 py-1 rounded bg-gray-200 hover:bg-gray-300 text-gray-700"
+                                        >
+                                            "Entfernen"
+                                        </button>
+                                    </ActionForm>
+                                </li>
+                            }.into_any()
+                        } else {
+                            view! {
+                                <li>
+                                    { objective.name } ": " { taken_by.unwrap_or("Nicht eingenommen".to_string()) }
+                                </li>
+                            }.into_any()
+                        }
+                    }).collect_view()
+                }
+            </ul>
+
+            {editable.then(|| view! {
+                <ActionForm action=add_objective attr:class="flex items-center gap-4 pt-2">
+                    <input type="hidden" name="round_id" value=round_id/>
+                    <input
+                        type="text"
+                        name="name"
+                        placeholder="Neues Ziel"
+                        class="flex-1 border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+                        required
+                    />
+                    <button
+                        type="submit"
+                        class="px-4 py-2 rounded bg-blue-600 hover:bg-blue-700 text-white"
+                    >
+                        "Ziel hinzufügen"
+                    </button>
+                </ActionForm>
+            })}
+        </div>
+    }
+}
+
+#[component]
+pub fn SeasonStandingsPage() -> impl IntoView {
+
+    let season = RwSignal::new(None::<i32>);
+    let standings = Resource::new(move || season.get(), get_season_standings);
+    let current_player = Resource::new(|| {}, |_| get_current_player());
+
+    let is_admin = move || current_player.get().and_then(|result| result.ok()).is_some_and(|player| player.admin);
+
+    view! {
+        <BackButton/>
+        <div class="p-8 max-w-4xl mx-auto">
+            <div class="flex items-center justify-between mb-6">
+                <h1 class="text-2xl font-semibold">
+                    "Saisonwertung"
+                </h1>
+                <Transition>
+                    <Show when=is_admin>
+                        <A href="/standings/modes"
+                            attr:class="bg-blue-600 hover:bg-blue-700 text-white font-medium py-2 px-4 rounded transition"
+                        >
+                            "Spielmodi"
+                        </A>
+                    </Show>
+                </Transition>
+            </div>
+
+            <Suspense fallback=move || view! { <p>"Lade Daten..."</p> }>
+                {move || {
+                    standings.get().map(|result| match result {
+                        Ok(standings) => view! {
+                            <div class="flex items-center gap-4 mb-4">
+                                <label for="season" class="text-left text-gray-700">
+                                    "Saison:"
+                                </label>
+                                <select
+                                    name="season"
+                                    class="border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+                                    on:change=move |ev| season.set(event_target_value(&ev).parse().ok())
+                                >
+                                    {
+                                        standings.seasons
+                                            .iter()
+                                            .map(|year| view! {
+                                                <option value=*year selected=*year == standings.season>{ *year }</option>
+                                            })
+                                            .collect_view()
+                                    }
+                                </select>
+                            </div>
+
+                            <h2 class="text-left text-xl font-semibold py-2">Teams</h2>
+                            <SeasonRecordTable records=standings.teams/>
+
+                            <h2 class="text-left text-xl font-semibold py-2">Züge</h2>
+                            <SeasonRecordTable records=standings.platoons/>
+                        }.into_any(),
+                        Err(e) => view! { <p>{ e.to_string() }</p> }.into_any(),
+                    })
+                }}
+            </Suspense>
+        </div>
+    }
+}
+
+#[component]
+fn SeasonRecordTable(records: Vec<SeasonRecord>) -> impl IntoView {
+
+    view! {
+        <div class="overflow-x-auto">
+            <table class="min-w-full border border-gray-200 shadow-sm rounded-md bg-white">
+                <thead class="bg-gray-100 text-gray-700">
+                    <tr>
+                        <th class="text-left py-2 px-4 border-b">Name</th>
+                        <th class="text-left py-2 px-4 border-b">Runden</th>
+                        <th class="text-left py-2 px-4 border-b">Siege</th>
+                        <th class="text-left py-2 px-4 border-b">Unentschieden</th>
+                        <th class="text-left py-2 px-4 border-b">Niederlagen</th>
+                        <th class="text-left py-2 px-4 border-b">Siegquote</th>
+                    </tr>
+                </thead>
+                <tbody>
+                    {
+                        records.into_iter().map(|record: SeasonRecord| view! {
+                            <tr class="hover:bg-gray-50">
+                                <th class="text-left py-2 px-4 border-b">{ record.name }</th>
+                                <th class="text-left py-2 px-4 border-b">{ record.record.rounds() }</th>
+                                <th class="text-left py-2 px-4 border-b">{ record.record.wins }</th>
+                                <th class="text-left py-2 px-4 border-b">{ record.record.draws }</th>
+                                <th class="text-left py-2 px-4 border-b">{ record.record.losses }</th>
+                                <th class="text-left py-2 px-4 border-b">
+                                    { record.record.win_rate().map(|rate| format!("{} %", rate)).unwrap_or("-".to_string()) }
+                                </th>
+                            </tr>
+                        }).collect_view()
+                    }
+                </tbody>
+            </table>
+        </div>
+    }
+}
+
+#[component]
+pub fn GameModesPage() -> impl IntoView {
+
+    let create_game_mode = ServerAction::<CreateGameMode>::new();
+    let delete_game_mode = ServerAction::<DeleteGameMode>::new();
+
+    let game_modes = Resource::new(
+        move || (create_game_mode.version().get(), delete_game_mode.version().get()),
+        |_| get_game_modes(),
+    );
+
+    let action_error = move || {
+        let create_error = create_game_mode.value().get().and_then(|result| result.err());
+        let delete_error = delete_game_mode.value().get().and_then(|result| result.err());
+
+        create_error.or(delete_error).map(|e| view! {
+            <p class="text-red-600">{ e.to_string() }</p>
+        })
+    };
+
+    view! {
+        <BackButton/>
+        <div class="p-8 max-w-4xl mx-auto">
+            <div class="flex items-center justify-between mb-6">
+                <h1 class="text-2xl font-semibold">
+                    "Spielmodi"
+                </h1>
+            </div>
+
+            <Suspense fallback=move || view! { <p>"Lade Daten..."</p> }>
+                {move || {
+                    game_modes.get().map(|result| match result {
+                        Ok(game_modes) => view! {
+                            <div class="overflow-x-auto">
+                                <table class="min-w-full border border-gray-200 shadow-sm rounded-md bg-white">
+                                    <thead class="bg-gray-100 text-gray-700">
+                                        <tr>
+                                            <th class="text-left py-2 px-4 border-b">Name</th>
+                                            <th class="text-left py-2 px-4 border-b">Beschreibung</th>
+                                            <th class="text-left py-2 px-4 border-b">Ziele</th>
+                                            <th class="py-2 px-4 border-b"></th>
+                                        </tr>
+                                    </thead>
+                                    <tbody>
+                                        {
+                                            game_modes.into_iter().map(|game_mode: GameMode| view! {
+                                                <tr class="hover:bg-gray-50">
+                                                    <th class="text-left py-2 px-4 border-b">{ game_mode.name.clone() }</th>
+                                                    <th class="text-left py-2 px-4 border-b font-normal whitespace-pre-line">{ game_mode.description.clone() }</th>
+                                                    <th class="text-left py-2 px-4 border-b font-normal">{ game_mode.objective_names().join(", ") }</th>
+                                                    <th class="py-2 px-4 border-b">
+                                                        <ActionForm action=delete_game_mode>
+                                                            <input type="hidden" name="id" value=game_mode.id/>
+                                                            <button
+                                                                type="submit"
+                                                                class="px-4 py-2 rounded bg-gray-200 hover:bg-gray-300 text-gray-700"
+                                                            >
+                                                                "Löschen"
+                                                            </button>
+                                                        </ActionForm>
+                                                    </th>
+                                                </tr>
+                                            }).collect_view()
+                                        }
+                                    </tbody>
+                                </table>
+                            </div>
+                        }.into_any(),
+                        Err(e) => view! { <p>{ e.to_string() }</p> }.into_any(),
+                    })
+                }}
+            </Suspense>
+
+            { action_error }
+
+            <h2 class="text-left text-xl font-semibold py-2">Neuer Spielmodus</h2>
+            <ActionForm action=create_game_mode attr:class="grid grid-cols-[auto_1fr] items-center gap-4">
+                <label for="game_mode_form[name]" class="text-left text-gray-700">
+                    "Name:"
+                </label>
+                <input
+                    type="text"
+                    name="game_mode_form[name]"
+                    placeholder="Domination"
+                    class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+                    required
+                />
+
+                <label for="game_mode_form[description]" class="text-left text-gray-700">
+                    "Beschreibung:"
+                </label>
+                <textarea
+                    name="game_mode_form[description]"
+                    rows="3"
+                    class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+                ></textarea>
+
+                <label for="game_mode_form[objectives]" class="text-left text-gray-700">
+                    "Ziele (eins pro Zeile):"
+                </label>
+                <textarea
+                    name="game_mode_form[objectives]"
+                    rows="4"
+                    placeholder="Flagge A\nFlagge B"
+                    class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+                ></textarea>
+
+                <div class="col-span-2 flex justify-end">
+                    <button
+                        type="submit"
+                        class="px-4 py-2 rounded bg-blue-600 hover:bg-blue-700 text-white"
+                    >
+                        "Spielmodus anlegen"
+                    </button>
+                </div>
+            </ActionForm>
+        </div>
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct GameModeForm {
+    name: String,
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    objectives: String,
+}
+
+/// Loads the round and checks that the current player organizes its event.
+#[cfg(feature = "ssr")]
+async fn require_round_organizer(
+    round_id: i64,
+    pool: &crate::database::DieselPool,
+) -> Result<crate::database::models::GameRound, AppError> {
+    use crate::auth::require_organizer;
+    use crate::database::{self, DatabaseError};
+
+    let round = database::find_game_round_for_id(round_id, pool)?
+        .ok_or(DatabaseError::EntityNotFound)?;
+    require_organizer(round.play_event_id, pool).await?;
+
+    Ok(round)
+}
+
+/// Pushes the event's scoreboard to everyone watching it.
+#[cfg(feature = "ssr")]
+fn publish_scoreboard(play_event_id: i64, pool: &crate::database::DieselPool) -> Result<(), AppError> {
+    use crate::scoreboard::{load_scoreboard, ScoreboardHub};
+
+    if let Some(hub) = use_context::<ScoreboardHub>() {
+        if let Some(scoreboard) = load_scoreboard(play_event_id, pool)? {
+            hub.publish(scoreboard);
+        }
+    }
+
+    Ok(())
+}
+
+#[server]
+async fn get_event_results(play_event_id: i64) -> Result<EventResults, AppError> {
+    use crate::auth::current_player;
+    use crate::database::{self, DatabaseError, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let player = current_player().await?;
+    let play_event = database::find_play_event_for_id(play_event_id, &pool)?
+        .ok_or(DatabaseError::EntityNotFound)?;
+
+    let factions = database::get_factions_for_event(play_event_id, &pool)?
+        .into_iter()
+        .map(Into::into)
+        .collect();
+    let rounds = database::get_game_rounds_for_events(vec![play_event_id], &pool)?;
+    let game_modes = database::get_game_modes(&pool)?
+        .into_iter()
+        .map(Into::into)
+        .collect();
+
+    Ok(EventResults::new(factions, rounds, game_modes, play_event.organizer_id == Some(player.id)))
+}
+
+#[server]
+pub async fn create_game_round(play_event_id: i64, name: String, game_mode_id: String) -> Result<(), AppError> {
+    use crate::auth::require_organizer;
+    use crate::database::{self, DatabaseError, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    require_organizer(play_event_id, &pool).await?;
+
+    let game_mode: Option<GameMode> = if game_mode_id.is_empty() {
+        None
+    } else {
+        let game_mode_id = game_mode_id.parse::<i64>()
+            .map_err(|_| AppError::InvalidInput("Spielmodus".to_string()))?;
+
+        Some(database::find_game_mode_for_id(game_mode_id, &pool)?
+            .ok_or(DatabaseError::EntityNotFound)?
+            .into())
+    };
+
+    let name = match (name.trim(), &game_mode) {
+        ("", Some(game_mode)) => game_mode.name.clone(),
+        ("", None) => return Err(AppError::InvalidInput("Name".to_string())),
+        (name, _) => name.to_string(),
+    };
+
+    database::create_game_round(play_event_id, name, game_mode, &pool)?;
+
+    publish_scoreboard(play_event_id, &pool)
+}
+
+#[server]
+async fn delete_game_round(round_id: i64) -> Result<(), AppError> {
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let round = require_round_organizer(round_id, &pool).await?;
+
+    database::delete_game_round(round_id, &pool)?;
+
+    publish_scoreboard(round.play_event_id, &pool)
+}
+
+#[server]
+async fn save_round_result(round_id: i64, scores: Vec<FactionScore>, notes: String) -> Result<(), AppError> {
+    use crate::database::{self, DatabaseError, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let round = require_round_organizer(round_id, &pool).await?;
+
+    let faction_ids: Vec<i64> = database::get_factions_for_event(round.play_event_id, &pool)?
+        .into_iter()
+        .map(|faction| faction.id)
+        .collect();
+
+    if scores.iter().any(|score| !faction_ids.contains(&score.faction_id)) {
+        return Err(DatabaseError::EntityNotFound.into());
+    }
+
+    if scores.iter().any(|score| score.points < 0) {
+        return Err(AppError::InvalidInput("Punkte".to_string()));
+    }
+
+    database::save_round_result(round_id, scores, notes, &pool)?;
+
+    publish_scoreboard(round.play_event_id, &pool)
+}
+
+#[server]
+pub async fn adjust_round_score(round_id: i64, faction_id: i64, delta: i32) -> Result<(), AppError> {
+    use crate::database::{self, DatabaseError, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let round = require_round_organizer(round_id, &pool).await?;
+
+    let faction = database::find_faction_for_id(faction_id, &pool)?
+        .ok_or(DatabaseError::EntityNotFound)?;
+
+    if faction.play_event_id != round.play_event_id {
+        return Err(DatabaseError::EntityNotFound.into());
+    }
+
+    database::adjust_round_score(round_id, faction_id, delta, &pool)?;
+
+    publish_scoreboard(round.play_event_id, &pool)
+}
+
+#[server]
+async fn add_round_objective(round_id: i64, name: String) -> Result<(), AppError> {
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let round = require_round_organizer(round_id, &pool).await?;
+
+    if name.trim().is_empty() {
+        return Err(AppError::InvalidInput("Ziel".to_string()));
+    }
+
+    database::add_round_objective(round_id, name.trim().to_string(), &pool)?;
+
+    publish_scoreboard(round.play_event_id, &pool)
+}
+
+#[server]
+async fn set_round_objective_faction(objective_id: i64, faction_id: Option<i64>) -> Result<(), AppError> {
+    use crate::database::{self, DatabaseError, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let objective = database::find_round_objective_for_id(objective_id, &pool)?
+        .ok_or(DatabaseError::EntityNotFound)?;
+    let round = require_round_organizer(objective.round_id, &pool).await?;
+
+    if let Some(faction_id) = faction_id {
+        let faction = database::find_faction_for_id(faction_id, &pool)?
+            .ok_or(DatabaseError::EntityNotFound)?;
+
+        if faction.play_event_id != round.play_event_id {
+            return Err(DatabaseError::EntityNotFound.into());
+        }
+    }
+
+    database::set_round_objective_faction(objective_id, faction_id, &pool)?;
+
+    publish_scoreboard(round.play_event_id, &pool)
+}
+
+#[server]
+async fn delete_round_objective(objective_id: i64) -> Result<(), AppError> {
+    use crate::database::{self, DatabaseError, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let objective = database::find_round_objective_for_id(objective_id, &pool)?
+        .ok_or(DatabaseError::EntityNotFound)?;
+    let round = require_round_organizer(objective.round_id, &pool).await?;
+
+    database::delete_round_objective(objective_id, &pool)?;
+
+    publish_scoreboard(round.play_event_id, &pool)
+}
+
+/// Team and platoon records of the season, the latest season with rounds if none is given.
+#[server]
+async fn get_season_standings(season: Option<i32>) -> Result<SeasonStandings, AppError> {
+    use std::collections::HashMap;
+
+    use chrono::Datelike;
+
+    use crate::auth::current_player;
+    use crate::database::{self, DieselPool};
+    use crate::domain::season_records;
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    current_player().await?;
+
+    let play_events = database::get_play_events_with_rounds(&pool)?;
+
+    let mut seasons: Vec<i32> = play_events.iter().map(|play_event| play_event.starts_at.year()).collect();
+    seasons.sort_unstable_by(|a, b| b.cmp(a));
+    seasons.dedup();
+
+    let Some(season) = season.or(seasons.first().copied()) else {
+        return Ok(SeasonStandings::default());
+    };
+
+    let play_event_ids: Vec<i64> = play_events
+        .iter()
+        .filter(|play_event| play_event.starts_at.year() == season)
+        .map(|play_event| play_event.id)
+        .collect();
+
+    let rounds = database::get_game_rounds_for_events(play_event_ids.clone(), &pool)?;
+    let assignments = database::get_faction_assignments_for_events(play_event_ids.clone(), &pool)?;
+
+    // players are credited to the team they were in when the event took place, not their current one
+    let event_teams = database::get_team_memberships_for_events(play_event_ids, &pool)?;
+    let all_teams = database::get_all_teams(&pool)?;
+    let team_platoons: HashMap<i64, i64> = all_teams
+        .iter()
+        .filter_map(|team| team.platoon_id.map(|platoon_id| (team.id, platoon_id)))
+        .collect();
+    let direct_platoons = database::get_direct_platoon_memberships(&pool)?;
+
+    let team_of = |play_event_id: i64, player_id: i64| event_teams.get(&(play_event_id, player_id)).copied();
+    let platoon_of = |play_event_id: i64, player_id: i64| match team_of(play_event_id, player_id) {
+        Some(team_id) => team_platoons.get(&team_id).copied(),
+        None => direct_platoons.get(&player_id).copied(),
+    };
+
+    let team_records = season_records(&rounds, &assignments, team_of);
+    let platoon_records = season_records(&rounds, &assignments, platoon_of);
+
+    let mut teams: Vec<SeasonRecord> = all_teams
+        .into_iter()
+        .filter_map(|team| team_records.get(&team.id).map(|record| SeasonRecord {
+            id: team.id,
+            name: team.name,
+            record: *record,
+        }))
+        .collect();
+    let mut platoons: Vec<SeasonRecord> = database::get_platoons_for_name_filter(String::new(), &pool)?
+        .into_iter()
+        .filter_map(|platoon| platoon_records.get(&platoon.id).map(|record| SeasonRecord {
+            id: platoon.id,
+            name: platoon.name,
+            record: *record,
+        }))
+        .collect();
+
+    for records in [&mut teams, &mut platoons] {
+        records.sort_by(|a, b| b.record.wins.cmp(&a.record.wins).then(a.record.losses.cmp(&b.record.losses)));
+    }
+
+    Ok(SeasonStandings { season, seasons, teams, platoons })
+}
+
+#[server]
+async fn get_game_modes() -> Result<Vec<GameMode>, AppError> {
+    use crate::auth::current_player;
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    current_player().await?;
+
+    let game_modes = database::get_game_modes(&pool)?;
+
+    Ok(game_modes.into_iter().map(Into::into).collect())
+}
+
+#[server]
+async fn create_game_mode(game_mode_form: GameModeForm) -> Result<(), AppError> {
+    use crate::auth::require_admin;
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    require_admin().await?;
+
+    if game_mode_form.name.trim().is_empty() {
+        return Err(AppError::InvalidInput("Name".to_string()));
+    }
+
+    let game_mode = GameMode {
+        id: None,
+        name: game_mode_form.name.trim().to_string(),
+        description: game_mode_form.description,
+        objectives: game_mode_form.objectives,
+    };
+
+    database::create_game_mode(game_mode, &pool)?;
+
+    Ok(())
+}
+
+#[server]
+async fn delete_game_mode(id: i64) -> Result<(), AppError> {
+    use crate::auth::require_admin;
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    require_admin().await?;
+
+    Ok(database::delete_game_mode(id, &pool)?)
+}
@@ -0,0 +1,1016 @@
+use leptos::prelude::*;
+use leptos::server_fn::codec::Json;
+use leptos::Params;
+use leptos_router::{components::A, hooks::use_params, params::Params};
+use serde::{Deserialize, Serialize};
+
+use crate::app::AppError;
+use crate::components::util::{datetime_input_value, format_datetime, BackButton};
+use crate::domain::{
+    AudienceOption, QuestionResult, Survey, SurveyAnswer, SurveyDetails, SurveyQuestion, SurveyQuestionKind,
+    SurveyResults,
+};
+
+#[component]
+pub fn Surveys() -> impl IntoView {
+    use crate::components::protected::Protected;
+    use leptos_router::components::Outlet;
+
+    view! {
+        <Protected>
+            <Outlet/>
+        </Protected>
+    }
+}
+
+#[component]
+pub fn SurveysTable() -> impl IntoView {
+
+    let surveys = Resource::new(|| {}, |_| get_surveys());
+    let can_write = Resource::new(|| {}, |_| can_write_surveys());
+
+    view! {
+        <BackButton/>
+        <div class="p-8 max-w-4xl mx-auto">
+            <div class="flex items-center justify-between mb-6">
+                <h1 class="text-2xl font-semibold">
+                    "Umfragen"
+                </h1>
+                <Transition>
+                    <Show when=move || can_write.get().and_then(|result| result.ok()).unwrap_or(false)>
+                        <A href="/surveys/new"
+                            attr:class="bg-blue-600 hover:bg-blue-700 text-white font-medium py-2 px-4 rounded transition"
+                        >
+                            "Neue Umfrage"
+                        </A>
+                    </Show>
+                </Transition>
+            </div>
+
+            <Suspense fallback=move || view! { <p>"Lade Daten..."</p> }>
+                {
+                    move || {
+                        surveys.get().map(|result| match result {
+                            Ok(surveys) if surveys.is_empty() => view! {
+                                <p class="text-gray-700">"Keine Umfragen"</p>
+                            }.into_any(),
+                            Ok(surveys) => {
+                                let now = chrono::Utc::now();
+
+                                view! {
+                                    <div class="overflow-x-auto">
+                                        <table class="min-w-full border border-gray-200 shadow-sm rounded-md bg-white">
+                                            <thead class="bg-gray-100 text-gray-700">
+                                                <tr>
+                                                    <th class="text-left py-2 px-4 border-b">Titel</th>
+                                                    <th class="text-left py-2 px-4 border-b">Zielgruppe</th>
+                                                    <th class="text-left py-2 px-4 border-b">Frist</th>
+                                                    <th class="text-left py-2 px-4 border-b">Status</th>
+                                                </tr>
+                                            </thead>
+                                            <tbody>
+                                                {
+                                                    surveys.into_iter().map(|survey: Survey| {
+                                                        let open = survey.open(now);
+
+                                                        view! {
+                                                            <tr class="hover:bg-gray-50">
+                                                                <th class="text-left py-2 px-4 border-b">
+                                                                    <A href=format!("/surveys/{}", survey.id.unwrap()) attr:class="hover:underline">{survey.title}</A>
+                                                                </th>
+                                                                <th class="text-left py-2 px-4 border-b">
+                                                                    { survey.audience.to_string() }
+                                                                </th>
+                                                                <th class="text-left py-2 px-4 border-b">
+                                                                    { survey.deadline.as_ref().map(format_datetime).unwrap_or("Keine Frist".to_string()) }
+                                                                </th>
+                                                                <th class="text-left py-2 px-4 border-b">
+                                                                    { if open { "Offen" } else { "Beendet" } }
+                                                                </th>
+                                                            </tr>
+                                                        }
+                                                    }).collect_view()
+                                                }
+                                            </tbody>
+                                        </table>
+                                    </div>
+                                }.into_any()
+                            },
+                            Err(e) => view! {
+                                <p>{ e.to_string() }</p>
+                            }.into_any(),
+                        })
+                    }
+                }
+            </Suspense>
+        </div>
+    }
+}
+
+#[component]
+pub fn SurveyNew() -> impl IntoView {
+
+    let create_new_survey = ServerAction::<CreateNewSurvey>::new();
+    let audiences = Resource::new(|| {}, |_| get_survey_audiences());
+    let field = |field: &str| format!("create_new_survey[{}]", field);
+
+    view! {
+        <div class="p-8 max-w-4xl mx-auto">
+            <div class="flex items-center justify-between mb-6">
+                <h1 class="text-2xl font-semibold">
+                    "Neue Umfrage anlegen"
+                </h1>
+            </div>
+
+            <ActionForm action=create_new_survey>
+                <div class="grid grid-cols-[auto_1fr] items-center gap-4">
+                    <label for=field("title") class="text-left text-gray-700">
+                        "Titel:"
+                    </label>
+                    <input
+                        type="text"
+                        name=field("title")
+                        class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+                        required
+                    />
+
+                    <label for=field("description") class="text-left text-gray-700">
+                        "Beschreibung:"
+                    </label>
+                    <textarea
+                        name=field("description")
+                        rows="4"
+                        class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+                    ></textarea>
+
+                    <label for=field("audience") class="text-left text-gray-700">
+                        "Zielgruppe:"
+                    </label>
+                    <Suspense fallback=move || view! { <p>"Lade Daten..."</p> }>
+                        {move || {
+                            audiences.get().map(|result| match result {
+                                Ok(options) => view! {
+                                    <select
+                                        name=field("audience")
+                                        class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+                                        required
+                                    >
+                                        {
+                                            options
+                                                .into_iter()
+                                                .map(|option: AudienceOption| view! {
+                                                    <option value=option.audience.form_value()>{ option.label }</option>
+                                                })
+                                                .collect_view()
+                                        }
+                                    </select>
+                                }.into_any(),
+                                Err(e) => view! { <p>{ e.to_string() }</p> }.into_any(),
+                            })
+                        }}
+                    </Suspense>
+
+                    <label for=field("anonymous") class="text-left text-gray-700">
+                        "Anonym:"
+                    </label>
+                    <input
+                        type="checkbox"
+                        name=field("anonymous")
+                        class="w-4 h-4 accent-green-600 border-2 border-gray-300 rounded"
+                        value="true"/>
+
+                    <label for=field("deadline") class="text-left text-gray-700">
+                        "Frist:"
+                    </label>
+                    <input
+                        type="datetime-local"
+                        name=field("deadline")
+                        class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+                    />
+                </div>
+
+                <div class="flex justify-end gap-2 mt-6">
+                    <A href="/surveys"
+                        attr:class="px-4 py-2 rounded bg-gray-200 hover:bg-gray-300 text-gray-700"
+                    >
+                        "Abbrechen"
+                    </A>
+                    <button
+                        type="submit"
+                        class="px-4 py-2 rounded bg-blue-600 hover:bg-blue-700 text-white"
+                    >
+                        "Erstellen"
+                    </button>
+                </div>
+            </ActionForm>
+
+            {move || create_new_survey.value().get().and_then(|result| result.err()).map(|e| view! {
+                <p class="text-red-600">{ e.to_string() }</p>
+            })}
+        </div>
+    }
+}
+
+#[component]
+pub fn SurveyEdit() -> impl IntoView {
+
+    let survey_id = use_params::<SurveyIdParameter>();
+
+    let add_question = ServerAction::<AddSurveyQuestion>::new();
+    let delete_question = ServerAction::<DeleteSurveyQuestion>::new();
+    let save_deadline = ServerAction::<SaveSurveyDeadline>::new();
+    let delete_survey = ServerAction::<DeleteSurvey>::new();
+
+    let details = Resource::new(
+        move || (
+            survey_id.read().clone(),
+            add_question.version().get(),
+            delete_question.version().get(),
+            save_deadline.version().get(),
+        ),
+        move |(params_result, ..)| get_survey_details(params_result.unwrap().id.unwrap()),
+    );
+
+    let action_error = move || {
+        let errors = [
+            add_question.value().get().and_then(|result| result.err()),
+            delete_question.value().get().and_then(|result| result.err()),
+            save_deadline.value().get().and_then(|result| result.err()),
+            delete_survey.value().get().and_then(|result| result.err()),
+        ];
+
+        errors
+            .into_iter()
+            .flatten()
+            .next()
+            .map(|e| view! { <p class="text-red-600">{ e.to_string() }</p> })
+    };
+
+    view! {
+        <BackButton/>
+        <div class="p-8 max-w-4xl mx-auto">
+            <Suspense fallback=move || view! { <p>"Lade Daten..."</p> }>
+                {
+                    move || {
+                        details.get().map(|result| match result {
+                            Ok(details) => {
+                                let survey_id = details.survey.id.unwrap();
+                                let has_answers = details.has_answers;
+
+                                view! {
+                                    <div class="flex items-center justify-between mb-6">
+                                        <h1 class="text-2xl font-semibold">
+                                            { details.survey.title.clone() }
+                                        </h1>
+                                        <div class="flex gap-2">
+                                            <A href=format!("/surveys/{}", survey_id)
+                                                attr:class="px-4 py-2 rounded bg-gray-200 hover:bg-gray-300 text-gray-700"
+                                            >
+                                                "Zur Umfrage"
+                                            </A>
+                                            <ActionForm action=delete_survey>
+                                                <input type="hidden" name="id" value=survey_id/>
+                                                <button
+                                                    type="submit"
+                                                    class="bg-red-600 hover:bg-red-700 text-white font-medium py-2 px-4 rounded transition"
+                                                >
+                                                    "Löschen"
+                                                </button>
+                                            </ActionForm>
+                                        </div>
+                                    </div>
+
+                                    <ActionForm action=save_deadline attr:class="flex items-center gap-4 mb-6">
+                                        <input type="hidden" name="survey_id" value=survey_id/>
+                                        <label for="deadline" class="text-left text-gray-700">
+                                            "Frist:"
+                                        </label>
+                                        <input
+                                            type="datetime-local"
+                                            name="deadline"
+                                            class="border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+                                            value=details.survey.deadline.as_ref().map(datetime_input_value)
+                                        />
+                                        <button
+                                            type="submit"
+                                            class="px-4 py-2 rounded bg-blue-600 hover:bg-blue-700 text-white"
+                                        >
+                                            "Speichern"
+                                        </button>
+                                    </ActionForm>
+
+                                    <h2 class="text-left text-xl font-semibold py-2">Fragen</h2>
+                                    <Show when=move || has_answers>
+                                        <p class="text-left text-gray-700 pb-2">
+                                            "Es liegen bereits Antworten vor, die Fragen können nicht mehr geändert werden."
+                                        </p>
+                                    </Show>
+                                    {
+                                        details.questions.into_iter().map(|question: SurveyQuestion| view! {
+                                            <div class="border border-gray-200 rounded p-4 bg-white text-left mb-4">
+                                                <div class="flex items-center justify-between">
+                                                    <h3 class="font-semibold">{ question.text }</h3>
+                                                    <Show when=move || !has_answers>
+                                                        <ActionForm action=delete_question>
+                                                            <input type="hidden" name="id" value=question.id/>
+                                                            <button
+                                                                type="submit"
+                                                                class="px-4 py-2 rounded bg-gray-200 hover:bg-gray-300 text-gray-700"
+                                                            >
+                                                                "Entfernen"
+                                                            </button>
+                                                        </ActionForm>
+                                                    </Show>
+                                                </div>
+                                                <p class="text-sm text-gray-500">{ question.kind.to_string() }</p>
+                                                <ul class="list-disc pl-6">
+                                                    {
+                                                        question.options
+                                                            .into_iter()
+                                                            .map(|option| view! { <li>{ option.text }</li> })
+                                                            .collect_view()
+                                                    }
+                                                </ul>
+                                            </div>
+                                        }).collect_view()
+                                    }
+
+                                    <Show when=move || !has_answers>
+                                        <SurveyQuestionForm survey_id=survey_id action=add_question/>
+                                    </Show>
+                                }.into_any()
+                            },
+                            Err(e) => view! {
+                                <p>{ e.to_string() }</p>
+                            }.into_any(),
+                        })
+                    }
+                }
+            </Suspense>
+
+            { action_error }
+        </div>
+    }
+}
+
+#[component]
+fn SurveyQuestionForm(survey_id: i64, action: ServerAction<AddSurveyQuestion>) -> impl IntoView {
+
+    let kind = RwSignal::new(SurveyQuestionKind::SingleChoice);
+    let kinds = [
+        SurveyQuestionKind::SingleChoice,
+        SurveyQuestionKind::MultipleChoice,
+        SurveyQuestionKind::FreeText,
+    ];
+
+    view! {
+        <h2 class="text-left text-xl font-semibold py-2">Frage hinzufügen</h2>
+        <ActionForm action=action>
+            <input type="hidden" name="survey_id" value=survey_id/>
+            <div class="grid grid-cols-[auto_1fr] items-center gap-4">
+                <label for="text" class="text-left text-gray-700">
+                    "Frage:"
+                </label>
+                <input
+                    type="text"
+                    name="text"
+                    class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+                    required
+                />
+
+                <label for="kind" class="text-left text-gray-700">
+                    "Art:"
+                </label>
+                <select
+                    name="kind"
+                    class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+                    on:change=move |ev| {
+                        if let Ok(selected) = event_target_value(&ev).parse() {
+                            kind.set(selected);
+                        }
+                    }
+                >
+                    {
+                        kinds
+                            .into_iter()
+                            .map(|option| view! {
+                                <option value=option.as_str()>{ option.to_string() }</option>
+                            })
+                            .collect_view()
+                    }
+                </select>
+
+                <Show when=move || kind.get().has_options()>
+                    <label for="options" class="text-left text-gray-700">
+                        "Antworten (eine pro Zeile):"
+                    </label>
+                    <textarea
+                        name="options"
+                        rows="4"
+                        class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+                    ></textarea>
+                </Show>
+            </div>
+
+            <div class="flex justify-end gap-2 mt-6">
+                <button
+                    type="submit"
+                    class="px-4 py-2 rounded bg-blue-600 hover:bg-blue-700 text-white"
+                >
+                    "Hinzufügen"
+                </button>
+            </div>
+        </ActionForm>
+    }
+}
+
+#[component]
+pub fn SurveyPage() -> impl IntoView {
+
+    let survey_id = use_params::<SurveyIdParameter>();
+    let submit_answers = ServerAction::<SubmitSurveyAnswers>::new();
+
+    let details = Resource::new(
+        move || (survey_id.read().clone(), submit_answers.version().get()),
+        move |(params_result, _)| get_survey_details(params_result.unwrap().id.unwrap()),
+    );
+    let results = Resource::new(
+        move || (survey_id.read().clone(), submit_answers.version().get()),
+        move |(params_result, _)| get_survey_results(params_result.unwrap().id.unwrap()),
+    );
+
+    view! {
+        <BackButton/>
+        <div class="p-8 max-w-4xl mx-auto">
+            <Suspense fallback=move || view! { <p>"Lade Daten..."</p> }>
+                {
+                    move || {
+                        details.get().map(|result| match result {
+                            Ok(details) => {
+                                let survey_id = details.survey.id.unwrap();
+                                let editable = details.editable;
+                                let results_visible = details.results_visible;
+
+                                view! {
+                                    <div class="flex items-center justify-between mb-2">
+                                        <h1 class="text-2xl font-semibold">
+                                            { details.survey.title.clone() }
+                                        </h1>
+                                        <Show when=move || editable>
+                                            <A href=format!("/surveys/{}/edit", survey_id)
+                                                attr:class="bg-blue-600 hover:bg-blue-700 text-white font-medium py-2 px-4 rounded transition"
+                                            >
+                                                "Bearbeiten"
+                                            </A>
+                                        </Show>
+                                    </div>
+                                    <p class="text-left text-sm text-gray-500 pb-2">
+                                        { details.survey.audience.to_string() }
+                                        " · "
+                                        { details.survey.deadline.as_ref().map(|deadline| format!("Frist: {}", format_datetime(deadline))).unwrap_or("Keine Frist".to_string()) }
+                                        { details.survey.anonymous.then_some(" · Anonym") }
+                                    </p>
+                                    <p class="text-left text-gray-700 pb-4 whitespace-pre-line">{ details.survey.description.clone() }</p>
+
+                                    {
+                                        if details.can_answer {
+                                            view! { <SurveyAnswerForm details=details action=submit_answers/> }.into_any()
+                                        } else {
+                                            view! {
+                                                <p class="text-left text-gray-700 pb-4">"Diese Umfrage nimmt keine Antworten an."</p>
+                                            }.into_any()
+                                        }
+                                    }
+
+                                    <Show when=move || results_visible>
+                                        <Suspense fallback=move || view! { <p>"Lade Daten..."</p> }>
+                                            {move || {
+                                                results.get().map(|result| match result {
+                                                    Ok(results) => view! { <SurveyResultsView results=results/> }.into_any(),
+                                                    Err(e) => view! { <p>{ e.to_string() }</p> }.into_any(),
+                                                })
+                                            }}
+                                        </Suspense>
+                                    </Show>
+                                }.into_any()
+                            },
+                            Err(e) => view! {
+                                <p>{ e.to_string() }</p>
+                            }.into_any(),
+                        })
+                    }
+                }
+            </Suspense>
+        </div>
+    }
+}
+
+#[component]
+fn SurveyAnswerForm(details: SurveyDetails, action: ServerAction<SubmitSurveyAnswers>) -> impl IntoView {
+
+    let survey_id = details.survey.id.unwrap();
+    let answered = !details.own_answers.is_empty();
+    let answers = RwSignal::new(details.own_answers);
+
+    let submit = move |_| {
+        action.dispatch(SubmitSurveyAnswers {
+            survey_id,
+            answers: answers.get_untracked(),
+        });
+    };
+
+    view! {
+        <div class="text-left">
+            <Show when=move || answered>
+                <p class="text-gray-700 pb-2">"Du hast bereits geantwortet und kannst deine Antworten bis zur Frist ändern."</p>
+            </Show>
+            {
+                details.questions.into_iter().map(|question: SurveyQuestion| {
+                    let question_id = question.id;
+                    let kind = question.kind;
+
+                    let input = match kind {
+                        SurveyQuestionKind::FreeText => {
+                            let value = answers
+                                .read_untracked()
+                                .iter()
+                                .find(|answer| answer.question_id == question_id)
+                                .and_then(|answer| answer.text.clone())
+                                .unwrap_or_default();
+
+                            view! {
+                                <textarea
+                                    rows="3"
+                                    class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+                                    on:input=move |ev| {
+                                        let text = event_target_value(&ev);
+
+                                        answers.update(|answers| {
+                                            answers.retain(|answer| answer.question_id != question_id);
+
+                                            if !text.trim().is_empty() {
+                                                answers.push(SurveyAnswer { question_id, option_id: None, text: Some(text) });
+                                            }
+                                        });
+                                    }
+                                >
+                                    { value }
+                                </textarea>
+                            }.into_any()
+                        },
+                        SurveyQuestionKind::SingleChoice | SurveyQuestionKind::MultipleChoice => {
+                            let multiple = kind == SurveyQuestionKind::MultipleChoice;
+
+                            question.options.into_iter().map(|option| {
+                                let option_id = option.id;
+                                let checked = move || answers
+                                    .read()
+                                    .iter()
+                                    .any(|answer| answer.option_id == Some(option_id));
+
+                                let toggle = move |_| answers.update(|answers| {
+                                    let selected = answers.iter().any(|answer| answer.option_id == Some(option_id));
+
+                                    if multiple && selected {
+                                        answers.retain(|answer| answer.option_id != Some(option_id));
+                                        return;
+                                    }
+
+                                    if !multiple {
+                                        answers.retain(|answer| answer.question_id != question_id);
+                                    }
+
+                                    answers.push(SurveyAnswer { question_id, option_id: Some(option_id), text: None });
+                                });
+
+                                view! {
+                                    <label class="flex items-center gap-2 py-1">
+                                        <input
+                                            type=if multiple { "checkbox" } else { "radio" }
+                                            name=format!("question-{}", question_id)
+                                            class="w-4 h-4 accent-green-600 border-2 border-gray-300 rounded"
+                                            prop:checked=checked
+                                            on:change=toggle
+                                        />
+                                        { option.text }
+                                    </label>
+                                }
+                            }).collect_view().into_any()
+                        },
+                    };
+
+                    view! {
+                        <div class="border border-gray-200 rounded p-4 bg-white mb-4">
+                            <h3 class="font-semibold">{ question.text }</h3>
+                            <p class="text-sm text-gray-500 pb-2">{ kind.to_string() }</p>
+                            { input }
+                        </div>
+                    }
+                }).collect_view()
+            }
+
+            <div class="flex justify-end gap-2 mb-6">
+                <button
+                    type="button"
+                    class="px-4 py-2 rounded bg-blue-600 hover:bg-blue-700 text-white"
+                    on:click=submit
+                >
+                    "Antworten speichern"
+                </button>
+            </div>
+
+            {move || action.value().get().map(|result| match result {
+                Ok(()) => view! { <p class="text-green-700">"Antworten gespeichert"</p> }.into_any(),
+                Err(e) => view! { <p class="text-red-600">{ e.to_string() }</p> }.into_any(),
+            })}
+        </div>
+    }
+}
+
+#[component]
+fn SurveyResultsView(results: SurveyResults) -> impl IntoView {
+
+    view! {
+        <h2 class="text-left text-xl font-semibold py-2">
+            "Ergebnisse"
+        </h2>
+        <p class="text-left text-gray-700 pb-4">
+            "Teilgenommen: " { results.respondents } " / " { results.audience_size }
+        </p>
+
+        {
+            results.questions.into_iter().map(|result: QuestionResult| view! {
+                <div class="border border-gray-200 rounded p-4 bg-white text-left mb-4">
+                    <h3 class="font-semibold">{ result.question.text }</h3>
+                    <p class="text-sm text-gray-500 pb-2">{ result.answered } " Antworten"</p>
+
+                    {
+                        result.options.into_iter().map(|option| view! {
+                            <div class="py-1">
+                                <div class="flex justify-between">
+                                    <span>{ option.option.text }</span>
+                                    <span class="text-gray-700">{ option.count } " (" { option.percent } " %)"</span>
+                                </div>
+                                <div class="w-full bg-gray-200 rounded h-3">
+                                    <div class="bg-blue-600 h-3 rounded" style=format!("width: {}%", option.percent)></div>
+                                </div>
+                                <Show when={
+                                    let empty = option.voters.is_empty();
+                                    move || !empty
+                                }>
+                                    <p class="text-sm text-gray-500">{ option.voters.join(", ") }</p>
+                                </Show>
+                            </div>
+                        }).collect_view()
+                    }
+
+                    <ul class="list-disc pl-6">
+                        {
+                            result.texts.into_iter().map(|answer| view! {
+                                <li class="whitespace-pre-line">
+                                    { answer.text }
+                                    { answer.tag_name.map(|tag_name| format!(" ({})", tag_name)) }
+                                </li>
+                            }).collect_view()
+                        }
+                    </ul>
+                </div>
+            }).collect_view()
+        }
+    }
+}
+
+#[derive(Params, PartialEq, Clone)]
+struct SurveyIdParameter {
+    id: Option<i64>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct SurveyForm {
+    title: String,
+    #[serde(default)]
+    description: String,
+    audience: String,
+    #[serde(default)]
+    anonymous: bool,
+    #[serde(default)]
+    deadline: String,
+}
+
+#[cfg(feature = "ssr")]
+impl SurveyForm {
+    fn into_survey(self, author_id: i64) -> Result<Survey, AppError> {
+        Ok(Survey {
+            id: None,
+            title: self.title,
+            description: self.description,
+            audience: self.audience
+                .parse()
+                .map_err(|_| AppError::InvalidInput("Zielgruppe".to_string()))?,
+            author_id: Some(author_id),
+            anonymous: self.anonymous,
+            deadline: parse_deadline(&self.deadline)?,
+        })
+    }
+}
+
+#[cfg(feature = "ssr")]
+fn parse_deadline(value: &str) -> Result<Option<chrono::DateTime<chrono::Utc>>, AppError> {
+    use crate::components::util::parse_datetime_input;
+
+    if value.is_empty() {
+        return Ok(None);
+    }
+
+    parse_datetime_input(value)
+        .map(Some)
+        .ok_or(AppError::InvalidInput("Frist".to_string()))
+}
+
+/// Surveys go to a team or platoon, so only those audiences of the announcement rules apply.
+#[cfg(feature = "ssr")]
+fn survey_audiences(
+    player: &crate::database::models::Player,
+    pool: &crate::database::DieselPool,
+) -> Result<Vec<AudienceOption>, AppError> {
+    use crate::components::announcement::allowed_audiences;
+    use crate::domain::AnnouncementAudience;
+
+    Ok(allowed_audiences(player, pool)?
+        .into_iter()
+        .filter(|option| matches!(option.audience, AnnouncementAudience::Platoon(_) | AnnouncementAudience::Team(_)))
+        .collect())
+}
+
+/// Loads the survey if the current player created it, is an admin or belongs to its audience.
+/// The flag tells whether the player may manage the survey.
+#[cfg(feature = "ssr")]
+async fn require_participant(
+    survey_id: i64,
+    pool: &crate::database::DieselPool,
+) -> Result<(crate::database::models::Player, Survey, bool), AppError> {
+    use crate::auth::{current_player, AuthError};
+    use crate::database::{self, DatabaseError};
+
+    let player = current_player().await?;
+    let survey: Survey = database::find_survey_for_id(survey_id, pool)?
+        .ok_or(DatabaseError::EntityNotFound)?
+        .into();
+
+    let manager = player.admin || survey.author_id == Some(player.id);
+    let member = database::get_player_ids_for_audience(survey.audience, pool)?.contains(&player.id);
+
+    if manager || member {
+        Ok((player, survey, manager))
+    } else {
+        Err(AppError::AuthError(AuthError::Forbidden))
+    }
+}
+
+#[cfg(feature = "ssr")]
+async fn require_manager(
+    survey_id: i64,
+    pool: &crate::database::DieselPool,
+) -> Result<Survey, AppError> {
+    use crate::auth::AuthError;
+
+    match require_participant(survey_id, pool).await? {
+        (_, survey, true) => Ok(survey),
+        _ => Err(AppError::AuthError(AuthError::Forbidden)),
+    }
+}
+
+#[server]
+pub async fn can_write_surveys() -> Result<bool, AppError> {
+    use crate::auth::current_player_if_logged_in;
+    use crate::database::DieselPool;
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    match current_player_if_logged_in().await? {
+        Some(player) => Ok(!survey_audiences(&player, &pool)?.is_empty()),
+        None => Ok(false),
+    }
+}
+
+#[server]
+async fn get_survey_audiences() -> Result<Vec<AudienceOption>, AppError> {
+    use crate::auth::current_player;
+    use crate::database::DieselPool;
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let player = current_player().await?;
+
+    survey_audiences(&player, &pool)
+}
+
+#[server]
+async fn get_surveys() -> Result<Vec<Survey>, AppError> {
+    use crate::auth::current_player;
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let player = current_player().await?;
+
+    let surveys = if player.admin {
+        database::get_all_surveys(&pool)?
+    } else {
+        database::get_surveys_for_player(player.id, &pool)?
+    };
+
+    Ok(surveys.into_iter().map(Into::into).collect())
+}
+
+#[server]
+async fn get_survey_details(id: i64) -> Result<SurveyDetails, AppError> {
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let (player, survey, manager) = require_participant(id, &pool).await?;
+    let member = database::get_player_ids_for_audience(survey.audience, &pool)?.contains(&player.id);
+
+    let questions = database::get_survey_questions(id, &pool)?;
+    let own_answers = database::get_survey_answers_for_player(id, player.id, &pool)?;
+    let open = survey.open(chrono::Utc::now());
+
+    Ok(SurveyDetails {
+        can_answer: member && open && !questions.is_empty(),
+        has_answers: database::survey_has_answers(id, &pool)?,
+        results_visible: manager || !open || !own_answers.is_empty(),
+        editable: manager,
+        survey,
+        questions,
+        own_answers,
+    })
+}
+
+#[server]
+async fn get_survey_results(id: i64) -> Result<SurveyResults, AppError> {
+    use crate::auth::AuthError;
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let (player, survey, manager) = require_participant(id, &pool).await?;
+
+    let open = survey.open(chrono::Utc::now());
+    let answered = !database::get_survey_answers_for_player(id, player.id, &pool)?.is_empty();
+
+    if !(manager || !open || answered) {
+        return Err(AppError::AuthError(AuthError::Forbidden));
+    }
+
+    let audience_size = database::get_player_ids_for_audience(survey.audience, &pool)?.len();
+    let questions = database::get_survey_questions(id, &pool)?;
+    let answers = database::get_survey_answers(id, &pool)?;
+
+    Ok(SurveyResults::new(&survey, questions, &answers, audience_size))
+}
+
+#[server(input = Json)]
+async fn submit_survey_answers(survey_id: i64, answers: Vec<SurveyAnswer>) -> Result<(), AppError> {
+    use crate::auth::AuthError;
+    use crate::database::{self, DieselPool};
+    use crate::domain::find_invalid_answer;
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let (player, survey, _) = require_participant(survey_id, &pool).await?;
+
+    if !database::get_player_ids_for_audience(survey.audience, &pool)?.contains(&player.id) {
+        return Err(AppError::AuthError(AuthError::Forbidden));
+    }
+
+    if !survey.open(chrono::Utc::now()) {
+        return Err(AppError::InvalidInput("Frist abgelaufen".to_string()));
+    }
+
+    let questions = database::get_survey_questions(survey_id, &pool)?;
+
+    match find_invalid_answer(&questions, &answers) {
+        Some(Ok(question)) => return Err(AppError::InvalidInput(question.text.clone())),
+        Some(Err(_)) => return Err(AppError::InvalidInput("Frage".to_string())),
+        None => (),
+    }
+
+    Ok(database::replace_survey_answers(survey_id, player.id, answers, &pool)?)
+}
+
+#[server]
+async fn create_new_survey(create_new_survey: SurveyForm) -> Result<(), AppError> {
+    use crate::auth::{current_player, AuthError};
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let author = current_player().await?;
+    let survey = create_new_survey.into_survey(author.id)?;
+
+    if !survey_audiences(&author, &pool)?.iter().any(|option| option.audience == survey.audience) {
+        return Err(AppError::AuthError(AuthError::Forbidden));
+    }
+
+    let id = database::create_survey(survey, &pool)?;
+
+    leptos_axum::redirect(&format!("/surveys/{}/edit", id));
+    Ok(())
+}
+
+#[server]
+async fn add_survey_question(
+    survey_id: i64,
+    text: String,
+    kind: String,
+    #[server(default)]
+    options: String,
+) -> Result<(), AppError> {
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    require_manager(survey_id, &pool).await?;
+
+    if database::survey_has_answers(survey_id, &pool)? {
+        return Err(AppError::InvalidInput("Umfrage hat bereits Antworten".to_string()));
+    }
+
+    let kind: SurveyQuestionKind = kind
+        .parse()
+        .map_err(|_| AppError::InvalidInput("Art".to_string()))?;
+
+    let options: Vec<String> = if kind.has_options() {
+        options
+            .lines()
+            .map(str::trim)
+            .filter(|option| !option.is_empty())
+            .map(str::to_string)
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    if kind.has_options() && options.len() < 2 {
+        return Err(AppError::InvalidInput("Antworten".to_string()));
+    }
+
+    database::add_survey_question(survey_id, text.trim(), kind, options, &pool)?;
+
+    Ok(())
+}
+
+#[server]
+async fn delete_survey_question(id: i64) -> Result<(), AppError> {
+    use crate::database::{self, DatabaseError, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    let survey_id = database::find_survey_id_for_question(id, &pool)?
+        .ok_or(DatabaseError::EntityNotFound)?;
+
+    require_manager(survey_id, &pool).await?;
+
+    if database::survey_has_answers(survey_id, &pool)? {
+        return Err(AppError::InvalidInput("Umfrage hat bereits Antworten".to_string()));
+    }
+
+    Ok(database::delete_survey_question(id, &pool)?)
+}
+
+#[server]
+async fn save_survey_deadline(survey_id: i64, deadline: String) -> Result<(), AppError> {
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    require_manager(survey_id, &pool).await?;
+
+    Ok(database::save_survey_deadline(survey_id, parse_deadline(&deadline)?, &pool)?)
+}
+
+#[server]
+async fn delete_survey(id: i64) -> Result<(), AppError> {
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    require_manager(id, &pool).await?;
+
+    database::delete_survey(id, &pool)?;
+
+    leptos_axum::redirect("/surveys");
+    Ok(())
+}
@@ -7,6 +7,10 @@ use serde::{Deserialize, Serialize};
 
 use crate::domain::{Platoon, Player};
 use crate::{app::AppError, domain::Team};
+use crate::components::attendance::TeamAttendanceSummary;
+use crate::components::calendar::TeamCalendarLinks;
+use crate::components::ledger::TeamBalances;
+use crate::components::membership::{DecideTeamJoinRequest, LeaveTeam, TeamHistoryPanel, TeamJoinPanel};
 use crate::components::util::{BackButton, OptionalLink, SelectFromServer};
 
 #[component]
@@ -106,9 +110,12 @@ pub fn TeamProfile() -> impl IntoView {
         move |team_id| load_team_by_id(team_id.unwrap().id.unwrap()),
     );
 
+    let decide_request = ServerAction::<DecideTeamJoinRequest>::new();
+    let leave_team = ServerAction::<LeaveTeam>::new();
+
     let members = Resource::new(
-        move || team_id.read().clone(),
-        move |team_id| get_players_for_team(team_id.unwrap().id.unwrap()),
+        move || (team_id.read().clone(), decide_request.version().get(), leave_team.version().get()),
+        move |(team_id, ..)| get_players_for_team(team_id.unwrap().id.unwrap()),
     );
 
     let contact_person = Resource::new(
@@ -205,6 +212,26 @@ pub fn TeamProfile() -> impl IntoView {
                                         <div class="col-span-2">
                                             <MembersTable members=members/>
                                         </div>
+
+                                        <div class="col-span-2">
+                                            <TeamJoinPanel team_id=team.id.unwrap() decide_request=decide_request leave_team=leave_team/>
+                                        </div>
+
+                                        <div class="col-span-2">
+                                            <TeamHistoryPanel team_id=team.id.unwrap() decide_request=decide_request leave_team=leave_team/>
+                                        </div>
+
+                                        <div class="col-span-2">
+                                            <TeamAttendanceSummary team_id=team.id.unwrap()/>
+                                        </div>
+
+                                        <div class="col-span-2">
+                                            <TeamBalances team_id=team.id.unwrap()/>
+                                        </div>
+
+                                        <div class="col-span-2">
+                                            <TeamCalendarLinks team_id=team.id.unwrap() platoon_id=team.platoon_id/>
+                                        </div>
                                     </div>
                                 </div>
                             }.into_any(),
@@ -670,11 +697,14 @@ async fn load_team_by_id(id: i64) -> Result<Team, AppError> {
 
 #[server]
 async fn create_new_team(create_new_team: CreateNewTeamForm) -> Result<(), AppError> {
+    use crate::auth::require_admin;
     use crate::database::{self, DieselPool};
 
     let pool = use_context::<DieselPool>()
         .ok_or_else(|| AppError::MissingContext)?;
 
+    require_admin().await?;
+
     let result = database::create_team(create_new_team.name, &pool);
 
     match result {
@@ -688,7 +718,8 @@ async fn create_new_team(create_new_team: CreateNewTeamForm) -> Result<(), AppEr
 
 #[server]
 async fn save_team(team_form: EditTeamForm) -> Result<(), AppError> {
-    use crate::database::{self, DieselPool};
+    use crate::auth::{current_player, AuthError};
+    use crate::database::{self, DatabaseError, DieselPool};
 
     let new_member_ids: HashSet<i64> = team_form.new_member_ids
         .split(",")
@@ -702,6 +733,32 @@ async fn save_team(team_form: EditTeamForm) -> Result<(), AppError> {
     let pool = use_context::<DieselPool>()
         .ok_or_else(|| AppError::MissingContext)?;
 
+    let editor = current_player().await?;
+    let current: Team = database::find_team_for_id(team_form.id, &pool)?
+        .ok_or(DatabaseError::EntityNotFound)?
+        .into();
+
+    // Contact persons manage their own team, but members only join through a request they approve
+    // and the contact role only goes to a current member, so nobody gets pulled into a team.
+    if !editor.admin {
+        let member_ids: HashSet<i64> = database::get_players_for_team(team_form.id, &pool)?
+            .into_iter()
+            .map(|player| player.id)
+            .collect();
+        let contact_person_allowed = team_form.contact_person_id
+            .is_none_or(|contact_person_id| {
+                Some(contact_person_id) == current.contact_person_id || member_ids.contains(&contact_person_id)
+            });
+
+        if current.contact_person_id != Some(editor.id)
+            || !new_member_ids.is_empty()
+            || team_form.platoon_id != current.platoon_id
+            || !contact_person_allowed
+        {
+            return Err(AppError::AuthError(AuthError::Forbidden));
+        }
+    }
+
     let team = Team {
         id: Some(team_form.id),
         name: team_form.name,
@@ -717,32 +774,28 @@ async fn save_team(team_form: EditTeamForm) -> Result<(), AppError> {
 
 #[server]
 async fn get_all_players() -> Result<Vec<Player>, AppError> {
+    use crate::auth::visible_players;
     use crate::database::{self, DieselPool};
 
     let pool = use_context::<DieselPool>()
         .ok_or_else(|| AppError::MissingContext)?;
 
     let database_players = database::get_all_players(&pool)?;
-    let domain_players = database_players
-        .into_iter()
-        .map(|db_player| db_player.into())
-        .collect();
+    let domain_players = visible_players(database_players, &pool).await?;
 
     Ok(domain_players)
 }
 
 #[server]
 async fn get_filtered_players(filter: String) -> Result<Vec<Player>, AppError> {
+    use crate::auth::visible_players;
     use crate::database::{self, DieselPool};
 
     let pool = use_context::<DieselPool>()
         .ok_or_else(|| AppError::MissingContext)?;
 
     let database_players = database::get_players_for_name_filter(filter, &pool)?;
-    let domain_players = database_players
-        .into_iter()
-        .map(|db_player| db_player.into())
-        .collect();
+    let domain_players = visible_players(database_players, &pool).await?;
 
     Ok(domain_players)
 }
@@ -750,6 +803,7 @@ async fn get_filtered_players(filter: String) -> Result<Vec<Player>, AppError> {
 
 #[server]
 async fn find_player_for_id(id: Option<i64>) -> Result<Option<Player>, AppError> {
+    use crate::auth::visible_players;
     use crate::database::{self, DieselPool};
 
     if id.is_none() {
@@ -760,8 +814,9 @@ async fn find_player_for_id(id: Option<i64>) -> Result<Option<Player>, AppError>
         .ok_or_else(|| AppError::MissingContext)?;
 
     let database_player = database::find_player_for_id(id.unwrap(), &pool)?;
-    let domain_player = database_player
-        .map(|db_player| db_player.into());
+    let domain_player = visible_players(database_player.into_iter().collect(), &pool)
+        .await?
+        .pop();
 
     Ok(domain_player)
 }
@@ -802,22 +857,21 @@ async fn find_platoon_for_id(id: Option<i64>) -> Result<Option<Platoon>, AppErro
 
 #[server]
 async fn get_players_for_team(team_id: i64) -> Result<Vec<Player>, AppError> {
+    use crate::auth::visible_players;
     use crate::database::{self, DieselPool};
 
     let pool = use_context::<DieselPool>()
         .ok_or_else(|| AppError::MissingContext)?;
 
     let database_players = database::get_players_for_team(team_id, &pool)?;
-    let domain_players = database_players
-        .into_iter()
-        .map(|db_player| db_player.into())
-        .collect();
+    let domain_players = visible_players(database_players, &pool).await?;
 
     Ok(domain_players)
 }
 
 #[server]
 async fn find_player_for_email(email: String) -> Result<Option<Player>, AppError> {
+    use crate::auth::visible_players;
     use crate::database::{self, DieselPool};
 
     if email.is_empty() {
@@ -827,9 +881,10 @@ async fn find_player_for_email(email: String) -> Result<Option<Player>, AppError
     let pool = use_context::<DieselPool>()
         .ok_or_else(|| AppError::MissingContext)?;
 
-    let database_player = database::find_player_for_email(&email, &pool);
-    let domain_player = database_player
-        .map(|db_player| db_player.map(Into::into));
+    let database_player = database::find_player_for_email(&email, &pool)?;
+    let domain_player = visible_players(database_player.into_iter().collect(), &pool)
+        .await?
+        .pop();
 
-    Ok(domain_player?)
+    Ok(domain_player)
 }
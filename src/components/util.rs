@@ -1,8 +1,46 @@
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
 use leptos::prelude::*;
 use serde::{Deserialize, Serialize};
 
 use crate::{app::AppError, domain::PrimaryKey};
 
+const DATETIME_INPUT_FORMAT: &str = "%Y-%m-%dT%H:%M";
+const DATE_INPUT_FORMAT: &str = "%Y-%m-%d";
+
+/// Parses the value of a `datetime-local` input. Event times are wall-clock times and stored as UTC.
+pub fn parse_datetime_input(value: &str) -> Option<DateTime<Utc>> {
+    NaiveDateTime::parse_from_str(value, DATETIME_INPUT_FORMAT)
+        .ok()
+        .map(|naive| naive.and_utc())
+}
+
+pub fn datetime_input_value(value: &DateTime<Utc>) -> String {
+    value.format(DATETIME_INPUT_FORMAT).to_string()
+}
+
+pub fn format_datetime(value: &DateTime<Utc>) -> String {
+    value.format("%d.%m.%Y %H:%M").to_string()
+}
+
+pub fn parse_date_input(value: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(value, DATE_INPUT_FORMAT).ok()
+}
+
+pub fn date_input_value(value: &NaiveDate) -> String {
+    value.format(DATE_INPUT_FORMAT).to_string()
+}
+
+pub fn format_date(value: &NaiveDate) -> String {
+    value.format("%d.%m.%Y").to_string()
+}
+
+pub fn format_cents(cents: i64) -> String {
+    let sign = if cents < 0 { "-" } else { "" };
+    let cents = cents.unsigned_abs();
+
+    format!("{}{},{:02} €", sign, cents / 100, cents % 100)
+}
+
 #[component]
 pub fn BackButton() -> impl IntoView {
     use leptos::web_sys;
@@ -0,0 +1,485 @@
+use std::collections::HashMap;
+
+use leptos::prelude::*;
+use leptos::Params;
+use leptos_router::{components::A, hooks::use_params, params::Params};
+use serde::{Deserialize, Serialize};
+
+use crate::app::AppError;
+use crate::components::util::{format_datetime, BackButton};
+use crate::domain::{DeliveryStatus, WebhookDelivery, WebhookEndpoint, WebhookEndpointDetails, WebhookEventType};
+
+#[cfg(feature = "ssr")]
+const DELIVERY_LOG_SIZE: i64 = 50;
+
+#[component]
+pub fn Webhooks() -> impl IntoView {
+    use crate::components::protected::Protected;
+    use leptos_router::components::Outlet;
+
+    view! {
+        <Protected>
+            <Outlet/>
+        </Protected>
+    }
+}
+
+#[component]
+pub fn WebhookEndpointsPage() -> impl IntoView {
+
+    let create_endpoint = ServerAction::<CreateWebhookEndpoint>::new();
+    let endpoints = Resource::new(move || create_endpoint.version().get(), |_| get_webhook_endpoints());
+
+    let action_error = move || create_endpoint.value().get().and_then(|result| result.err()).map(|e| view! {
+        <p class="text-red-600">{ e.to_string() }</p>
+    });
+
+    view! {
+        <BackButton/>
+        <div class="p-8 max-w-4xl mx-auto">
+            <h1 class="text-2xl font-semibold mb-6">
+                "Webhooks"
+            </h1>
+
+            <Suspense fallback=move || view! { <p>"Lade Daten..."</p> }>
+                {move || {
+                    endpoints.get().map(|result| match result {
+                        Ok(endpoints) if endpoints.is_empty() => view! {
+                            <p class="text-gray-700">"Keine Webhooks eingerichtet"</p>
+                        }.into_any(),
+                        Ok(endpoints) => view! {
+                            <div class="overflow-x-auto">
+                                <table class="min-w-full border border-gray-200 shadow-sm rounded-md bg-white">
+                                    <thead class="bg-gray-100 text-gray-700">
+                                        <tr>
+                                            <th class="text-left py-2 px-4 border-b">Name</th>
+                                            <th class="text-left py-2 px-4 border-b">URL</th>
+                                            <th class="text-left py-2 px-4 border-b">Ereignisse</th>
+                                            <th class="text-left py-2 px-4 border-b">Status</th>
+                                        </tr>
+                                    </thead>
+                                    <tbody>
+                                        {
+                                            endpoints.into_iter().map(|endpoint: WebhookEndpoint| view! {
+                                                <tr class="hover:bg-gray-50">
+                                                    <th class="text-left py-2 px-4 border-b">
+                                                        <A href=format!("/webhooks/{}", endpoint.id.unwrap()) attr:class="hover:underline">{ endpoint.name }</A>
+                                                    </th>
+                                                    <th class="text-left py-2 px-4 border-b font-normal break-all">{ endpoint.url }</th>
+                                                    <th class="text-left py-2 px-4 border-b font-normal">
+                                                        {
+                                                            endpoint.event_types
+                                                                .iter()
+                                                                .map(ToString::to_string)
+                                                                .collect::<Vec<_>>()
+                                                                .join(", ")
+                                                        }
+                                                    </th>
+                                                    <th class="text-left py-2 px-4 border-b">
+                                                        { if endpoint.active { "Aktiv" } else { "Pausiert" } }
+                                                    </th>
+                                                </tr>
+                                            }).collect_view()
+                                        }
+                                    </tbody>
+                                </table>
+                            </div>
+                        }.into_any(),
+                        Err(e) => view! { <p>{ e.to_string() }</p> }.into_any(),
+                    })
+                }}
+            </Suspense>
+
+            { action_error }
+
+            <h2 class="text-left text-xl font-semibold py-2 mt-8">Neuer Webhook</h2>
+            <ActionForm action=create_endpoint>
+                <WebhookFormFields
+                    form_name="create_webhook_endpoint"
+                    endpoint=WebhookEndpoint { active: true, event_types: WebhookEventType::SUBSCRIBABLE.to_vec(), ..Default::default() }
+                />
+                <div class="flex justify-end pt-4">
+                    <button type="submit" class="px-4 py-2 rounded bg-blue-600 hover:bg-blue-700 text-white">
+                        "Webhook anlegen"
+                    </button>
+                </div>
+            </ActionForm>
+        </div>
+    }
+}
+
+#[component]
+pub fn WebhookEndpointPage() -> impl IntoView {
+
+    let endpoint_id = use_params::<WebhookEndpointIdParameter>();
+
+    let save_endpoint = ServerAction::<SaveWebhookEndpoint>::new();
+    let delete_endpoint = ServerAction::<DeleteWebhookEndpoint>::new();
+    let send_test = ServerAction::<SendWebhookTest>::new();
+
+    let details = Resource::new(
+        move || (endpoint_id.read().clone(), save_endpoint.version().get(), send_test.version().get()),
+        move |(params_result, _, _)| get_webhook_endpoint_details(params_result.unwrap().id.unwrap()),
+    );
+
+    let action_error = move || {
+        let errors = [
+            save_endpoint.value().get().and_then(|result| result.err()),
+            delete_endpoint.value().get().and_then(|result| result.err()),
+            send_test.value().get().and_then(|result| result.err()),
+        ];
+
+        errors.into_iter().flatten().next().map(|e| view! {
+            <p class="text-red-600">{ e.to_string() }</p>
+        })
+    };
+
+    let test_result = move || send_test.value().get().and_then(|result| result.ok()).map(|delivery: WebhookDelivery| {
+        match delivery.status {
+            DeliveryStatus::Sent => view! {
+                <p class="text-green-700">"Test wurde zugestellt."</p>
+            }.into_any(),
+            _ => view! {
+                <p class="text-red-600">
+                    { format!("Test fehlgeschlagen: {}", delivery.last_error.unwrap_or_default()) }
+                </p>
+            }.into_any(),
+        }
+    });
+
+    view! {
+        <BackButton/>
+        <div class="p-8 max-w-4xl mx-auto">
+            <Suspense fallback=move || view! { <p>"Lade Daten..."</p> }>
+                {move || {
+                    details.get().map(|result| match result {
+                        Ok(WebhookEndpointDetails { endpoint, deliveries }) => view! {
+                            <div class="flex items-center justify-between mb-6">
+                                <h1 class="text-2xl font-semibold">
+                                    { endpoint.name.clone() }
+                                </h1>
+                                <div class="flex gap-4">
+                                    <ActionForm action=send_test>
+                                        <input type="hidden" name="id" value=endpoint.id/>
+                                        <button
+                                            type="submit"
+                                            class="px-4 py-2 rounded bg-blue-600 hover:bg-blue-700 text-white"
+                                        >
+                                            "Test senden"
+                                        </button>
+                                    </ActionForm>
+                                    <ActionForm action=delete_endpoint>
+                                        <input type="hidden" name="id" value=endpoint.id/>
+                                        <button
+                                            type="submit"
+                                            class="px-4 py-2 rounded bg-gray-200 hover:bg-gray-300 text-gray-700"
+                                        >
+                                            "Löschen"
+                                        </button>
+                                    </ActionForm>
+                                </div>
+                            </div>
+
+                            <div class="grid grid-cols-[auto_1fr] items-center gap-4 pb-4">
+                                <span class="text-left text-gray-700">"Secret:"</span>
+                                <code class="text-left px-3 py-2 bg-gray-100 rounded break-all">{ endpoint.secret.clone() }</code>
+                            </div>
+                            <p class="text-left text-sm text-gray-500 pb-4">
+                                "Jede Anfrage ist im Header X-AS-Manager-Signature mit HMAC-SHA256 über "
+                                "\"<X-AS-Manager-Timestamp>.<Inhalt>\" und diesem Secret signiert."
+                            </p>
+
+                            <ActionForm action=save_endpoint>
+                                <input type="hidden" name="webhook_endpoint_form[id]" value=endpoint.id/>
+                                <WebhookFormFields form_name="webhook_endpoint_form" endpoint=endpoint.clone()/>
+                                <div class="flex justify-end pt-4">
+                                    <button type="submit" class="px-4 py-2 rounded bg-blue-600 hover:bg-blue-700 text-white">
+                                        "Speichern"
+                                    </button>
+                                </div>
+                            </ActionForm>
+
+                            <h2 class="text-left text-xl font-semibold py-2 mt-8">Zustellungen</h2>
+                            <DeliveryLogTable deliveries=deliveries/>
+                        }.into_any(),
+                        Err(e) => view! { <p>{ e.to_string() }</p> }.into_any(),
+                    })
+                }}
+            </Suspense>
+
+            { test_result }
+            { action_error }
+        </div>
+    }
+}
+
+#[component]
+fn WebhookFormFields(form_name: &'static str, endpoint: WebhookEndpoint) -> impl IntoView {
+    let field = move |field: &str| format!("{}[{}]", form_name, field);
+
+    view! {
+        <div class="grid grid-cols-[auto_1fr] items-center gap-4">
+            <label for=field("name") class="text-left text-gray-700">
+                "Name:"
+            </label>
+            <input
+                type="text"
+                name=field("name")
+                class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+                value=endpoint.name.clone()
+                required
+            />
+
+            <label for=field("url") class="text-left text-gray-700">
+                "URL:"
+            </label>
+            <input
+                type="url"
+                name=field("url")
+                class="w-full border border-gray-300 rounded px-3 py-2 focus:outline-none focus:ring focus:border-blue-300"
+                placeholder="https://"
+                value=endpoint.url.clone()
+                required
+            />
+
+            <label for=field("active") class="text-left text-gray-700">
+                "Aktiv:"
+            </label>
+            <input
+                type="checkbox"
+                name=field("active")
+                class="w-4 h-4 accent-green-600 border-2 border-gray-300 rounded"
+                value="true"
+                checked=endpoint.active/>
+
+            <span class="text-left text-gray-700 self-start">"Ereignisse:"</span>
+            <div class="flex flex-col gap-2 text-left">
+                {
+                    WebhookEventType::SUBSCRIBABLE
+                        .into_iter()
+                        .map(|event_type| view! {
+                            <label class="flex items-center gap-2">
+                                <input
+                                    type="checkbox"
+                                    name=format!("{}[{}]", field("event_types"), event_type.as_str())
+                                    class="w-4 h-4 accent-green-600 border-2 border-gray-300 rounded"
+                                    value="true"
+                                    checked=endpoint.subscribes_to(event_type)/>
+                                { event_type.to_string() }
+                            </label>
+                        })
+                        .collect_view()
+                }
+            </div>
+        </div>
+    }
+}
+
+#[component]
+fn DeliveryLogTable(deliveries: Vec<WebhookDelivery>) -> impl IntoView {
+
+    if deliveries.is_empty() {
+        return view! { <p class="text-gray-700">"Noch keine Zustellungen"</p> }.into_any();
+    }
+
+    view! {
+        <div class="overflow-x-auto">
+            <table class="min-w-full border border-gray-200 shadow-sm rounded-md bg-white">
+                <thead class="bg-gray-100 text-gray-700">
+                    <tr>
+                        <th class="text-left py-2 px-4 border-b">Erstellt</th>
+                        <th class="text-left py-2 px-4 border-b">Ereignis</th>
+                        <th class="text-left py-2 px-4 border-b">Status</th>
+                        <th class="text-left py-2 px-4 border-b">Versuche</th>
+                        <th class="text-left py-2 px-4 border-b">Fehler</th>
+                    </tr>
+                </thead>
+                <tbody>
+                    {
+                        deliveries.into_iter().map(|delivery: WebhookDelivery| {
+                            let status = match delivery.status {
+                                DeliveryStatus::Sent => format!(
+                                    "{} {}",
+                                    delivery.status,
+                                    delivery.sent_at.as_ref().map(format_datetime).unwrap_or_default(),
+                                ),
+                                DeliveryStatus::Pending if delivery.attempts > 0 => format!(
+                                    "Neuer Versuch {}",
+                                    format_datetime(&delivery.next_attempt_at),
+                                ),
+                                _ => delivery.status.to_string(),
+                            };
+
+                            view! {
+                                <tr class="hover:bg-gray-50">
+                                    <th class="text-left py-2 px-4 border-b">{ format_datetime(&delivery.created_at) }</th>
+                                    <th class="text-left py-2 px-4 border-b">
+                                        <details>
+                                            <summary class="cursor-pointer">{ delivery.event_type.to_string() }</summary>
+                                            <pre class="text-xs font-normal whitespace-pre-wrap break-all">{ delivery.payload }</pre>
+                                        </details>
+                                    </th>
+                                    <th class="text-left py-2 px-4 border-b">{ status }</th>
+                                    <th class="text-left py-2 px-4 border-b">{ delivery.attempts }</th>
+                                    <th class="text-left py-2 px-4 border-b font-normal break-all">{ delivery.last_error }</th>
+                                </tr>
+                            }
+                        }).collect_view()
+                    }
+                </tbody>
+            </table>
+        </div>
+    }.into_any()
+}
+
+#[derive(Params, PartialEq, Clone)]
+struct WebhookEndpointIdParameter {
+    id: Option<i64>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+struct WebhookEndpointForm {
+    #[serde(default)]
+    id: Option<i64>,
+    name: String,
+    url: String,
+    #[serde(default)]
+    active: bool,
+    #[serde(default)]
+    event_types: HashMap<String, String>,
+}
+
+#[cfg(feature = "ssr")]
+impl WebhookEndpointForm {
+    /// Local receivers are allowed, so plain http works as well.
+    fn into_endpoint(self, secret: String) -> Result<WebhookEndpoint, AppError> {
+        let url = self.url.trim().to_string();
+
+        if !url.starts_with("https://") && !url.starts_with("http://") {
+            return Err(AppError::InvalidInput("URL".to_string()));
+        }
+
+        let event_types = WebhookEventType::SUBSCRIBABLE
+            .into_iter()
+            .filter(|event_type| self.event_types.contains_key(event_type.as_str()))
+            .collect();
+
+        Ok(WebhookEndpoint {
+            id: self.id,
+            name: self.name,
+            url,
+            secret,
+            active: self.active,
+            event_types,
+        })
+    }
+}
+
+#[server]
+pub async fn can_manage_webhooks() -> Result<bool, AppError> {
+    use crate::auth::current_player_if_logged_in;
+
+    Ok(current_player_if_logged_in().await?.is_some_and(|player| player.admin))
+}
+
+#[server]
+async fn get_webhook_endpoints() -> Result<Vec<WebhookEndpoint>, AppError> {
+    use crate::auth::require_admin;
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    require_admin().await?;
+
+    Ok(database::get_webhook_endpoints(&pool)?)
+}
+
+#[server]
+async fn get_webhook_endpoint_details(id: i64) -> Result<WebhookEndpointDetails, AppError> {
+    use crate::auth::require_admin;
+    use crate::database::{self, DatabaseError, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    require_admin().await?;
+
+    let endpoint = database::find_webhook_endpoint_for_id(id, &pool)?
+        .ok_or(DatabaseError::EntityNotFound)?;
+    let deliveries = database::get_webhook_deliveries_for_endpoint(id, DELIVERY_LOG_SIZE, &pool)?;
+
+    Ok(WebhookEndpointDetails { endpoint, deliveries })
+}
+
+#[server]
+async fn create_webhook_endpoint(create_webhook_endpoint: WebhookEndpointForm) -> Result<(), AppError> {
+    use crate::auth::require_admin;
+    use crate::database::{self, DieselPool};
+    use crate::webhook::generate_secret;
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    require_admin().await?;
+
+    let endpoint = create_webhook_endpoint.into_endpoint(generate_secret())?;
+    let id = database::create_webhook_endpoint(endpoint, &pool)?;
+
+    leptos_axum::redirect(&format!("/webhooks/{}", id));
+    Ok(())
+}
+
+#[server]
+async fn save_webhook_endpoint(webhook_endpoint_form: WebhookEndpointForm) -> Result<(), AppError> {
+    use crate::auth::require_admin;
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    require_admin().await?;
+
+    if webhook_endpoint_form.id.is_none() {
+        return Err(AppError::InvalidInput("Id".to_string()));
+    }
+
+    Ok(database::save_webhook_endpoint(webhook_endpoint_form.into_endpoint(String::new())?, &pool)?)
+}
+
+#[server]
+async fn delete_webhook_endpoint(id: i64) -> Result<(), AppError> {
+    use crate::auth::require_admin;
+    use crate::database::{self, DieselPool};
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+
+    require_admin().await?;
+
+    database::delete_webhook_endpoint(id, &pool)?;
+
+    leptos_axum::redirect("/webhooks");
+    Ok(())
+}
+
+/// Sends a ping right away and waits for the receiver, so the admin sees the outcome directly.
+#[server]
+async fn send_webhook_test(id: i64) -> Result<WebhookDelivery, AppError> {
+    use crate::auth::require_admin;
+    use crate::database::{self, DatabaseError, DieselPool};
+    use crate::notification::DeliveryChannels;
+    use crate::webhook;
+
+    let pool = use_context::<DieselPool>()
+        .ok_or(AppError::MissingContext)?;
+    let channels = use_context::<DeliveryChannels>()
+        .ok_or(AppError::MissingContext)?;
+
+    require_admin().await?;
+
+    let endpoint = database::find_webhook_endpoint_for_id(id, &pool)?
+        .ok_or(DatabaseError::EntityNotFound)?;
+
+    Ok(webhook::send_test(&endpoint, channels.webhook.as_ref(), &pool).await?)
+}
@@ -0,0 +1,62 @@
+use axum::{
+    extract::Path,
+    http::{header, StatusCode},
+    response::IntoResponse,
+    routing::get,
+    Extension, Router,
+};
+
+use crate::auth::AuthSession;
+use crate::database::{self, DieselPool};
+
+/// Largest scanned consent form that is accepted for upload.
+pub const MAX_DOCUMENT_SIZE: usize = 5 * 1024 * 1024;
+
+/// Routes serving the uploaded consent forms, only to admins and the player they belong to.
+pub fn router<S>(pool: DieselPool) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    Router::new()
+        .route("/consents/{id}/document", get(consent_document))
+        .layer(Extension(pool))
+}
+
+/// Tells the document type from the file's magic bytes instead of trusting the upload's content type.
+pub fn detect_document_content_type(data: &[u8]) -> Option<&'static str> {
+    if data.starts_with(b"%PDF-") {
+        Some("application/pdf")
+    } else if data.starts_with(b"\x89PNG\r\n\x1a\n") {
+        Some("image/png")
+    } else if data.starts_with(b"\xff\xd8\xff") {
+        Some("image/jpeg")
+    } else {
+        None
+    }
+}
+
+async fn consent_document(
+    auth_session: AuthSession,
+    Extension(pool): Extension<DieselPool>,
+    Path(id): Path<i64>,
+) -> Result<axum::response::Response, StatusCode> {
+    let Some(user) = auth_session.user else {
+        return Err(StatusCode::UNAUTHORIZED);
+    };
+
+    let (player_id, content_type, data) = database::find_parental_consent_document(id, &pool)
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    if !user.admin && user.id != player_id {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, content_type),
+            (header::CACHE_CONTROL, "private, no-cache".to_string()),
+        ],
+        data,
+    ).into_response())
+}
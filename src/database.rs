@@ -1,9 +1,12 @@
 #[cfg(feature = "ssr")]
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashMap, HashSet};
 
+#[cfg(feature = "ssr")]
+use chrono::{DateTime, NaiveDate, Utc};
 #[cfg(feature = "ssr")]
 use diesel::{
     dsl::count_star,
+    Connection,
     ExpressionMethods,
     QueryDsl,
     RunQueryDsl,
@@ -26,17 +29,23 @@ pub mod schema;
 pub fn save_player(player: domain::Player, pool: &DieselPool) -> Result<(), DatabaseError> {
     use schema::players::dsl::*;
 
-    diesel::update(players)
-        .filter(id.eq(player.id.unwrap()))
-        .set((
-            tag_name.eq(&player.tag_name),
-            email.eq(&player.email),
-            active.eq(&player.active),
-            team_id.eq(&player.team_id),
-        ))
-        .execute(&mut pool.get().expect("diesel"))
-        .map(|_| ())
-        .map_err(DatabaseError::from)
+    pool.get().expect("diesel").transaction(|connection| {
+        diesel::update(players)
+            .filter(id.eq(player.id.unwrap()))
+            .set((
+                tag_name.eq(&player.tag_name),
+                email.eq(&player.email),
+                active.eq(&player.active),
+                real_name.eq(&player.real_name),
+                birth_date.eq(&player.birth_date),
+                emergency_contact.eq(&player.emergency_contact),
+                role.eq(player.role.map(|player_role| player_role.as_str())),
+                phone.eq(&player.phone),
+            ))
+            .execute(connection)?;
+
+        change_player_team(player.id.unwrap(), player.team_id, connection)
+    })
 }
 
 #[cfg(feature = "ssr")]
@@ -95,6 +104,31 @@ pub fn get_all_players(pool: &DieselPool) -> Result<Vec<models::Player>, Databas
         .map_err(DatabaseError::from)
 }
 
+/// Part of a listing, `limit` rows starting at `offset`.
+#[cfg(feature = "ssr")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PageWindow {
+    pub limit: i64,
+    pub offset: i64,
+}
+
+/// Returns the players in the window by id, together with the number of all players.
+#[cfg(feature = "ssr")]
+pub fn get_players_page(window: PageWindow, pool: &DieselPool) -> Result<(Vec<models::Player>, i64), DatabaseError> {
+    use schema::players::dsl::*;
+
+    let connection = &mut pool.get().expect("diesel");
+
+    let total = players.count().get_result(connection)?;
+    let page = players
+        .order(id.asc())
+        .limit(window.limit)
+        .offset(window.offset)
+        .load::<models::Player>(connection)?;
+
+    Ok((page, total))
+}
+
 #[cfg(feature = "ssr")]
 pub fn get_players_for_team(filter_team_id: i64, pool: &DieselPool) -> Result<Vec<models::Player>, DatabaseError> {
     use diesel::ExpressionMethods;
@@ -107,6 +141,30 @@ pub fn get_players_for_team(filter_team_id: i64, pool: &DieselPool) -> Result<Ve
     Ok(result)
 }
 
+#[cfg(feature = "ssr")]
+pub fn get_players_for_team_page(
+    filter_team_id: i64,
+    window: PageWindow,
+    pool: &DieselPool,
+) -> Result<(Vec<models::Player>, i64), DatabaseError> {
+    use schema::players::dsl::*;
+
+    let connection = &mut pool.get().expect("diesel");
+
+    let total = players
+        .filter(team_id.eq(Some(filter_team_id)))
+        .count()
+        .get_result(connection)?;
+    let page = players
+        .filter(team_id.eq(Some(filter_team_id)))
+        .order(id.asc())
+        .limit(window.limit)
+        .offset(window.offset)
+        .load::<models::Player>(connection)?;
+
+    Ok((page, total))
+}
+
 #[cfg(feature = "ssr")]
 pub fn create_player(
     new_player_email: String,
@@ -168,6 +226,22 @@ pub fn get_all_teams(pool: &DieselPool) -> Result<Vec<models::Team>, DatabaseErr
         .map_err(DatabaseError::from)
 }
 
+#[cfg(feature = "ssr")]
+pub fn get_teams_page(window: PageWindow, pool: &DieselPool) -> Result<(Vec<models::Team>, i64), DatabaseError> {
+    use schema::teams::dsl::*;
+
+    let connection = &mut pool.get().expect("diesel");
+
+    let total = teams.count().get_result(connection)?;
+    let page = teams
+        .order(id.asc())
+        .limit(window.limit)
+        .offset(window.offset)
+        .load::<models::Team>(connection)?;
+
+    Ok((page, total))
+}
+
 #[cfg(feature = "ssr")]
 pub fn get_teams_for_name_filter(
     filter_name: String,
@@ -223,6 +297,19 @@ pub fn create_team(create_name: String, pool: &DieselPool) -> Result<(), Databas
     }
 }
 
+#[cfg(feature = "ssr")]
+pub fn get_teams_for_contact_person(
+    search_contact_person_id: i64,
+    pool: &DieselPool,
+) -> Result<Vec<models::Team>, DatabaseError> {
+    use schema::teams::dsl::*;
+
+    teams
+        .filter(contact_person_id.eq(search_contact_person_id))
+        .load::<models::Team>(&mut pool.get().expect("diesel"))
+        .map_err(DatabaseError::from)
+}
+
 #[cfg(feature = "ssr")]
 pub fn save_team(
     team: domain::Team,
@@ -230,30 +317,13 @@ pub fn save_team(
     removed_member_ids: HashSet<i64>,
     pool: &DieselPool
 ) -> Result<(), DatabaseError> {
-    let connection = &mut pool.get().expect("diesel");
-
-    {
-        use schema::teams::dsl::*;
-
-        diesel::update(teams)
-            .filter(id.eq(team.id.unwrap()))
-            .set((
-                name.eq(&team.name),
-                contact_person_id.eq(team.contact_person_id),
-                platoon_id.eq(team.platoon_id),
-            ))
-            .execute(connection)
-            .map(|_| ())
-            .map_err(DatabaseError::from)?;
-    }
-
-    {
-        use schema::players::dsl::*;
-
+    pool.get().expect("diesel").transaction(|connection| {
         let mut new_member_ids = new_member_ids;
         let mut removed_member_ids = removed_member_ids;
 
         if let Some(contact_person_id) = team.contact_person_id {
+            use schema::players::dsl::*;
+
             let matches: i64 = players
                 .filter(id.eq(contact_person_id))
                 .filter(team_id.eq(team.id.unwrap()))
@@ -269,19 +339,411 @@ pub fn save_team(
             }
         }
 
-        if !new_member_ids.is_empty() {
-            diesel::update(players)
-                .filter(id.eq_any(new_member_ids))
-                .set(team_id.eq(team.id))
+        let current_member_ids: HashSet<i64> = {
+            use schema::players::dsl::*;
+
+            players
+                .filter(team_id.eq(team.id.unwrap()))
+                .select(id)
+                .load::<i64>(connection)?
+                .into_iter()
+                .collect()
+        };
+
+        for member_id in removed_member_ids.intersection(&current_member_ids) {
+            change_player_team(*member_id, None, connection)?;
+        }
+
+        for member_id in new_member_ids {
+            change_player_team(member_id, team.id, connection)?;
+        }
+
+        {
+            use schema::teams::dsl::*;
+
+            diesel::update(teams)
+                .filter(id.eq(team.id.unwrap()))
+                .set((
+                    name.eq(&team.name),
+                    contact_person_id.eq(team.contact_person_id),
+                    platoon_id.eq(team.platoon_id),
+                ))
                 .execute(connection)?;
         }
 
-        if !removed_member_ids.is_empty() {
-            diesel::update(players)
-                .filter(id.eq_any(removed_member_ids))
-                .set(team_id.eq(None::<i64>))
+        Ok(())
+    })
+}
+
+/// Membership history of a player, latest first.
+#[cfg(feature = "ssr")]
+pub fn get_team_memberships_for_player(
+    search_player_id: i64,
+    pool: &DieselPool,
+) -> Result<Vec<domain::TeamMembership>, DatabaseError> {
+    use schema::team_memberships;
+
+    load_team_memberships(
+        team_memberships::table
+            .filter(team_memberships::player_id.eq(search_player_id))
+            .into_boxed(),
+        &mut pool.get().expect("diesel"),
+    )
+}
+
+/// Membership history of a team, latest first.
+#[cfg(feature = "ssr")]
+pub fn get_team_memberships_for_team(
+    search_team_id: i64,
+    pool: &DieselPool,
+) -> Result<Vec<domain::TeamMembership>, DatabaseError> {
+    use schema::team_memberships;
+
+    load_team_memberships(
+        team_memberships::table
+            .filter(team_memberships::team_id.eq(search_team_id))
+            .into_boxed(),
+        &mut pool.get().expect("diesel"),
+    )
+}
+
+/// Events a member of the team was registered for while being in the team, by start.
+#[cfg(feature = "ssr")]
+pub fn get_play_events_for_team(
+    search_team_id: i64,
+    pool: &DieselPool,
+) -> Result<Vec<models::PlayEvent>, DatabaseError> {
+    use schema::{play_event_registrations, play_events, team_memberships};
+
+    let connection = &mut pool.get().expect("diesel");
+
+    let memberships: Vec<(i64, NaiveDate, Option<NaiveDate>)> = team_memberships::table
+        .filter(team_memberships::team_id.eq(search_team_id))
+        .select((team_memberships::player_id, team_memberships::joined_on, team_memberships::left_on))
+        .load(connection)?;
+
+    let registrations: Vec<(i64, models::PlayEvent)> = play_event_registrations::table
+        .inner_join(play_events::table)
+        .filter(play_event_registrations::player_id.eq_any(memberships.iter().map(|(member_id, ..)| *member_id).collect::<Vec<_>>()))
+        .filter(play_event_registrations::status.ne(domain::RegistrationStatus::Withdrawn.as_str()))
+        .order((play_events::starts_at.asc(), play_events::id.asc()))
+        .select((play_event_registrations::player_id, models::PlayEvent::as_select()))
+        .load(connection)?;
+
+    let mut play_event_ids = HashSet::new();
+
+    Ok(registrations
+        .into_iter()
+        .filter(|(member_id, play_event)| {
+            let date = play_event.starts_at.date_naive();
+
+            memberships.iter().any(|(player_id, joined_on, left_on)| {
+                player_id == member_id && *joined_on <= date && left_on.is_none_or(|left_on| date < left_on)
+            })
+        })
+        .filter_map(|(_, play_event)| play_event_ids.insert(play_event.id).then_some(play_event))
+        .collect())
+}
+
+/// The members the team had on the given date.
+#[cfg(feature = "ssr")]
+pub fn get_team_roster_as_of(
+    search_team_id: i64,
+    date: NaiveDate,
+    pool: &DieselPool,
+) -> Result<Vec<domain::TeamMembership>, DatabaseError> {
+    use diesel::BoolExpressionMethods;
+    use schema::team_memberships;
+
+    load_team_memberships(
+        team_memberships::table
+            .filter(team_memberships::team_id.eq(search_team_id))
+            .filter(team_memberships::joined_on.le(date))
+            .filter(team_memberships::left_on.is_null().or(team_memberships::left_on.gt(date)))
+            .into_boxed(),
+        &mut pool.get().expect("diesel"),
+    )
+}
+
+#[cfg(feature = "ssr")]
+fn load_team_memberships(
+    query: schema::team_memberships::BoxedQuery<'_, diesel::pg::Pg>,
+    connection: &mut PgConnection,
+) -> Result<Vec<domain::TeamMembership>, DatabaseError> {
+    use schema::{players, team_memberships, teams};
+
+    let memberships: Vec<models::TeamMembership> = query
+        .order((team_memberships::joined_on.desc(), team_memberships::id.desc()))
+        .load(connection)?;
+
+    let player_ids: Vec<i64> = memberships.iter().map(|membership| membership.player_id).collect();
+    let team_ids: Vec<i64> = memberships.iter().map(|membership| membership.team_id).collect();
+
+    let tag_names: HashMap<i64, String> = players::table
+        .filter(players::id.eq_any(player_ids))
+        .select((players::id, players::tag_name))
+        .load::<(i64, String)>(connection)?
+        .into_iter()
+        .collect();
+
+    let team_names: HashMap<i64, String> = teams::table
+        .filter(teams::id.eq_any(team_ids))
+        .select((teams::id, teams::name))
+        .load::<(i64, String)>(connection)?
+        .into_iter()
+        .collect();
+
+    Ok(memberships
+        .into_iter()
+        .map(|membership| domain::TeamMembership {
+            id: Some(membership.id),
+            player_id: membership.player_id,
+            tag_name: tag_names.get(&membership.player_id).cloned().unwrap_or_default(),
+            team_id: membership.team_id,
+            team_name: team_names.get(&membership.team_id).cloned().unwrap_or_default(),
+            joined_on: membership.joined_on,
+            left_on: membership.left_on,
+        })
+        .collect())
+}
+
+#[cfg(feature = "ssr")]
+pub fn create_team_join_request(
+    request_team_id: i64,
+    request_player_id: i64,
+    request_message: &str,
+    pool: &DieselPool,
+) -> Result<i64, DatabaseError> {
+    use models::NewTeamJoinRequest;
+    use schema::{players, team_join_requests, teams};
+
+    pool.get().expect("diesel").transaction(|connection| {
+        teams::table
+            .find(request_team_id)
+            .select(teams::id)
+            .first::<i64>(connection)
+            .optional()?
+            .ok_or(DatabaseError::EntityNotFound)?;
+
+        let current_team_id: Option<i64> = players::table
+            .find(request_player_id)
+            .select(players::team_id)
+            .first(connection)?;
+
+        if current_team_id == Some(request_team_id) {
+            return Err(DatabaseError::AlreadyTeamMember);
+        }
+
+        let pending: i64 = team_join_requests::table
+            .filter(team_join_requests::team_id.eq(request_team_id))
+            .filter(team_join_requests::player_id.eq(request_player_id))
+            .filter(team_join_requests::status.eq(domain::JoinRequestStatus::Pending.as_str()))
+            .select(count_star())
+            .first(connection)?;
+
+        if pending > 0 {
+            return Err(DatabaseError::JoinRequestPending);
+        }
+
+        let new_request = NewTeamJoinRequest {
+            team_id: request_team_id,
+            player_id: request_player_id,
+            message: request_message,
+        };
+
+        diesel::insert_into(team_join_requests::table)
+            .values(&new_request)
+            .returning(team_join_requests::id)
+            .get_result(connection)
+            .map_err(DatabaseError::from)
+    })
+}
+
+/// Pending join requests for the team, or only the one of the given player.
+#[cfg(feature = "ssr")]
+pub fn get_pending_team_join_requests(
+    search_team_id: i64,
+    search_player_id: Option<i64>,
+    pool: &DieselPool,
+) -> Result<Vec<domain::TeamJoinRequest>, DatabaseError> {
+    use schema::{players, team_join_requests};
+
+    let mut query = team_join_requests::table
+        .inner_join(players::table)
+        .filter(team_join_requests::team_id.eq(search_team_id))
+        .filter(team_join_requests::status.eq(domain::JoinRequestStatus::Pending.as_str()))
+        .order(team_join_requests::created_at.asc())
+        .select((models::TeamJoinRequest::as_select(), players::tag_name))
+        .into_boxed();
+
+    if let Some(search_player_id) = search_player_id {
+        query = query.filter(team_join_requests::player_id.eq(search_player_id));
+    }
+
+    let rows = query.load::<(models::TeamJoinRequest, String)>(&mut pool.get().expect("diesel"))?;
+
+    Ok(rows
+        .into_iter()
+        .map(|(request, tag_name)| domain::TeamJoinRequest {
+            id: request.id,
+            team_id: request.team_id,
+            player_id: request.player_id,
+            tag_name,
+            message: request.message,
+            status: request.status.parse().expect("join request status constrained by database"),
+            requested_at: request.created_at,
+        })
+        .collect())
+}
+
+#[cfg(feature = "ssr")]
+pub fn find_team_join_request_for_id(
+    search_id: i64,
+    pool: &DieselPool,
+) -> Result<Option<models::TeamJoinRequest>, DatabaseError> {
+    use schema::team_join_requests::dsl::*;
+
+    team_join_requests
+        .find(search_id)
+        .first::<models::TeamJoinRequest>(&mut pool.get().expect("diesel"))
+        .optional()
+        .map_err(DatabaseError::from)
+}
+
+/// Approving moves the player into the team and withdraws their other pending requests.
+#[cfg(feature = "ssr")]
+pub fn decide_team_join_request(
+    decide_id: i64,
+    approve: bool,
+    decide_by_id: i64,
+    pool: &DieselPool,
+) -> Result<(), DatabaseError> {
+    use domain::JoinRequestStatus;
+    use schema::team_join_requests::dsl::*;
+
+    pool.get().expect("diesel").transaction(|connection| {
+        let request: models::TeamJoinRequest = team_join_requests
+            .find(decide_id)
+            .for_update()
+            .first(connection)
+            .optional()?
+            .ok_or(DatabaseError::EntityNotFound)?;
+
+        if request.status != JoinRequestStatus::Pending.as_str() {
+            return Err(DatabaseError::JoinRequestDecided);
+        }
+
+        let new_status = if approve {
+            JoinRequestStatus::Approved
+        } else {
+            JoinRequestStatus::Rejected
+        };
+
+        diesel::update(team_join_requests.find(decide_id))
+            .set((
+                status.eq(new_status.as_str()),
+                decided_by_id.eq(Some(decide_by_id)),
+                decided_at.eq(Some(Utc::now())),
+            ))
+            .execute(connection)?;
+
+        if approve {
+            diesel::update(team_join_requests)
+                .filter(player_id.eq(request.player_id))
+                .filter(status.eq(JoinRequestStatus::Pending.as_str()))
+                .set(status.eq(JoinRequestStatus::Withdrawn.as_str()))
                 .execute(connection)?;
+
+            change_player_team(request.player_id, Some(request.team_id), connection)?;
+        }
+
+        Ok(())
+    })
+}
+
+#[cfg(feature = "ssr")]
+pub fn withdraw_team_join_request(withdraw_id: i64, pool: &DieselPool) -> Result<(), DatabaseError> {
+    use domain::JoinRequestStatus;
+    use schema::team_join_requests::dsl::*;
+
+    let updated = diesel::update(team_join_requests.find(withdraw_id))
+        .filter(status.eq(JoinRequestStatus::Pending.as_str()))
+        .set(status.eq(JoinRequestStatus::Withdrawn.as_str()))
+        .execute(&mut pool.get().expect("diesel"))?;
+
+    if updated == 0 {
+        return Err(DatabaseError::JoinRequestDecided);
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "ssr")]
+pub fn leave_team(leave_player_id: i64, pool: &DieselPool) -> Result<(), DatabaseError> {
+    use schema::players;
+
+    pool.get().expect("diesel").transaction(|connection| {
+        let current_team_id: Option<i64> = players::table
+            .find(leave_player_id)
+            .select(players::team_id)
+            .for_update()
+            .first(connection)?;
+
+        if current_team_id.is_none() {
+            return Err(DatabaseError::NotTeamMember);
         }
+
+        change_player_team(leave_player_id, None, connection)
+    })
+}
+
+/// Moves the player into the team, or out of any team, and keeps the membership history in step.
+/// A contact person moving out leaves their old team without one.
+#[cfg(feature = "ssr")]
+fn change_player_team(
+    change_player_id: i64,
+    new_team_id: Option<i64>,
+    connection: &mut PgConnection,
+) -> Result<(), DatabaseError> {
+    use models::NewTeamMembership;
+    use schema::{players, team_memberships, teams};
+
+    let current_team_id: Option<i64> = players::table
+        .find(change_player_id)
+        .select(players::team_id)
+        .first(connection)?;
+
+    if current_team_id == new_team_id {
+        return Ok(());
+    }
+
+    let today = Utc::now().date_naive();
+
+    if let Some(current_team_id) = current_team_id {
+        diesel::update(teams::table.find(current_team_id))
+            .filter(teams::contact_person_id.eq(change_player_id))
+            .set(teams::contact_person_id.eq(None::<i64>))
+            .execute(connection)?;
+    }
+
+    diesel::update(players::table.find(change_player_id))
+        .set(players::team_id.eq(new_team_id))
+        .execute(connection)?;
+
+    diesel::update(team_memberships::table)
+        .filter(team_memberships::player_id.eq(change_player_id))
+        .filter(team_memberships::left_on.is_null())
+        .set(team_memberships::left_on.eq(Some(today)))
+        .execute(connection)?;
+
+    if let Some(new_team_id) = new_team_id {
+        diesel::insert_into(team_memberships::table)
+            .values(&NewTeamMembership {
+                player_id: change_player_id,
+                team_id: new_team_id,
+                joined_on: today,
+            })
+            .execute(connection)?;
     }
 
     Ok(())
@@ -320,6 +782,4786 @@ pub fn get_platoons_for_name_filter(
     Ok(query.load::<models::Platoon>(&mut pool.get().expect("diesel"))?)
 }
 
+#[cfg(feature = "ssr")]
+pub fn get_platoons_page(window: PageWindow, pool: &DieselPool) -> Result<(Vec<models::Platoon>, i64), DatabaseError> {
+    use schema::platoons::dsl::*;
+
+    let connection = &mut pool.get().expect("diesel");
+
+    let total = platoons.count().get_result(connection)?;
+    let page = platoons
+        .order(id.asc())
+        .limit(window.limit)
+        .offset(window.offset)
+        .load::<models::Platoon>(connection)?;
+
+    Ok((page, total))
+}
+
+#[cfg(feature = "ssr")]
+pub fn get_all_announcements(pool: &DieselPool) -> Result<Vec<models::Announcement>, DatabaseError> {
+    use schema::announcements::dsl::*;
+
+    announcements
+        .order(publish_at.desc())
+        .load::<models::Announcement>(&mut pool.get().expect("diesel"))
+        .map_err(DatabaseError::from)
+}
+
+#[cfg(feature = "ssr")]
+pub fn get_announcements_for_author(
+    search_author_id: i64,
+    pool: &DieselPool,
+) -> Result<Vec<models::Announcement>, DatabaseError> {
+    use schema::announcements::dsl::*;
+
+    announcements
+        .filter(author_id.eq(search_author_id))
+        .order(publish_at.desc())
+        .load::<models::Announcement>(&mut pool.get().expect("diesel"))
+        .map_err(DatabaseError::from)
+}
+
+/// Published announcements addressed to everyone or, with a player, to one of the player's audiences.
+#[cfg(feature = "ssr")]
+pub fn get_published_announcements(
+    search_player_id: Option<i64>,
+    pool: &DieselPool,
+) -> Result<Vec<models::Announcement>, DatabaseError> {
+    use diesel::BoolExpressionMethods;
+    use schema::announcements::dsl::*;
+
+    let connection = &mut pool.get().expect("diesel");
+    let now = Utc::now();
+
+    let player_audiences = match search_player_id {
+        Some(search_player_id) => get_audiences_for_player(search_player_id, connection)?,
+        None => Vec::new(),
+    };
+
+    let mut platoon_ids = Vec::new();
+    let mut team_ids = Vec::new();
+    let mut play_event_ids = Vec::new();
+
+    for player_audience in player_audiences {
+        match player_audience {
+            domain::AnnouncementAudience::Everyone => (),
+            domain::AnnouncementAudience::Platoon(target_id) => platoon_ids.push(target_id),
+            domain::AnnouncementAudience::Team(target_id) => team_ids.push(target_id),
+            domain::AnnouncementAudience::PlayEvent(target_id) => play_event_ids.push(target_id),
+        }
+    }
+
+    let addressed = audience.eq("everyone")
+        .or(audience.eq("platoon").and(audience_id.eq_any(platoon_ids)))
+        .or(audience.eq("team").and(audience_id.eq_any(team_ids)))
+        .or(audience.eq("play_event").and(audience_id.eq_any(play_event_ids)));
+
+    announcements
+        .filter(hidden.eq(false))
+        .filter(publish_at.le(now))
+        .filter(expires_at.is_null().or(expires_at.gt(now)))
+        .filter(addressed)
+        .order(publish_at.desc())
+        .load::<models::Announcement>(connection)
+        .map_err(DatabaseError::from)
+}
+
+#[cfg(feature = "ssr")]
+pub fn get_read_announcement_ids(
+    search_player_id: i64,
+    pool: &DieselPool,
+) -> Result<Vec<i64>, DatabaseError> {
+    use schema::announcement_reads::dsl::*;
+
+    announcement_reads
+        .filter(player_id.eq(search_player_id))
+        .select(announcement_id)
+        .load(&mut pool.get().expect("diesel"))
+        .map_err(DatabaseError::from)
+}
+
+#[cfg(feature = "ssr")]
+pub fn mark_announcement_read(
+    read_announcement_id: i64,
+    read_player_id: i64,
+    pool: &DieselPool,
+) -> Result<(), DatabaseError> {
+    use schema::announcement_reads::dsl::*;
+
+    diesel::insert_into(announcement_reads)
+        .values((announcement_id.eq(read_announcement_id), player_id.eq(read_player_id)))
+        .on_conflict_do_nothing()
+        .execute(&mut pool.get().expect("diesel"))
+        .map(|_| ())
+        .map_err(DatabaseError::from)
+}
+
+/// Everyone in the announcement's audience together with the time they read it.
+#[cfg(feature = "ssr")]
+pub fn get_announcement_readers(
+    search_announcement_id: i64,
+    search_audience: domain::AnnouncementAudience,
+    pool: &DieselPool,
+) -> Result<Vec<domain::AnnouncementReader>, DatabaseError> {
+    use schema::{announcement_reads, players};
+
+    let connection = &mut pool.get().expect("diesel");
+
+    let audience_player_ids = get_audience_player_ids(search_audience, connection)?;
+
+    let reads: HashMap<i64, chrono::DateTime<Utc>> = announcement_reads::table
+        .filter(announcement_reads::announcement_id.eq(search_announcement_id))
+        .select((announcement_reads::player_id, announcement_reads::created_at))
+        .load(connection)?
+        .into_iter()
+        .collect();
+
+    let audience_players: Vec<(i64, String)> = players::table
+        .filter(players::id.eq_any(audience_player_ids))
+        .order(players::tag_name.asc())
+        .select((players::id, players::tag_name))
+        .load(connection)?;
+
+    Ok(audience_players
+        .into_iter()
+        .map(|(reader_id, tag_name)| domain::AnnouncementReader {
+            player_id: reader_id,
+            tag_name,
+            read_at: reads.get(&reader_id).copied(),
+        })
+        .collect())
+}
+
+#[cfg(feature = "ssr")]
+pub fn get_platoons_for_leader(
+    search_leader_id: i64,
+    pool: &DieselPool,
+) -> Result<Vec<models::Platoon>, DatabaseError> {
+    use diesel::BoolExpressionMethods;
+    use schema::platoons::dsl::*;
+
+    platoons
+        .filter(leader_id.eq(search_leader_id).or(deputy_leader_id.eq(search_leader_id)))
+        .load::<models::Platoon>(&mut pool.get().expect("diesel"))
+        .map_err(DatabaseError::from)
+}
+
+#[cfg(feature = "ssr")]
+pub fn get_play_events_for_organizer(
+    search_organizer_id: i64,
+    pool: &DieselPool,
+) -> Result<Vec<models::PlayEvent>, DatabaseError> {
+    use schema::play_events::dsl::*;
+
+    play_events
+        .filter(organizer_id.eq(search_organizer_id))
+        .order(starts_at.asc())
+        .load::<models::PlayEvent>(&mut pool.get().expect("diesel"))
+        .map_err(DatabaseError::from)
+}
+
+/// Players whose private profile the leader may see: participants of the events they organize
+/// and members of the teams and platoons they lead.
+#[cfg(feature = "ssr")]
+pub fn get_player_ids_led_by(
+    search_leader_id: i64,
+    pool: &DieselPool,
+) -> Result<HashSet<i64>, DatabaseError> {
+    use schema::{play_event_registrations, play_events};
+
+    let participant_ids: Vec<i64> = play_event_registrations::table
+        .inner_join(play_events::table)
+        .filter(play_events::organizer_id.eq(search_leader_id))
+        .filter(play_event_registrations::status.ne(domain::RegistrationStatus::Withdrawn.as_str()))
+        .select(play_event_registrations::player_id)
+        .load(&mut pool.get().expect("diesel"))?;
+
+    let mut player_ids = get_member_ids_led_by(search_leader_id, pool)?;
+    player_ids.extend(participant_ids);
+
+    Ok(player_ids)
+}
+
+/// Members of the teams the leader is contact person of and of the platoons they lead or deputize.
+/// Unlike [`get_player_ids_led_by`] this leaves out event participants, anyone can organize an event.
+#[cfg(feature = "ssr")]
+pub fn get_member_ids_led_by(
+    search_leader_id: i64,
+    pool: &DieselPool,
+) -> Result<HashSet<i64>, DatabaseError> {
+    use diesel::{BoolExpressionMethods, JoinOnDsl, NullableExpressionMethods};
+    use schema::{platoons, players, teams};
+
+    let connection = &mut pool.get().expect("diesel");
+
+    let member_ids: Vec<i64> = players::table
+        .inner_join(teams::table.on(players::team_id.eq(teams::id.nullable())))
+        .filter(teams::contact_person_id.eq(search_leader_id))
+        .select(players::id)
+        .load(connection)?;
+
+    let platoon_ids: Vec<i64> = platoons::table
+        .filter(platoons::leader_id.eq(search_leader_id).or(platoons::deputy_leader_id.eq(search_leader_id)))
+        .select(platoons::id)
+        .load(connection)?;
+
+    let mut player_ids: HashSet<i64> = member_ids.into_iter().collect();
+
+    for search_platoon_id in platoon_ids {
+        player_ids.extend(platoon_player_ids(search_platoon_id, connection)?);
+    }
+
+    Ok(player_ids)
+}
+
+#[cfg(feature = "ssr")]
+pub fn find_announcement_for_id(
+    search_id: i64,
+    pool: &DieselPool,
+) -> Result<Option<models::Announcement>, DatabaseError> {
+    use schema::announcements::dsl::*;
+
+    announcements
+        .find(search_id)
+        .get_result(&mut pool.get().expect("diesel"))
+        .optional()
+        .map_err(DatabaseError::from)
+}
+
+#[cfg(feature = "ssr")]
+pub fn create_announcement(announcement: domain::Announcement, pool: &DieselPool) -> Result<i64, DatabaseError> {
+    use models::NewAnnouncement;
+    use schema::announcements;
+
+    let new_announcement = NewAnnouncement {
+        title: &announcement.title,
+        content: &announcement.content,
+        hidden: announcement.hidden,
+        publish_at: announcement.publish_at,
+        expires_at: announcement.expires_at,
+        author_id: announcement.author_id,
+        audience: announcement.audience.as_str(),
+        audience_id: announcement.audience.target_id(),
+    };
+
+    diesel::insert_into(announcements::table)
+        .values(&new_announcement)
+        .returning(announcements::id)
+        .get_result(&mut pool.get().expect("diesel"))
+        .map_err(DatabaseError::from)
+}
+
+#[cfg(feature = "ssr")]
+pub fn save_announcement(announcement: domain::Announcement, pool: &DieselPool) -> Result<(), DatabaseError> {
+    use schema::announcements::dsl::*;
+
+    diesel::update(announcements)
+        .filter(id.eq(announcement.id.unwrap()))
+        .set((
+            title.eq(&announcement.title),
+            content.eq(&announcement.content),
+            hidden.eq(announcement.hidden),
+            publish_at.eq(announcement.publish_at),
+            expires_at.eq(announcement.expires_at),
+            audience.eq(announcement.audience.as_str()),
+            audience_id.eq(announcement.audience.target_id()),
+        ))
+        .execute(&mut pool.get().expect("diesel"))
+        .map(|_| ())
+        .map_err(DatabaseError::from)
+}
+
+#[cfg(feature = "ssr")]
+pub fn delete_announcement(delete_id: i64, pool: &DieselPool) -> Result<(), DatabaseError> {
+    use schema::announcements::dsl::*;
+
+    diesel::delete(announcements.find(delete_id))
+        .execute(&mut pool.get().expect("diesel"))
+        .map(|_| ())
+        .map_err(DatabaseError::from)
+}
+
+#[cfg(feature = "ssr")]
+pub fn get_surveys_for_player(
+    search_player_id: i64,
+    pool: &DieselPool,
+) -> Result<Vec<models::Survey>, DatabaseError> {
+    use diesel::BoolExpressionMethods;
+    use schema::surveys::dsl::*;
+
+    let connection = &mut pool.get().expect("diesel");
+
+    let mut platoon_ids = Vec::new();
+    let mut team_ids = Vec::new();
+
+    for player_audience in get_audiences_for_player(search_player_id, connection)? {
+        match player_audience {
+            domain::AnnouncementAudience::Platoon(target_id) => platoon_ids.push(target_id),
+            domain::AnnouncementAudience::Team(target_id) => team_ids.push(target_id),
+            _ => (),
+        }
+    }
+
+    let addressed = audience.eq("platoon").and(audience_id.eq_any(platoon_ids))
+        .or(audience.eq("team").and(audience_id.eq_any(team_ids)))
+        .or(author_id.eq(Some(search_player_id)));
+
+    surveys
+        .filter(addressed)
+        .order(created_at.desc())
+        .load::<models::Survey>(connection)
+        .map_err(DatabaseError::from)
+}
+
+#[cfg(feature = "ssr")]
+pub fn get_all_surveys(pool: &DieselPool) -> Result<Vec<models::Survey>, DatabaseError> {
+    use schema::surveys::dsl::*;
+
+    surveys
+        .order(created_at.desc())
+        .load::<models::Survey>(&mut pool.get().expect("diesel"))
+        .map_err(DatabaseError::from)
+}
+
+#[cfg(feature = "ssr")]
+pub fn find_survey_for_id(search_id: i64, pool: &DieselPool) -> Result<Option<models::Survey>, DatabaseError> {
+    use schema::surveys::dsl::*;
+
+    surveys
+        .find(search_id)
+        .first::<models::Survey>(&mut pool.get().expect("diesel"))
+        .optional()
+        .map_err(DatabaseError::from)
+}
+
+#[cfg(feature = "ssr")]
+pub fn create_survey(survey: domain::Survey, pool: &DieselPool) -> Result<i64, DatabaseError> {
+    use models::NewSurvey;
+    use schema::surveys;
+
+    let new_survey = NewSurvey {
+        title: &survey.title,
+        description: &survey.description,
+        audience: survey.audience.as_str(),
+        audience_id: survey.audience.target_id().ok_or(DatabaseError::EntityNotFound)?,
+        author_id: survey.author_id,
+        anonymous: survey.anonymous,
+        deadline: survey.deadline,
+    };
+
+    diesel::insert_into(surveys::table)
+        .values(&new_survey)
+        .returning(surveys::id)
+        .get_result(&mut pool.get().expect("diesel"))
+        .map_err(DatabaseError::from)
+}
+
+#[cfg(feature = "ssr")]
+pub fn save_survey_deadline(
+    save_id: i64,
+    save_deadline: Option<chrono::DateTime<Utc>>,
+    pool: &DieselPool,
+) -> Result<(), DatabaseError> {
+    use schema::surveys::dsl::*;
+
+    diesel::update(surveys.find(save_id))
+        .set(deadline.eq(save_deadline))
+        .execute(&mut pool.get().expect("diesel"))
+        .map(|_| ())
+        .map_err(DatabaseError::from)
+}
+
+#[cfg(feature = "ssr")]
+pub fn delete_survey(delete_id: i64, pool: &DieselPool) -> Result<(), DatabaseError> {
+    use schema::surveys::dsl::*;
+
+    diesel::delete(surveys.find(delete_id))
+        .execute(&mut pool.get().expect("diesel"))
+        .map(|_| ())
+        .map_err(DatabaseError::from)
+}
+
+#[cfg(feature = "ssr")]
+pub fn get_survey_questions(
+    search_survey_id: i64,
+    pool: &DieselPool,
+) -> Result<Vec<domain::SurveyQuestion>, DatabaseError> {
+    use schema::{survey_question_options, survey_questions};
+
+    let connection = &mut pool.get().expect("diesel");
+
+    let questions = survey_questions::table
+        .filter(survey_questions::survey_id.eq(search_survey_id))
+        .order((survey_questions::position.asc(), survey_questions::id.asc()))
+        .load::<models::SurveyQuestion>(connection)?;
+
+    let question_ids: Vec<i64> = questions.iter().map(|question| question.id).collect();
+
+    let mut options: HashMap<i64, Vec<domain::SurveyOption>> = HashMap::new();
+
+    for option in survey_question_options::table
+        .filter(survey_question_options::question_id.eq_any(question_ids))
+        .order((survey_question_options::position.asc(), survey_question_options::id.asc()))
+        .load::<models::SurveyQuestionOption>(connection)?
+    {
+        options.entry(option.question_id).or_default().push(domain::SurveyOption {
+            id: option.id,
+            text: option.text,
+        });
+    }
+
+    Ok(questions
+        .into_iter()
+        .map(|question| domain::SurveyQuestion {
+            id: question.id,
+            kind: question.kind.parse().expect("question kind constrained by database"),
+            text: question.text,
+            options: options.remove(&question.id).unwrap_or_default(),
+        })
+        .collect())
+}
+
+#[cfg(feature = "ssr")]
+pub fn add_survey_question(
+    create_survey_id: i64,
+    create_text: &str,
+    create_kind: domain::SurveyQuestionKind,
+    create_options: Vec<String>,
+    pool: &DieselPool,
+) -> Result<i64, DatabaseError> {
+    use diesel::dsl::max;
+    use models::{NewSurveyQuestion, NewSurveyQuestionOption};
+    use schema::{survey_question_options, survey_questions};
+
+    pool.get().expect("diesel").transaction(|connection| {
+        let last_position: Option<i32> = survey_questions::table
+            .filter(survey_questions::survey_id.eq(create_survey_id))
+            .select(max(survey_questions::position))
+            .first(connection)?;
+
+        let question_id = diesel::insert_into(survey_questions::table)
+            .values(&NewSurveyQuestion {
+                survey_id: create_survey_id,
+                position: last_position.map_or(0, |position| position + 1),
+                text: create_text,
+                kind: create_kind.as_str(),
+            })
+            .returning(survey_questions::id)
+            .get_result(connection)?;
+
+        let new_options: Vec<NewSurveyQuestionOption> = create_options
+            .iter()
+            .enumerate()
+            .map(|(position, text)| NewSurveyQuestionOption {
+                question_id,
+                position: position as i32,
+                text,
+            })
+            .collect();
+
+        diesel::insert_into(survey_question_options::table)
+            .values(&new_options)
+            .execute(connection)?;
+
+        Ok(question_id)
+    })
+}
+
+#[cfg(feature = "ssr")]
+pub fn find_survey_id_for_question(
+    search_question_id: i64,
+    pool: &DieselPool,
+) -> Result<Option<i64>, DatabaseError> {
+    use schema::survey_questions::dsl::*;
+
+    survey_questions
+        .find(search_question_id)
+        .select(survey_id)
+        .first(&mut pool.get().expect("diesel"))
+        .optional()
+        .map_err(DatabaseError::from)
+}
+
+#[cfg(feature = "ssr")]
+pub fn delete_survey_question(delete_id: i64, pool: &DieselPool) -> Result<(), DatabaseError> {
+    use schema::survey_questions::dsl::*;
+
+    diesel::delete(survey_questions.find(delete_id))
+        .execute(&mut pool.get().expect("diesel"))
+        .map(|_| ())
+        .map_err(DatabaseError::from)
+}
+
+#[cfg(feature = "ssr")]
+pub fn survey_has_answers(search_survey_id: i64, pool: &DieselPool) -> Result<bool, DatabaseError> {
+    use schema::survey_answers::dsl::*;
+
+    diesel::select(diesel::dsl::exists(survey_answers.filter(survey_id.eq(search_survey_id))))
+        .get_result(&mut pool.get().expect("diesel"))
+        .map_err(DatabaseError::from)
+}
+
+#[cfg(feature = "ssr")]
+pub fn get_survey_answers_for_player(
+    search_survey_id: i64,
+    search_player_id: i64,
+    pool: &DieselPool,
+) -> Result<Vec<domain::SurveyAnswer>, DatabaseError> {
+    use schema::survey_answers::dsl::*;
+
+    Ok(survey_answers
+        .filter(survey_id.eq(search_survey_id))
+        .filter(player_id.eq(search_player_id))
+        .order(id.asc())
+        .load::<models::SurveyAnswer>(&mut pool.get().expect("diesel"))?
+        .into_iter()
+        .map(Into::into)
+        .collect())
+}
+
+#[cfg(feature = "ssr")]
+pub fn get_survey_answers(
+    search_survey_id: i64,
+    pool: &DieselPool,
+) -> Result<Vec<domain::RespondentAnswer>, DatabaseError> {
+    use schema::{players, survey_answers};
+
+    let answers: Vec<(models::SurveyAnswer, String)> = survey_answers::table
+        .inner_join(players::table)
+        .filter(survey_answers::survey_id.eq(search_survey_id))
+        .order((players::tag_name.asc(), survey_answers::id.asc()))
+        .select((models::SurveyAnswer::as_select(), players::tag_name))
+        .load(&mut pool.get().expect("diesel"))?;
+
+    Ok(answers
+        .into_iter()
+        .map(|(answer, tag_name)| domain::RespondentAnswer {
+            player_id: answer.player_id,
+            tag_name,
+            answer: answer.into(),
+        })
+        .collect())
+}
+
+/// Replaces all answers of the player, so changing an answer is the same as answering again.
+#[cfg(feature = "ssr")]
+pub fn replace_survey_answers(
+    save_survey_id: i64,
+    save_player_id: i64,
+    answers: Vec<domain::SurveyAnswer>,
+    pool: &DieselPool,
+) -> Result<(), DatabaseError> {
+    use models::NewSurveyAnswer;
+    use schema::survey_answers;
+
+    pool.get().expect("diesel").transaction(|connection| {
+        diesel::delete(
+            survey_answers::table
+                .filter(survey_answers::survey_id.eq(save_survey_id))
+                .filter(survey_answers::player_id.eq(save_player_id)),
+        )
+        .execute(connection)?;
+
+        let new_answers: Vec<NewSurveyAnswer> = answers
+            .iter()
+            .map(|answer| NewSurveyAnswer {
+                survey_id: save_survey_id,
+                question_id: answer.question_id,
+                player_id: save_player_id,
+                option_id: answer.option_id,
+                text: answer.text.as_deref(),
+            })
+            .collect();
+
+        diesel::insert_into(survey_answers::table)
+            .values(&new_answers)
+            .execute(connection)?;
+
+        Ok(())
+    })
+}
+
+#[cfg(feature = "ssr")]
+pub fn get_player_ids_for_audience(
+    search_audience: domain::AnnouncementAudience,
+    pool: &DieselPool,
+) -> Result<Vec<i64>, DatabaseError> {
+    get_audience_player_ids(search_audience, &mut pool.get().expect("diesel"))
+}
+
+#[cfg(feature = "ssr")]
+pub fn get_all_play_events(pool: &DieselPool) -> Result<Vec<models::PlayEvent>, DatabaseError> {
+    use schema::play_events::dsl::*;
+
+    play_events
+        .order(starts_at.asc())
+        .load::<models::PlayEvent>(&mut pool.get().expect("diesel"))
+        .map_err(DatabaseError::from)
+}
+
+#[cfg(feature = "ssr")]
+pub fn get_play_events_page(
+    window: PageWindow,
+    pool: &DieselPool,
+) -> Result<(Vec<models::PlayEvent>, i64), DatabaseError> {
+    use schema::play_events::dsl::*;
+
+    let connection = &mut pool.get().expect("diesel");
+
+    let total = play_events.count().get_result(connection)?;
+    let page = play_events
+        .order((starts_at.asc(), id.asc()))
+        .limit(window.limit)
+        .offset(window.offset)
+        .load::<models::PlayEvent>(connection)?;
+
+    Ok((page, total))
+}
+
+#[cfg(feature = "ssr")]
+pub fn find_play_event_for_id(
+    search_id: i64,
+    pool: &DieselPool,
+) -> Result<Option<models::PlayEvent>, DatabaseError> {
+    use schema::play_events::dsl::*;
+
+    play_events
+        .find(search_id)
+        .get_result(&mut pool.get().expect("diesel"))
+        .optional()
+        .map_err(DatabaseError::from)
+}
+
+#[cfg(feature = "ssr")]
+pub fn create_play_event(play_event: domain::PlayEvent, pool: &DieselPool) -> Result<i64, DatabaseError> {
+    use models::NewPlayEvent;
+    use schema::play_events;
+
+    let new_play_event = NewPlayEvent {
+        name: &play_event.name,
+        description: &play_event.description,
+        starts_at: play_event.starts_at,
+        ends_at: play_event.ends_at,
+        organizer_id: play_event.organizer_id,
+        capacity: play_event.capacity,
+        registration_deadline: play_event.registration_deadline,
+        min_age: play_event.min_age,
+        location_id: play_event.location_id,
+    };
+
+    diesel::insert_into(play_events::table)
+        .values(&new_play_event)
+        .returning(play_events::id)
+        .get_result(&mut pool.get().expect("diesel"))
+        .map_err(DatabaseError::from)
+}
+
+#[cfg(feature = "ssr")]
+pub fn save_play_event(play_event: domain::PlayEvent, pool: &DieselPool) -> Result<Vec<i64>, DatabaseError> {
+    use schema::play_events::dsl::*;
+
+    pool.get().expect("diesel").transaction(|connection| {
+        let event = diesel::update(play_events)
+            .filter(id.eq(play_event.id.unwrap()))
+            .set((
+                name.eq(&play_event.name),
+                description.eq(&play_event.description),
+                starts_at.eq(play_event.starts_at),
+                ends_at.eq(play_event.ends_at),
+                capacity.eq(play_event.capacity),
+                registration_deadline.eq(play_event.registration_deadline),
+                min_age.eq(play_event.min_age),
+                location_id.eq(play_event.location_id),
+                sequence.eq(sequence + 1),
+            ))
+            .returning(models::PlayEvent::as_returning())
+            .get_result(connection)?;
+
+        promote_waitlisted(&event, connection)
+    })
+}
+
+#[cfg(feature = "ssr")]
+pub fn cancel_play_event(cancel_id: i64, pool: &DieselPool) -> Result<(), DatabaseError> {
+    use schema::play_events::dsl::*;
+
+    diesel::update(play_events)
+        .filter(id.eq(cancel_id))
+        .filter(cancelled_at.is_null())
+        .set((
+            cancelled_at.eq(Some(Utc::now())),
+            sequence.eq(sequence + 1),
+        ))
+        .execute(&mut pool.get().expect("diesel"))?;
+
+    Ok(())
+}
+
+/// Events with a registered or waitlisted registration of any of the given players.
+#[cfg(feature = "ssr")]
+pub fn get_play_events_for_players(
+    search_player_ids: Vec<i64>,
+    pool: &DieselPool,
+) -> Result<Vec<models::PlayEvent>, DatabaseError> {
+    use schema::{play_event_registrations, play_events};
+
+    let registered_event_ids = play_event_registrations::table
+        .filter(play_event_registrations::player_id.eq_any(search_player_ids))
+        .filter(play_event_registrations::status.ne(domain::RegistrationStatus::Withdrawn.as_str()))
+        .select(play_event_registrations::play_event_id);
+
+    play_events::table
+        .filter(play_events::id.eq_any(registered_event_ids))
+        .order(play_events::starts_at.asc())
+        .load::<models::PlayEvent>(&mut pool.get().expect("diesel"))
+        .map_err(DatabaseError::from)
+}
+
+/// Ids of the players in the platoon's teams and of the platoon members without a team.
+#[cfg(feature = "ssr")]
+pub fn get_player_ids_for_platoon(
+    search_platoon_id: i64,
+    pool: &DieselPool,
+) -> Result<Vec<i64>, DatabaseError> {
+    platoon_player_ids(search_platoon_id, &mut pool.get().expect("diesel"))
+}
+
+#[cfg(feature = "ssr")]
+pub fn find_calendar_token(
+    search_player_id: i64,
+    pool: &DieselPool,
+) -> Result<Option<String>, DatabaseError> {
+    use schema::calendar_tokens::dsl::*;
+
+    calendar_tokens
+        .find(search_player_id)
+        .select(token)
+        .first(&mut pool.get().expect("diesel"))
+        .optional()
+        .map_err(DatabaseError::from)
+}
+
+#[cfg(feature = "ssr")]
+pub fn find_player_id_for_calendar_token(
+    search_token: &str,
+    pool: &DieselPool,
+) -> Result<Option<i64>, DatabaseError> {
+    use schema::calendar_tokens::dsl::*;
+
+    calendar_tokens
+        .filter(token.eq(search_token))
+        .select(player_id)
+        .first(&mut pool.get().expect("diesel"))
+        .optional()
+        .map_err(DatabaseError::from)
+}
+
+/// Stores a new calendar token for the player, invalidating the previous one.
+#[cfg(feature = "ssr")]
+pub fn save_calendar_token(
+    save_player_id: i64,
+    save_token: &str,
+    pool: &DieselPool,
+) -> Result<(), DatabaseError> {
+    use schema::calendar_tokens::dsl::*;
+
+    diesel::insert_into(calendar_tokens)
+        .values((player_id.eq(save_player_id), token.eq(save_token)))
+        .on_conflict(player_id)
+        .do_update()
+        .set(token.eq(save_token))
+        .execute(&mut pool.get().expect("diesel"))?;
+
+    Ok(())
+}
+
+#[cfg(feature = "ssr")]
+pub fn find_registration(
+    search_play_event_id: i64,
+    search_player_id: i64,
+    pool: &DieselPool,
+) -> Result<Option<models::PlayEventRegistration>, DatabaseError> {
+    use schema::play_event_registrations::dsl::*;
+
+    play_event_registrations
+        .filter(play_event_id.eq(search_play_event_id))
+        .filter(player_id.eq(search_player_id))
+        .first(&mut pool.get().expect("diesel"))
+        .optional()
+        .map_err(DatabaseError::from)
+}
+
+#[cfg(feature = "ssr")]
+pub fn register_player_for_event(
+    register_play_event_id: i64,
+    register_player_id: i64,
+    register_registered_by_id: i64,
+    pool: &DieselPool,
+) -> Result<domain::RegistrationStatus, DatabaseError> {
+    pool.get().expect("diesel").transaction(|connection| {
+        let event = lock_play_event(register_play_event_id, connection)?;
+
+        register_in_transaction(&event, register_player_id, register_registered_by_id, connection)
+    })
+}
+
+#[cfg(feature = "ssr")]
+pub fn register_team_for_event(
+    register_play_event_id: i64,
+    register_team_id: i64,
+    register_registered_by_id: i64,
+    pool: &DieselPool,
+) -> Result<Vec<(i64, domain::RegistrationStatus)>, DatabaseError> {
+    pool.get().expect("diesel").transaction(|connection| {
+        let event = lock_play_event(register_play_event_id, connection)?;
+
+        let member_ids: Vec<i64> = {
+            use schema::players::dsl::*;
+
+            players
+                .filter(team_id.eq(Some(register_team_id)))
+                .filter(active.eq(true))
+                .order(id.asc())
+                .select(id)
+                .load(connection)?
+        };
+
+        let mut results = Vec::with_capacity(member_ids.len());
+
+        for member_id in member_ids {
+            match register_in_transaction(&event, member_id, register_registered_by_id, connection) {
+                Ok(status) => results.push((member_id, status)),
+                Err(DatabaseError::AlreadyRegistered | DatabaseError::NotEligible(_)) => (),
+                Err(err) => return Err(err),
+            }
+        }
+
+        Ok(results)
+    })
+}
+
+/// Withdraws the player, a ride they offered is cancelled for its passengers.
+#[cfg(feature = "ssr")]
+pub fn withdraw_player_from_event(
+    withdraw_play_event_id: i64,
+    withdraw_player_id: i64,
+    pool: &DieselPool,
+) -> Result<domain::Withdrawal, DatabaseError> {
+    use schema::play_event_registrations::dsl::*;
+
+    pool.get().expect("diesel").transaction(|connection| {
+        let event = lock_play_event(withdraw_play_event_id, connection)?;
+
+        let updated = diesel::update(play_event_registrations)
+            .filter(play_event_id.eq(withdraw_play_event_id))
+            .filter(player_id.eq(withdraw_player_id))
+            .filter(status.ne(domain::RegistrationStatus::Withdrawn.as_str()))
+            .set((
+                status.eq(domain::RegistrationStatus::Withdrawn.as_str()),
+                withdrawn_at.eq(Some(Utc::now())),
+            ))
+            .execute(connection)?;
+
+        if updated == 0 {
+            return Err(DatabaseError::NotRegistered);
+        }
+
+        let mut stranded_passenger_ids = Vec::new();
+
+        {
+            use schema::faction_assignments;
+
+            diesel::delete(faction_assignments::table)
+                .filter(faction_assignments::play_event_id.eq(withdraw_play_event_id))
+                .filter(faction_assignments::player_id.eq(withdraw_player_id))
+                .execute(connection)?;
+        }
+
+        {
+            use schema::rental_loans;
+
+            diesel::delete(rental_loans::table)
+                .filter(rental_loans::play_event_id.eq(withdraw_play_event_id))
+                .filter(rental_loans::player_id.eq(withdraw_player_id))
+                .filter(rental_loans::checked_out_at.is_null())
+                .execute(connection)?;
+        }
+
+        {
+            use schema::{ride_bookings, ride_offers, ride_requests};
+
+            let own_offer_id = ride_offers::table
+                .filter(ride_offers::play_event_id.eq(withdraw_play_event_id))
+                .filter(ride_offers::driver_id.eq(withdraw_player_id))
+                .filter(ride_offers::cancelled_at.is_null())
+                .select(ride_offers::id)
+                .first::<i64>(connection)
+                .optional()?;
+
+            if let Some(own_offer_id) = own_offer_id {
+                stranded_passenger_ids = cancel_ride_offer_in_transaction(own_offer_id, connection)?;
+            }
+
+            diesel::delete(ride_requests::table)
+                .filter(ride_requests::play_event_id.eq(withdraw_play_event_id))
+                .filter(ride_requests::passenger_id.eq(withdraw_player_id))
+                .execute(connection)?;
+
+            let event_offer_ids = ride_offers::table
+                .filter(ride_offers::play_event_id.eq(withdraw_play_event_id))
+                .select(ride_offers::id);
+
+            diesel::update(ride_bookings::table)
+                .filter(ride_bookings::ride_offer_id.eq_any(event_offer_ids))
+                .filter(ride_bookings::passenger_id.eq(withdraw_player_id))
+                .filter(ride_bookings::status.eq_any([
+                    domain::BookingStatus::Requested.as_str(),
+                    domain::BookingStatus::Confirmed.as_str(),
+                ]))
+                .set((
+                    ride_bookings::status.eq(domain::BookingStatus::Cancelled.as_str()),
+                    ride_bookings::driver_seen.eq(false),
+                ))
+                .execute(connection)?;
+        }
+
+        Ok(domain::Withdrawal {
+            promoted_ids: promote_waitlisted(&event, connection)?,
+            stranded_passenger_ids,
+        })
+    })
+}
+
+#[cfg(feature = "ssr")]
+pub fn get_participants_for_event(
+    search_play_event_id: i64,
+    pool: &DieselPool,
+) -> Result<Vec<domain::Participant>, DatabaseError> {
+    load_participants(search_play_event_id, None, &mut pool.get().expect("diesel"))
+}
+
+/// Returns the participants in the window by registration, together with the number of all participants.
+#[cfg(feature = "ssr")]
+pub fn get_participants_for_event_page(
+    search_play_event_id: i64,
+    window: PageWindow,
+    pool: &DieselPool,
+) -> Result<(Vec<domain::Participant>, i64), DatabaseError> {
+    use schema::play_event_registrations;
+
+    let connection = &mut pool.get().expect("diesel");
+
+    let total = play_event_registrations::table
+        .filter(play_event_registrations::play_event_id.eq(search_play_event_id))
+        .filter(play_event_registrations::status.ne(domain::RegistrationStatus::Withdrawn.as_str()))
+        .count()
+        .get_result(connection)?;
+    let page = load_participants(search_play_event_id, Some(window), connection)?;
+
+    Ok((page, total))
+}
+
+#[cfg(feature = "ssr")]
+fn load_participants(
+    search_play_event_id: i64,
+    window: Option<PageWindow>,
+    connection: &mut PgConnection,
+) -> Result<Vec<domain::Participant>, DatabaseError> {
+    use diesel::{JoinOnDsl, NullableExpressionMethods};
+    use schema::{platoons, play_event_registrations, players, teams};
+
+    let mut query = play_event_registrations::table
+        .inner_join(players::table.on(players::id.eq(play_event_registrations::player_id)))
+        .left_join(teams::table.on(players::team_id.eq(teams::id.nullable())))
+        .left_join(platoons::table.on(teams::platoon_id.eq(platoons::id.nullable())))
+        .filter(play_event_registrations::play_event_id.eq(search_play_event_id))
+        .filter(play_event_registrations::status.ne(domain::RegistrationStatus::Withdrawn.as_str()))
+        .order((play_event_registrations::registered_at.asc(), play_event_registrations::id.asc()))
+        .select((
+            models::PlayEventRegistration::as_select(),
+            players::tag_name,
+            teams::id.nullable(),
+            teams::name.nullable(),
+            platoons::id.nullable(),
+            platoons::name.nullable(),
+        ))
+        .into_boxed();
+
+    if let Some(window) = window {
+        query = query.limit(window.limit).offset(window.offset);
+    }
+
+    let rows = query
+        .load::<(models::PlayEventRegistration, String, Option<i64>, Option<String>, Option<i64>, Option<String>)>(
+            connection,
+        )?;
+
+    let participants = rows
+        .into_iter()
+        .map(|(registration, tag_name, team_id, team_name, platoon_id, platoon_name)| {
+            let registration = domain::Registration::from(registration);
+
+            domain::Participant {
+                player_id: registration.player_id,
+                tag_name,
+                team_id,
+                team_name,
+                platoon_id,
+                platoon_name,
+                status: registration.status,
+                registered_at: registration.registered_at,
+                checked_in_at: registration.checked_in_at,
+            }
+        })
+        .collect();
+
+    Ok(participants)
+}
+
+#[cfg(feature = "ssr")]
+pub fn find_registration_for_id(
+    search_id: i64,
+    pool: &DieselPool,
+) -> Result<Option<models::PlayEventRegistration>, DatabaseError> {
+    use schema::play_event_registrations::dsl::*;
+
+    play_event_registrations
+        .find(search_id)
+        .get_result(&mut pool.get().expect("diesel"))
+        .optional()
+        .map_err(DatabaseError::from)
+}
+
+/// Checks in the holder of the registration unless a reason to turn them away is found.
+#[cfg(feature = "ssr")]
+pub fn check_in_registration(
+    registration_id: i64,
+    check_in_play_event_id: i64,
+    check_in_by_id: i64,
+    pool: &DieselPool,
+) -> Result<domain::CheckInResult, DatabaseError> {
+    use domain::{CheckInOutcome, CheckInResult, RegistrationStatus};
+    use schema::play_event_registrations::dsl::*;
+
+    pool.get().expect("diesel").transaction(|connection| {
+        let registration: Option<models::PlayEventRegistration> = play_event_registrations
+            .find(registration_id)
+            .for_update()
+            .first(connection)
+            .optional()?;
+
+        let Some(registration) = registration else {
+            return Ok(CheckInResult::invalid(CheckInOutcome::InvalidTicket));
+        };
+
+        let player: models::Player = {
+            use schema::players;
+
+            players::table
+                .find(registration.player_id)
+                .first(connection)?
+        };
+
+        let registration = domain::Registration::from(registration);
+        let suspensions = suspensions_for_player(player.id, connection)?;
+        let suspension = domain::active_suspension(&suspensions, Utc::now().date_naive());
+
+        let outcome = if registration.play_event_id != check_in_play_event_id {
+            CheckInOutcome::WrongEvent
+        } else if let Some(at) = registration.checked_in_at {
+            CheckInOutcome::AlreadyCheckedIn(at)
+        } else if registration.status != RegistrationStatus::Registered {
+            CheckInOutcome::NotRegistered(registration.status)
+        } else if !player.active {
+            CheckInOutcome::PlayerInactive
+        } else if let Some(suspension) = suspension {
+            CheckInOutcome::Suspended(suspension.ends_on)
+        } else {
+            CheckInOutcome::CheckedIn
+        };
+
+        if outcome.is_ok() {
+            diesel::update(play_event_registrations.find(registration.id))
+                .set((
+                    checked_in_at.eq(Some(Utc::now())),
+                    checked_in_by_id.eq(Some(check_in_by_id)),
+                ))
+                .execute(connection)?;
+        }
+
+        Ok(CheckInResult {
+            outcome,
+            player_id: Some(player.id),
+            tag_name: Some(player.tag_name),
+        })
+    })
+}
+
+/// Loads the registrations of the given players, or of everybody if no players are given.
+/// Cancelled events are left out, nobody could attend them.
+#[cfg(feature = "ssr")]
+pub fn get_attendance_records(
+    filter_player_ids: Option<Vec<i64>>,
+    pool: &DieselPool,
+) -> Result<Vec<domain::AttendanceRecord>, DatabaseError> {
+    use schema::{play_event_registrations, play_events};
+
+    let mut query = play_event_registrations::table
+        .inner_join(play_events::table)
+        .filter(play_events::cancelled_at.is_null())
+        .select((
+            play_event_registrations::player_id,
+            play_events::id,
+            play_events::starts_at,
+            play_events::ends_at,
+            play_events::registration_deadline,
+            play_event_registrations::status,
+            play_event_registrations::withdrawn_at,
+            play_event_registrations::checked_in_at,
+        ))
+        .into_boxed();
+
+    if let Some(filter_player_ids) = filter_player_ids {
+        query = query.filter(play_event_registrations::player_id.eq_any(filter_player_ids));
+    }
+
+    let rows = query.load::<(
+        i64,
+        i64,
+        chrono::DateTime<Utc>,
+        chrono::DateTime<Utc>,
+        Option<chrono::DateTime<Utc>>,
+        String,
+        Option<chrono::DateTime<Utc>>,
+        Option<chrono::DateTime<Utc>>,
+    )>(&mut pool.get().expect("diesel"))?;
+
+    let records = rows
+        .into_iter()
+        .map(|(player_id, play_event_id, starts_at, ends_at, registration_deadline, status, withdrawn_at, checked_in_at)| {
+            domain::AttendanceRecord {
+                player_id,
+                play_event_id,
+                starts_at,
+                ends_at,
+                registration_deadline,
+                status: status.parse().expect("registration status constrained by database"),
+                withdrawn_at,
+                checked_in_at,
+            }
+        })
+        .collect();
+
+    Ok(records)
+}
+
+#[cfg(feature = "ssr")]
+pub fn get_factions_for_event(
+    search_play_event_id: i64,
+    pool: &DieselPool,
+) -> Result<Vec<models::Faction>, DatabaseError> {
+    use schema::factions::dsl::*;
+
+    factions
+        .filter(play_event_id.eq(search_play_event_id))
+        .order(id.asc())
+        .load::<models::Faction>(&mut pool.get().expect("diesel"))
+        .map_err(DatabaseError::from)
+}
+
+#[cfg(feature = "ssr")]
+pub fn find_faction_for_id(
+    search_id: i64,
+    pool: &DieselPool,
+) -> Result<Option<models::Faction>, DatabaseError> {
+    use schema::factions::dsl::*;
+
+    factions
+        .find(search_id)
+        .get_result(&mut pool.get().expect("diesel"))
+        .optional()
+        .map_err(DatabaseError::from)
+}
+
+#[cfg(feature = "ssr")]
+pub fn create_faction(
+    create_play_event_id: i64,
+    create_name: String,
+    pool: &DieselPool,
+) -> Result<(), DatabaseError> {
+    use models::NewFaction;
+    use schema::factions;
+
+    let new_faction = NewFaction {
+        play_event_id: create_play_event_id,
+        name: &create_name,
+    };
+
+    diesel::insert_into(factions::table)
+        .values(&new_faction)
+        .execute(&mut pool.get().expect("diesel"))
+        .map(|_| ())
+        .map_err(DatabaseError::from)
+}
+
+#[cfg(feature = "ssr")]
+pub fn delete_faction(delete_id: i64, pool: &DieselPool) -> Result<(), DatabaseError> {
+    use schema::factions::dsl::*;
+
+    diesel::delete(factions.find(delete_id))
+        .execute(&mut pool.get().expect("diesel"))
+        .map(|_| ())
+        .map_err(DatabaseError::from)
+}
+
+#[cfg(feature = "ssr")]
+pub fn get_faction_assignments_for_event(
+    search_play_event_id: i64,
+    pool: &DieselPool,
+) -> Result<Vec<models::FactionAssignment>, DatabaseError> {
+    use schema::faction_assignments::dsl::*;
+
+    faction_assignments
+        .filter(play_event_id.eq(search_play_event_id))
+        .select(models::FactionAssignment::as_select())
+        .load(&mut pool.get().expect("diesel"))
+        .map_err(DatabaseError::from)
+}
+
+/// Moves the players into the faction, or removes their assignment if no faction is given.
+#[cfg(feature = "ssr")]
+pub fn assign_players_to_faction(
+    assign_play_event_id: i64,
+    assign_player_ids: Vec<i64>,
+    assign_faction_id: Option<i64>,
+    pool: &DieselPool,
+) -> Result<(), DatabaseError> {
+    use diesel::upsert::excluded;
+    use schema::faction_assignments::dsl::*;
+
+    let connection = &mut pool.get().expect("diesel");
+
+    match assign_faction_id {
+        Some(assign_faction_id) => {
+            let assignments: Vec<models::FactionAssignment> = assign_player_ids
+                .into_iter()
+                .map(|assign_player_id| models::FactionAssignment {
+                    play_event_id: assign_play_event_id,
+                    player_id: assign_player_id,
+                    faction_id: assign_faction_id,
+                })
+                .collect();
+
+            diesel::insert_into(faction_assignments)
+                .values(&assignments)
+                .on_conflict((play_event_id, player_id))
+                .do_update()
+                .set(faction_id.eq(excluded(faction_id)))
+                .execute(connection)?;
+        }
+        None => {
+            diesel::delete(faction_assignments)
+                .filter(play_event_id.eq(assign_play_event_id))
+                .filter(player_id.eq_any(assign_player_ids))
+                .execute(connection)?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "ssr")]
+pub fn replace_faction_assignments(
+    replace_play_event_id: i64,
+    assignments: Vec<(i64, i64)>,
+    pool: &DieselPool,
+) -> Result<(), DatabaseError> {
+    use schema::faction_assignments::dsl::*;
+
+    let assignments: Vec<models::FactionAssignment> = assignments
+        .into_iter()
+        .map(|(assign_player_id, assign_faction_id)| models::FactionAssignment {
+            play_event_id: replace_play_event_id,
+            player_id: assign_player_id,
+            faction_id: assign_faction_id,
+        })
+        .collect();
+
+    pool.get().expect("diesel").transaction(|connection| {
+        diesel::delete(faction_assignments)
+            .filter(play_event_id.eq(replace_play_event_id))
+            .execute(connection)?;
+
+        diesel::insert_into(faction_assignments)
+            .values(&assignments)
+            .execute(connection)?;
+
+        Ok(())
+    })
+}
+
+#[cfg(feature = "ssr")]
+pub fn get_replicas_for_player(
+    search_player_id: i64,
+    pool: &DieselPool,
+) -> Result<Vec<models::Replica>, DatabaseError> {
+    use schema::replicas::dsl::*;
+
+    replicas
+        .filter(player_id.eq(search_player_id))
+        .order((class.asc(), name.asc()))
+        .load::<models::Replica>(&mut pool.get().expect("diesel"))
+        .map_err(DatabaseError::from)
+}
+
+#[cfg(feature = "ssr")]
+pub fn find_replica_for_id(search_id: i64, pool: &DieselPool) -> Result<Option<models::Replica>, DatabaseError> {
+    use schema::replicas::dsl::*;
+
+    replicas
+        .find(search_id)
+        .first::<models::Replica>(&mut pool.get().expect("diesel"))
+        .optional()
+        .map_err(DatabaseError::from)
+}
+
+#[cfg(feature = "ssr")]
+pub fn create_replica(replica: domain::Replica, pool: &DieselPool) -> Result<i64, DatabaseError> {
+    use models::NewReplica;
+    use schema::replicas;
+
+    let new_replica = NewReplica {
+        player_id: replica.player_id,
+        name: &replica.name,
+        class: replica.class.as_str(),
+        fps: replica.fps,
+        bb_weight: replica.bb_weight,
+    };
+
+    diesel::insert_into(replicas::table)
+        .values(&new_replica)
+        .returning(replicas::id)
+        .get_result(&mut pool.get().expect("diesel"))
+        .map_err(DatabaseError::from)
+}
+
+#[cfg(feature = "ssr")]
+pub fn delete_replica(delete_id: i64, pool: &DieselPool) -> Result<(), DatabaseError> {
+    use schema::replicas::dsl::*;
+
+    diesel::delete(replicas.find(delete_id))
+        .execute(&mut pool.get().expect("diesel"))
+        .map(|_| ())
+        .map_err(DatabaseError::from)
+}
+
+#[cfg(feature = "ssr")]
+pub fn get_class_limits_for_event(
+    search_play_event_id: i64,
+    pool: &DieselPool,
+) -> Result<Vec<models::PlayEventClassLimit>, DatabaseError> {
+    use schema::play_event_class_limits::dsl::*;
+
+    play_event_class_limits
+        .filter(play_event_id.eq(search_play_event_id))
+        .order(class.asc())
+        .load::<models::PlayEventClassLimit>(&mut pool.get().expect("diesel"))
+        .map_err(DatabaseError::from)
+}
+
+#[cfg(feature = "ssr")]
+pub fn save_class_limit(
+    save_play_event_id: i64,
+    limit: domain::ClassLimit,
+    pool: &DieselPool,
+) -> Result<(), DatabaseError> {
+    use models::NewPlayEventClassLimit;
+    use schema::play_event_class_limits::dsl::*;
+
+    let new_limit = NewPlayEventClassLimit {
+        play_event_id: save_play_event_id,
+        class: limit.class.as_str(),
+        max_joules: limit.max_joules,
+        min_distance_meters: limit.min_distance_meters,
+    };
+
+    diesel::insert_into(play_event_class_limits)
+        .values(&new_limit)
+        .on_conflict((play_event_id, class))
+        .do_update()
+        .set((
+            max_joules.eq(limit.max_joules),
+            min_distance_meters.eq(limit.min_distance_meters),
+        ))
+        .execute(&mut pool.get().expect("diesel"))?;
+
+    Ok(())
+}
+
+#[cfg(feature = "ssr")]
+pub fn delete_class_limit(
+    delete_play_event_id: i64,
+    delete_class: domain::ReplicaClass,
+    pool: &DieselPool,
+) -> Result<(), DatabaseError> {
+    use schema::play_event_class_limits::dsl::*;
+
+    diesel::delete(play_event_class_limits.find((delete_play_event_id, delete_class.as_str())))
+        .execute(&mut pool.get().expect("diesel"))
+        .map(|_| ())
+        .map_err(DatabaseError::from)
+}
+
+/// Replicas of all registered players, together with their tag name and whether they checked in.
+#[cfg(feature = "ssr")]
+pub fn get_replicas_for_event(
+    search_play_event_id: i64,
+    pool: &DieselPool,
+) -> Result<Vec<(models::Replica, String, bool)>, DatabaseError> {
+    use diesel::JoinOnDsl;
+    use schema::{play_event_registrations, players, replicas};
+
+    let rows = replicas::table
+        .inner_join(players::table)
+        .inner_join(play_event_registrations::table.on(play_event_registrations::player_id.eq(replicas::player_id)))
+        .filter(play_event_registrations::play_event_id.eq(search_play_event_id))
+        .filter(play_event_registrations::status.eq(domain::RegistrationStatus::Registered.as_str()))
+        .order((players::tag_name.asc(), replicas::class.asc(), replicas::name.asc()))
+        .select((
+            models::Replica::as_select(),
+            players::tag_name,
+            play_event_registrations::checked_in_at,
+        ))
+        .load::<(models::Replica, String, Option<chrono::DateTime<Utc>>)>(&mut pool.get().expect("diesel"))?;
+
+    Ok(rows
+        .into_iter()
+        .map(|(replica, tag_name, checked_in_at)| (replica, tag_name, checked_in_at.is_some()))
+        .collect())
+}
+
+#[cfg(feature = "ssr")]
+pub fn get_parental_consents_for_player(
+    search_player_id: i64,
+    pool: &DieselPool,
+) -> Result<Vec<domain::ParentalConsent>, DatabaseError> {
+    parental_consents_for_player(search_player_id, &mut pool.get().expect("diesel"))
+}
+
+#[cfg(feature = "ssr")]
+fn parental_consents_for_player(
+    search_player_id: i64,
+    connection: &mut PgConnection,
+) -> Result<Vec<domain::ParentalConsent>, DatabaseError> {
+    use schema::parental_consent_documents;
+    use schema::parental_consents::dsl::*;
+
+    let search_consents = parental_consents
+        .filter(player_id.eq(search_player_id))
+        .order(valid_until.desc())
+        .load::<models::ParentalConsent>(connection)?;
+
+    let document_consent_ids: HashSet<i64> = parental_consent_documents::table
+        .filter(parental_consent_documents::consent_id.eq_any(search_consents.iter().map(|consent| consent.id).collect::<Vec<_>>()))
+        .select(parental_consent_documents::consent_id)
+        .load::<i64>(connection)?
+        .into_iter()
+        .collect();
+
+    Ok(search_consents
+        .into_iter()
+        .map(|consent| {
+            let has_document = document_consent_ids.contains(&consent.id);
+            domain::ParentalConsent::from((consent, has_document))
+        })
+        .collect())
+}
+
+#[cfg(feature = "ssr")]
+pub fn find_parental_consent_for_id(
+    search_id: i64,
+    pool: &DieselPool,
+) -> Result<Option<models::ParentalConsent>, DatabaseError> {
+    use schema::parental_consents::dsl::*;
+
+    parental_consents
+        .find(search_id)
+        .first::<models::ParentalConsent>(&mut pool.get().expect("diesel"))
+        .optional()
+        .map_err(DatabaseError::from)
+}
+
+/// Stores the consent together with the uploaded form, if there is one.
+#[cfg(feature = "ssr")]
+pub fn create_parental_consent(
+    consent: domain::ParentalConsent,
+    document: Option<(&str, &[u8])>,
+    pool: &DieselPool,
+) -> Result<i64, DatabaseError> {
+    use models::NewParentalConsent;
+    use schema::{parental_consent_documents, parental_consents};
+
+    let new_consent = NewParentalConsent {
+        player_id: consent.player_id,
+        kind: consent.kind.as_str(),
+        guardian_name: &consent.guardian_name,
+        note: &consent.note,
+        recorded_by_id: consent.recorded_by_id,
+        valid_until: consent.valid_until,
+    };
+
+    pool.get().expect("diesel").transaction(|connection| {
+        let create_id = diesel::insert_into(parental_consents::table)
+            .values(&new_consent)
+            .returning(parental_consents::id)
+            .get_result::<i64>(connection)?;
+
+        if let Some((create_content_type, create_data)) = document {
+            diesel::insert_into(parental_consent_documents::table)
+                .values((
+                    parental_consent_documents::consent_id.eq(create_id),
+                    parental_consent_documents::content_type.eq(create_content_type),
+                    parental_consent_documents::data.eq(create_data),
+                ))
+                .execute(connection)?;
+        }
+
+        Ok(create_id)
+    })
+}
+
+/// The player the consent belongs to, with the uploaded form's content type and data.
+#[cfg(feature = "ssr")]
+pub fn find_parental_consent_document(
+    search_consent_id: i64,
+    pool: &DieselPool,
+) -> Result<Option<(i64, String, Vec<u8>)>, DatabaseError> {
+    use schema::{parental_consent_documents, parental_consents};
+
+    parental_consent_documents::table
+        .inner_join(parental_consents::table)
+        .filter(parental_consent_documents::consent_id.eq(search_consent_id))
+        .select((
+            parental_consents::player_id,
+            parental_consent_documents::content_type,
+            parental_consent_documents::data,
+        ))
+        .first(&mut pool.get().expect("diesel"))
+        .optional()
+        .map_err(DatabaseError::from)
+}
+
+#[cfg(feature = "ssr")]
+pub fn delete_parental_consent(delete_id: i64, pool: &DieselPool) -> Result<(), DatabaseError> {
+    use schema::parental_consents::dsl::*;
+
+    diesel::delete(parental_consents.find(delete_id))
+        .execute(&mut pool.get().expect("diesel"))
+        .map(|_| ())
+        .map_err(DatabaseError::from)
+}
+
+#[cfg(feature = "ssr")]
+pub fn get_incident_reports_for_player(
+    search_player_id: i64,
+    pool: &DieselPool,
+) -> Result<Vec<domain::IncidentReport>, DatabaseError> {
+    use diesel::NullableExpressionMethods;
+    use schema::{incident_reports, play_events, players};
+
+    let connection = &mut pool.get().expect("diesel");
+
+    let rows = incident_reports::table
+        .left_join(play_events::table)
+        .filter(incident_reports::player_id.eq(search_player_id))
+        .order((incident_reports::created_at.desc(), incident_reports::id.desc()))
+        .select((models::IncidentReport::as_select(), play_events::name.nullable()))
+        .load::<(models::IncidentReport, Option<String>)>(connection)?;
+
+    let reporter_ids: Vec<i64> = rows
+        .iter()
+        .filter_map(|(report, _)| report.reported_by_id)
+        .collect();
+
+    let reporter_names: HashMap<i64, String> = players::table
+        .filter(players::id.eq_any(reporter_ids))
+        .select((players::id, players::tag_name))
+        .load::<(i64, String)>(connection)?
+        .into_iter()
+        .collect();
+
+    Ok(rows
+        .into_iter()
+        .map(|(report, play_event_name)| domain::IncidentReport {
+            id: Some(report.id),
+            player_id: report.player_id,
+            play_event_id: report.play_event_id,
+            play_event_name,
+            reported_by_id: report.reported_by_id,
+            reported_by_name: report.reported_by_id.and_then(|reporter_id| reporter_names.get(&reporter_id).cloned()),
+            category: report.category.parse().expect("incident category constrained by database"),
+            description: report.description,
+            reported_at: report.created_at,
+        })
+        .collect())
+}
+
+#[cfg(feature = "ssr")]
+pub fn find_incident_report_for_id(
+    search_id: i64,
+    pool: &DieselPool,
+) -> Result<Option<models::IncidentReport>, DatabaseError> {
+    use schema::incident_reports::dsl::*;
+
+    incident_reports
+        .find(search_id)
+        .first::<models::IncidentReport>(&mut pool.get().expect("diesel"))
+        .optional()
+        .map_err(DatabaseError::from)
+}
+
+#[cfg(feature = "ssr")]
+pub fn create_incident_report(report: domain::IncidentReport, pool: &DieselPool) -> Result<i64, DatabaseError> {
+    use models::NewIncidentReport;
+    use schema::incident_reports;
+
+    let new_report = NewIncidentReport {
+        player_id: report.player_id,
+        play_event_id: report.play_event_id,
+        reported_by_id: report.reported_by_id,
+        category: report.category.as_str(),
+        description: &report.description,
+    };
+
+    diesel::insert_into(incident_reports::table)
+        .values(&new_report)
+        .returning(incident_reports::id)
+        .get_result(&mut pool.get().expect("diesel"))
+        .map_err(DatabaseError::from)
+}
+
+#[cfg(feature = "ssr")]
+pub fn delete_incident_report(delete_id: i64, pool: &DieselPool) -> Result<(), DatabaseError> {
+    use schema::incident_reports::dsl::*;
+
+    diesel::delete(incident_reports.find(delete_id))
+        .execute(&mut pool.get().expect("diesel"))
+        .map(|_| ())
+        .map_err(DatabaseError::from)
+}
+
+#[cfg(feature = "ssr")]
+pub fn get_suspensions_for_player(
+    search_player_id: i64,
+    pool: &DieselPool,
+) -> Result<Vec<domain::Suspension>, DatabaseError> {
+    suspensions_for_player(search_player_id, &mut pool.get().expect("diesel"))
+}
+
+#[cfg(feature = "ssr")]
+fn suspensions_for_player(
+    search_player_id: i64,
+    connection: &mut PgConnection,
+) -> Result<Vec<domain::Suspension>, DatabaseError> {
+    use schema::suspensions::dsl::*;
+
+    suspensions
+        .filter(player_id.eq(search_player_id))
+        .order((starts_on.desc(), id.desc()))
+        .load::<models::Suspension>(connection)
+        .map(|rows| rows.into_iter().map(domain::Suspension::from).collect())
+        .map_err(DatabaseError::from)
+}
+
+#[cfg(feature = "ssr")]
+pub fn find_suspension_for_id(
+    search_id: i64,
+    pool: &DieselPool,
+) -> Result<Option<models::Suspension>, DatabaseError> {
+    use schema::suspensions::dsl::*;
+
+    suspensions
+        .find(search_id)
+        .first::<models::Suspension>(&mut pool.get().expect("diesel"))
+        .optional()
+        .map_err(DatabaseError::from)
+}
+
+#[cfg(feature = "ssr")]
+pub fn create_suspension(suspension: domain::Suspension, pool: &DieselPool) -> Result<i64, DatabaseError> {
+    use models::NewSuspension;
+    use schema::suspensions;
+
+    let new_suspension = NewSuspension {
+        player_id: suspension.player_id,
+        incident_report_id: suspension.incident_report_id,
+        issued_by_id: suspension.issued_by_id,
+        reason: &suspension.reason,
+        starts_on: suspension.starts_on,
+        ends_on: suspension.ends_on,
+    };
+
+    diesel::insert_into(suspensions::table)
+        .values(&new_suspension)
+        .returning(suspensions::id)
+        .get_result(&mut pool.get().expect("diesel"))
+        .map_err(DatabaseError::from)
+}
+
+/// Ends the suspension the day before `today`, a suspension that has not started yet is removed.
+#[cfg(feature = "ssr")]
+pub fn lift_suspension(lift_id: i64, today: NaiveDate, pool: &DieselPool) -> Result<(), DatabaseError> {
+    use diesel::BoolExpressionMethods;
+    use schema::suspensions::dsl::*;
+
+    pool.get().expect("diesel").transaction(|connection| {
+        diesel::delete(suspensions.filter(id.eq(lift_id)).filter(starts_on.ge(today)))
+            .execute(connection)?;
+
+        diesel::update(suspensions.filter(id.eq(lift_id)).filter(ends_on.is_null().or(ends_on.ge(today))))
+            .set(ends_on.eq(today.pred_opt()))
+            .execute(connection)?;
+
+        Ok(())
+    })
+}
+
+#[cfg(feature = "ssr")]
+pub fn get_all_legal_documents(pool: &DieselPool) -> Result<Vec<models::LegalDocument>, DatabaseError> {
+    use schema::legal_documents::dsl::*;
+
+    legal_documents
+        .order((kind.asc(), version.desc()))
+        .load::<models::LegalDocument>(&mut pool.get().expect("diesel"))
+        .map_err(DatabaseError::from)
+}
+
+#[cfg(feature = "ssr")]
+pub fn find_legal_document_for_id(
+    search_id: i64,
+    pool: &DieselPool,
+) -> Result<Option<models::LegalDocument>, DatabaseError> {
+    use schema::legal_documents::dsl::*;
+
+    legal_documents
+        .find(search_id)
+        .first::<models::LegalDocument>(&mut pool.get().expect("diesel"))
+        .optional()
+        .map_err(DatabaseError::from)
+}
+
+/// Creates an unpublished document with the next version number of its kind.
+#[cfg(feature = "ssr")]
+pub fn create_legal_document(document: domain::LegalDocument, pool: &DieselPool) -> Result<i64, DatabaseError> {
+    use diesel::dsl::max;
+    use models::NewLegalDocument;
+    use schema::legal_documents;
+
+    pool.get().expect("diesel").transaction(|connection| {
+        let latest_version: Option<i32> = legal_documents::table
+            .filter(legal_documents::kind.eq(document.kind.as_str()))
+            .select(max(legal_documents::version))
+            .first(connection)?;
+
+        let new_document = NewLegalDocument {
+            kind: document.kind.as_str(),
+            version: latest_version.unwrap_or(0) + 1,
+            title: &document.title,
+            content: &document.content,
+            author_id: document.author_id,
+        };
+
+        diesel::insert_into(legal_documents::table)
+            .values(&new_document)
+            .returning(legal_documents::id)
+            .get_result(connection)
+            .map_err(DatabaseError::from)
+    })
+}
+
+#[cfg(feature = "ssr")]
+pub fn publish_legal_document(publish_id: i64, pool: &DieselPool) -> Result<(), DatabaseError> {
+    use schema::legal_documents::dsl::*;
+
+    diesel::update(legal_documents.find(publish_id))
+        .filter(published_at.is_null())
+        .set(published_at.eq(Utc::now()))
+        .execute(&mut pool.get().expect("diesel"))
+        .map(|_| ())
+        .map_err(DatabaseError::from)
+}
+
+/// Published documents are kept, players have accepted them.
+#[cfg(feature = "ssr")]
+pub fn delete_legal_document_draft(delete_id: i64, pool: &DieselPool) -> Result<(), DatabaseError> {
+    use schema::legal_documents::dsl::*;
+
+    diesel::delete(legal_documents.find(delete_id))
+        .filter(published_at.is_null())
+        .execute(&mut pool.get().expect("diesel"))
+        .map(|_| ())
+        .map_err(DatabaseError::from)
+}
+
+#[cfg(feature = "ssr")]
+pub fn get_current_legal_documents(pool: &DieselPool) -> Result<Vec<models::LegalDocument>, DatabaseError> {
+    current_legal_documents(&mut pool.get().expect("diesel"))
+}
+
+#[cfg(feature = "ssr")]
+fn current_legal_documents(connection: &mut PgConnection) -> Result<Vec<models::LegalDocument>, DatabaseError> {
+    use schema::legal_documents::dsl::*;
+
+    legal_documents
+        .filter(published_at.is_not_null())
+        .distinct_on(kind)
+        .order((kind.asc(), version.desc()))
+        .load::<models::LegalDocument>(connection)
+        .map_err(DatabaseError::from)
+}
+
+/// Current documents the player has not accepted yet.
+#[cfg(feature = "ssr")]
+pub fn get_pending_legal_documents(
+    search_player_id: i64,
+    pool: &DieselPool,
+) -> Result<Vec<models::LegalDocument>, DatabaseError> {
+    use schema::legal_document_acceptances::dsl::*;
+
+    let connection = &mut pool.get().expect("diesel");
+
+    let accepted_ids: HashSet<i64> = legal_document_acceptances
+        .filter(player_id.eq(search_player_id))
+        .select(document_id)
+        .load::<i64>(connection)?
+        .into_iter()
+        .collect();
+
+    Ok(current_legal_documents(connection)?
+        .into_iter()
+        .filter(|document| !accepted_ids.contains(&document.id))
+        .collect())
+}
+
+#[cfg(feature = "ssr")]
+pub fn accept_legal_documents(
+    accept_document_ids: &[i64],
+    accept_player_id: i64,
+    accept_ip_address: Option<String>,
+    pool: &DieselPool,
+) -> Result<(), DatabaseError> {
+    use schema::legal_document_acceptances::dsl::*;
+
+    let values: Vec<_> = accept_document_ids
+        .iter()
+        .map(|accept_document_id| (
+            document_id.eq(*accept_document_id),
+            player_id.eq(accept_player_id),
+            ip_address.eq(accept_ip_address.clone()),
+        ))
+        .collect();
+
+    diesel::insert_into(legal_document_acceptances)
+        .values(&values)
+        .on_conflict_do_nothing()
+        .execute(&mut pool.get().expect("diesel"))
+        .map(|_| ())
+        .map_err(DatabaseError::from)
+}
+
+#[cfg(feature = "ssr")]
+pub fn get_legal_acceptances_for_player(
+    search_player_id: i64,
+    pool: &DieselPool,
+) -> Result<Vec<domain::LegalAcceptance>, DatabaseError> {
+    use schema::{legal_document_acceptances, legal_documents};
+
+    let acceptances: Vec<(models::LegalDocument, chrono::DateTime<Utc>, Option<String>)> = legal_document_acceptances::table
+        .inner_join(legal_documents::table)
+        .filter(legal_document_acceptances::player_id.eq(search_player_id))
+        .order(legal_document_acceptances::created_at.desc())
+        .select((
+            models::LegalDocument::as_select(),
+            legal_document_acceptances::created_at,
+            legal_document_acceptances::ip_address,
+        ))
+        .load(&mut pool.get().expect("diesel"))?;
+
+    Ok(acceptances
+        .into_iter()
+        .map(|(document, accepted_at, ip_address)| {
+            let document = domain::LegalDocument::from(document);
+
+            domain::LegalAcceptance {
+                document_id: document.id.unwrap(),
+                kind: document.kind,
+                version: document.version,
+                title: document.title,
+                accepted_at,
+                ip_address,
+            }
+        })
+        .collect())
+}
+
+/// Players of the list who accepted the current version of every published document.
+#[cfg(feature = "ssr")]
+pub fn get_player_ids_with_current_acceptance(
+    search_player_ids: Vec<i64>,
+    pool: &DieselPool,
+) -> Result<HashSet<i64>, DatabaseError> {
+    use schema::legal_document_acceptances::dsl::*;
+
+    let connection = &mut pool.get().expect("diesel");
+
+    let current_ids: Vec<i64> = current_legal_documents(connection)?
+        .into_iter()
+        .map(|document| document.id)
+        .collect();
+
+    let acceptances: Vec<i64> = legal_document_acceptances
+        .filter(document_id.eq_any(&current_ids))
+        .filter(player_id.eq_any(&search_player_ids))
+        .select(player_id)
+        .load(connection)?;
+
+    let mut counts: HashMap<i64, usize> = HashMap::new();
+
+    for accepted_player_id in acceptances {
+        *counts.entry(accepted_player_id).or_default() += 1;
+    }
+
+    Ok(search_player_ids
+        .into_iter()
+        .filter(|search_player_id| counts.get(search_player_id).copied().unwrap_or(0) == current_ids.len())
+        .collect())
+}
+
+#[cfg(feature = "ssr")]
+pub fn get_fees(pool: &DieselPool) -> Result<Vec<models::Fee>, DatabaseError> {
+    use schema::fees::dsl::*;
+
+    fees
+        .order((kind.asc(), name.asc()))
+        .load::<models::Fee>(&mut pool.get().expect("diesel"))
+        .map_err(DatabaseError::from)
+}
+
+#[cfg(feature = "ssr")]
+pub fn find_fee_for_id(search_id: i64, pool: &DieselPool) -> Result<Option<models::Fee>, DatabaseError> {
+    use schema::fees::dsl::*;
+
+    fees
+        .find(search_id)
+        .first::<models::Fee>(&mut pool.get().expect("diesel"))
+        .optional()
+        .map_err(DatabaseError::from)
+}
+
+#[cfg(feature = "ssr")]
+pub fn create_fee(fee: domain::Fee, pool: &DieselPool) -> Result<i64, DatabaseError> {
+    use models::NewFee;
+    use schema::fees;
+
+    let new_fee = NewFee {
+        name: &fee.name,
+        kind: fee.kind.as_str(),
+        amount_cents: fee.amount_cents,
+        play_event_id: fee.play_event_id,
+    };
+
+    diesel::insert_into(fees::table)
+        .values(&new_fee)
+        .returning(fees::id)
+        .get_result(&mut pool.get().expect("diesel"))
+        .map_err(DatabaseError::from)
+}
+
+/// Charges already booked from the fee stay, they only lose the link to it.
+#[cfg(feature = "ssr")]
+pub fn delete_fee(delete_id: i64, pool: &DieselPool) -> Result<(), DatabaseError> {
+    use schema::fees::dsl::*;
+
+    diesel::delete(fees.find(delete_id))
+        .execute(&mut pool.get().expect("diesel"))
+        .map(|_| ())
+        .map_err(DatabaseError::from)
+}
+
+/// Membership fees go to all active players, event fees to the registered participants.
+#[cfg(feature = "ssr")]
+pub fn get_fee_target_player_ids(fee: &domain::Fee, pool: &DieselPool) -> Result<Vec<i64>, DatabaseError> {
+    use schema::{play_event_registrations, players};
+
+    let connection = &mut pool.get().expect("diesel");
+
+    match (fee.kind, fee.play_event_id) {
+        (domain::FeeKind::Membership, _) => players::table
+            .filter(players::active.eq(true))
+            .select(players::id)
+            .load(connection)
+            .map_err(DatabaseError::from),
+        (domain::FeeKind::Event, Some(search_play_event_id)) => play_event_registrations::table
+            .filter(play_event_registrations::play_event_id.eq(search_play_event_id))
+            .filter(play_event_registrations::status.eq(domain::RegistrationStatus::Registered.as_str()))
+            .select(play_event_registrations::player_id)
+            .load(connection)
+            .map_err(DatabaseError::from),
+        _ => Ok(Vec::new()),
+    }
+}
+
+/// Players who were already charged the fee are skipped, returns the number of new charges.
+#[cfg(feature = "ssr")]
+pub fn charge_fee(
+    fee: &domain::Fee,
+    charge_player_ids: &[i64],
+    charge_created_by_id: i64,
+    pool: &DieselPool,
+) -> Result<usize, DatabaseError> {
+    use models::NewCharge;
+    use schema::charges;
+
+    let new_charges: Vec<NewCharge> = charge_player_ids
+        .iter()
+        .map(|charge_player_id| NewCharge {
+            player_id: *charge_player_id,
+            fee_id: fee.id,
+            description: &fee.name,
+            amount_cents: fee.amount_cents,
+            created_by_id: Some(charge_created_by_id),
+        })
+        .collect();
+
+    diesel::insert_into(charges::table)
+        .values(&new_charges)
+        .on_conflict_do_nothing()
+        .execute(&mut pool.get().expect("diesel"))
+        .map_err(DatabaseError::from)
+}
+
+#[cfg(feature = "ssr")]
+pub fn create_charge(charge: domain::Charge, pool: &DieselPool) -> Result<i64, DatabaseError> {
+    use models::NewCharge;
+    use schema::charges;
+
+    let new_charge = NewCharge {
+        player_id: charge.player_id,
+        fee_id: charge.fee_id,
+        description: &charge.description,
+        amount_cents: charge.amount_cents,
+        created_by_id: charge.created_by_id,
+    };
+
+    diesel::insert_into(charges::table)
+        .values(&new_charge)
+        .returning(charges::id)
+        .get_result(&mut pool.get().expect("diesel"))
+        .map_err(DatabaseError::from)
+}
+
+#[cfg(feature = "ssr")]
+pub fn find_charge_for_id(search_id: i64, pool: &DieselPool) -> Result<Option<models::Charge>, DatabaseError> {
+    use schema::charges::dsl::*;
+
+    charges
+        .find(search_id)
+        .first(&mut pool.get().expect("diesel"))
+        .optional()
+        .map_err(DatabaseError::from)
+}
+
+#[cfg(feature = "ssr")]
+pub fn delete_charge(delete_id: i64, pool: &DieselPool) -> Result<(), DatabaseError> {
+    use schema::charges::dsl::*;
+
+    diesel::delete(charges.find(delete_id))
+        .execute(&mut pool.get().expect("diesel"))
+        .map(|_| ())
+        .map_err(DatabaseError::from)
+}
+
+#[cfg(feature = "ssr")]
+pub fn get_charges_for_player(
+    search_player_id: i64,
+    pool: &DieselPool,
+) -> Result<Vec<models::Charge>, DatabaseError> {
+    use schema::charges::dsl::*;
+
+    charges
+        .filter(player_id.eq(search_player_id))
+        .order(created_at.desc())
+        .load::<models::Charge>(&mut pool.get().expect("diesel"))
+        .map_err(DatabaseError::from)
+}
+
+#[cfg(feature = "ssr")]
+pub fn create_payment(payment: domain::Payment, pool: &DieselPool) -> Result<i64, DatabaseError> {
+    use models::NewPayment;
+    use schema::payments;
+
+    let new_payment = NewPayment {
+        player_id: payment.player_id,
+        charge_id: payment.charge_id,
+        amount_cents: payment.amount_cents,
+        method: payment.method.as_str(),
+        reference: &payment.reference,
+        paid_on: payment.paid_on,
+        recorded_by_id: payment.recorded_by_id,
+    };
+
+    diesel::insert_into(payments::table)
+        .values(&new_payment)
+        .returning(payments::id)
+        .get_result(&mut pool.get().expect("diesel"))
+        .map_err(DatabaseError::from)
+}
+
+#[cfg(feature = "ssr")]
+pub fn delete_payment(delete_id: i64, pool: &DieselPool) -> Result<(), DatabaseError> {
+    use schema::payments::dsl::*;
+
+    diesel::delete(payments.find(delete_id))
+        .execute(&mut pool.get().expect("diesel"))
+        .map(|_| ())
+        .map_err(DatabaseError::from)
+}
+
+#[cfg(feature = "ssr")]
+pub fn get_payments_for_player(
+    search_player_id: i64,
+    pool: &DieselPool,
+) -> Result<Vec<models::Payment>, DatabaseError> {
+    use schema::payments::dsl::*;
+
+    payments
+        .filter(player_id.eq(search_player_id))
+        .order((paid_on.desc(), id.desc()))
+        .load::<models::Payment>(&mut pool.get().expect("diesel"))
+        .map_err(DatabaseError::from)
+}
+
+/// Balances of the given players, or of everyone with a ledger entry without a filter.
+#[cfg(feature = "ssr")]
+fn player_balances(
+    search_player_ids: Option<&[i64]>,
+    connection: &mut PgConnection,
+) -> Result<Vec<domain::PlayerBalance>, DatabaseError> {
+    use schema::{charges, payments, players};
+
+    let mut charge_query = charges::table
+        .select((charges::player_id, charges::amount_cents))
+        .into_boxed();
+    let mut payment_query = payments::table
+        .select((payments::player_id, payments::amount_cents))
+        .into_boxed();
+
+    if let Some(search_player_ids) = search_player_ids {
+        charge_query = charge_query.filter(charges::player_id.eq_any(search_player_ids));
+        payment_query = payment_query.filter(payments::player_id.eq_any(search_player_ids));
+    }
+
+    let mut balances: HashMap<i64, domain::PlayerBalance> = search_player_ids
+        .unwrap_or_default()
+        .iter()
+        .map(|search_player_id| (*search_player_id, domain::PlayerBalance {
+            player_id: *search_player_id,
+            ..Default::default()
+        }))
+        .collect();
+
+    for (charge_player_id, cents) in charge_query.load::<(i64, i64)>(connection)? {
+        balances
+            .entry(charge_player_id)
+            .or_insert_with(|| domain::PlayerBalance { player_id: charge_player_id, ..Default::default() })
+            .charged_cents += cents;
+    }
+
+    for (payment_player_id, cents) in payment_query.load::<(i64, i64)>(connection)? {
+        balances
+            .entry(payment_player_id)
+            .or_insert_with(|| domain::PlayerBalance { player_id: payment_player_id, ..Default::default() })
+            .paid_cents += cents;
+    }
+
+    let tag_names: HashMap<i64, String> = players::table
+        .filter(players::id.eq_any(balances.keys().copied().collect::<Vec<_>>()))
+        .select((players::id, players::tag_name))
+        .load(connection)?
+        .into_iter()
+        .collect();
+
+    let mut balances: Vec<domain::PlayerBalance> = balances
+        .into_values()
+        .map(|balance| domain::PlayerBalance {
+            tag_name: tag_names.get(&balance.player_id).cloned().unwrap_or_default(),
+            ..balance
+        })
+        .collect();
+
+    balances.sort_by(|a, b| a.tag_name.cmp(&b.tag_name));
+
+    Ok(balances)
+}
+
+#[cfg(feature = "ssr")]
+pub fn get_balances_for_players(
+    search_player_ids: &[i64],
+    pool: &DieselPool,
+) -> Result<Vec<domain::PlayerBalance>, DatabaseError> {
+    player_balances(Some(search_player_ids), &mut pool.get().expect("diesel"))
+}
+
+/// Charges by the year they were made, payments by the day they were received.
+#[cfg(feature = "ssr")]
+pub fn get_treasurer_report(year: i32, pool: &DieselPool) -> Result<domain::TreasurerReport, DatabaseError> {
+    use diesel::NullableExpressionMethods;
+    use schema::{charges, fees, payments};
+
+    let connection = &mut pool.get().expect("diesel");
+
+    let first_day = NaiveDate::from_ymd_opt(year, 1, 1).ok_or(DatabaseError::EntityNotFound)?;
+    let next_first_day = NaiveDate::from_ymd_opt(year + 1, 1, 1).ok_or(DatabaseError::EntityNotFound)?;
+    let start = first_day.and_hms_opt(0, 0, 0).expect("midnight exists").and_utc();
+    let end = next_first_day.and_hms_opt(0, 0, 0).expect("midnight exists").and_utc();
+
+    let year_charges: Vec<(Option<String>, i64)> = charges::table
+        .left_join(fees::table)
+        .filter(charges::created_at.ge(start))
+        .filter(charges::created_at.lt(end))
+        .select((fees::kind.nullable(), charges::amount_cents))
+        .load(connection)?;
+
+    let year_payments: Vec<(String, i64)> = payments::table
+        .filter(payments::paid_on.ge(first_day))
+        .filter(payments::paid_on.lt(next_first_day))
+        .select((payments::method, payments::amount_cents))
+        .load(connection)?;
+
+    let mut charged_by_kind: BTreeMap<Option<domain::FeeKind>, i64> = BTreeMap::new();
+
+    for (fee_kind, cents) in year_charges {
+        let fee_kind = fee_kind.map(|fee_kind| fee_kind.parse().expect("fee kind constrained by database"));
+        *charged_by_kind.entry(fee_kind).or_default() += cents;
+    }
+
+    let mut paid_by_method: BTreeMap<domain::PaymentMethod, i64> = BTreeMap::new();
+
+    for (method, cents) in year_payments {
+        *paid_by_method.entry(method.parse().expect("payment method constrained by database")).or_default() += cents;
+    }
+
+    let debtors = player_balances(None, connection)?
+        .into_iter()
+        .filter(|balance| balance.outstanding_cents() > 0)
+        .collect();
+
+    Ok(domain::TreasurerReport {
+        year,
+        charged_by_kind: charged_by_kind.into_iter().collect(),
+        paid_by_method: paid_by_method.into_iter().collect(),
+        debtors,
+    })
+}
+
+/// Returns the number of transactions that weren't imported before.
+#[cfg(feature = "ssr")]
+pub fn create_bank_transactions(
+    transactions: &[domain::BankTransaction],
+    create_imported_by_id: i64,
+    pool: &DieselPool,
+) -> Result<usize, DatabaseError> {
+    use models::NewBankTransaction;
+    use schema::bank_transactions;
+
+    let new_transactions: Vec<NewBankTransaction> = transactions
+        .iter()
+        .map(|transaction| NewBankTransaction {
+            import_key: &transaction.import_key,
+            booked_on: transaction.booked_on,
+            amount_cents: transaction.amount_cents,
+            counterparty: &transaction.counterparty,
+            reference: &transaction.reference,
+            imported_by_id: Some(create_imported_by_id),
+        })
+        .collect();
+
+    diesel::insert_into(bank_transactions::table)
+        .values(&new_transactions)
+        .on_conflict(bank_transactions::import_key)
+        .do_nothing()
+        .execute(&mut pool.get().expect("diesel"))
+        .map_err(DatabaseError::from)
+}
+
+#[cfg(feature = "ssr")]
+pub fn find_bank_transaction_for_id(
+    search_id: i64,
+    pool: &DieselPool,
+) -> Result<Option<models::BankTransaction>, DatabaseError> {
+    use schema::bank_transactions::dsl::*;
+
+    bank_transactions
+        .find(search_id)
+        .first(&mut pool.get().expect("diesel"))
+        .optional()
+        .map_err(DatabaseError::from)
+}
+
+#[cfg(feature = "ssr")]
+pub fn get_open_bank_transactions(pool: &DieselPool) -> Result<Vec<models::BankTransaction>, DatabaseError> {
+    use schema::bank_transactions::dsl::*;
+
+    bank_transactions
+        .filter(payment_id.is_null())
+        .filter(ignored.eq(false))
+        .order((booked_on.asc(), id.asc()))
+        .load::<models::BankTransaction>(&mut pool.get().expect("diesel"))
+        .map_err(DatabaseError::from)
+}
+
+#[cfg(feature = "ssr")]
+pub fn ignore_bank_transaction(ignore_id: i64, pool: &DieselPool) -> Result<(), DatabaseError> {
+    use schema::bank_transactions::dsl::*;
+
+    diesel::update(bank_transactions.find(ignore_id))
+        .filter(payment_id.is_null())
+        .set(ignored.eq(true))
+        .execute(&mut pool.get().expect("diesel"))
+        .map(|_| ())
+        .map_err(DatabaseError::from)
+}
+
+/// Books the transfer as payment of the player. Fails with `AlreadyBooked` if it was booked or
+/// ignored in the meantime, so a transfer never turns into two payments.
+#[cfg(feature = "ssr")]
+pub fn book_bank_transaction(
+    book_id: i64,
+    book_player_id: i64,
+    book_charge_id: Option<i64>,
+    book_recorded_by_id: i64,
+    pool: &DieselPool,
+) -> Result<(), DatabaseError> {
+    use models::NewPayment;
+    use schema::{bank_transactions, payments};
+
+    pool.get().expect("diesel").transaction(|connection| {
+        let transaction: models::BankTransaction = bank_transactions::table
+            .find(book_id)
+            .for_update()
+            .first(connection)
+            .optional()?
+            .ok_or(DatabaseError::EntityNotFound)?;
+
+        if transaction.payment_id.is_some() || transaction.ignored {
+            return Err(DatabaseError::AlreadyBooked);
+        }
+
+        let create_payment_id: i64 = diesel::insert_into(payments::table)
+            .values(&NewPayment {
+                player_id: book_player_id,
+                charge_id: book_charge_id,
+                amount_cents: transaction.amount_cents,
+                method: domain::PaymentMethod::Transfer.as_str(),
+                reference: &transaction.reference,
+                paid_on: transaction.booked_on,
+                recorded_by_id: Some(book_recorded_by_id),
+            })
+            .returning(payments::id)
+            .get_result(connection)?;
+
+        diesel::update(bank_transactions::table.find(book_id))
+            .set(bank_transactions::payment_id.eq(create_payment_id))
+            .execute(connection)?;
+
+        Ok(())
+    })
+}
+
+/// Charges with an amount left to pay, oldest first.
+#[cfg(feature = "ssr")]
+pub fn get_open_charges(pool: &DieselPool) -> Result<Vec<domain::OpenCharge>, DatabaseError> {
+    use diesel::NullableExpressionMethods;
+    use schema::{charges, payments, players};
+
+    let connection = &mut pool.get().expect("diesel");
+
+    let all_charges: Vec<(models::Charge, String)> = charges::table
+        .inner_join(players::table)
+        .order(charges::created_at.asc())
+        .select((models::Charge::as_select(), players::tag_name))
+        .load(connection)?;
+
+    let mut paid: HashMap<i64, i64> = HashMap::new();
+
+    for (payment_charge_id, cents) in payments::table
+        .filter(payments::charge_id.is_not_null())
+        .select((payments::charge_id.assume_not_null(), payments::amount_cents))
+        .load::<(i64, i64)>(connection)?
+    {
+        *paid.entry(payment_charge_id).or_default() += cents;
+    }
+
+    Ok(all_charges
+        .into_iter()
+        .map(|(charge, tag_name)| domain::OpenCharge {
+            outstanding_cents: charge.amount_cents - paid.get(&charge.id).copied().unwrap_or(0),
+            charge: charge.into(),
+            tag_name,
+        })
+        .filter(|open_charge| open_charge.outstanding_cents > 0)
+        .collect())
+}
+
+#[cfg(feature = "ssr")]
+pub fn get_payers(pool: &DieselPool) -> Result<Vec<domain::Payer>, DatabaseError> {
+    use schema::players::dsl::*;
+
+    Ok(players
+        .order(tag_name.asc())
+        .select((id, tag_name, real_name))
+        .load::<(i64, String, Option<String>)>(&mut pool.get().expect("diesel"))?
+        .into_iter()
+        .map(|(payer_id, payer_tag_name, payer_real_name)| domain::Payer {
+            player_id: payer_id,
+            tag_name: payer_tag_name,
+            real_name: payer_real_name,
+        })
+        .collect())
+}
+
+#[cfg(feature = "ssr")]
+pub fn get_rental_items(pool: &DieselPool) -> Result<Vec<models::RentalItem>, DatabaseError> {
+    use schema::rental_items::dsl::*;
+
+    rental_items
+        .order((kind.asc(), name.asc()))
+        .load::<models::RentalItem>(&mut pool.get().expect("diesel"))
+        .map_err(DatabaseError::from)
+}
+
+#[cfg(feature = "ssr")]
+pub fn find_rental_item_for_id(
+    search_id: i64,
+    pool: &DieselPool,
+) -> Result<Option<models::RentalItem>, DatabaseError> {
+    use schema::rental_items::dsl::*;
+
+    rental_items
+        .find(search_id)
+        .first(&mut pool.get().expect("diesel"))
+        .optional()
+        .map_err(DatabaseError::from)
+}
+
+#[cfg(feature = "ssr")]
+pub fn create_rental_item(item: domain::RentalItem, pool: &DieselPool) -> Result<i64, DatabaseError> {
+    use models::NewRentalItem;
+    use schema::rental_items;
+
+    let new_item = NewRentalItem {
+        kind: item.kind.as_str(),
+        name: &item.name,
+        serial_number: &item.serial_number,
+        condition: item.condition.as_str(),
+        status: item.status.as_str(),
+    };
+
+    diesel::insert_into(rental_items::table)
+        .values(&new_item)
+        .returning(rental_items::id)
+        .get_result(&mut pool.get().expect("diesel"))
+        .map_err(DatabaseError::from)
+}
+
+#[cfg(feature = "ssr")]
+pub fn update_rental_item_state(
+    update_id: i64,
+    update_condition: domain::ItemCondition,
+    update_status: domain::RentalItemStatus,
+    pool: &DieselPool,
+) -> Result<(), DatabaseError> {
+    use schema::rental_items::dsl::*;
+
+    diesel::update(rental_items.find(update_id))
+        .set((condition.eq(update_condition.as_str()), status.eq(update_status.as_str())))
+        .execute(&mut pool.get().expect("diesel"))
+        .map(|_| ())
+        .map_err(DatabaseError::from)
+}
+
+#[cfg(feature = "ssr")]
+pub fn delete_rental_item(delete_id: i64, pool: &DieselPool) -> Result<(), DatabaseError> {
+    use schema::rental_items::dsl::*;
+
+    diesel::delete(rental_items.find(delete_id))
+        .execute(&mut pool.get().expect("diesel"))
+        .map(|_| ())
+        .map_err(DatabaseError::from)
+}
+
+/// Adds item, player and event to the loans, keeping their order.
+#[cfg(feature = "ssr")]
+fn rental_loan_details(
+    loans: Vec<models::RentalLoan>,
+    connection: &mut PgConnection,
+) -> Result<Vec<domain::RentalLoan>, DatabaseError> {
+    use schema::{play_events, players, rental_items};
+
+    let items: HashMap<i64, domain::RentalItem> = rental_items::table
+        .filter(rental_items::id.eq_any(loans.iter().map(|loan| loan.item_id).collect::<Vec<_>>()))
+        .load::<models::RentalItem>(connection)?
+        .into_iter()
+        .map(|item| (item.id, item.into()))
+        .collect();
+
+    let tag_names: HashMap<i64, String> = players::table
+        .filter(players::id.eq_any(loans.iter().map(|loan| loan.player_id).collect::<Vec<_>>()))
+        .select((players::id, players::tag_name))
+        .load(connection)?
+        .into_iter()
+        .collect();
+
+    let events: HashMap<i64, (String, DateTime<Utc>)> = play_events::table
+        .filter(play_events::id.eq_any(loans.iter().map(|loan| loan.play_event_id).collect::<Vec<_>>()))
+        .select((play_events::id, play_events::name, play_events::ends_at))
+        .load::<(i64, String, DateTime<Utc>)>(connection)?
+        .into_iter()
+        .map(|(event_id, event_name, event_ends_at)| (event_id, (event_name, event_ends_at)))
+        .collect();
+
+    Ok(loans
+        .into_iter()
+        .map(|loan| {
+            let (play_event_name, play_event_ends_at) = events[&loan.play_event_id].clone();
+
+            domain::RentalLoan {
+                id: loan.id,
+                item: items[&loan.item_id].clone(),
+                player_id: loan.player_id,
+                tag_name: tag_names.get(&loan.player_id).cloned().unwrap_or_default(),
+                play_event_id: loan.play_event_id,
+                play_event_name,
+                play_event_ends_at,
+                checked_out_at: loan.checked_out_at,
+                returned_at: loan.returned_at,
+                damage_note: loan.damage_note,
+            }
+        })
+        .collect())
+}
+
+/// Reserved and issued loans, i.e. everything not yet returned.
+#[cfg(feature = "ssr")]
+pub fn get_open_rental_loans(pool: &DieselPool) -> Result<Vec<domain::RentalLoan>, DatabaseError> {
+    use schema::rental_loans::dsl::*;
+
+    let connection = &mut pool.get().expect("diesel");
+
+    let loans = rental_loans
+        .filter(returned_at.is_null())
+        .order(created_at.asc())
+        .load::<models::RentalLoan>(connection)?;
+
+    rental_loan_details(loans, connection)
+}
+
+#[cfg(feature = "ssr")]
+pub fn get_rental_loans_for_item(
+    search_item_id: i64,
+    pool: &DieselPool,
+) -> Result<Vec<domain::RentalLoan>, DatabaseError> {
+    use schema::rental_loans::dsl::*;
+
+    let connection = &mut pool.get().expect("diesel");
+
+    let loans = rental_loans
+        .filter(item_id.eq(search_item_id))
+        .order(created_at.desc())
+        .load::<models::RentalLoan>(connection)?;
+
+    rental_loan_details(loans, connection)
+}
+
+#[cfg(feature = "ssr")]
+pub fn get_rental_loans_for_player_event(
+    search_player_id: i64,
+    search_play_event_id: i64,
+    pool: &DieselPool,
+) -> Result<Vec<domain::RentalLoan>, DatabaseError> {
+    use schema::rental_loans::dsl::*;
+
+    let connection = &mut pool.get().expect("diesel");
+
+    let loans = rental_loans
+        .filter(player_id.eq(search_player_id))
+        .filter(play_event_id.eq(search_play_event_id))
+        .order(created_at.asc())
+        .load::<models::RentalLoan>(connection)?;
+
+    rental_loan_details(loans, connection)
+}
+
+#[cfg(feature = "ssr")]
+pub fn find_rental_loan_for_id(
+    search_id: i64,
+    pool: &DieselPool,
+) -> Result<Option<models::RentalLoan>, DatabaseError> {
+    use schema::rental_loans::dsl::*;
+
+    rental_loans
+        .find(search_id)
+        .first(&mut pool.get().expect("diesel"))
+        .optional()
+        .map_err(DatabaseError::from)
+}
+
+/// Items that can be handed out for the event: rentable, not reserved for it and not still out from another event.
+#[cfg(feature = "ssr")]
+fn free_rental_items(
+    search_play_event_id: i64,
+    connection: &mut PgConnection,
+) -> Result<Vec<domain::RentalItem>, DatabaseError> {
+    use diesel::BoolExpressionMethods;
+    use schema::{rental_items, rental_loans};
+
+    let taken_item_ids: HashSet<i64> = rental_loans::table
+        .filter(rental_loans::returned_at.is_null())
+        .filter(
+            rental_loans::play_event_id.eq(search_play_event_id)
+                .or(rental_loans::checked_out_at.is_not_null())
+        )
+        .select(rental_loans::item_id)
+        .load::<i64>(connection)?
+        .into_iter()
+        .collect();
+
+    Ok(rental_items::table
+        .order(rental_items::id.asc())
+        .load::<models::RentalItem>(connection)?
+        .into_iter()
+        .filter(|item| !taken_item_ids.contains(&item.id))
+        .map(domain::RentalItem::from)
+        .filter(domain::RentalItem::is_rentable)
+        .collect())
+}
+
+#[cfg(feature = "ssr")]
+pub fn get_available_rental_kinds(
+    search_play_event_id: i64,
+    pool: &DieselPool,
+) -> Result<Vec<(domain::RentalKind, usize)>, DatabaseError> {
+    let free_items = free_rental_items(search_play_event_id, &mut pool.get().expect("diesel"))?;
+
+    Ok(domain::RentalKind::ALL
+        .into_iter()
+        .map(|rental_kind| (rental_kind, free_items.iter().filter(|item| item.kind == rental_kind).count()))
+        .collect())
+}
+
+/// Reserves any free item of the kind. Players get at most one item per kind and event.
+#[cfg(feature = "ssr")]
+pub fn reserve_rental(
+    reserve_kind: domain::RentalKind,
+    reserve_player_id: i64,
+    reserve_play_event_id: i64,
+    pool: &DieselPool,
+) -> Result<i64, DatabaseError> {
+    use schema::{rental_items, rental_loans};
+
+    pool.get().expect("diesel").transaction(|connection| {
+        lock_play_event(reserve_play_event_id, connection)?;
+
+        let reserved_kinds: Vec<String> = rental_loans::table
+            .inner_join(rental_items::table)
+            .filter(rental_loans::player_id.eq(reserve_player_id))
+            .filter(rental_loans::play_event_id.eq(reserve_play_event_id))
+            .filter(rental_loans::returned_at.is_null())
+            .select(rental_items::kind)
+            .load(connection)?;
+
+        if reserved_kinds.iter().any(|reserved_kind| reserved_kind == reserve_kind.as_str()) {
+            return Err(DatabaseError::AlreadyReserved);
+        }
+
+        let item = free_rental_items(reserve_play_event_id, connection)?
+            .into_iter()
+            .find(|item| item.kind == reserve_kind)
+            .ok_or(DatabaseError::RentalUnavailable)?;
+
+        diesel::insert_into(rental_loans::table)
+            .values((
+                rental_loans::item_id.eq(item.id.unwrap()),
+                rental_loans::player_id.eq(reserve_player_id),
+                rental_loans::play_event_id.eq(reserve_play_event_id),
+            ))
+            .returning(rental_loans::id)
+            .get_result(connection)
+            .map_err(DatabaseError::from)
+    })
+}
+
+/// Hands out a specific item without prior reservation.
+#[cfg(feature = "ssr")]
+pub fn issue_rental_item(
+    issue_item_id: i64,
+    issue_player_id: i64,
+    issue_play_event_id: i64,
+    issue_issued_by_id: i64,
+    pool: &DieselPool,
+) -> Result<i64, DatabaseError> {
+    use schema::rental_loans;
+
+    pool.get().expect("diesel").transaction(|connection| {
+        lock_play_event(issue_play_event_id, connection)?;
+
+        if !free_rental_items(issue_play_event_id, connection)?
+            .iter()
+            .any(|item| item.id == Some(issue_item_id))
+        {
+            return Err(DatabaseError::RentalUnavailable);
+        }
+
+        diesel::insert_into(rental_loans::table)
+            .values((
+                rental_loans::item_id.eq(issue_item_id),
+                rental_loans::player_id.eq(issue_player_id),
+                rental_loans::play_event_id.eq(issue_play_event_id),
+                rental_loans::checked_out_at.eq(Some(Utc::now())),
+                rental_loans::issued_by_id.eq(Some(issue_issued_by_id)),
+            ))
+            .returning(rental_loans::id)
+            .get_result(connection)
+            .map_err(DatabaseError::from)
+    })
+}
+
+#[cfg(feature = "ssr")]
+pub fn check_out_rental_loan(
+    check_out_id: i64,
+    check_out_issued_by_id: i64,
+    pool: &DieselPool,
+) -> Result<(), DatabaseError> {
+    use schema::rental_loans::dsl::*;
+
+    pool.get().expect("diesel").transaction(|connection| {
+        let loan: models::RentalLoan = rental_loans
+            .find(check_out_id)
+            .for_update()
+            .first(connection)
+            .optional()?
+            .ok_or(DatabaseError::EntityNotFound)?;
+
+        // the item may still be out from an earlier event
+        let still_out: i64 = rental_loans
+            .filter(item_id.eq(loan.item_id))
+            .filter(checked_out_at.is_not_null())
+            .filter(returned_at.is_null())
+            .count()
+            .get_result(connection)?;
+
+        if loan.checked_out_at.is_some() || still_out > 0 {
+            return Err(DatabaseError::RentalUnavailable);
+        }
+
+        diesel::update(rental_loans.find(check_out_id))
+            .set((checked_out_at.eq(Some(Utc::now())), issued_by_id.eq(Some(check_out_issued_by_id))))
+            .execute(connection)?;
+
+        Ok(())
+    })
+}
+
+/// Only reservations can be cancelled, issued items have to be returned.
+#[cfg(feature = "ssr")]
+pub fn cancel_rental_reservation(cancel_id: i64, pool: &DieselPool) -> Result<(), DatabaseError> {
+    use schema::rental_loans::dsl::*;
+
+    diesel::delete(rental_loans.find(cancel_id))
+        .filter(checked_out_at.is_null())
+        .execute(&mut pool.get().expect("diesel"))
+        .map(|_| ())
+        .map_err(DatabaseError::from)
+}
+
+/// Closes the loan and records the condition the item came back in.
+#[cfg(feature = "ssr")]
+pub fn return_rental_loan(
+    return_id: i64,
+    return_damage_note: &str,
+    return_condition: domain::ItemCondition,
+    pool: &DieselPool,
+) -> Result<(), DatabaseError> {
+    use schema::{rental_items, rental_loans};
+
+    pool.get().expect("diesel").transaction(|connection| {
+        let return_item_id: i64 = diesel::update(rental_loans::table.find(return_id))
+            .filter(rental_loans::checked_out_at.is_not_null())
+            .filter(rental_loans::returned_at.is_null())
+            .set((
+                rental_loans::returned_at.eq(Some(Utc::now())),
+                rental_loans::damage_note.eq(return_damage_note),
+            ))
+            .returning(rental_loans::item_id)
+            .get_result(connection)
+            .optional()?
+            .ok_or(DatabaseError::EntityNotFound)?;
+
+        diesel::update(rental_items::table.find(return_item_id))
+            .set(rental_items::condition.eq(return_condition.as_str()))
+            .execute(connection)?;
+
+        Ok(())
+    })
+}
+
+#[cfg(feature = "ssr")]
+pub fn get_locations_for_name_filter(
+    filter_name: String,
+    pool: &DieselPool,
+) -> Result<Vec<(models::Location, bool)>, DatabaseError> {
+    use schema::locations::dsl::*;
+
+    let connection = &mut pool.get().expect("diesel");
+    let mut query = locations.into_boxed();
+
+    if !filter_name.is_empty() {
+        use diesel::PgTextExpressionMethods;
+
+        let pattern = format!("%{}%", filter_name);
+        query = query.filter(name.ilike(pattern));
+    }
+
+    let search_locations = query
+        .order(name.asc())
+        .load::<models::Location>(connection)?;
+
+    with_location_maps(search_locations, connection)
+}
+
+#[cfg(feature = "ssr")]
+pub fn find_location_for_id(
+    search_id: i64,
+    pool: &DieselPool,
+) -> Result<Option<(models::Location, bool)>, DatabaseError> {
+    use schema::locations::dsl::*;
+
+    let connection = &mut pool.get().expect("diesel");
+
+    let search_location = locations
+        .find(search_id)
+        .first::<models::Location>(connection)
+        .optional()?;
+
+    Ok(with_location_maps(search_location.into_iter().collect(), connection)?.pop())
+}
+
+/// Pairs each location with whether a site map was uploaded for it.
+#[cfg(feature = "ssr")]
+fn with_location_maps(
+    search_locations: Vec<models::Location>,
+    connection: &mut PgConnection,
+) -> Result<Vec<(models::Location, bool)>, DatabaseError> {
+    use schema::location_maps::dsl::*;
+
+    let map_location_ids: HashSet<i64> = location_maps
+        .filter(location_id.eq_any(search_locations.iter().map(|location| location.id).collect::<Vec<_>>()))
+        .select(location_id)
+        .load::<i64>(connection)?
+        .into_iter()
+        .collect();
+
+    Ok(search_locations
+        .into_iter()
+        .map(|location| {
+            let has_map = map_location_ids.contains(&location.id);
+            (location, has_map)
+        })
+        .collect())
+}
+
+#[cfg(feature = "ssr")]
+pub fn create_location(location: domain::Location, pool: &DieselPool) -> Result<i64, DatabaseError> {
+    use models::NewLocation;
+    use schema::locations;
+
+    let new_location = NewLocation {
+        name: &location.name,
+        address: &location.address,
+        latitude: location.latitude,
+        longitude: location.longitude,
+        field_rules: &location.field_rules,
+        parking_info: &location.parking_info,
+        safe_zone_notes: &location.safe_zone_notes,
+    };
+
+    diesel::insert_into(locations::table)
+        .values(&new_location)
+        .returning(locations::id)
+        .get_result(&mut pool.get().expect("diesel"))
+        .map_err(DatabaseError::from)
+}
+
+#[cfg(feature = "ssr")]
+pub fn save_location(location: domain::Location, pool: &DieselPool) -> Result<(), DatabaseError> {
+    use schema::locations::dsl::*;
+
+    diesel::update(locations.find(location.id.unwrap()))
+        .set((
+            name.eq(&location.name),
+            address.eq(&location.address),
+            latitude.eq(location.latitude),
+            longitude.eq(location.longitude),
+            field_rules.eq(&location.field_rules),
+            parking_info.eq(&location.parking_info),
+            safe_zone_notes.eq(&location.safe_zone_notes),
+        ))
+        .execute(&mut pool.get().expect("diesel"))
+        .map(|_| ())
+        .map_err(DatabaseError::from)
+}
+
+#[cfg(feature = "ssr")]
+pub fn delete_location(delete_id: i64, pool: &DieselPool) -> Result<(), DatabaseError> {
+    use schema::locations::dsl::*;
+
+    diesel::delete(locations.find(delete_id))
+        .execute(&mut pool.get().expect("diesel"))
+        .map(|_| ())
+        .map_err(DatabaseError::from)
+}
+
+#[cfg(feature = "ssr")]
+pub fn save_location_map(
+    save_location_id: i64,
+    save_content_type: &str,
+    save_data: &[u8],
+    pool: &DieselPool,
+) -> Result<(), DatabaseError> {
+    use schema::location_maps::dsl::*;
+
+    diesel::insert_into(location_maps)
+        .values((
+            location_id.eq(save_location_id),
+            content_type.eq(save_content_type),
+            data.eq(save_data),
+        ))
+        .on_conflict(location_id)
+        .do_update()
+        .set((content_type.eq(save_content_type), data.eq(save_data)))
+        .execute(&mut pool.get().expect("diesel"))
+        .map(|_| ())
+        .map_err(DatabaseError::from)
+}
+
+#[cfg(feature = "ssr")]
+pub fn find_location_map(
+    search_location_id: i64,
+    pool: &DieselPool,
+) -> Result<Option<(String, Vec<u8>)>, DatabaseError> {
+    use schema::location_maps::dsl::*;
+
+    location_maps
+        .find(search_location_id)
+        .select((content_type, data))
+        .first(&mut pool.get().expect("diesel"))
+        .optional()
+        .map_err(DatabaseError::from)
+}
+
+#[cfg(feature = "ssr")]
+pub fn delete_location_map(delete_location_id: i64, pool: &DieselPool) -> Result<(), DatabaseError> {
+    use schema::location_maps::dsl::*;
+
+    diesel::delete(location_maps.find(delete_location_id))
+        .execute(&mut pool.get().expect("diesel"))
+        .map(|_| ())
+        .map_err(DatabaseError::from)
+}
+
+#[cfg(feature = "ssr")]
+pub fn get_game_modes(pool: &DieselPool) -> Result<Vec<models::GameMode>, DatabaseError> {
+    use schema::game_modes::dsl::*;
+
+    game_modes
+        .order(name.asc())
+        .load::<models::GameMode>(&mut pool.get().expect("diesel"))
+        .map_err(DatabaseError::from)
+}
+
+#[cfg(feature = "ssr")]
+pub fn find_game_mode_for_id(
+    search_id: i64,
+    pool: &DieselPool,
+) -> Result<Option<models::GameMode>, DatabaseError> {
+    use schema::game_modes::dsl::*;
+
+    game_modes
+        .find(search_id)
+        .first(&mut pool.get().expect("diesel"))
+        .optional()
+        .map_err(DatabaseError::from)
+}
+
+#[cfg(feature = "ssr")]
+pub fn create_game_mode(game_mode: domain::GameMode, pool: &DieselPool) -> Result<i64, DatabaseError> {
+    use models::NewGameMode;
+    use schema::game_modes;
+
+    let new_game_mode = NewGameMode {
+        name: &game_mode.name,
+        description: &game_mode.description,
+        objectives: &game_mode.objectives,
+    };
+
+    diesel::insert_into(game_modes::table)
+        .values(&new_game_mode)
+        .returning(game_modes::id)
+        .get_result(&mut pool.get().expect("diesel"))
+        .map_err(DatabaseError::from)
+}
+
+#[cfg(feature = "ssr")]
+pub fn delete_game_mode(delete_id: i64, pool: &DieselPool) -> Result<(), DatabaseError> {
+    use schema::game_modes::dsl::*;
+
+    diesel::delete(game_modes.find(delete_id))
+        .execute(&mut pool.get().expect("diesel"))
+        .map(|_| ())
+        .map_err(DatabaseError::from)
+}
+
+/// The rounds of the events with their scores and objectives, in the order they were played.
+#[cfg(feature = "ssr")]
+pub fn get_game_rounds_for_events(
+    search_play_event_ids: Vec<i64>,
+    pool: &DieselPool,
+) -> Result<Vec<domain::GameRound>, DatabaseError> {
+    use diesel::NullableExpressionMethods;
+    use schema::{game_modes, game_rounds, round_objectives, round_scores};
+
+    let connection = &mut pool.get().expect("diesel");
+
+    let rounds: Vec<(models::GameRound, Option<String>)> = game_rounds::table
+        .left_join(game_modes::table)
+        .filter(game_rounds::play_event_id.eq_any(&search_play_event_ids))
+        .order((game_rounds::play_event_id.asc(), game_rounds::number.asc()))
+        .select((models::GameRound::as_select(), game_modes::name.nullable()))
+        .load(connection)?;
+    let round_ids: Vec<i64> = rounds.iter().map(|(round, _)| round.id).collect();
+
+    let mut scores: HashMap<i64, Vec<domain::FactionScore>> = HashMap::new();
+    for score in round_scores::table
+        .filter(round_scores::round_id.eq_any(&round_ids))
+        .order(round_scores::faction_id.asc())
+        .load::<models::RoundScore>(connection)?
+    {
+        scores.entry(score.round_id).or_default().push(domain::FactionScore {
+            faction_id: score.faction_id,
+            points: score.points,
+        });
+    }
+
+    let mut objectives: HashMap<i64, Vec<domain::RoundObjective>> = HashMap::new();
+    for objective in round_objectives::table
+        .filter(round_objectives::round_id.eq_any(&round_ids))
+        .order(round_objectives::id.asc())
+        .load::<models::RoundObjective>(connection)?
+    {
+        objectives.entry(objective.round_id).or_default().push(domain::RoundObjective {
+            id: objective.id,
+            name: objective.name,
+            faction_id: objective.faction_id,
+        });
+    }
+
+    Ok(rounds
+        .into_iter()
+        .map(|(round, game_mode_name)| domain::GameRound {
+            id: round.id,
+            play_event_id: round.play_event_id,
+            number: round.number,
+            name: round.name,
+            notes: round.notes,
+            game_mode_name,
+            scores: scores.remove(&round.id).unwrap_or_default(),
+            objectives: objectives.remove(&round.id).unwrap_or_default(),
+        })
+        .collect())
+}
+
+#[cfg(feature = "ssr")]
+pub fn find_game_round_for_id(
+    search_id: i64,
+    pool: &DieselPool,
+) -> Result<Option<models::GameRound>, DatabaseError> {
+    use schema::game_rounds::dsl::*;
+
+    game_rounds
+        .find(search_id)
+        .first(&mut pool.get().expect("diesel"))
+        .optional()
+        .map_err(DatabaseError::from)
+}
+
+/// Adds the next round to the event, starting with the objectives of the game mode.
+#[cfg(feature = "ssr")]
+pub fn create_game_round(
+    create_play_event_id: i64,
+    create_name: String,
+    game_mode: Option<domain::GameMode>,
+    pool: &DieselPool,
+) -> Result<i64, DatabaseError> {
+    use diesel::dsl::max;
+    use models::{NewGameRound, NewRoundObjective};
+    use schema::{game_rounds, round_objectives};
+
+    pool.get().expect("diesel").transaction(|connection| {
+        lock_play_event(create_play_event_id, connection)?;
+
+        let last_number: Option<i32> = game_rounds::table
+            .filter(game_rounds::play_event_id.eq(create_play_event_id))
+            .select(max(game_rounds::number))
+            .first(connection)?;
+
+        let new_round = NewGameRound {
+            play_event_id: create_play_event_id,
+            game_mode_id: game_mode.as_ref().and_then(|game_mode| game_mode.id),
+            number: last_number.unwrap_or(0) + 1,
+            name: &create_name,
+        };
+
+        let create_round_id = diesel::insert_into(game_rounds::table)
+            .values(&new_round)
+            .returning(game_rounds::id)
+            .get_result(connection)?;
+
+        let objective_names = game_mode.map(|game_mode| game_mode.objective_names()).unwrap_or_default();
+        let new_objectives: Vec<NewRoundObjective> = objective_names
+            .iter()
+            .map(|objective_name| NewRoundObjective {
+                round_id: create_round_id,
+                name: objective_name,
+            })
+            .collect();
+
+        diesel::insert_into(round_objectives::table)
+            .values(&new_objectives)
+            .execute(connection)?;
+
+        Ok(create_round_id)
+    })
+}
+
+#[cfg(feature = "ssr")]
+pub fn delete_game_round(delete_id: i64, pool: &DieselPool) -> Result<(), DatabaseError> {
+    use schema::game_rounds::dsl::*;
+
+    diesel::delete(game_rounds.find(delete_id))
+        .execute(&mut pool.get().expect("diesel"))
+        .map(|_| ())
+        .map_err(DatabaseError::from)
+}
+
+/// Replaces the scores of the round and saves its notes.
+#[cfg(feature = "ssr")]
+pub fn save_round_result(
+    save_round_id: i64,
+    scores: Vec<domain::FactionScore>,
+    save_notes: String,
+    pool: &DieselPool,
+) -> Result<(), DatabaseError> {
+    use schema::{game_rounds, round_scores};
+
+    pool.get().expect("diesel").transaction(|connection| {
+        diesel::update(game_rounds::table.find(save_round_id))
+            .set(game_rounds::notes.eq(&save_notes))
+            .execute(connection)?;
+
+        diesel::delete(round_scores::table.filter(round_scores::round_id.eq(save_round_id)))
+            .execute(connection)?;
+
+        let new_scores: Vec<_> = scores
+            .iter()
+            .map(|score| (
+                round_scores::round_id.eq(save_round_id),
+                round_scores::faction_id.eq(score.faction_id),
+                round_scores::points.eq(score.points),
+            ))
+            .collect();
+
+        diesel::insert_into(round_scores::table)
+            .values(&new_scores)
+            .execute(connection)?;
+
+        Ok(())
+    })
+}
+
+/// Changes the faction's points in the round by the delta, never going below zero.
+#[cfg(feature = "ssr")]
+pub fn adjust_round_score(
+    adjust_round_id: i64,
+    adjust_faction_id: i64,
+    delta: i32,
+    pool: &DieselPool,
+) -> Result<(), DatabaseError> {
+    use schema::round_scores::dsl::*;
+
+    pool.get().expect("diesel").transaction(|connection| {
+        let current_points: Option<i32> = round_scores
+            .find((adjust_round_id, adjust_faction_id))
+            .select(points)
+            .for_update()
+            .first(connection)
+            .optional()?;
+
+        let new_points = current_points.unwrap_or(0).saturating_add(delta).max(0);
+
+        diesel::insert_into(round_scores)
+            .values((round_id.eq(adjust_round_id), faction_id.eq(adjust_faction_id), points.eq(new_points)))
+            .on_conflict((round_id, faction_id))
+            .do_update()
+            .set(points.eq(new_points))
+            .execute(connection)?;
+
+        Ok(())
+    })
+}
+
+#[cfg(feature = "ssr")]
+pub fn add_round_objective(
+    add_round_id: i64,
+    add_name: String,
+    pool: &DieselPool,
+) -> Result<(), DatabaseError> {
+    use models::NewRoundObjective;
+    use schema::round_objectives;
+
+    let new_objective = NewRoundObjective {
+        round_id: add_round_id,
+        name: &add_name,
+    };
+
+    diesel::insert_into(round_objectives::table)
+        .values(&new_objective)
+        .execute(&mut pool.get().expect("diesel"))
+        .map(|_| ())
+        .map_err(DatabaseError::from)
+}
+
+#[cfg(feature = "ssr")]
+pub fn find_round_objective_for_id(
+    search_id: i64,
+    pool: &DieselPool,
+) -> Result<Option<models::RoundObjective>, DatabaseError> {
+    use schema::round_objectives::dsl::*;
+
+    round_objectives
+        .find(search_id)
+        .first(&mut pool.get().expect("diesel"))
+        .optional()
+        .map_err(DatabaseError::from)
+}
+
+#[cfg(feature = "ssr")]
+pub fn set_round_objective_faction(
+    update_id: i64,
+    update_faction_id: Option<i64>,
+    pool: &DieselPool,
+) -> Result<(), DatabaseError> {
+    use schema::round_objectives::dsl::*;
+
+    diesel::update(round_objectives.find(update_id))
+        .set(faction_id.eq(update_faction_id))
+        .execute(&mut pool.get().expect("diesel"))
+        .map(|_| ())
+        .map_err(DatabaseError::from)
+}
+
+#[cfg(feature = "ssr")]
+pub fn delete_round_objective(delete_id: i64, pool: &DieselPool) -> Result<(), DatabaseError> {
+    use schema::round_objectives::dsl::*;
+
+    diesel::delete(round_objectives.find(delete_id))
+        .execute(&mut pool.get().expect("diesel"))
+        .map(|_| ())
+        .map_err(DatabaseError::from)
+}
+
+#[cfg(feature = "ssr")]
+pub fn get_play_events_with_rounds(pool: &DieselPool) -> Result<Vec<models::PlayEvent>, DatabaseError> {
+    use schema::{game_rounds, play_events};
+
+    let round_event_ids = game_rounds::table.select(game_rounds::play_event_id);
+
+    play_events::table
+        .filter(play_events::id.eq_any(round_event_ids))
+        .order(play_events::starts_at.asc())
+        .select(models::PlayEvent::as_select())
+        .load(&mut pool.get().expect("diesel"))
+        .map_err(DatabaseError::from)
+}
+
+/// Event, player and faction of every assignment at the events.
+#[cfg(feature = "ssr")]
+pub fn get_faction_assignments_for_events(
+    search_play_event_ids: Vec<i64>,
+    pool: &DieselPool,
+) -> Result<Vec<(i64, i64, i64)>, DatabaseError> {
+    use schema::faction_assignments::dsl::*;
+
+    faction_assignments
+        .filter(play_event_id.eq_any(search_play_event_ids))
+        .select((play_event_id, player_id, faction_id))
+        .load(&mut pool.get().expect("diesel"))
+        .map_err(DatabaseError::from)
+}
+
+/// Team of each player at each of the events, from the membership history on the day the event started.
+#[cfg(feature = "ssr")]
+pub fn get_team_memberships_for_events(
+    search_play_event_ids: Vec<i64>,
+    pool: &DieselPool,
+) -> Result<HashMap<(i64, i64), i64>, DatabaseError> {
+    use schema::{play_events, team_memberships};
+
+    let connection = &mut pool.get().expect("diesel");
+
+    let event_dates: Vec<(i64, NaiveDate)> = play_events::table
+        .filter(play_events::id.eq_any(search_play_event_ids))
+        .select((play_events::id, play_events::starts_at))
+        .load::<(i64, DateTime<Utc>)>(connection)?
+        .into_iter()
+        .map(|(play_event_id, starts_at)| (play_event_id, starts_at.date_naive()))
+        .collect();
+
+    let memberships: Vec<(i64, i64, NaiveDate, Option<NaiveDate>)> = team_memberships::table
+        .select((
+            team_memberships::player_id,
+            team_memberships::team_id,
+            team_memberships::joined_on,
+            team_memberships::left_on,
+        ))
+        .load(connection)?;
+
+    let mut event_teams = HashMap::new();
+
+    for (play_event_id, date) in event_dates {
+        for (player_id, team_id, joined_on, left_on) in &memberships {
+            if *joined_on <= date && left_on.is_none_or(|left_on| date < left_on) {
+                event_teams.insert((play_event_id, *player_id), *team_id);
+            }
+        }
+    }
+
+    Ok(event_teams)
+}
+
+/// Platoon of the players who belong to one directly, without a team.
+#[cfg(feature = "ssr")]
+pub fn get_direct_platoon_memberships(pool: &DieselPool) -> Result<HashMap<i64, i64>, DatabaseError> {
+    use schema::platoon_player_without_team::dsl::*;
+
+    platoon_player_without_team
+        .select((player_id, platoon_id))
+        .load::<(i64, i64)>(&mut pool.get().expect("diesel"))
+        .map(|memberships| memberships.into_iter().collect())
+        .map_err(DatabaseError::from)
+}
+
+/// Offers with all their bookings and the open seat requests of the event.
+#[cfg(feature = "ssr")]
+pub fn get_carpool_for_event(
+    search_play_event_id: i64,
+    pool: &DieselPool,
+) -> Result<(Vec<domain::RideOffer>, Vec<domain::RideRequest>), DatabaseError> {
+    use schema::{players, ride_bookings, ride_offers, ride_requests};
+
+    let connection = &mut pool.get().expect("diesel");
+
+    let offers = ride_offers::table
+        .inner_join(players::table)
+        .filter(ride_offers::play_event_id.eq(search_play_event_id))
+        .filter(ride_offers::cancelled_at.is_null())
+        .order((ride_offers::departs_at.asc(), ride_offers::id.asc()))
+        .select((models::RideOffer::as_select(), players::tag_name))
+        .load::<(models::RideOffer, String)>(connection)?;
+
+    let offer_ids: Vec<i64> = offers.iter().map(|(offer, _)| offer.id).collect();
+
+    let mut bookings: HashMap<i64, Vec<domain::RideBooking>> = HashMap::new();
+
+    for (booking, passenger_name) in ride_bookings::table
+        .inner_join(players::table)
+        .filter(ride_bookings::ride_offer_id.eq_any(offer_ids))
+        .order((ride_bookings::created_at.asc(), ride_bookings::id.asc()))
+        .select((models::RideBooking::as_select(), players::tag_name))
+        .load::<(models::RideBooking, String)>(connection)?
+    {
+        bookings.entry(booking.ride_offer_id).or_default().push(domain::RideBooking {
+            id: booking.id,
+            ride_offer_id: booking.ride_offer_id,
+            passenger_id: booking.passenger_id,
+            passenger_name,
+            status: booking.status.parse().expect("booking status constrained by database"),
+            driver_seen: booking.driver_seen,
+        });
+    }
+
+    let offers = offers
+        .into_iter()
+        .map(|(offer, driver_name)| domain::RideOffer {
+            id: Some(offer.id),
+            play_event_id: offer.play_event_id,
+            driver_id: offer.driver_id,
+            driver_name,
+            area: offer.area,
+            start_location: offer.start_location,
+            seats: offer.seats,
+            departs_at: offer.departs_at,
+            notes: offer.notes,
+            bookings: bookings.remove(&offer.id).unwrap_or_default(),
+        })
+        .collect();
+
+    let requests = ride_requests::table
+        .inner_join(players::table)
+        .filter(ride_requests::play_event_id.eq(search_play_event_id))
+        .order((ride_requests::created_at.asc(), ride_requests::id.asc()))
+        .select((models::RideRequest::as_select(), players::tag_name))
+        .load::<(models::RideRequest, String)>(connection)?
+        .into_iter()
+        .map(|(request, passenger_name)| domain::RideRequest {
+            id: Some(request.id),
+            play_event_id: request.play_event_id,
+            passenger_id: request.passenger_id,
+            passenger_name,
+            area: request.area,
+            notes: request.notes,
+        })
+        .collect();
+
+    Ok((offers, requests))
+}
+
+/// A driver who cancelled before offers again in the same row, its old bookings stay cancelled.
+#[cfg(feature = "ssr")]
+pub fn create_ride_offer(offer: domain::RideOffer, pool: &DieselPool) -> Result<i64, DatabaseError> {
+    use models::NewRideOffer;
+    use schema::ride_offers;
+
+    pool.get().expect("diesel").transaction(|connection| {
+        let existing = ride_offers::table
+            .filter(ride_offers::play_event_id.eq(offer.play_event_id))
+            .filter(ride_offers::driver_id.eq(offer.driver_id))
+            .select((ride_offers::id, ride_offers::cancelled_at))
+            .first::<(i64, Option<DateTime<Utc>>)>(connection)
+            .optional()?;
+
+        let new_offer = NewRideOffer {
+            play_event_id: offer.play_event_id,
+            driver_id: offer.driver_id,
+            area: &offer.area,
+            start_location: &offer.start_location,
+            seats: offer.seats,
+            departs_at: offer.departs_at,
+            notes: &offer.notes,
+        };
+
+        match existing {
+            Some((_, None)) => Err(DatabaseError::RideAlreadyOffered),
+            Some((existing_id, Some(_))) => {
+                diesel::update(ride_offers::table.find(existing_id))
+                    .set((
+                        ride_offers::area.eq(new_offer.area),
+                        ride_offers::start_location.eq(new_offer.start_location),
+                        ride_offers::seats.eq(new_offer.seats),
+                        ride_offers::departs_at.eq(new_offer.departs_at),
+                        ride_offers::notes.eq(new_offer.notes),
+                        ride_offers::cancelled_at.eq(None::<DateTime<Utc>>),
+                    ))
+                    .execute(connection)?;
+
+                Ok(existing_id)
+            },
+            None => diesel::insert_into(ride_offers::table)
+                .values(&new_offer)
+                .returning(ride_offers::id)
+                .get_result(connection)
+                .map_err(DatabaseError::from),
+        }
+    })
+}
+
+#[cfg(feature = "ssr")]
+pub fn find_ride_offer_for_id(
+    search_id: i64,
+    pool: &DieselPool,
+) -> Result<Option<models::RideOffer>, DatabaseError> {
+    use schema::ride_offers::dsl::*;
+
+    ride_offers
+        .find(search_id)
+        .first::<models::RideOffer>(&mut pool.get().expect("diesel"))
+        .optional()
+        .map_err(DatabaseError::from)
+}
+
+/// Cancels the offer and its open bookings and returns the passengers who lost their seat.
+#[cfg(feature = "ssr")]
+pub fn cancel_ride_offer(cancel_id: i64, pool: &DieselPool) -> Result<Vec<i64>, DatabaseError> {
+    pool.get().expect("diesel").transaction(|connection| {
+        cancel_ride_offer_in_transaction(cancel_id, connection)
+    })
+}
+
+#[cfg(feature = "ssr")]
+fn cancel_ride_offer_in_transaction(cancel_id: i64, connection: &mut PgConnection) -> Result<Vec<i64>, DatabaseError> {
+    use domain::BookingStatus;
+    use schema::{ride_bookings, ride_offers};
+
+    diesel::update(ride_offers::table.find(cancel_id))
+        .filter(ride_offers::cancelled_at.is_null())
+        .set(ride_offers::cancelled_at.eq(Some(Utc::now())))
+        .execute(connection)?;
+
+    // the driver cancelled themselves, so there is nothing left for them to see
+    diesel::update(ride_bookings::table)
+        .filter(ride_bookings::ride_offer_id.eq(cancel_id))
+        .filter(ride_bookings::status.eq_any([BookingStatus::Requested.as_str(), BookingStatus::Confirmed.as_str()]))
+        .set((
+            ride_bookings::status.eq(BookingStatus::Cancelled.as_str()),
+            ride_bookings::driver_seen.eq(true),
+        ))
+        .returning(ride_bookings::passenger_id)
+        .get_results(connection)
+        .map_err(DatabaseError::from)
+}
+
+#[cfg(feature = "ssr")]
+pub fn create_ride_request(request: domain::RideRequest, pool: &DieselPool) -> Result<i64, DatabaseError> {
+    use models::NewRideRequest;
+    use schema::ride_requests;
+
+    pool.get().expect("diesel").transaction(|connection| {
+        let existing: i64 = ride_requests::table
+            .filter(ride_requests::play_event_id.eq(request.play_event_id))
+            .filter(ride_requests::passenger_id.eq(request.passenger_id))
+            .select(count_star())
+            .first(connection)?;
+
+        if existing > 0 {
+            return Err(DatabaseError::RideAlreadyRequested);
+        }
+
+        let new_request = NewRideRequest {
+            play_event_id: request.play_event_id,
+            passenger_id: request.passenger_id,
+            area: &request.area,
+            notes: &request.notes,
+        };
+
+        diesel::insert_into(ride_requests::table)
+            .values(&new_request)
+            .returning(ride_requests::id)
+            .get_result(connection)
+            .map_err(DatabaseError::from)
+    })
+}
+
+#[cfg(feature = "ssr")]
+pub fn find_ride_request_for_id(
+    search_id: i64,
+    pool: &DieselPool,
+) -> Result<Option<models::RideRequest>, DatabaseError> {
+    use schema::ride_requests::dsl::*;
+
+    ride_requests
+        .find(search_id)
+        .first::<models::RideRequest>(&mut pool.get().expect("diesel"))
+        .optional()
+        .map_err(DatabaseError::from)
+}
+
+#[cfg(feature = "ssr")]
+pub fn delete_ride_request(delete_id: i64, pool: &DieselPool) -> Result<(), DatabaseError> {
+    use schema::ride_requests::dsl::*;
+
+    diesel::delete(ride_requests.find(delete_id))
+        .execute(&mut pool.get().expect("diesel"))
+        .map(|_| ())
+        .map_err(DatabaseError::from)
+}
+
+/// Asks the driver for a seat, a passenger holds at most one seat per event.
+#[cfg(feature = "ssr")]
+pub fn book_ride_seat(
+    book_ride_offer_id: i64,
+    book_passenger_id: i64,
+    pool: &DieselPool,
+) -> Result<(), DatabaseError> {
+    use domain::BookingStatus;
+    use schema::{ride_bookings, ride_offers};
+
+    pool.get().expect("diesel").transaction(|connection| {
+        let offer: models::RideOffer = ride_offers::table
+            .find(book_ride_offer_id)
+            .for_update()
+            .first(connection)
+            .optional()?
+            .ok_or(DatabaseError::EntityNotFound)?;
+
+        if offer.cancelled_at.is_some() {
+            return Err(DatabaseError::BookingClosed);
+        }
+
+        let active_statuses = [BookingStatus::Requested.as_str(), BookingStatus::Confirmed.as_str()];
+
+        let event_offer_ids = ride_offers::table
+            .filter(ride_offers::play_event_id.eq(offer.play_event_id))
+            .select(ride_offers::id);
+
+        let booked: i64 = ride_bookings::table
+            .filter(ride_bookings::ride_offer_id.eq_any(event_offer_ids))
+            .filter(ride_bookings::passenger_id.eq(book_passenger_id))
+            .filter(ride_bookings::status.eq_any(active_statuses))
+            .select(count_star())
+            .first(connection)?;
+
+        if booked > 0 {
+            return Err(DatabaseError::SeatAlreadyBooked);
+        }
+
+        if confirmed_seats(offer.id, connection)? >= i64::from(offer.seats) {
+            return Err(DatabaseError::RideFull);
+        }
+
+        let updated = diesel::update(ride_bookings::table)
+            .filter(ride_bookings::ride_offer_id.eq(offer.id))
+            .filter(ride_bookings::passenger_id.eq(book_passenger_id))
+            .set((
+                ride_bookings::status.eq(BookingStatus::Requested.as_str()),
+                ride_bookings::driver_seen.eq(false),
+            ))
+            .execute(connection)?;
+
+        if updated == 0 {
+            diesel::insert_into(ride_bookings::table)
+                .values((
+                    ride_bookings::ride_offer_id.eq(offer.id),
+                    ride_bookings::passenger_id.eq(book_passenger_id),
+                ))
+                .execute(connection)?;
+        }
+
+        Ok(())
+    })
+}
+
+#[cfg(feature = "ssr")]
+pub fn find_ride_booking_for_id(
+    search_id: i64,
+    pool: &DieselPool,
+) -> Result<Option<models::RideBooking>, DatabaseError> {
+    use schema::ride_bookings::dsl::*;
+
+    ride_bookings
+        .find(search_id)
+        .first::<models::RideBooking>(&mut pool.get().expect("diesel"))
+        .optional()
+        .map_err(DatabaseError::from)
+}
+
+/// The driver confirms or declines a requested seat. A confirmed passenger no longer needs their seat request.
+#[cfg(feature = "ssr")]
+pub fn decide_ride_booking(decide_id: i64, confirm: bool, pool: &DieselPool) -> Result<(), DatabaseError> {
+    use domain::BookingStatus;
+    use schema::{ride_bookings, ride_offers, ride_requests};
+
+    pool.get().expect("diesel").transaction(|connection| {
+        let booking: models::RideBooking = ride_bookings::table
+            .find(decide_id)
+            .first(connection)
+            .optional()?
+            .ok_or(DatabaseError::EntityNotFound)?;
+
+        let offer: models::RideOffer = ride_offers::table
+            .find(booking.ride_offer_id)
+            .for_update()
+            .first(connection)?;
+
+        if booking.status != BookingStatus::Requested.as_str() {
+            return Err(DatabaseError::BookingClosed);
+        }
+
+        if confirm && confirmed_seats(offer.id, connection)? >= i64::from(offer.seats) {
+            return Err(DatabaseError::RideFull);
+        }
+
+        let new_status = if confirm {
+            BookingStatus::Confirmed
+        } else {
+            BookingStatus::Declined
+        };
+
+        diesel::update(ride_bookings::table.find(decide_id))
+            .set((
+                ride_bookings::status.eq(new_status.as_str()),
+                ride_bookings::driver_seen.eq(true),
+            ))
+            .execute(connection)?;
+
+        if confirm {
+            diesel::delete(ride_requests::table)
+                .filter(ride_requests::play_event_id.eq(offer.play_event_id))
+                .filter(ride_requests::passenger_id.eq(booking.passenger_id))
+                .execute(connection)?;
+        }
+
+        Ok(())
+    })
+}
+
+/// The passenger releases their seat, the driver is shown the change until they have seen it.
+#[cfg(feature = "ssr")]
+pub fn cancel_ride_booking(cancel_id: i64, pool: &DieselPool) -> Result<(), DatabaseError> {
+    use domain::BookingStatus;
+    use schema::ride_bookings::dsl::*;
+
+    let updated = diesel::update(ride_bookings.find(cancel_id))
+        .filter(status.eq_any([BookingStatus::Requested.as_str(), BookingStatus::Confirmed.as_str()]))
+        .set((
+            status.eq(BookingStatus::Cancelled.as_str()),
+            driver_seen.eq(false),
+        ))
+        .execute(&mut pool.get().expect("diesel"))?;
+
+    if updated == 0 {
+        return Err(DatabaseError::BookingClosed);
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "ssr")]
+pub fn mark_ride_bookings_seen(search_ride_offer_id: i64, pool: &DieselPool) -> Result<(), DatabaseError> {
+    use schema::ride_bookings::dsl::*;
+
+    diesel::update(ride_bookings)
+        .filter(ride_offer_id.eq(search_ride_offer_id))
+        .set(driver_seen.eq(true))
+        .execute(&mut pool.get().expect("diesel"))
+        .map(|_| ())
+        .map_err(DatabaseError::from)
+}
+
+#[cfg(feature = "ssr")]
+fn confirmed_seats(search_ride_offer_id: i64, connection: &mut PgConnection) -> Result<i64, DatabaseError> {
+    use schema::ride_bookings::dsl::*;
+
+    ride_bookings
+        .filter(ride_offer_id.eq(search_ride_offer_id))
+        .filter(status.eq(domain::BookingStatus::Confirmed.as_str()))
+        .select(count_star())
+        .first(connection)
+        .map_err(DatabaseError::from)
+}
+
+/// Puts the notification into the recipients' inboxes and queues a delivery for every channel they chose.
+#[cfg(feature = "ssr")]
+pub fn enqueue_notifications(
+    recipient_ids: &[i64],
+    notification: &domain::Notification,
+    pool: &DieselPool,
+) -> Result<(), DatabaseError> {
+    pool.get().expect("diesel").transaction(|connection| {
+        insert_notifications(recipient_ids, notification, connection)
+    })
+}
+
+/// Queues notifications for announcements that became visible since the last call and returns them.
+#[cfg(feature = "ssr")]
+pub fn enqueue_due_announcement_notifications(pool: &DieselPool) -> Result<Vec<domain::Announcement>, DatabaseError> {
+    use diesel::BoolExpressionMethods;
+    use schema::announcements::dsl::*;
+
+    let now = Utc::now();
+
+    pool.get().expect("diesel").transaction(|connection| {
+        let due: Vec<models::Announcement> = announcements
+            .filter(hidden.eq(false))
+            .filter(notified_at.is_null())
+            .filter(publish_at.le(now))
+            .filter(expires_at.is_null().or(expires_at.gt(now)))
+            .for_update()
+            .skip_locked()
+            .load(connection)?;
+
+        for announcement in &due {
+            let Ok(announcement_audience) = domain::AnnouncementAudience::from_parts(&announcement.audience, announcement.audience_id) else {
+                continue;
+            };
+
+            let recipient_ids: Vec<i64> = get_audience_player_ids(announcement_audience, connection)?
+                .into_iter()
+                .filter(|recipient_id| Some(*recipient_id) != announcement.author_id)
+                .collect();
+
+            let notification = domain::Notification::new(
+                domain::NotificationKind::Announcement,
+                announcement.title.clone(),
+                announcement.content.chars().take(280).collect::<String>(),
+                "/",
+            );
+
+            insert_notifications(&recipient_ids, &notification, connection)?;
+        }
+
+        diesel::update(announcements)
+            .filter(id.eq_any(due.iter().map(|announcement| announcement.id)))
+            .set(notified_at.eq(Some(now)))
+            .execute(connection)?;
+
+        Ok(due.into_iter().map(Into::into).collect())
+    })
+}
+
+#[cfg(feature = "ssr")]
+fn insert_notifications(
+    recipient_ids: &[i64],
+    notification: &domain::Notification,
+    connection: &mut PgConnection,
+) -> Result<(), DatabaseError> {
+    use domain::{DeliveryChannel, NotificationPreference};
+    use models::NewNotification;
+    use schema::{notification_deliveries, notification_preferences, notification_settings, notifications};
+
+    let recipient_ids: HashSet<i64> = recipient_ids.iter().copied().collect();
+
+    let preferences: HashMap<i64, NotificationPreference> = notification_preferences::table
+        .filter(notification_preferences::player_id.eq_any(&recipient_ids))
+        .filter(notification_preferences::kind.eq(notification.kind.as_str()))
+        .select(models::NotificationPreference::as_select())
+        .load(connection)?
+        .into_iter()
+        .map(|preference| (preference.player_id, NotificationPreference {
+            kind: notification.kind,
+            email: preference.email,
+            webhook: preference.webhook,
+        }))
+        .collect();
+
+    let webhook_urls: HashMap<i64, String> = notification_settings::table
+        .filter(notification_settings::player_id.eq_any(&recipient_ids))
+        .filter(notification_settings::webhook_url.ne(""))
+        .select((notification_settings::player_id, notification_settings::webhook_url))
+        .load(connection)?
+        .into_iter()
+        .collect();
+
+    let mut deliveries = Vec::new();
+
+    for recipient_id in recipient_ids {
+        let new_notification = NewNotification {
+            player_id: recipient_id,
+            kind: notification.kind.as_str(),
+            title: &notification.title,
+            body: &notification.body,
+            link: &notification.link,
+        };
+
+        let created_id: i64 = diesel::insert_into(notifications::table)
+            .values(&new_notification)
+            .returning(notifications::id)
+            .get_result(connection)?;
+
+        let preference = preferences
+            .get(&recipient_id)
+            .copied()
+            .unwrap_or(NotificationPreference::default_for(notification.kind));
+
+        if preference.wants(DeliveryChannel::Email) {
+            deliveries.push((
+                notification_deliveries::notification_id.eq(created_id),
+                notification_deliveries::channel.eq(DeliveryChannel::Email.as_str()),
+            ));
+        }
+
+        if preference.wants(DeliveryChannel::Webhook) && webhook_urls.contains_key(&recipient_id) {
+            deliveries.push((
+                notification_deliveries::notification_id.eq(created_id),
+                notification_deliveries::channel.eq(DeliveryChannel::Webhook.as_str()),
+            ));
+        }
+    }
+
+    diesel::insert_into(notification_deliveries::table)
+        .values(deliveries)
+        .execute(connection)?;
+
+    Ok(())
+}
+
+#[cfg(feature = "ssr")]
+pub fn get_notifications_for_player(
+    search_player_id: i64,
+    limit: i64,
+    pool: &DieselPool,
+) -> Result<Vec<domain::Notification>, DatabaseError> {
+    use schema::notifications::dsl::*;
+
+    notifications
+        .filter(player_id.eq(search_player_id))
+        .order((created_at.desc(), id.desc()))
+        .limit(limit)
+        .load::<models::Notification>(&mut pool.get().expect("diesel"))
+        .map(|rows| rows.into_iter().map(Into::into).collect())
+        .map_err(DatabaseError::from)
+}
+
+#[cfg(feature = "ssr")]
+pub fn count_unread_notifications(search_player_id: i64, pool: &DieselPool) -> Result<i64, DatabaseError> {
+    use schema::notifications::dsl::*;
+
+    notifications
+        .filter(player_id.eq(search_player_id))
+        .filter(read_at.is_null())
+        .select(count_star())
+        .first(&mut pool.get().expect("diesel"))
+        .map_err(DatabaseError::from)
+}
+
+/// Marks one notification or, without an id, the whole inbox of the player as read.
+#[cfg(feature = "ssr")]
+pub fn mark_notifications_read(
+    search_player_id: i64,
+    search_id: Option<i64>,
+    pool: &DieselPool,
+) -> Result<(), DatabaseError> {
+    use schema::notifications::dsl::*;
+
+    let mut unread = notifications
+        .filter(player_id.eq(search_player_id))
+        .filter(read_at.is_null())
+        .select(id)
+        .into_boxed();
+
+    if let Some(search_id) = search_id {
+        unread = unread.filter(id.eq(search_id));
+    }
+
+    diesel::update(notifications)
+        .filter(id.eq_any(unread))
+        .set(read_at.eq(Some(Utc::now())))
+        .execute(&mut pool.get().expect("diesel"))
+        .map(|_| ())
+        .map_err(DatabaseError::from)
+}
+
+#[cfg(feature = "ssr")]
+pub fn get_notification_settings(
+    search_player_id: i64,
+    pool: &DieselPool,
+) -> Result<domain::NotificationSettings, DatabaseError> {
+    use domain::{NotificationKind, NotificationPreference};
+    use schema::{notification_preferences, notification_settings};
+
+    let connection = &mut pool.get().expect("diesel");
+
+    let webhook_url = notification_settings::table
+        .filter(notification_settings::player_id.eq(search_player_id))
+        .select(notification_settings::webhook_url)
+        .first::<String>(connection)
+        .optional()?
+        .unwrap_or_default();
+
+    let stored: Vec<models::NotificationPreference> = notification_preferences::table
+        .filter(notification_preferences::player_id.eq(search_player_id))
+        .select(models::NotificationPreference::as_select())
+        .load(connection)?;
+
+    let preferences = NotificationKind::ALL
+        .into_iter()
+        .map(|kind| stored
+            .iter()
+            .find(|preference| preference.kind == kind.as_str())
+            .map(|preference| NotificationPreference {
+                kind,
+                email: preference.email,
+                webhook: preference.webhook,
+            })
+            .unwrap_or(NotificationPreference::default_for(kind)))
+        .collect();
+
+    Ok(domain::NotificationSettings { webhook_url, preferences })
+}
+
+#[cfg(feature = "ssr")]
+pub fn save_notification_settings(
+    save_player_id: i64,
+    settings: domain::NotificationSettings,
+    pool: &DieselPool,
+) -> Result<(), DatabaseError> {
+    use schema::{notification_preferences, notification_settings};
+
+    pool.get().expect("diesel").transaction(|connection| {
+        diesel::insert_into(notification_settings::table)
+            .values((
+                notification_settings::player_id.eq(save_player_id),
+                notification_settings::webhook_url.eq(&settings.webhook_url),
+            ))
+            .on_conflict(notification_settings::player_id)
+            .do_update()
+            .set(notification_settings::webhook_url.eq(&settings.webhook_url))
+            .execute(connection)?;
+
+        for preference in &settings.preferences {
+            diesel::insert_into(notification_preferences::table)
+                .values((
+                    notification_preferences::player_id.eq(save_player_id),
+                    notification_preferences::kind.eq(preference.kind.as_str()),
+                    notification_preferences::email.eq(preference.email),
+                    notification_preferences::webhook.eq(preference.webhook),
+                ))
+                .on_conflict((notification_preferences::player_id, notification_preferences::kind))
+                .do_update()
+                .set((
+                    notification_preferences::email.eq(preference.email),
+                    notification_preferences::webhook.eq(preference.webhook),
+                ))
+                .execute(connection)?;
+        }
+
+        Ok(())
+    })
+}
+
+/// Takes up to `limit` due deliveries off the queue. Each one counts as attempted and is only
+/// handed out again after `lease`, so a worker that dies mid-send does not lose it.
+#[cfg(feature = "ssr")]
+pub fn claim_due_notification_deliveries(
+    limit: i64,
+    lease: chrono::Duration,
+    pool: &DieselPool,
+) -> Result<Vec<domain::PendingDelivery>, DatabaseError> {
+    use domain::DeliveryStatus;
+    use schema::{notification_deliveries, notification_settings, notifications, players};
+
+    let now = Utc::now();
+
+    pool.get().expect("diesel").transaction(|connection| {
+        let due_ids: Vec<i64> = notification_deliveries::table
+            .filter(notification_deliveries::status.eq(DeliveryStatus::Pending.as_str()))
+            .filter(notification_deliveries::next_attempt_at.le(now))
+            .order(notification_deliveries::next_attempt_at.asc())
+            .limit(limit)
+            .select(notification_deliveries::id)
+            .for_update()
+            .skip_locked()
+            .load(connection)?;
+
+        let claimed: Vec<(models::NotificationDelivery, models::Notification, String)> = diesel::update(notification_deliveries::table)
+            .filter(notification_deliveries::id.eq_any(&due_ids))
+            .set((
+                notification_deliveries::attempts.eq(notification_deliveries::attempts + 1),
+                notification_deliveries::next_attempt_at.eq(now + lease),
+            ))
+            .execute(connection)
+            .and_then(|_| notification_deliveries::table
+                .inner_join(notifications::table.inner_join(players::table))
+                .filter(notification_deliveries::id.eq_any(&due_ids))
+                .select((
+                    models::NotificationDelivery::as_select(),
+                    models::Notification::as_select(),
+                    players::email,
+                ))
+                .load(connection))?;
+
+        let recipient_ids: Vec<i64> = claimed.iter().map(|(_, notification, _)| notification.player_id).collect();
+
+        let webhook_urls: HashMap<i64, String> = notification_settings::table
+            .filter(notification_settings::player_id.eq_any(recipient_ids))
+            .select((notification_settings::player_id, notification_settings::webhook_url))
+            .load(connection)?
+            .into_iter()
+            .collect();
+
+        Ok(claimed
+            .into_iter()
+            .map(|(delivery, notification, recipient_email)| domain::PendingDelivery {
+                id: delivery.id,
+                channel: delivery.channel.parse().expect("delivery channel constrained by database"),
+                attempts: delivery.attempts,
+                recipient_email,
+                webhook_url: webhook_urls.get(&notification.player_id).cloned().unwrap_or_default(),
+                notification: notification.into(),
+            })
+            .collect())
+    })
+}
+
+/// Records the outcome of a delivery. A failed delivery with a retry time goes back into the queue.
+#[cfg(feature = "ssr")]
+pub fn finish_notification_delivery(
+    finish_id: i64,
+    outcome: Result<(), String>,
+    retry_at: Option<DateTime<Utc>>,
+    pool: &DieselPool,
+) -> Result<(), DatabaseError> {
+    use domain::DeliveryStatus;
+    use schema::notification_deliveries::dsl::*;
+
+    let connection = &mut pool.get().expect("diesel");
+    let delivery = diesel::update(notification_deliveries.find(finish_id));
+
+    match (outcome, retry_at) {
+        (Ok(()), _) => delivery
+            .set((status.eq(DeliveryStatus::Sent.as_str()), sent_at.eq(Some(Utc::now())), last_error.eq(None::<String>)))
+            .execute(connection),
+        (Err(error), Some(retry_at)) => delivery
+            .set((next_attempt_at.eq(retry_at), last_error.eq(Some(error))))
+            .execute(connection),
+        (Err(error), None) => delivery
+            .set((status.eq(DeliveryStatus::Failed.as_str()), last_error.eq(Some(error))))
+            .execute(connection),
+    }
+    .map(|_| ())
+    .map_err(DatabaseError::from)
+}
+
+#[cfg(feature = "ssr")]
+pub fn get_webhook_endpoints(pool: &DieselPool) -> Result<Vec<domain::WebhookEndpoint>, DatabaseError> {
+    use schema::{webhook_endpoints, webhook_subscriptions};
+
+    let connection = &mut pool.get().expect("diesel");
+
+    let endpoints: Vec<models::WebhookEndpoint> = webhook_endpoints::table
+        .order(webhook_endpoints::name.asc())
+        .select(models::WebhookEndpoint::as_select())
+        .load(connection)?;
+
+    let mut subscriptions: HashMap<i64, Vec<domain::WebhookEventType>> = HashMap::new();
+
+    for (subscription_endpoint_id, subscription_event_type) in webhook_subscriptions::table
+        .select((webhook_subscriptions::endpoint_id, webhook_subscriptions::event_type))
+        .load::<(i64, String)>(connection)?
+    {
+        subscriptions
+            .entry(subscription_endpoint_id)
+            .or_default()
+            .push(subscription_event_type.parse().expect("webhook event type constrained by database"));
+    }
+
+    Ok(endpoints
+        .into_iter()
+        .map(|endpoint| {
+            let mut event_types = subscriptions.remove(&endpoint.id).unwrap_or_default();
+            event_types.sort_by_key(|event_type| domain::WebhookEventType::SUBSCRIBABLE.iter().position(|other| other == event_type));
+
+            domain::WebhookEndpoint {
+                id: Some(endpoint.id),
+                name: endpoint.name,
+                url: endpoint.url,
+                secret: endpoint.secret,
+                active: endpoint.active,
+                event_types,
+            }
+        })
+        .collect())
+}
+
+#[cfg(feature = "ssr")]
+pub fn find_webhook_endpoint_for_id(
+    search_id: i64,
+    pool: &DieselPool,
+) -> Result<Option<domain::WebhookEndpoint>, DatabaseError> {
+    Ok(get_webhook_endpoints(pool)?
+        .into_iter()
+        .find(|endpoint| endpoint.id == Some(search_id)))
+}
+
+#[cfg(feature = "ssr")]
+pub fn create_webhook_endpoint(endpoint: domain::WebhookEndpoint, pool: &DieselPool) -> Result<i64, DatabaseError> {
+    use models::NewWebhookEndpoint;
+    use schema::webhook_endpoints;
+
+    let new_endpoint = NewWebhookEndpoint {
+        name: &endpoint.name,
+        url: &endpoint.url,
+        secret: &endpoint.secret,
+        active: endpoint.active,
+    };
+
+    pool.get().expect("diesel").transaction(|connection| {
+        let created_id = diesel::insert_into(webhook_endpoints::table)
+            .values(&new_endpoint)
+            .returning(webhook_endpoints::id)
+            .get_result(connection)?;
+
+        replace_webhook_subscriptions(created_id, &endpoint.event_types, connection)?;
+
+        Ok(created_id)
+    })
+}
+
+/// Updates everything but the secret, which stays fixed for the lifetime of the endpoint.
+#[cfg(feature = "ssr")]
+pub fn save_webhook_endpoint(endpoint: domain::WebhookEndpoint, pool: &DieselPool) -> Result<(), DatabaseError> {
+    use schema::webhook_endpoints::dsl::*;
+
+    let save_id = endpoint.id.unwrap();
+
+    pool.get().expect("diesel").transaction(|connection| {
+        diesel::update(webhook_endpoints.find(save_id))
+            .set((
+                name.eq(&endpoint.name),
+                url.eq(&endpoint.url),
+                active.eq(endpoint.active),
+            ))
+            .execute(connection)?;
+
+        replace_webhook_subscriptions(save_id, &endpoint.event_types, connection)
+    })
+}
+
+#[cfg(feature = "ssr")]
+fn replace_webhook_subscriptions(
+    save_endpoint_id: i64,
+    event_types: &[domain::WebhookEventType],
+    connection: &mut PgConnection,
+) -> Result<(), DatabaseError> {
+    use schema::webhook_subscriptions::dsl::*;
+
+    diesel::delete(webhook_subscriptions.filter(endpoint_id.eq(save_endpoint_id)))
+        .execute(connection)?;
+
+    diesel::insert_into(webhook_subscriptions)
+        .values(event_types
+            .iter()
+            .map(|subscribed| (endpoint_id.eq(save_endpoint_id), event_type.eq(subscribed.as_str())))
+            .collect::<Vec<_>>())
+        .execute(connection)?;
+
+    Ok(())
+}
+
+#[cfg(feature = "ssr")]
+pub fn delete_webhook_endpoint(delete_id: i64, pool: &DieselPool) -> Result<(), DatabaseError> {
+    use schema::webhook_endpoints::dsl::*;
+
+    diesel::delete(webhook_endpoints.find(delete_id))
+        .execute(&mut pool.get().expect("diesel"))
+        .map(|_| ())
+        .map_err(DatabaseError::from)
+}
+
+/// Queues the payload for every active endpoint subscribed to the event type.
+#[cfg(feature = "ssr")]
+pub fn enqueue_webhook_deliveries(
+    search_event_type: domain::WebhookEventType,
+    save_payload: &str,
+    pool: &DieselPool,
+) -> Result<usize, DatabaseError> {
+    use schema::{webhook_deliveries, webhook_endpoints, webhook_subscriptions};
+
+    let connection = &mut pool.get().expect("diesel");
+
+    let endpoint_ids: Vec<i64> = webhook_subscriptions::table
+        .inner_join(webhook_endpoints::table)
+        .filter(webhook_endpoints::active.eq(true))
+        .filter(webhook_subscriptions::event_type.eq(search_event_type.as_str()))
+        .select(webhook_subscriptions::endpoint_id)
+        .load(connection)?;
+
+    diesel::insert_into(webhook_deliveries::table)
+        .values(endpoint_ids
+            .into_iter()
+            .map(|subscribed_endpoint_id| (
+                webhook_deliveries::endpoint_id.eq(subscribed_endpoint_id),
+                webhook_deliveries::event_type.eq(search_event_type.as_str()),
+                webhook_deliveries::payload.eq(save_payload),
+            ))
+            .collect::<Vec<_>>())
+        .execute(connection)
+        .map_err(DatabaseError::from)
+}
+
+/// Logs a delivery that is sent right away instead of by the worker, e.g. from the test button.
+/// It is stored as already attempted and leased, so the worker leaves it alone.
+#[cfg(feature = "ssr")]
+pub fn create_immediate_webhook_delivery(
+    save_endpoint_id: i64,
+    save_event_type: domain::WebhookEventType,
+    save_payload: &str,
+    lease: chrono::Duration,
+    pool: &DieselPool,
+) -> Result<i64, DatabaseError> {
+    use schema::webhook_deliveries::dsl::*;
+
+    diesel::insert_into(webhook_deliveries)
+        .values((
+            endpoint_id.eq(save_endpoint_id),
+            event_type.eq(save_event_type.as_str()),
+            payload.eq(save_payload),
+            attempts.eq(1),
+            next_attempt_at.eq(Utc::now() + lease),
+        ))
+        .returning(id)
+        .get_result(&mut pool.get().expect("diesel"))
+        .map_err(DatabaseError::from)
+}
+
+/// Takes up to `limit` due webhook deliveries off the queue, together with their endpoint.
+/// Like notification deliveries they count as attempted and are leased for `lease`.
+#[cfg(feature = "ssr")]
+pub fn claim_due_webhook_deliveries(
+    limit: i64,
+    lease: chrono::Duration,
+    pool: &DieselPool,
+) -> Result<Vec<(domain::WebhookDelivery, domain::WebhookEndpoint)>, DatabaseError> {
+    use domain::DeliveryStatus;
+    use schema::{webhook_deliveries, webhook_endpoints};
+
+    let now = Utc::now();
+
+    pool.get().expect("diesel").transaction(|connection| {
+        let due_ids: Vec<i64> = webhook_deliveries::table
+            .filter(webhook_deliveries::status.eq(DeliveryStatus::Pending.as_str()))
+            .filter(webhook_deliveries::next_attempt_at.le(now))
+            .order(webhook_deliveries::next_attempt_at.asc())
+            .limit(limit)
+            .select(webhook_deliveries::id)
+            .for_update()
+            .skip_locked()
+            .load(connection)?;
+
+        let claimed: Vec<(models::WebhookDelivery, models::WebhookEndpoint)> = diesel::update(webhook_deliveries::table)
+            .filter(webhook_deliveries::id.eq_any(&due_ids))
+            .set((
+                webhook_deliveries::attempts.eq(webhook_deliveries::attempts + 1),
+                webhook_deliveries::next_attempt_at.eq(now + lease),
+            ))
+            .execute(connection)
+            .and_then(|_| webhook_deliveries::table
+                .inner_join(webhook_endpoints::table)
+                .filter(webhook_deliveries::id.eq_any(&due_ids))
+                .select((
+                    models::WebhookDelivery::as_select(),
+                    models::WebhookEndpoint::as_select(),
+                ))
+                .load(connection))?;
+
+        Ok(claimed
+            .into_iter()
+            .map(|(delivery, endpoint)| (delivery.into(), domain::WebhookEndpoint {
+                id: Some(endpoint.id),
+                name: endpoint.name,
+                url: endpoint.url,
+                secret: endpoint.secret,
+                active: endpoint.active,
+                event_types: Vec::new(),
+            }))
+            .collect())
+    })
+}
+
+/// Records the outcome of a webhook delivery, see [`finish_notification_delivery`].
+#[cfg(feature = "ssr")]
+pub fn finish_webhook_delivery(
+    finish_id: i64,
+    outcome: Result<(), String>,
+    retry_at: Option<DateTime<Utc>>,
+    pool: &DieselPool,
+) -> Result<(), DatabaseError> {
+    use domain::DeliveryStatus;
+    use schema::webhook_deliveries::dsl::*;
+
+    let connection = &mut pool.get().expect("diesel");
+    let delivery = diesel::update(webhook_deliveries.find(finish_id));
+
+    match (outcome, retry_at) {
+        (Ok(()), _) => delivery
+            .set((status.eq(DeliveryStatus::Sent.as_str()), sent_at.eq(Some(Utc::now())), last_error.eq(None::<String>)))
+            .execute(connection),
+        (Err(error), Some(retry_at)) => delivery
+            .set((next_attempt_at.eq(retry_at), last_error.eq(Some(error))))
+            .execute(connection),
+        (Err(error), None) => delivery
+            .set((status.eq(DeliveryStatus::Failed.as_str()), last_error.eq(Some(error))))
+            .execute(connection),
+    }
+    .map(|_| ())
+    .map_err(DatabaseError::from)
+}
+
+#[cfg(feature = "ssr")]
+pub fn find_webhook_delivery_for_id(
+    search_id: i64,
+    pool: &DieselPool,
+) -> Result<Option<domain::WebhookDelivery>, DatabaseError> {
+    use schema::webhook_deliveries::dsl::*;
+
+    webhook_deliveries
+        .find(search_id)
+        .select(models::WebhookDelivery::as_select())
+        .get_result(&mut pool.get().expect("diesel"))
+        .optional()
+        .map(|delivery| delivery.map(Into::into))
+        .map_err(DatabaseError::from)
+}
+
+#[cfg(feature = "ssr")]
+pub fn get_webhook_deliveries_for_endpoint(
+    search_endpoint_id: i64,
+    limit: i64,
+    pool: &DieselPool,
+) -> Result<Vec<domain::WebhookDelivery>, DatabaseError> {
+    use schema::webhook_deliveries::dsl::*;
+
+    webhook_deliveries
+        .filter(endpoint_id.eq(search_endpoint_id))
+        .order((created_at.desc(), id.desc()))
+        .limit(limit)
+        .select(models::WebhookDelivery::as_select())
+        .load(&mut pool.get().expect("diesel"))
+        .map(|rows| rows.into_iter().map(Into::into).collect())
+        .map_err(DatabaseError::from)
+}
+
+#[cfg(feature = "ssr")]
+pub fn get_api_tokens_for_player(
+    search_player_id: i64,
+    pool: &DieselPool,
+) -> Result<Vec<domain::ApiToken>, DatabaseError> {
+    use schema::api_tokens::dsl::*;
+
+    api_tokens
+        .filter(player_id.eq(search_player_id))
+        .order((created_at.desc(), id.desc()))
+        .select(models::ApiToken::as_select())
+        .load(&mut pool.get().expect("diesel"))
+        .map(|rows| rows.into_iter().map(Into::into).collect())
+        .map_err(DatabaseError::from)
+}
+
+#[cfg(feature = "ssr")]
+pub fn create_api_token(
+    create_player_id: i64,
+    create_name: &str,
+    create_token_hash: &str,
+    pool: &DieselPool,
+) -> Result<i64, DatabaseError> {
+    use schema::api_tokens::dsl::*;
+
+    diesel::insert_into(api_tokens)
+        .values((
+            player_id.eq(create_player_id),
+            name.eq(create_name),
+            token_hash.eq(create_token_hash),
+        ))
+        .returning(id)
+        .get_result(&mut pool.get().expect("diesel"))
+        .map_err(DatabaseError::from)
+}
+
+/// Players can only delete their own tokens.
+#[cfg(feature = "ssr")]
+pub fn delete_api_token(delete_player_id: i64, delete_id: i64, pool: &DieselPool) -> Result<(), DatabaseError> {
+    use schema::api_tokens::dsl::*;
+
+    match diesel::delete(api_tokens.filter(id.eq(delete_id)).filter(player_id.eq(delete_player_id)))
+        .execute(&mut pool.get().expect("diesel"))?
+    {
+        0 => Err(DatabaseError::EntityNotFound),
+        _ => Ok(()),
+    }
+}
+
+/// Returns the owner of the token and records that the token was used.
+#[cfg(feature = "ssr")]
+pub fn find_player_for_api_token(
+    search_token_hash: &str,
+    pool: &DieselPool,
+) -> Result<Option<models::Player>, DatabaseError> {
+    use schema::{api_tokens, players};
+
+    pool.get().expect("diesel").transaction(|connection| {
+        let found: Option<(i64, models::Player)> = api_tokens::table
+            .inner_join(players::table)
+            .filter(api_tokens::token_hash.eq(search_token_hash))
+            .select((api_tokens::id, models::Player::as_select()))
+            .first(connection)
+            .optional()?;
+
+        let Some((token_id, player)) = found else {
+            return Ok(None);
+        };
+
+        diesel::update(api_tokens::table.find(token_id))
+            .set(api_tokens::last_used_at.eq(Some(Utc::now())))
+            .execute(connection)?;
+
+        Ok(Some(player))
+    })
+}
+
+#[cfg(feature = "ssr")]
+fn lock_play_event(
+    search_id: i64,
+    connection: &mut PgConnection,
+) -> Result<models::PlayEvent, DatabaseError> {
+    use schema::play_events::dsl::*;
+
+    play_events
+        .find(search_id)
+        .for_update()
+        .first(connection)
+        .optional()?
+        .ok_or(DatabaseError::EntityNotFound)
+}
+
+#[cfg(feature = "ssr")]
+fn count_registered(
+    search_play_event_id: i64,
+    connection: &mut PgConnection,
+) -> Result<i64, DatabaseError> {
+    use schema::play_event_registrations::dsl::*;
+
+    play_event_registrations
+        .filter(play_event_id.eq(search_play_event_id))
+        .filter(status.eq(domain::RegistrationStatus::Registered.as_str()))
+        .select(count_star())
+        .first(connection)
+        .map_err(DatabaseError::from)
+}
+
+#[cfg(feature = "ssr")]
+fn register_in_transaction(
+    event: &models::PlayEvent,
+    register_player_id: i64,
+    register_registered_by_id: i64,
+    connection: &mut PgConnection,
+) -> Result<domain::RegistrationStatus, DatabaseError> {
+    use domain::RegistrationStatus;
+    use models::NewPlayEventRegistration;
+    use schema::play_event_registrations::{self, dsl::*};
+
+    let domain_event = domain::PlayEvent::from(event.clone());
+
+    if !domain_event.registration_open(Utc::now()) {
+        return Err(DatabaseError::RegistrationClosed);
+    }
+
+    let (player_active, player_birth_date): (bool, Option<NaiveDate>) = {
+        use schema::players;
+
+        players::table
+            .find(register_player_id)
+            .select((players::active, players::birth_date))
+            .first(connection)
+            .optional()?
+            .ok_or(DatabaseError::EntityNotFound)?
+    };
+
+    if !player_active {
+        return Err(DatabaseError::PlayerInactive);
+    }
+
+    let suspensions = suspensions_for_player(register_player_id, connection)?;
+
+    if let Some(suspension) = domain::active_suspension(&suspensions, domain_event.starts_at.date_naive()) {
+        return Err(DatabaseError::NotEligible(domain::ParticipationBlock::Suspended(suspension.ends_on)));
+    }
+
+    let consents = parental_consents_for_player(register_player_id, connection)?;
+
+    domain::check_participation(player_birth_date, &domain_event, &consents)
+        .map_err(DatabaseError::NotEligible)?;
+
+    let existing: Option<models::PlayEventRegistration> = play_event_registrations
+        .filter(play_event_id.eq(event.id))
+        .filter(player_id.eq(register_player_id))
+        .first(connection)
+        .optional()?;
+
+    if let Some(ref existing) = existing {
+        if existing.status != RegistrationStatus::Withdrawn.as_str() {
+            return Err(DatabaseError::AlreadyRegistered);
+        }
+    }
+
+    let new_status = if domain_event.has_free_spot(count_registered(event.id, connection)?) {
+        RegistrationStatus::Registered
+    } else {
+        RegistrationStatus::Waitlisted
+    };
+
+    match existing {
+        Some(existing) => {
+            diesel::update(play_event_registrations.find(existing.id))
+                .set((
+                    status.eq(new_status.as_str()),
+                    registered_by_id.eq(register_registered_by_id),
+                    registered_at.eq(Utc::now()),
+                    withdrawn_at.eq(None::<chrono::DateTime<Utc>>),
+                ))
+                .execute(connection)?;
+        }
+        None => {
+            let new_registration = NewPlayEventRegistration {
+                play_event_id: event.id,
+                player_id: register_player_id,
+                registered_by_id: register_registered_by_id,
+                status: new_status.as_str(),
+            };
+
+            diesel::insert_into(play_event_registrations::table)
+                .values(&new_registration)
+                .execute(connection)?;
+        }
+    }
+
+    Ok(new_status)
+}
+
+/// Moves waitlisted players up in registration order until the capacity is reached.
+#[cfg(feature = "ssr")]
+fn promote_waitlisted(
+    event: &models::PlayEvent,
+    connection: &mut PgConnection,
+) -> Result<Vec<i64>, DatabaseError> {
+    use domain::RegistrationStatus;
+    use schema::play_event_registrations::dsl::*;
+
+    let mut waitlist = play_event_registrations
+        .filter(play_event_id.eq(event.id))
+        .filter(status.eq(RegistrationStatus::Waitlisted.as_str()))
+        .order((registered_at.asc(), id.asc()))
+        .select(player_id)
+        .into_boxed();
+
+    if let Some(capacity) = event.capacity {
+        let free_spots = (i64::from(capacity) - count_registered(event.id, connection)?).max(0);
+        waitlist = waitlist.limit(free_spots);
+    }
+
+    let promoted_ids: Vec<i64> = waitlist.load(connection)?;
+
+    if !promoted_ids.is_empty() {
+        diesel::update(play_event_registrations)
+            .filter(play_event_id.eq(event.id))
+            .filter(player_id.eq_any(&promoted_ids))
+            .set(status.eq(RegistrationStatus::Registered.as_str()))
+            .execute(connection)?;
+    }
+
+    Ok(promoted_ids)
+}
+
+#[cfg(feature = "ssr")]
+fn platoon_player_ids(
+    search_platoon_id: i64,
+    connection: &mut PgConnection,
+) -> Result<Vec<i64>, DatabaseError> {
+    use diesel::NullableExpressionMethods;
+    use schema::{platoon_player_without_team, players, teams};
+
+    let team_ids = teams::table
+        .filter(teams::platoon_id.eq(Some(search_platoon_id)))
+        .select(teams::id.nullable());
+
+    let mut player_ids: Vec<i64> = players::table
+        .filter(players::team_id.eq_any(team_ids))
+        .select(players::id)
+        .load(connection)?;
+
+    player_ids.extend(
+        platoon_player_without_team::table
+            .filter(platoon_player_without_team::platoon_id.eq(search_platoon_id))
+            .select(platoon_player_without_team::player_id)
+            .load::<i64>(connection)?,
+    );
+
+    Ok(player_ids)
+}
+
+/// The platoon, team and event audiences the player belongs to.
+#[cfg(feature = "ssr")]
+fn get_audiences_for_player(
+    search_player_id: i64,
+    connection: &mut PgConnection,
+) -> Result<Vec<domain::AnnouncementAudience>, DatabaseError> {
+    use diesel::{JoinOnDsl, NullableExpressionMethods};
+    use domain::AnnouncementAudience;
+    use schema::{platoon_player_without_team, play_event_registrations, players, teams};
+
+    let mut audiences = Vec::new();
+
+    let player_team: Option<(i64, Option<i64>)> = players::table
+        .inner_join(teams::table.on(players::team_id.eq(teams::id.nullable())))
+        .filter(players::id.eq(search_player_id))
+        .select((teams::id, teams::platoon_id))
+        .first(connection)
+        .optional()?;
+
+    if let Some((team_id, platoon_id)) = player_team {
+        audiences.push(AnnouncementAudience::Team(team_id));
+        audiences.extend(platoon_id.map(AnnouncementAudience::Platoon));
+    }
+
+    audiences.extend(
+        platoon_player_without_team::table
+            .filter(platoon_player_without_team::player_id.eq(search_player_id))
+            .select(platoon_player_without_team::platoon_id)
+            .load::<i64>(connection)?
+            .into_iter()
+            .map(AnnouncementAudience::Platoon),
+    );
+
+    audiences.extend(
+        play_event_registrations::table
+            .filter(play_event_registrations::player_id.eq(search_player_id))
+            .filter(play_event_registrations::status.ne(domain::RegistrationStatus::Withdrawn.as_str()))
+            .select(play_event_registrations::play_event_id)
+            .load::<i64>(connection)?
+            .into_iter()
+            .map(AnnouncementAudience::PlayEvent),
+    );
+
+    Ok(audiences)
+}
+
+#[cfg(feature = "ssr")]
+fn get_audience_player_ids(
+    search_audience: domain::AnnouncementAudience,
+    connection: &mut PgConnection,
+) -> Result<Vec<i64>, DatabaseError> {
+    use domain::AnnouncementAudience;
+    use schema::{play_event_registrations, players};
+
+    match search_audience {
+        AnnouncementAudience::Everyone => players::table
+            .filter(players::active.eq(true))
+            .select(players::id)
+            .load(connection)
+            .map_err(DatabaseError::from),
+        AnnouncementAudience::Platoon(search_platoon_id) => platoon_player_ids(search_platoon_id, connection),
+        AnnouncementAudience::Team(search_team_id) => players::table
+            .filter(players::team_id.eq(Some(search_team_id)))
+            .select(players::id)
+            .load(connection)
+            .map_err(DatabaseError::from),
+        AnnouncementAudience::PlayEvent(search_play_event_id) => play_event_registrations::table
+            .filter(play_event_registrations::play_event_id.eq(search_play_event_id))
+            .filter(play_event_registrations::status.ne(domain::RegistrationStatus::Withdrawn.as_str()))
+            .select(play_event_registrations::player_id)
+            .load(connection)
+            .map_err(DatabaseError::from),
+    }
+}
+
 #[derive(Debug, Clone, thiserror::Error, Serialize, Deserialize, PartialEq, Eq)]
 pub enum DatabaseError {
     #[error("entity not found")]
@@ -328,6 +5570,40 @@ pub enum DatabaseError {
     CreateUserEmailExists(String),
     #[error("user with tag name {0} already exists")]
     CreateUserTagNameExists(String),
+    #[error("registration for this event is closed")]
+    RegistrationClosed,
+    #[error("player is already registered for this event")]
+    AlreadyRegistered,
+    #[error("player is not registered for this event")]
+    NotRegistered,
+    #[error("player is not active")]
+    PlayerInactive,
+    #[error("player may not take part in this event")]
+    NotEligible(crate::domain::ParticipationBlock),
+    #[error("bank transaction is already booked")]
+    AlreadyBooked,
+    #[error("no rental item of this kind is available")]
+    RentalUnavailable,
+    #[error("a rental item of this kind is already reserved")]
+    AlreadyReserved,
+    #[error("player is already a member of this team")]
+    AlreadyTeamMember,
+    #[error("player is not a member of a team")]
+    NotTeamMember,
+    #[error("a join request for this team is already pending")]
+    JoinRequestPending,
+    #[error("join request was already decided")]
+    JoinRequestDecided,
+    #[error("player already offers a ride to this event")]
+    RideAlreadyOffered,
+    #[error("player already asked for a ride to this event")]
+    RideAlreadyRequested,
+    #[error("player already has a seat for this event")]
+    SeatAlreadyBooked,
+    #[error("no seat left in this ride")]
+    RideFull,
+    #[error("booking is no longer open")]
+    BookingClosed,
     #[error("diesel error")]
     Diesel,
 }
@@ -1,4 +1,4 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use diesel::prelude::*;
 
 #[derive(Queryable, Selectable, Debug, Clone)]
@@ -13,6 +13,12 @@ pub struct Player {
     pub password_hash: Vec<u8>,
     pub created_at: DateTime<Utc>,
     pub updated_at: Option<DateTime<Utc>>,
+    pub admin: bool,
+    pub real_name: Option<String>,
+    pub birth_date: Option<NaiveDate>,
+    pub emergency_contact: Option<String>,
+    pub role: Option<String>,
+    pub phone: Option<String>,
 }
 
 #[derive(Insertable)]
@@ -58,11 +64,817 @@ pub struct Platoon {
     pub updated_at: Option<DateTime<Utc>>,
 }
 
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = super::schema::play_events)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct PlayEvent {
+    pub id: i64,
+    pub name: String,
+    pub description: String,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+    pub organizer_id: Option<i64>,
+    pub capacity: Option<i32>,
+    pub registration_deadline: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+    pub sequence: i32,
+    pub cancelled_at: Option<DateTime<Utc>>,
+    pub min_age: Option<i32>,
+    pub location_id: Option<i64>,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = super::schema::play_events)]
+pub struct NewPlayEvent<'a> {
+    pub name: &'a str,
+    pub description: &'a str,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+    pub organizer_id: Option<i64>,
+    pub capacity: Option<i32>,
+    pub registration_deadline: Option<DateTime<Utc>>,
+    pub min_age: Option<i32>,
+    pub location_id: Option<i64>,
+}
+
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = super::schema::play_event_registrations)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct PlayEventRegistration {
+    pub id: i64,
+    pub play_event_id: i64,
+    pub player_id: i64,
+    pub registered_by_id: i64,
+    pub status: String,
+    pub registered_at: DateTime<Utc>,
+    pub withdrawn_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+    pub checked_in_at: Option<DateTime<Utc>>,
+    pub checked_in_by_id: Option<i64>,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = super::schema::play_event_registrations)]
+pub struct NewPlayEventRegistration<'a> {
+    pub play_event_id: i64,
+    pub player_id: i64,
+    pub registered_by_id: i64,
+    pub status: &'a str,
+}
+
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = super::schema::factions)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct Faction {
+    pub id: i64,
+    pub play_event_id: i64,
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = super::schema::factions)]
+pub struct NewFaction<'a> {
+    pub play_event_id: i64,
+    pub name: &'a str,
+}
+
+#[derive(Queryable, Selectable, Insertable, Debug, Clone)]
+#[diesel(table_name = super::schema::faction_assignments)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct FactionAssignment {
+    pub play_event_id: i64,
+    pub player_id: i64,
+    pub faction_id: i64,
+}
+
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = super::schema::announcements)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
 pub struct Announcement {
-    id: i64,
-    title: String,
-    content: String,
-    hidden: bool,
-    created_at: DateTime<Utc>,
-    updated_at: Option<DateTime<Utc>>,
+    pub id: i64,
+    pub title: String,
+    pub content: String,
+    pub hidden: bool,
+    pub publish_at: DateTime<Utc>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub author_id: Option<i64>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+    pub audience: String,
+    pub audience_id: Option<i64>,
+    pub notified_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = super::schema::announcements)]
+pub struct NewAnnouncement<'a> {
+    pub title: &'a str,
+    pub content: &'a str,
+    pub hidden: bool,
+    pub publish_at: DateTime<Utc>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub author_id: Option<i64>,
+    pub audience: &'a str,
+    pub audience_id: Option<i64>,
+}
+
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = super::schema::surveys)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct Survey {
+    pub id: i64,
+    pub title: String,
+    pub description: String,
+    pub audience: String,
+    pub audience_id: i64,
+    pub author_id: Option<i64>,
+    pub anonymous: bool,
+    pub deadline: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = super::schema::surveys)]
+pub struct NewSurvey<'a> {
+    pub title: &'a str,
+    pub description: &'a str,
+    pub audience: &'a str,
+    pub audience_id: i64,
+    pub author_id: Option<i64>,
+    pub anonymous: bool,
+    pub deadline: Option<DateTime<Utc>>,
+}
+
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = super::schema::survey_questions)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct SurveyQuestion {
+    pub id: i64,
+    pub survey_id: i64,
+    pub position: i32,
+    pub text: String,
+    pub kind: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = super::schema::survey_questions)]
+pub struct NewSurveyQuestion<'a> {
+    pub survey_id: i64,
+    pub position: i32,
+    pub text: &'a str,
+    pub kind: &'a str,
+}
+
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = super::schema::survey_question_options)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct SurveyQuestionOption {
+    pub id: i64,
+    pub question_id: i64,
+    pub position: i32,
+    pub text: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = super::schema::survey_question_options)]
+pub struct NewSurveyQuestionOption<'a> {
+    pub question_id: i64,
+    pub position: i32,
+    pub text: &'a str,
+}
+
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = super::schema::survey_answers)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct SurveyAnswer {
+    pub id: i64,
+    pub survey_id: i64,
+    pub question_id: i64,
+    pub player_id: i64,
+    pub option_id: Option<i64>,
+    pub text: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = super::schema::survey_answers)]
+pub struct NewSurveyAnswer<'a> {
+    pub survey_id: i64,
+    pub question_id: i64,
+    pub player_id: i64,
+    pub option_id: Option<i64>,
+    pub text: Option<&'a str>,
+}
+
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = super::schema::replicas)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct Replica {
+    pub id: i64,
+    pub player_id: i64,
+    pub name: String,
+    pub class: String,
+    pub fps: f64,
+    pub bb_weight: f64,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = super::schema::replicas)]
+pub struct NewReplica<'a> {
+    pub player_id: i64,
+    pub name: &'a str,
+    pub class: &'a str,
+    pub fps: f64,
+    pub bb_weight: f64,
+}
+
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = super::schema::play_event_class_limits)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct PlayEventClassLimit {
+    pub play_event_id: i64,
+    pub class: String,
+    pub max_joules: f64,
+    pub min_distance_meters: i32,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = super::schema::play_event_class_limits)]
+pub struct NewPlayEventClassLimit<'a> {
+    pub play_event_id: i64,
+    pub class: &'a str,
+    pub max_joules: f64,
+    pub min_distance_meters: i32,
+}
+
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = super::schema::parental_consents)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct ParentalConsent {
+    pub id: i64,
+    pub player_id: i64,
+    pub kind: String,
+    pub guardian_name: String,
+    pub note: String,
+    pub recorded_by_id: Option<i64>,
+    pub valid_until: NaiveDate,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = super::schema::parental_consents)]
+pub struct NewParentalConsent<'a> {
+    pub player_id: i64,
+    pub kind: &'a str,
+    pub guardian_name: &'a str,
+    pub note: &'a str,
+    pub recorded_by_id: Option<i64>,
+    pub valid_until: NaiveDate,
+}
+
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = super::schema::incident_reports)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct IncidentReport {
+    pub id: i64,
+    pub player_id: i64,
+    pub play_event_id: Option<i64>,
+    pub reported_by_id: Option<i64>,
+    pub category: String,
+    pub description: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = super::schema::incident_reports)]
+pub struct NewIncidentReport<'a> {
+    pub player_id: i64,
+    pub play_event_id: Option<i64>,
+    pub reported_by_id: Option<i64>,
+    pub category: &'a str,
+    pub description: &'a str,
+}
+
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = super::schema::suspensions)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct Suspension {
+    pub id: i64,
+    pub player_id: i64,
+    pub incident_report_id: Option<i64>,
+    pub issued_by_id: Option<i64>,
+    pub reason: String,
+    pub starts_on: NaiveDate,
+    pub ends_on: Option<NaiveDate>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = super::schema::suspensions)]
+pub struct NewSuspension<'a> {
+    pub player_id: i64,
+    pub incident_report_id: Option<i64>,
+    pub issued_by_id: Option<i64>,
+    pub reason: &'a str,
+    pub starts_on: NaiveDate,
+    pub ends_on: Option<NaiveDate>,
+}
+
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = super::schema::legal_documents)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct LegalDocument {
+    pub id: i64,
+    pub kind: String,
+    pub version: i32,
+    pub title: String,
+    pub content: String,
+    pub author_id: Option<i64>,
+    pub published_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = super::schema::legal_documents)]
+pub struct NewLegalDocument<'a> {
+    pub kind: &'a str,
+    pub version: i32,
+    pub title: &'a str,
+    pub content: &'a str,
+    pub author_id: Option<i64>,
+}
+
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = super::schema::fees)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct Fee {
+    pub id: i64,
+    pub name: String,
+    pub kind: String,
+    pub amount_cents: i64,
+    pub play_event_id: Option<i64>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = super::schema::fees)]
+pub struct NewFee<'a> {
+    pub name: &'a str,
+    pub kind: &'a str,
+    pub amount_cents: i64,
+    pub play_event_id: Option<i64>,
+}
+
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = super::schema::charges)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct Charge {
+    pub id: i64,
+    pub player_id: i64,
+    pub fee_id: Option<i64>,
+    pub description: String,
+    pub amount_cents: i64,
+    pub created_by_id: Option<i64>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = super::schema::charges)]
+pub struct NewCharge<'a> {
+    pub player_id: i64,
+    pub fee_id: Option<i64>,
+    pub description: &'a str,
+    pub amount_cents: i64,
+    pub created_by_id: Option<i64>,
+}
+
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = super::schema::payments)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct Payment {
+    pub id: i64,
+    pub player_id: i64,
+    pub charge_id: Option<i64>,
+    pub amount_cents: i64,
+    pub method: String,
+    pub reference: String,
+    pub paid_on: NaiveDate,
+    pub recorded_by_id: Option<i64>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = super::schema::payments)]
+pub struct NewPayment<'a> {
+    pub player_id: i64,
+    pub charge_id: Option<i64>,
+    pub amount_cents: i64,
+    pub method: &'a str,
+    pub reference: &'a str,
+    pub paid_on: NaiveDate,
+    pub recorded_by_id: Option<i64>,
+}
+
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = super::schema::bank_transactions)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct BankTransaction {
+    pub id: i64,
+    pub import_key: String,
+    pub booked_on: NaiveDate,
+    pub amount_cents: i64,
+    pub counterparty: String,
+    pub reference: String,
+    pub payment_id: Option<i64>,
+    pub ignored: bool,
+    pub imported_by_id: Option<i64>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = super::schema::bank_transactions)]
+pub struct NewBankTransaction<'a> {
+    pub import_key: &'a str,
+    pub booked_on: NaiveDate,
+    pub amount_cents: i64,
+    pub counterparty: &'a str,
+    pub reference: &'a str,
+    pub imported_by_id: Option<i64>,
+}
+
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = super::schema::rental_items)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct RentalItem {
+    pub id: i64,
+    pub kind: String,
+    pub name: String,
+    pub serial_number: String,
+    pub condition: String,
+    pub status: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = super::schema::rental_items)]
+pub struct NewRentalItem<'a> {
+    pub kind: &'a str,
+    pub name: &'a str,
+    pub serial_number: &'a str,
+    pub condition: &'a str,
+    pub status: &'a str,
+}
+
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = super::schema::rental_loans)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct RentalLoan {
+    pub id: i64,
+    pub item_id: i64,
+    pub player_id: i64,
+    pub play_event_id: i64,
+    pub checked_out_at: Option<DateTime<Utc>>,
+    pub returned_at: Option<DateTime<Utc>>,
+    pub damage_note: String,
+    pub issued_by_id: Option<i64>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = super::schema::locations)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct Location {
+    pub id: i64,
+    pub name: String,
+    pub address: String,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub field_rules: String,
+    pub parking_info: String,
+    pub safe_zone_notes: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = super::schema::locations)]
+pub struct NewLocation<'a> {
+    pub name: &'a str,
+    pub address: &'a str,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub field_rules: &'a str,
+    pub parking_info: &'a str,
+    pub safe_zone_notes: &'a str,
+}
+
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = super::schema::game_modes)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct GameMode {
+    pub id: i64,
+    pub name: String,
+    pub description: String,
+    pub objectives: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = super::schema::game_modes)]
+pub struct NewGameMode<'a> {
+    pub name: &'a str,
+    pub description: &'a str,
+    pub objectives: &'a str,
+}
+
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = super::schema::game_rounds)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct GameRound {
+    pub id: i64,
+    pub play_event_id: i64,
+    pub game_mode_id: Option<i64>,
+    pub number: i32,
+    pub name: String,
+    pub notes: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = super::schema::game_rounds)]
+pub struct NewGameRound<'a> {
+    pub play_event_id: i64,
+    pub game_mode_id: Option<i64>,
+    pub number: i32,
+    pub name: &'a str,
+}
+
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = super::schema::round_objectives)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct RoundObjective {
+    pub id: i64,
+    pub round_id: i64,
+    pub name: String,
+    pub faction_id: Option<i64>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = super::schema::round_objectives)]
+pub struct NewRoundObjective<'a> {
+    pub round_id: i64,
+    pub name: &'a str,
+}
+
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = super::schema::round_scores)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct RoundScore {
+    pub round_id: i64,
+    pub faction_id: i64,
+    pub points: i32,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = super::schema::team_join_requests)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct TeamJoinRequest {
+    pub id: i64,
+    pub team_id: i64,
+    pub player_id: i64,
+    pub message: String,
+    pub status: String,
+    pub decided_by_id: Option<i64>,
+    pub decided_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = super::schema::team_join_requests)]
+pub struct NewTeamJoinRequest<'a> {
+    pub team_id: i64,
+    pub player_id: i64,
+    pub message: &'a str,
+}
+
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = super::schema::team_memberships)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct TeamMembership {
+    pub id: i64,
+    pub player_id: i64,
+    pub team_id: i64,
+    pub joined_on: NaiveDate,
+    pub left_on: Option<NaiveDate>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = super::schema::team_memberships)]
+pub struct NewTeamMembership {
+    pub player_id: i64,
+    pub team_id: i64,
+    pub joined_on: NaiveDate,
+}
+
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = super::schema::ride_offers)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct RideOffer {
+    pub id: i64,
+    pub play_event_id: i64,
+    pub driver_id: i64,
+    pub area: String,
+    pub start_location: String,
+    pub seats: i32,
+    pub departs_at: DateTime<Utc>,
+    pub notes: String,
+    pub cancelled_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = super::schema::ride_offers)]
+pub struct NewRideOffer<'a> {
+    pub play_event_id: i64,
+    pub driver_id: i64,
+    pub area: &'a str,
+    pub start_location: &'a str,
+    pub seats: i32,
+    pub departs_at: DateTime<Utc>,
+    pub notes: &'a str,
+}
+
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = super::schema::ride_requests)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct RideRequest {
+    pub id: i64,
+    pub play_event_id: i64,
+    pub passenger_id: i64,
+    pub area: String,
+    pub notes: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = super::schema::ride_requests)]
+pub struct NewRideRequest<'a> {
+    pub play_event_id: i64,
+    pub passenger_id: i64,
+    pub area: &'a str,
+    pub notes: &'a str,
+}
+
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = super::schema::ride_bookings)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct RideBooking {
+    pub id: i64,
+    pub ride_offer_id: i64,
+    pub passenger_id: i64,
+    pub status: String,
+    pub driver_seen: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = super::schema::notifications)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct Notification {
+    pub id: i64,
+    pub player_id: i64,
+    pub kind: String,
+    pub title: String,
+    pub body: String,
+    pub link: String,
+    pub read_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = super::schema::notifications)]
+pub struct NewNotification<'a> {
+    pub player_id: i64,
+    pub kind: &'a str,
+    pub title: &'a str,
+    pub body: &'a str,
+    pub link: &'a str,
+}
+
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = super::schema::notification_deliveries)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct NotificationDelivery {
+    pub id: i64,
+    pub notification_id: i64,
+    pub channel: String,
+    pub status: String,
+    pub attempts: i32,
+    pub next_attempt_at: DateTime<Utc>,
+    pub last_error: Option<String>,
+    pub sent_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = super::schema::notification_preferences)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct NotificationPreference {
+    pub id: i64,
+    pub player_id: i64,
+    pub kind: String,
+    pub email: bool,
+    pub webhook: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = super::schema::webhook_endpoints)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct WebhookEndpoint {
+    pub id: i64,
+    pub name: String,
+    pub url: String,
+    pub secret: String,
+    pub active: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = super::schema::webhook_endpoints)]
+pub struct NewWebhookEndpoint<'a> {
+    pub name: &'a str,
+    pub url: &'a str,
+    pub secret: &'a str,
+    pub active: bool,
+}
+
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = super::schema::webhook_deliveries)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct WebhookDelivery {
+    pub id: i64,
+    pub endpoint_id: i64,
+    pub event_type: String,
+    pub payload: String,
+    pub status: String,
+    pub attempts: i32,
+    pub next_attempt_at: DateTime<Utc>,
+    pub last_error: Option<String>,
+    pub sent_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = super::schema::api_tokens)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct ApiToken {
+    pub id: i64,
+    pub player_id: i64,
+    pub name: String,
+    pub token_hash: String,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
 }
@@ -1,5 +1,289 @@
 // @generated automatically by Diesel CLI.
 
+diesel::table! {
+    announcement_reads (announcement_id, player_id) {
+        announcement_id -> Int8,
+        player_id -> Int8,
+        created_at -> Timestamptz,
+        updated_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    announcements (id) {
+        id -> Int8,
+        title -> Text,
+        content -> Text,
+        hidden -> Bool,
+        publish_at -> Timestamptz,
+        expires_at -> Nullable<Timestamptz>,
+        author_id -> Nullable<Int8>,
+        created_at -> Timestamptz,
+        updated_at -> Nullable<Timestamptz>,
+        audience -> Text,
+        audience_id -> Nullable<Int8>,
+        notified_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    api_tokens (id) {
+        id -> Int8,
+        player_id -> Int8,
+        name -> Text,
+        token_hash -> Text,
+        last_used_at -> Nullable<Timestamptz>,
+        created_at -> Timestamptz,
+        updated_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    bank_transactions (id) {
+        id -> Int8,
+        import_key -> Text,
+        booked_on -> Date,
+        amount_cents -> Int8,
+        counterparty -> Text,
+        reference -> Text,
+        payment_id -> Nullable<Int8>,
+        ignored -> Bool,
+        imported_by_id -> Nullable<Int8>,
+        created_at -> Timestamptz,
+        updated_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    calendar_tokens (player_id) {
+        player_id -> Int8,
+        token -> Text,
+        created_at -> Timestamptz,
+        updated_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    charges (id) {
+        id -> Int8,
+        player_id -> Int8,
+        fee_id -> Nullable<Int8>,
+        description -> Text,
+        amount_cents -> Int8,
+        created_by_id -> Nullable<Int8>,
+        created_at -> Timestamptz,
+        updated_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    faction_assignments (play_event_id, player_id) {
+        play_event_id -> Int8,
+        player_id -> Int8,
+        faction_id -> Int8,
+        created_at -> Timestamptz,
+        updated_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    factions (id) {
+        id -> Int8,
+        play_event_id -> Int8,
+        name -> Text,
+        created_at -> Timestamptz,
+        updated_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    fees (id) {
+        id -> Int8,
+        name -> Text,
+        kind -> Text,
+        amount_cents -> Int8,
+        play_event_id -> Nullable<Int8>,
+        created_at -> Timestamptz,
+        updated_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    game_modes (id) {
+        id -> Int8,
+        name -> Text,
+        description -> Text,
+        objectives -> Text,
+        created_at -> Timestamptz,
+        updated_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    game_rounds (id) {
+        id -> Int8,
+        play_event_id -> Int8,
+        game_mode_id -> Nullable<Int8>,
+        number -> Int4,
+        name -> Text,
+        notes -> Text,
+        created_at -> Timestamptz,
+        updated_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    incident_reports (id) {
+        id -> Int8,
+        player_id -> Int8,
+        play_event_id -> Nullable<Int8>,
+        reported_by_id -> Nullable<Int8>,
+        category -> Text,
+        description -> Text,
+        created_at -> Timestamptz,
+        updated_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    legal_document_acceptances (document_id, player_id) {
+        document_id -> Int8,
+        player_id -> Int8,
+        ip_address -> Nullable<Text>,
+        created_at -> Timestamptz,
+        updated_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    legal_documents (id) {
+        id -> Int8,
+        kind -> Text,
+        version -> Int4,
+        title -> Text,
+        content -> Text,
+        author_id -> Nullable<Int8>,
+        published_at -> Nullable<Timestamptz>,
+        created_at -> Timestamptz,
+        updated_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    location_maps (location_id) {
+        location_id -> Int8,
+        content_type -> Text,
+        data -> Bytea,
+        created_at -> Timestamptz,
+        updated_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    locations (id) {
+        id -> Int8,
+        name -> Text,
+        address -> Text,
+        latitude -> Nullable<Float8>,
+        longitude -> Nullable<Float8>,
+        field_rules -> Text,
+        parking_info -> Text,
+        safe_zone_notes -> Text,
+        created_at -> Timestamptz,
+        updated_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    notification_deliveries (id) {
+        id -> Int8,
+        notification_id -> Int8,
+        channel -> Text,
+        status -> Text,
+        attempts -> Int4,
+        next_attempt_at -> Timestamptz,
+        last_error -> Nullable<Text>,
+        sent_at -> Nullable<Timestamptz>,
+        created_at -> Timestamptz,
+        updated_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    notification_preferences (id) {
+        id -> Int8,
+        player_id -> Int8,
+        kind -> Text,
+        email -> Bool,
+        webhook -> Bool,
+        created_at -> Timestamptz,
+        updated_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    notification_settings (id) {
+        id -> Int8,
+        player_id -> Int8,
+        webhook_url -> Text,
+        created_at -> Timestamptz,
+        updated_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    notifications (id) {
+        id -> Int8,
+        player_id -> Int8,
+        kind -> Text,
+        title -> Text,
+        body -> Text,
+        link -> Text,
+        read_at -> Nullable<Timestamptz>,
+        created_at -> Timestamptz,
+        updated_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    parental_consent_documents (consent_id) {
+        consent_id -> Int8,
+        content_type -> Text,
+        data -> Bytea,
+        created_at -> Timestamptz,
+        updated_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    parental_consents (id) {
+        id -> Int8,
+        player_id -> Int8,
+        kind -> Text,
+        guardian_name -> Text,
+        note -> Text,
+        recorded_by_id -> Nullable<Int8>,
+        valid_until -> Date,
+        created_at -> Timestamptz,
+        updated_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    payments (id) {
+        id -> Int8,
+        player_id -> Int8,
+        charge_id -> Nullable<Int8>,
+        amount_cents -> Int8,
+        method -> Text,
+        reference -> Text,
+        paid_on -> Date,
+        recorded_by_id -> Nullable<Int8>,
+        created_at -> Timestamptz,
+        updated_at -> Nullable<Timestamptz>,
+    }
+}
+
 diesel::table! {
     platoon_player_without_team (platoon_id, player_id) {
         platoon_id -> Int8,
@@ -22,6 +306,52 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    play_event_class_limits (play_event_id, class) {
+        play_event_id -> Int8,
+        class -> Text,
+        max_joules -> Float8,
+        min_distance_meters -> Int4,
+        created_at -> Timestamptz,
+        updated_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    play_event_registrations (id) {
+        id -> Int8,
+        play_event_id -> Int8,
+        player_id -> Int8,
+        registered_by_id -> Int8,
+        status -> Text,
+        registered_at -> Timestamptz,
+        withdrawn_at -> Nullable<Timestamptz>,
+        created_at -> Timestamptz,
+        updated_at -> Nullable<Timestamptz>,
+        checked_in_at -> Nullable<Timestamptz>,
+        checked_in_by_id -> Nullable<Int8>,
+    }
+}
+
+diesel::table! {
+    play_events (id) {
+        id -> Int8,
+        name -> Text,
+        description -> Text,
+        starts_at -> Timestamptz,
+        ends_at -> Timestamptz,
+        organizer_id -> Nullable<Int8>,
+        capacity -> Nullable<Int4>,
+        registration_deadline -> Nullable<Timestamptz>,
+        created_at -> Timestamptz,
+        updated_at -> Nullable<Timestamptz>,
+        sequence -> Int4,
+        cancelled_at -> Nullable<Timestamptz>,
+        min_age -> Nullable<Int4>,
+        location_id -> Nullable<Int8>,
+    }
+}
+
 diesel::table! {
     players (id) {
         id -> Int8,
@@ -32,6 +362,205 @@ diesel::table! {
         password_hash -> Bytea,
         created_at -> Timestamptz,
         updated_at -> Nullable<Timestamptz>,
+        admin -> Bool,
+        real_name -> Nullable<Text>,
+        birth_date -> Nullable<Date>,
+        emergency_contact -> Nullable<Text>,
+        role -> Nullable<Text>,
+        phone -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    rental_items (id) {
+        id -> Int8,
+        kind -> Text,
+        name -> Text,
+        serial_number -> Text,
+        condition -> Text,
+        status -> Text,
+        created_at -> Timestamptz,
+        updated_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    rental_loans (id) {
+        id -> Int8,
+        item_id -> Int8,
+        player_id -> Int8,
+        play_event_id -> Int8,
+        checked_out_at -> Nullable<Timestamptz>,
+        returned_at -> Nullable<Timestamptz>,
+        damage_note -> Text,
+        issued_by_id -> Nullable<Int8>,
+        created_at -> Timestamptz,
+        updated_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    replicas (id) {
+        id -> Int8,
+        player_id -> Int8,
+        name -> Text,
+        class -> Text,
+        fps -> Float8,
+        bb_weight -> Float8,
+        created_at -> Timestamptz,
+        updated_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    ride_bookings (id) {
+        id -> Int8,
+        ride_offer_id -> Int8,
+        passenger_id -> Int8,
+        status -> Text,
+        driver_seen -> Bool,
+        created_at -> Timestamptz,
+        updated_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    ride_offers (id) {
+        id -> Int8,
+        play_event_id -> Int8,
+        driver_id -> Int8,
+        area -> Text,
+        start_location -> Text,
+        seats -> Int4,
+        departs_at -> Timestamptz,
+        notes -> Text,
+        cancelled_at -> Nullable<Timestamptz>,
+        created_at -> Timestamptz,
+        updated_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    ride_requests (id) {
+        id -> Int8,
+        play_event_id -> Int8,
+        passenger_id -> Int8,
+        area -> Text,
+        notes -> Text,
+        created_at -> Timestamptz,
+        updated_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    round_objectives (id) {
+        id -> Int8,
+        round_id -> Int8,
+        name -> Text,
+        faction_id -> Nullable<Int8>,
+        created_at -> Timestamptz,
+        updated_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    round_scores (round_id, faction_id) {
+        round_id -> Int8,
+        faction_id -> Int8,
+        points -> Int4,
+        created_at -> Timestamptz,
+        updated_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    survey_answers (id) {
+        id -> Int8,
+        survey_id -> Int8,
+        question_id -> Int8,
+        player_id -> Int8,
+        option_id -> Nullable<Int8>,
+        text -> Nullable<Text>,
+        created_at -> Timestamptz,
+        updated_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    survey_question_options (id) {
+        id -> Int8,
+        question_id -> Int8,
+        position -> Int4,
+        text -> Text,
+        created_at -> Timestamptz,
+        updated_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    survey_questions (id) {
+        id -> Int8,
+        survey_id -> Int8,
+        position -> Int4,
+        text -> Text,
+        kind -> Text,
+        created_at -> Timestamptz,
+        updated_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    surveys (id) {
+        id -> Int8,
+        title -> Text,
+        description -> Text,
+        audience -> Text,
+        audience_id -> Int8,
+        author_id -> Nullable<Int8>,
+        anonymous -> Bool,
+        deadline -> Nullable<Timestamptz>,
+        created_at -> Timestamptz,
+        updated_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    suspensions (id) {
+        id -> Int8,
+        player_id -> Int8,
+        incident_report_id -> Nullable<Int8>,
+        issued_by_id -> Nullable<Int8>,
+        reason -> Text,
+        starts_on -> Date,
+        ends_on -> Nullable<Date>,
+        created_at -> Timestamptz,
+        updated_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    team_join_requests (id) {
+        id -> Int8,
+        team_id -> Int8,
+        player_id -> Int8,
+        message -> Text,
+        status -> Text,
+        decided_by_id -> Nullable<Int8>,
+        decided_at -> Nullable<Timestamptz>,
+        created_at -> Timestamptz,
+        updated_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    team_memberships (id) {
+        id -> Int8,
+        player_id -> Int8,
+        team_id -> Int8,
+        joined_on -> Date,
+        left_on -> Nullable<Date>,
+        created_at -> Timestamptz,
+        updated_at -> Nullable<Timestamptz>,
     }
 }
 
@@ -46,13 +575,157 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    webhook_deliveries (id) {
+        id -> Int8,
+        endpoint_id -> Int8,
+        event_type -> Text,
+        payload -> Text,
+        status -> Text,
+        attempts -> Int4,
+        next_attempt_at -> Timestamptz,
+        last_error -> Nullable<Text>,
+        sent_at -> Nullable<Timestamptz>,
+        created_at -> Timestamptz,
+        updated_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    webhook_endpoints (id) {
+        id -> Int8,
+        name -> Text,
+        url -> Text,
+        secret -> Text,
+        active -> Bool,
+        created_at -> Timestamptz,
+        updated_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    webhook_subscriptions (endpoint_id, event_type) {
+        endpoint_id -> Int8,
+        event_type -> Text,
+        created_at -> Timestamptz,
+        updated_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::joinable!(announcement_reads -> announcements (announcement_id));
+diesel::joinable!(announcement_reads -> players (player_id));
+diesel::joinable!(announcements -> players (author_id));
+diesel::joinable!(api_tokens -> players (player_id));
+diesel::joinable!(bank_transactions -> payments (payment_id));
+diesel::joinable!(bank_transactions -> players (imported_by_id));
+diesel::joinable!(calendar_tokens -> players (player_id));
+diesel::joinable!(charges -> fees (fee_id));
+diesel::joinable!(charges -> players (player_id));
+diesel::joinable!(faction_assignments -> factions (faction_id));
+diesel::joinable!(faction_assignments -> play_events (play_event_id));
+diesel::joinable!(faction_assignments -> players (player_id));
+diesel::joinable!(factions -> play_events (play_event_id));
+diesel::joinable!(fees -> play_events (play_event_id));
+diesel::joinable!(game_rounds -> game_modes (game_mode_id));
+diesel::joinable!(game_rounds -> play_events (play_event_id));
+diesel::joinable!(incident_reports -> play_events (play_event_id));
+diesel::joinable!(incident_reports -> players (player_id));
+diesel::joinable!(legal_document_acceptances -> legal_documents (document_id));
+diesel::joinable!(legal_document_acceptances -> players (player_id));
+diesel::joinable!(legal_documents -> players (author_id));
+diesel::joinable!(location_maps -> locations (location_id));
+diesel::joinable!(notification_deliveries -> notifications (notification_id));
+diesel::joinable!(notification_preferences -> players (player_id));
+diesel::joinable!(notification_settings -> players (player_id));
+diesel::joinable!(notifications -> players (player_id));
+diesel::joinable!(parental_consent_documents -> parental_consents (consent_id));
+diesel::joinable!(parental_consents -> players (player_id));
+diesel::joinable!(payments -> charges (charge_id));
+diesel::joinable!(payments -> players (player_id));
 diesel::joinable!(platoon_player_without_team -> platoons (platoon_id));
 diesel::joinable!(platoon_player_without_team -> players (player_id));
+diesel::joinable!(play_event_class_limits -> play_events (play_event_id));
+diesel::joinable!(play_event_registrations -> play_events (play_event_id));
+diesel::joinable!(play_events -> locations (location_id));
+diesel::joinable!(play_events -> players (organizer_id));
+diesel::joinable!(rental_loans -> play_events (play_event_id));
+diesel::joinable!(rental_loans -> players (player_id));
+diesel::joinable!(rental_loans -> rental_items (item_id));
+diesel::joinable!(replicas -> players (player_id));
+diesel::joinable!(ride_bookings -> players (passenger_id));
+diesel::joinable!(ride_bookings -> ride_offers (ride_offer_id));
+diesel::joinable!(ride_offers -> play_events (play_event_id));
+diesel::joinable!(ride_offers -> players (driver_id));
+diesel::joinable!(ride_requests -> play_events (play_event_id));
+diesel::joinable!(ride_requests -> players (passenger_id));
+diesel::joinable!(round_objectives -> factions (faction_id));
+diesel::joinable!(round_objectives -> game_rounds (round_id));
+diesel::joinable!(round_scores -> factions (faction_id));
+diesel::joinable!(round_scores -> game_rounds (round_id));
+diesel::joinable!(survey_answers -> players (player_id));
+diesel::joinable!(survey_answers -> survey_question_options (option_id));
+diesel::joinable!(survey_answers -> survey_questions (question_id));
+diesel::joinable!(survey_answers -> surveys (survey_id));
+diesel::joinable!(survey_question_options -> survey_questions (question_id));
+diesel::joinable!(survey_questions -> surveys (survey_id));
+diesel::joinable!(surveys -> players (author_id));
+diesel::joinable!(suspensions -> incident_reports (incident_report_id));
+diesel::joinable!(suspensions -> players (player_id));
+diesel::joinable!(team_join_requests -> players (player_id));
+diesel::joinable!(team_join_requests -> teams (team_id));
+diesel::joinable!(team_memberships -> players (player_id));
+diesel::joinable!(team_memberships -> teams (team_id));
 diesel::joinable!(teams -> platoons (platoon_id));
+diesel::joinable!(webhook_deliveries -> webhook_endpoints (endpoint_id));
+diesel::joinable!(webhook_subscriptions -> webhook_endpoints (endpoint_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
+    announcement_reads,
+    announcements,
+    api_tokens,
+    bank_transactions,
+    calendar_tokens,
+    charges,
+    faction_assignments,
+    factions,
+    fees,
+    game_modes,
+    game_rounds,
+    incident_reports,
+    legal_document_acceptances,
+    legal_documents,
+    location_maps,
+    locations,
+    notification_deliveries,
+    notification_preferences,
+    notification_settings,
+    notifications,
+    parental_consent_documents,
+    parental_consents,
+    payments,
     platoon_player_without_team,
     platoons,
+    play_event_class_limits,
+    play_event_registrations,
+    play_events,
     players,
+    rental_items,
+    rental_loans,
+    replicas,
+    ride_bookings,
+    ride_offers,
+    ride_requests,
+    round_objectives,
+    round_scores,
+    survey_answers,
+    survey_question_options,
+    survey_questions,
+    surveys,
+    suspensions,
+    team_join_requests,
+    team_memberships,
     teams,
+    webhook_deliveries,
+    webhook_endpoints,
+    webhook_subscriptions,
 );
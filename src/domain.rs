@@ -1,3 +1,10 @@
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    fmt,
+    str::FromStr,
+};
+
+use chrono::{DateTime, Duration, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 
 #[cfg(feature = "ssr")]
@@ -10,6 +17,24 @@ pub struct Player {
     pub tag_name: String,
     pub active: bool,
     pub team_id: Option<i64>,
+    pub admin: bool,
+    pub real_name: Option<String>,
+    pub birth_date: Option<NaiveDate>,
+    pub emergency_contact: Option<String>,
+    pub role: Option<PlayerRole>,
+    pub phone: Option<String>,
+}
+
+impl Player {
+    /// Removes the fields only the player, admins, event organizers and leaders may see.
+    pub fn without_private_fields(self) -> Self {
+        Self {
+            birth_date: None,
+            emergency_contact: None,
+            phone: None,
+            ..self
+        }
+    }
 }
 
 impl PrimaryKey for Player {
@@ -27,10 +52,69 @@ impl From<database::models::Player> for Player {
             tag_name: value.tag_name,
             active: value.active,
             team_id: value.team_id,
+            admin: value.admin,
+            real_name: value.real_name,
+            birth_date: value.birth_date,
+            emergency_contact: value.emergency_contact,
+            role: value.role.map(|role| role.parse().expect("player role constrained by database")),
+            phone: value.phone,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlayerRole {
+    Rifleman,
+    Medic,
+    Sniper,
+    Support,
+}
+
+impl PlayerRole {
+    pub const ALL: [PlayerRole; 4] = [
+        PlayerRole::Rifleman,
+        PlayerRole::Medic,
+        PlayerRole::Sniper,
+        PlayerRole::Support,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PlayerRole::Rifleman => "rifleman",
+            PlayerRole::Medic => "medic",
+            PlayerRole::Sniper => "sniper",
+            PlayerRole::Support => "support",
+        }
+    }
+}
+
+impl FromStr for PlayerRole {
+    type Err = UnknownVariant;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "rifleman" => Ok(PlayerRole::Rifleman),
+            "medic" => Ok(PlayerRole::Medic),
+            "sniper" => Ok(PlayerRole::Sniper),
+            "support" => Ok(PlayerRole::Support),
+            other => Err(UnknownVariant(other.to_string())),
         }
     }
 }
 
+impl fmt::Display for PlayerRole {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = match self {
+            PlayerRole::Rifleman => "Schütze",
+            PlayerRole::Medic => "Sanitäter",
+            PlayerRole::Sniper => "Scharfschütze",
+            PlayerRole::Support => "Support",
+        };
+
+        write!(f, "{}", text)
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
 pub struct Team {
     pub id: Option<i64>,
@@ -57,6 +141,98 @@ impl From<database::models::Team> for Team {
     }
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JoinRequestStatus {
+    Pending,
+    Approved,
+    Rejected,
+    Withdrawn,
+}
+
+impl JoinRequestStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JoinRequestStatus::Pending => "pending",
+            JoinRequestStatus::Approved => "approved",
+            JoinRequestStatus::Rejected => "rejected",
+            JoinRequestStatus::Withdrawn => "withdrawn",
+        }
+    }
+}
+
+impl FromStr for JoinRequestStatus {
+    type Err = UnknownVariant;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pending" => Ok(JoinRequestStatus::Pending),
+            "approved" => Ok(JoinRequestStatus::Approved),
+            "rejected" => Ok(JoinRequestStatus::Rejected),
+            "withdrawn" => Ok(JoinRequestStatus::Withdrawn),
+            other => Err(UnknownVariant(other.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for JoinRequestStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = match self {
+            JoinRequestStatus::Pending => "Offen",
+            JoinRequestStatus::Approved => "Angenommen",
+            JoinRequestStatus::Rejected => "Abgelehnt",
+            JoinRequestStatus::Withdrawn => "Zurückgezogen",
+        };
+
+        write!(f, "{}", text)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct TeamJoinRequest {
+    pub id: i64,
+    pub team_id: i64,
+    pub player_id: i64,
+    pub tag_name: String,
+    pub message: String,
+    pub status: JoinRequestStatus,
+    pub requested_at: DateTime<Utc>,
+}
+
+/// What the current player may do about their membership in a team.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct TeamMembershipStatus {
+    pub member: bool,
+    pub own_request: Option<TeamJoinRequest>,
+    /// Open requests, only filled for the contact person and admins.
+    pub pending_requests: Vec<TeamJoinRequest>,
+    pub can_decide: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct TeamMembership {
+    pub id: Option<i64>,
+    pub player_id: i64,
+    pub tag_name: String,
+    pub team_id: i64,
+    pub team_name: String,
+    /// The player is a member from `joined_on` up to the day before `left_on`.
+    pub joined_on: NaiveDate,
+    pub left_on: Option<NaiveDate>,
+}
+
+impl PrimaryKey for TeamMembership {
+    fn key(&self) -> Option<i64> {
+        self.id
+    }
+}
+
+/// Membership history of a team and the past events its roster can be looked up for.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct TeamHistory {
+    pub memberships: Vec<TeamMembership>,
+    pub past_events: Vec<PlayEvent>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
 pub struct Platoon {
     pub id: Option<i64>,
@@ -87,6 +263,3669 @@ impl From<database::models::Platoon> for Platoon {
     }
 }
 
-pub trait PrimaryKey {
-    fn key(&self) -> Option<i64>;
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct PlayEvent {
+    pub id: Option<i64>,
+    pub name: String,
+    pub description: String,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+    pub organizer_id: Option<i64>,
+    pub capacity: Option<i32>,
+    pub registration_deadline: Option<DateTime<Utc>>,
+    pub sequence: i32,
+    pub cancelled_at: Option<DateTime<Utc>>,
+    pub min_age: Option<i32>,
+    pub location_id: Option<i64>,
+}
+
+impl PlayEvent {
+    /// Registration closes at the deadline or, without one, when the event starts.
+    pub fn registration_open(&self, now: DateTime<Utc>) -> bool {
+        !self.is_cancelled()
+            && now < self.starts_at
+            && self.registration_deadline.is_none_or(|deadline| now <= deadline)
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled_at.is_some()
+    }
+
+    pub fn has_free_spot(&self, registered_count: i64) -> bool {
+        self.capacity
+            .is_none_or(|capacity| registered_count < i64::from(capacity))
+    }
+}
+
+impl PrimaryKey for PlayEvent {
+    fn key(&self) -> Option<i64> {
+        self.id
+    }
+}
+
+#[cfg(feature = "ssr")]
+impl From<database::models::PlayEvent> for PlayEvent {
+    fn from(value: database::models::PlayEvent) -> Self {
+        Self {
+            id: Some(value.id),
+            name: value.name,
+            description: value.description,
+            starts_at: value.starts_at,
+            ends_at: value.ends_at,
+            organizer_id: value.organizer_id,
+            capacity: value.capacity,
+            registration_deadline: value.registration_deadline,
+            sequence: value.sequence,
+            cancelled_at: value.cancelled_at,
+            min_age: value.min_age,
+            location_id: value.location_id,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct Location {
+    pub id: Option<i64>,
+    pub name: String,
+    pub address: String,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub field_rules: String,
+    pub parking_info: String,
+    pub safe_zone_notes: String,
+    pub has_map: bool,
+}
+
+impl Location {
+    pub fn coordinates(&self) -> Option<(f64, f64)> {
+        self.latitude.zip(self.longitude)
+    }
+
+    pub fn map_url(&self) -> Option<String> {
+        self.coordinates().map(|(latitude, longitude)| format!(
+            "https://www.openstreetmap.org/?mlat={0}&mlon={1}#map=16/{0}/{1}",
+            latitude, longitude,
+        ))
+    }
+
+    /// Route planning to the coordinates, or to the address if the location has none.
+    pub fn directions_url(&self) -> Option<String> {
+        let destination = match self.coordinates() {
+            Some((latitude, longitude)) => format!("{},{}", latitude, longitude),
+            None if !self.address.trim().is_empty() => encode_query_value(self.address.trim()),
+            None => return None,
+        };
+
+        Some(format!("https://www.google.com/maps/dir/?api=1&destination={}", destination))
+    }
+}
+
+impl PrimaryKey for Location {
+    fn key(&self) -> Option<i64> {
+        self.id
+    }
+}
+
+#[cfg(feature = "ssr")]
+impl From<(database::models::Location, bool)> for Location {
+    fn from((value, has_map): (database::models::Location, bool)) -> Self {
+        Self {
+            id: Some(value.id),
+            name: value.name,
+            address: value.address,
+            latitude: value.latitude,
+            longitude: value.longitude,
+            field_rules: value.field_rules,
+            parking_info: value.parking_info,
+            safe_zone_notes: value.safe_zone_notes,
+            has_map,
+        }
+    }
+}
+
+fn encode_query_value(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => (byte as char).to_string(),
+            b' ' => "+".to_string(),
+            byte => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct Announcement {
+    pub id: Option<i64>,
+    pub title: String,
+    pub content: String,
+    pub hidden: bool,
+    pub publish_at: DateTime<Utc>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub author_id: Option<i64>,
+    pub audience: AnnouncementAudience,
+}
+
+impl Announcement {
+    pub fn status(&self, now: DateTime<Utc>) -> AnnouncementStatus {
+        if self.hidden {
+            AnnouncementStatus::Hidden
+        } else if now < self.publish_at {
+            AnnouncementStatus::Scheduled
+        } else if self.expires_at.is_some_and(|expires_at| expires_at <= now) {
+            AnnouncementStatus::Expired
+        } else {
+            AnnouncementStatus::Published
+        }
+    }
+}
+
+impl PrimaryKey for Announcement {
+    fn key(&self) -> Option<i64> {
+        self.id
+    }
+}
+
+#[cfg(feature = "ssr")]
+impl From<database::models::Announcement> for Announcement {
+    fn from(value: database::models::Announcement) -> Self {
+        Self {
+            id: Some(value.id),
+            title: value.title,
+            content: value.content,
+            hidden: value.hidden,
+            publish_at: value.publish_at,
+            expires_at: value.expires_at,
+            author_id: value.author_id,
+            audience: AnnouncementAudience::from_parts(&value.audience, value.audience_id)
+                .expect("announcement audience constrained by database"),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum AnnouncementAudience {
+    #[default]
+    Everyone,
+    Platoon(i64),
+    Team(i64),
+    PlayEvent(i64),
+}
+
+impl AnnouncementAudience {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AnnouncementAudience::Everyone => "everyone",
+            AnnouncementAudience::Platoon(_) => "platoon",
+            AnnouncementAudience::Team(_) => "team",
+            AnnouncementAudience::PlayEvent(_) => "play_event",
+        }
+    }
+
+    pub fn target_id(&self) -> Option<i64> {
+        match self {
+            AnnouncementAudience::Everyone => None,
+            AnnouncementAudience::Platoon(id)
+            | AnnouncementAudience::Team(id)
+            | AnnouncementAudience::PlayEvent(id) => Some(*id),
+        }
+    }
+
+    pub fn from_parts(kind: &str, target_id: Option<i64>) -> Result<Self, UnknownVariant> {
+        match (kind, target_id) {
+            ("everyone", None) => Ok(AnnouncementAudience::Everyone),
+            ("platoon", Some(id)) => Ok(AnnouncementAudience::Platoon(id)),
+            ("team", Some(id)) => Ok(AnnouncementAudience::Team(id)),
+            ("play_event", Some(id)) => Ok(AnnouncementAudience::PlayEvent(id)),
+            (other, _) => Err(UnknownVariant(other.to_string())),
+        }
+    }
+
+    /// Value used in select inputs, e.g. `team:3`.
+    pub fn form_value(&self) -> String {
+        match self.target_id() {
+            Some(id) => format!("{}:{}", self.as_str(), id),
+            None => self.as_str().to_string(),
+        }
+    }
+}
+
+impl FromStr for AnnouncementAudience {
+    type Err = UnknownVariant;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (kind, target_id) = match s.split_once(':') {
+            Some((kind, id)) => (kind, Some(id.parse().map_err(|_| UnknownVariant(s.to_string()))?)),
+            None => (s, None),
+        };
+
+        AnnouncementAudience::from_parts(kind, target_id)
+    }
+}
+
+impl fmt::Display for AnnouncementAudience {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = match self {
+            AnnouncementAudience::Everyone => "Alle",
+            AnnouncementAudience::Platoon(_) => "Zug",
+            AnnouncementAudience::Team(_) => "Team",
+            AnnouncementAudience::PlayEvent(_) => "Event-Teilnehmer",
+        };
+
+        write!(f, "{}", text)
+    }
+}
+
+/// An audience the current player may address, with a label naming the platoon, team or event.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct AudienceOption {
+    pub audience: AnnouncementAudience,
+    pub label: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct AnnouncementFeedEntry {
+    pub announcement: Announcement,
+    pub read: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct AnnouncementReader {
+    pub player_id: i64,
+    pub tag_name: String,
+    pub read_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnnouncementStatus {
+    Hidden,
+    Scheduled,
+    Published,
+    Expired,
+}
+
+impl fmt::Display for AnnouncementStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = match self {
+            AnnouncementStatus::Hidden => "Versteckt",
+            AnnouncementStatus::Scheduled => "Geplant",
+            AnnouncementStatus::Published => "Veröffentlicht",
+            AnnouncementStatus::Expired => "Abgelaufen",
+        };
+
+        write!(f, "{}", text)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RegistrationStatus {
+    Registered,
+    Waitlisted,
+    Withdrawn,
+}
+
+impl RegistrationStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RegistrationStatus::Registered => "registered",
+            RegistrationStatus::Waitlisted => "waitlisted",
+            RegistrationStatus::Withdrawn => "withdrawn",
+        }
+    }
+}
+
+impl FromStr for RegistrationStatus {
+    type Err = UnknownVariant;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "registered" => Ok(RegistrationStatus::Registered),
+            "waitlisted" => Ok(RegistrationStatus::Waitlisted),
+            "withdrawn" => Ok(RegistrationStatus::Withdrawn),
+            other => Err(UnknownVariant(other.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for RegistrationStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = match self {
+            RegistrationStatus::Registered => "Angemeldet",
+            RegistrationStatus::Waitlisted => "Warteliste",
+            RegistrationStatus::Withdrawn => "Abgemeldet",
+        };
+
+        write!(f, "{}", text)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Registration {
+    pub id: i64,
+    pub play_event_id: i64,
+    pub player_id: i64,
+    pub registered_by_id: i64,
+    pub status: RegistrationStatus,
+    pub registered_at: DateTime<Utc>,
+    pub withdrawn_at: Option<DateTime<Utc>>,
+    pub checked_in_at: Option<DateTime<Utc>>,
+}
+
+#[cfg(feature = "ssr")]
+impl From<database::models::PlayEventRegistration> for Registration {
+    fn from(value: database::models::PlayEventRegistration) -> Self {
+        Self {
+            id: value.id,
+            play_event_id: value.play_event_id,
+            player_id: value.player_id,
+            registered_by_id: value.registered_by_id,
+            status: value.status.parse().expect("registration status constrained by database"),
+            registered_at: value.registered_at,
+            withdrawn_at: value.withdrawn_at,
+            checked_in_at: value.checked_in_at,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Participant {
+    pub player_id: i64,
+    pub tag_name: String,
+    pub team_id: Option<i64>,
+    pub team_name: Option<String>,
+    pub platoon_id: Option<i64>,
+    pub platoon_name: Option<String>,
+    pub status: RegistrationStatus,
+    pub registered_at: DateTime<Utc>,
+    pub checked_in_at: Option<DateTime<Utc>>,
+}
+
+pub type ParticipantGroups = BTreeMap<Option<String>, BTreeMap<Option<String>, Vec<Participant>>>;
+
+/// Groups participants by platoon and team name, players without either sort first.
+pub fn group_participants(participants: Vec<Participant>) -> ParticipantGroups {
+    let mut groups = ParticipantGroups::new();
+
+    for participant in participants {
+        groups
+            .entry(participant.platoon_name.clone())
+            .or_default()
+            .entry(participant.team_name.clone())
+            .or_default()
+            .push(participant);
+    }
+
+    groups
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Ticket {
+    pub token: String,
+    pub qr_svg: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub enum CheckInOutcome {
+    CheckedIn,
+    AlreadyCheckedIn(DateTime<Utc>),
+    NotRegistered(RegistrationStatus),
+    PlayerInactive,
+    Suspended(Option<NaiveDate>),
+    WrongEvent,
+    InvalidTicket,
+}
+
+impl CheckInOutcome {
+    pub fn is_ok(&self) -> bool {
+        matches!(self, CheckInOutcome::CheckedIn)
+    }
+}
+
+impl fmt::Display for CheckInOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CheckInOutcome::CheckedIn => write!(f, "Eingecheckt"),
+            CheckInOutcome::AlreadyCheckedIn(at) => {
+                write!(f, "Bereits eingecheckt um {}", at.format("%d.%m.%Y %H:%M"))
+            }
+            CheckInOutcome::NotRegistered(status) => write!(f, "Nicht angemeldet ({})", status),
+            CheckInOutcome::PlayerInactive => write!(f, "Spieler ist nicht aktiv"),
+            CheckInOutcome::Suspended(Some(ends_on)) => {
+                write!(f, "Spieler ist bis {} gesperrt", ends_on.format("%d.%m.%Y"))
+            }
+            CheckInOutcome::Suspended(None) => write!(f, "Spieler ist gesperrt"),
+            CheckInOutcome::WrongEvent => write!(f, "Ticket gehört zu einem anderen Event"),
+            CheckInOutcome::InvalidTicket => write!(f, "Ungültiges Ticket"),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct CheckInResult {
+    pub outcome: CheckInOutcome,
+    pub player_id: Option<i64>,
+    pub tag_name: Option<String>,
+}
+
+impl CheckInResult {
+    pub fn invalid(outcome: CheckInOutcome) -> Self {
+        Self { outcome, player_id: None, tag_name: None }
+    }
+}
+
+/// Without a registration deadline, withdrawing later than this before the start counts as late.
+pub const LATE_WITHDRAWAL_WITHOUT_DEADLINE: Duration = Duration::hours(48);
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct AttendanceRecord {
+    pub player_id: i64,
+    pub play_event_id: i64,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+    pub registration_deadline: Option<DateTime<Utc>>,
+    pub status: RegistrationStatus,
+    pub withdrawn_at: Option<DateTime<Utc>>,
+    pub checked_in_at: Option<DateTime<Utc>>,
+}
+
+impl AttendanceRecord {
+    pub fn attended(&self) -> bool {
+        self.checked_in_at.is_some()
+    }
+
+    pub fn no_show(&self) -> bool {
+        self.status == RegistrationStatus::Registered && self.checked_in_at.is_none()
+    }
+
+    pub fn late_withdrawal(&self) -> bool {
+        let threshold = self
+            .registration_deadline
+            .unwrap_or(self.starts_at - LATE_WITHDRAWAL_WITHOUT_DEADLINE);
+
+        self.status == RegistrationStatus::Withdrawn
+            && self.withdrawn_at.is_some_and(|withdrawn_at| withdrawn_at > threshold)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AttendanceStats {
+    pub registrations: u32,
+    pub attended: u32,
+    pub no_shows: u32,
+    pub late_withdrawals: u32,
+    pub current_streak: u32,
+    pub longest_streak: u32,
+}
+
+impl AttendanceStats {
+    /// Only events that are over count. Waitlisted players never had a spot and players who
+    /// withdrew in time gave it back, both are left out.
+    pub fn from_records<'a, I>(records: I, now: DateTime<Utc>) -> Self
+    where
+        I: IntoIterator<Item = &'a AttendanceRecord>,
+    {
+        let mut records: Vec<&AttendanceRecord> = records
+            .into_iter()
+            .filter(|record| record.ends_at < now)
+            .filter(|record| match record.status {
+                RegistrationStatus::Waitlisted => false,
+                RegistrationStatus::Withdrawn => record.late_withdrawal(),
+                _ => true,
+            })
+            .collect();
+        records.sort_by_key(|record| record.starts_at);
+
+        let mut stats = Self::default();
+
+        for record in records {
+            stats.registrations += 1;
+
+            if record.attended() {
+                stats.attended += 1;
+                stats.current_streak += 1;
+                stats.longest_streak = stats.longest_streak.max(stats.current_streak);
+            } else if record.no_show() {
+                stats.no_shows += 1;
+                stats.current_streak = 0;
+            } else if record.late_withdrawal() {
+                stats.late_withdrawals += 1;
+            }
+        }
+
+        stats
+    }
+
+    /// Sums up the counts, streaks are the best ones among the combined stats.
+    pub fn combine(self, other: Self) -> Self {
+        Self {
+            registrations: self.registrations + other.registrations,
+            attended: self.attended + other.attended,
+            no_shows: self.no_shows + other.no_shows,
+            late_withdrawals: self.late_withdrawals + other.late_withdrawals,
+            current_streak: self.current_streak.max(other.current_streak),
+            longest_streak: self.longest_streak.max(other.longest_streak),
+        }
+    }
+
+    /// Share of registrations that ended with the player on the field, in percent.
+    pub fn reliability(&self) -> Option<u32> {
+        (self.registrations > 0).then(|| self.attended * 100 / self.registrations)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct PlayerAttendance {
+    pub player_id: i64,
+    pub tag_name: String,
+    pub team_id: Option<i64>,
+    pub stats: AttendanceStats,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct TeamAttendance {
+    pub team_id: i64,
+    pub name: String,
+    pub stats: AttendanceStats,
+    pub members: Vec<PlayerAttendance>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct AttendanceReport {
+    pub players: Vec<PlayerAttendance>,
+    pub teams: Vec<TeamAttendance>,
+}
+
+impl AttendanceReport {
+    /// Team stats only count the events a player took part in as a member of that team,
+    /// `team_of` gives the team of a player at an event.
+    pub fn new<F>(
+        players: Vec<Player>,
+        teams: Vec<Team>,
+        records: &[AttendanceRecord],
+        team_of: F,
+        now: DateTime<Utc>,
+    ) -> Self
+    where
+        F: Fn(i64, i64) -> Option<i64>,
+    {
+        let mut records_by_player: HashMap<i64, Vec<&AttendanceRecord>> = HashMap::new();
+        for record in records {
+            records_by_player.entry(record.player_id).or_default().push(record);
+        }
+
+        let teams = teams
+            .into_iter()
+            .filter_map(|team| {
+                let team_id = team.id?;
+                let members: Vec<PlayerAttendance> = players
+                    .iter()
+                    .filter_map(|player| {
+                        let player_id = player.id?;
+                        let team_records: Vec<&AttendanceRecord> = records_by_player
+                            .get(&player_id)
+                            .into_iter()
+                            .flatten()
+                            .copied()
+                            .filter(|record| team_of(record.play_event_id, player_id) == Some(team_id))
+                            .collect();
+
+                        (player.team_id == Some(team_id) || !team_records.is_empty()).then(|| PlayerAttendance {
+                            player_id,
+                            tag_name: player.tag_name.clone(),
+                            team_id: player.team_id,
+                            stats: AttendanceStats::from_records(team_records, now),
+                        })
+                    })
+                    .collect();
+                let stats = members
+                    .iter()
+                    .fold(AttendanceStats::default(), |stats, member| stats.combine(member.stats));
+
+                Some(TeamAttendance { team_id, name: team.name, stats, members })
+            })
+            .collect();
+
+        let players = players
+            .into_iter()
+            .filter_map(|player| {
+                let player_id = player.id?;
+                let records = records_by_player.remove(&player_id).unwrap_or_default();
+
+                Some(PlayerAttendance {
+                    player_id,
+                    tag_name: player.tag_name,
+                    team_id: player.team_id,
+                    stats: AttendanceStats::from_records(records, now),
+                })
+            })
+            .collect();
+
+        Self { players, teams }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct Faction {
+    pub id: Option<i64>,
+    pub play_event_id: i64,
+    pub name: String,
+}
+
+impl PrimaryKey for Faction {
+    fn key(&self) -> Option<i64> {
+        self.id
+    }
+}
+
+#[cfg(feature = "ssr")]
+impl From<database::models::Faction> for Faction {
+    fn from(value: database::models::Faction) -> Self {
+        Self {
+            id: Some(value.id),
+            play_event_id: value.play_event_id,
+            name: value.name,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct FactionMembers {
+    pub faction: Faction,
+    pub members: Vec<Participant>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct FactionOverview {
+    pub factions: Vec<FactionMembers>,
+    pub unassigned: Vec<Participant>,
+    pub editable: bool,
+}
+
+impl FactionOverview {
+    /// Only registered participants take part in the faction split, waitlisted players are left out.
+    pub fn new(
+        factions: Vec<Faction>,
+        assignments: &HashMap<i64, i64>,
+        participants: Vec<Participant>,
+        editable: bool,
+    ) -> Self {
+        let mut factions: Vec<FactionMembers> = factions
+            .into_iter()
+            .map(|faction| FactionMembers { faction, members: Vec::new() })
+            .collect();
+        let mut unassigned = Vec::new();
+
+        for participant in participants {
+            if participant.status != RegistrationStatus::Registered {
+                continue;
+            }
+
+            let faction = assignments
+                .get(&participant.player_id)
+                .and_then(|faction_id| {
+                    factions.iter_mut().find(|members| members.faction.id == Some(*faction_id))
+                });
+
+            match faction {
+                Some(faction) => faction.members.push(participant),
+                None => unassigned.push(participant),
+            }
+        }
+
+        Self { factions, unassigned, editable }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+enum BalanceUnit {
+    Platoon(i64),
+    Team(i64),
+    Player(i64),
+}
+
+/// Splits the registered participants over the given factions and returns `(player_id, faction_id)` pairs.
+///
+/// Platoons are kept together when possible. If splitting platoons into their teams evens out the
+/// headcount better, teams are kept together instead. Players without team are distributed alone.
+pub fn balance_factions(participants: &[Participant], faction_ids: &[i64]) -> Vec<(i64, i64)> {
+    if faction_ids.is_empty() {
+        return Vec::new();
+    }
+
+    let registered: Vec<&Participant> = participants
+        .iter()
+        .filter(|participant| participant.status == RegistrationStatus::Registered)
+        .collect();
+
+    let by_platoon = distribute_units(&registered, faction_ids.len(), |participant| {
+        match (participant.platoon_id, participant.team_id) {
+            (Some(platoon_id), _) => BalanceUnit::Platoon(platoon_id),
+            (None, Some(team_id)) => BalanceUnit::Team(team_id),
+            (None, None) => BalanceUnit::Player(participant.player_id),
+        }
+    });
+    let by_team = distribute_units(&registered, faction_ids.len(), |participant| {
+        match participant.team_id {
+            Some(team_id) => BalanceUnit::Team(team_id),
+            None => BalanceUnit::Player(participant.player_id),
+        }
+    });
+
+    let distribution = if headcount_spread(&by_team) < headcount_spread(&by_platoon) {
+        by_team
+    } else {
+        by_platoon
+    };
+
+    distribution
+        .into_iter()
+        .zip(faction_ids)
+        .flat_map(|(player_ids, faction_id)| {
+            player_ids.into_iter().map(move |player_id| (player_id, *faction_id))
+        })
+        .collect()
+}
+
+fn distribute_units<F>(participants: &[&Participant], faction_count: usize, unit_of: F) -> Vec<Vec<i64>>
+where
+    F: Fn(&Participant) -> BalanceUnit,
+{
+    let mut units: BTreeMap<BalanceUnit, Vec<i64>> = BTreeMap::new();
+
+    for participant in participants {
+        units.entry(unit_of(participant)).or_default().push(participant.player_id);
+    }
+
+    let mut units: Vec<Vec<i64>> = units.into_values().collect();
+    // largest units first, ties keep the deterministic key order of the map
+    units.sort_by_key(|unit| std::cmp::Reverse(unit.len()));
+
+    let mut factions: Vec<Vec<i64>> = vec![Vec::new(); faction_count];
+
+    for unit in units {
+        let smallest = factions
+            .iter_mut()
+            .min_by_key(|members| members.len())
+            .expect("at least one faction");
+
+        smallest.extend(unit);
+    }
+
+    factions
+}
+
+fn headcount_spread(factions: &[Vec<i64>]) -> usize {
+    let largest = factions.iter().map(Vec::len).max().unwrap_or(0);
+    let smallest = factions.iter().map(Vec::len).min().unwrap_or(0);
+
+    largest - smallest
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct Survey {
+    pub id: Option<i64>,
+    pub title: String,
+    pub description: String,
+    pub audience: AnnouncementAudience,
+    pub author_id: Option<i64>,
+    pub anonymous: bool,
+    pub deadline: Option<DateTime<Utc>>,
+}
+
+impl Survey {
+    /// Answers can be given and changed until the deadline.
+    pub fn open(&self, now: DateTime<Utc>) -> bool {
+        self.deadline.is_none_or(|deadline| now <= deadline)
+    }
+}
+
+impl PrimaryKey for Survey {
+    fn key(&self) -> Option<i64> {
+        self.id
+    }
+}
+
+#[cfg(feature = "ssr")]
+impl From<database::models::Survey> for Survey {
+    fn from(value: database::models::Survey) -> Self {
+        Self {
+            id: Some(value.id),
+            title: value.title,
+            description: value.description,
+            audience: AnnouncementAudience::from_parts(&value.audience, Some(value.audience_id))
+                .expect("survey audience constrained by database"),
+            author_id: value.author_id,
+            anonymous: value.anonymous,
+            deadline: value.deadline,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SurveyQuestionKind {
+    SingleChoice,
+    MultipleChoice,
+    FreeText,
+}
+
+impl SurveyQuestionKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SurveyQuestionKind::SingleChoice => "single_choice",
+            SurveyQuestionKind::MultipleChoice => "multiple_choice",
+            SurveyQuestionKind::FreeText => "free_text",
+        }
+    }
+
+    pub fn has_options(&self) -> bool {
+        *self != SurveyQuestionKind::FreeText
+    }
+}
+
+impl FromStr for SurveyQuestionKind {
+    type Err = UnknownVariant;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "single_choice" => Ok(SurveyQuestionKind::SingleChoice),
+            "multiple_choice" => Ok(SurveyQuestionKind::MultipleChoice),
+            "free_text" => Ok(SurveyQuestionKind::FreeText),
+            other => Err(UnknownVariant(other.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for SurveyQuestionKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = match self {
+            SurveyQuestionKind::SingleChoice => "Einfachauswahl",
+            SurveyQuestionKind::MultipleChoice => "Mehrfachauswahl",
+            SurveyQuestionKind::FreeText => "Freitext",
+        };
+
+        write!(f, "{}", text)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SurveyQuestion {
+    pub id: i64,
+    pub text: String,
+    pub kind: SurveyQuestionKind,
+    pub options: Vec<SurveyOption>,
+}
+
+impl SurveyQuestion {
+    /// Checks a player's answers to this question. Leaving a question out is allowed.
+    pub fn accepts(&self, answers: &[&SurveyAnswer]) -> bool {
+        if answers.is_empty() {
+            return true;
+        }
+
+        let known_option = |answer: &&SurveyAnswer| {
+            answer.text.is_none()
+                && answer
+                    .option_id
+                    .is_some_and(|option_id| self.options.iter().any(|option| option.id == option_id))
+        };
+
+        match self.kind {
+            SurveyQuestionKind::SingleChoice => answers.len() == 1 && answers.iter().all(known_option),
+            SurveyQuestionKind::MultipleChoice => {
+                let distinct: std::collections::HashSet<Option<i64>> =
+                    answers.iter().map(|answer| answer.option_id).collect();
+
+                distinct.len() == answers.len() && answers.iter().all(known_option)
+            }
+            SurveyQuestionKind::FreeText => {
+                answers.len() == 1
+                    && answers[0].option_id.is_none()
+                    && answers[0].text.as_ref().is_some_and(|text| !text.trim().is_empty())
+            }
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SurveyOption {
+    pub id: i64,
+    pub text: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SurveyAnswer {
+    pub question_id: i64,
+    pub option_id: Option<i64>,
+    pub text: Option<String>,
+}
+
+#[cfg(feature = "ssr")]
+impl From<database::models::SurveyAnswer> for SurveyAnswer {
+    fn from(value: database::models::SurveyAnswer) -> Self {
+        Self {
+            question_id: value.question_id,
+            option_id: value.option_id,
+            text: value.text,
+        }
+    }
+}
+
+/// Returns the first question whose answers don't fit it, or an answer to an unknown question.
+pub fn find_invalid_answer<'a>(
+    questions: &'a [SurveyQuestion],
+    answers: &[SurveyAnswer],
+) -> Option<Result<&'a SurveyQuestion, i64>> {
+    if let Some(answer) = answers
+        .iter()
+        .find(|answer| !questions.iter().any(|question| question.id == answer.question_id))
+    {
+        return Some(Err(answer.question_id));
+    }
+
+    questions
+        .iter()
+        .find(|question| {
+            let question_answers: Vec<&SurveyAnswer> = answers
+                .iter()
+                .filter(|answer| answer.question_id == question.id)
+                .collect();
+
+            !question.accepts(&question_answers)
+        })
+        .map(Ok)
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SurveyDetails {
+    pub survey: Survey,
+    pub questions: Vec<SurveyQuestion>,
+    pub own_answers: Vec<SurveyAnswer>,
+    pub editable: bool,
+    pub can_answer: bool,
+    pub has_answers: bool,
+    pub results_visible: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct RespondentAnswer {
+    pub player_id: i64,
+    pub tag_name: String,
+    pub answer: SurveyAnswer,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SurveyResults {
+    pub respondents: usize,
+    pub audience_size: usize,
+    pub questions: Vec<QuestionResult>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct QuestionResult {
+    pub question: SurveyQuestion,
+    pub answered: usize,
+    pub options: Vec<OptionResult>,
+    pub texts: Vec<TextAnswer>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct OptionResult {
+    pub option: SurveyOption,
+    pub count: usize,
+    pub percent: u32,
+    pub voters: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct TextAnswer {
+    pub tag_name: Option<String>,
+    pub text: String,
+}
+
+impl SurveyResults {
+    /// Percentages are relative to the players who answered the question, so multiple choice
+    /// results can add up to more than 100. Names are left out for anonymous surveys.
+    pub fn new(
+        survey: &Survey,
+        questions: Vec<SurveyQuestion>,
+        answers: &[RespondentAnswer],
+        audience_size: usize,
+    ) -> Self {
+        use std::collections::HashSet;
+
+        let respondents = answers
+            .iter()
+            .map(|answer| answer.player_id)
+            .collect::<HashSet<_>>()
+            .len();
+        let name = |answer: &RespondentAnswer| (!survey.anonymous).then(|| answer.tag_name.clone());
+
+        let questions = questions
+            .into_iter()
+            .map(|question| {
+                let question_answers: Vec<&RespondentAnswer> = answers
+                    .iter()
+                    .filter(|answer| answer.answer.question_id == question.id)
+                    .collect();
+                let answered = question_answers
+                    .iter()
+                    .map(|answer| answer.player_id)
+                    .collect::<HashSet<_>>()
+                    .len();
+
+                let options = question
+                    .options
+                    .iter()
+                    .map(|option| {
+                        let votes: Vec<&&RespondentAnswer> = question_answers
+                            .iter()
+                            .filter(|answer| answer.answer.option_id == Some(option.id))
+                            .collect();
+                        let percent = (votes.len() * 100).checked_div(answered).unwrap_or(0) as u32;
+
+                        OptionResult {
+                            option: option.clone(),
+                            count: votes.len(),
+                            percent,
+                            voters: votes.iter().flat_map(|answer| name(answer)).collect(),
+                        }
+                    })
+                    .collect();
+
+                let texts = question_answers
+                    .iter()
+                    .filter_map(|answer| {
+                        answer.answer.text.clone().map(|text| TextAnswer {
+                            tag_name: name(answer),
+                            text,
+                        })
+                    })
+                    .collect();
+
+                QuestionResult {
+                    question,
+                    answered,
+                    options,
+                    texts,
+                }
+            })
+            .collect();
+
+        Self {
+            respondents,
+            audience_size,
+            questions,
+        }
+    }
+}
+
+/// BB weight in gram that FPS limits are usually given for.
+pub const REFERENCE_BB_WEIGHT: f64 = 0.20;
+const METERS_PER_FOOT: f64 = 0.3048;
+
+/// Muzzle energy in joules of a BB with the given weight in gram.
+pub fn joules_from_fps(fps: f64, bb_weight: f64) -> f64 {
+    let velocity = fps * METERS_PER_FOOT;
+
+    0.5 * (bb_weight / 1000.0) * velocity * velocity
+}
+
+/// Velocity in FPS a BB with the given weight in gram needs for the muzzle energy.
+pub fn fps_from_joules(joules: f64, bb_weight: f64) -> f64 {
+    (2.0 * joules / (bb_weight / 1000.0)).sqrt() / METERS_PER_FOOT
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ReplicaClass {
+    #[default]
+    Aeg,
+    Dmr,
+    Sniper,
+    Pistol,
+}
+
+impl ReplicaClass {
+    pub const ALL: [ReplicaClass; 4] = [
+        ReplicaClass::Aeg,
+        ReplicaClass::Dmr,
+        ReplicaClass::Sniper,
+        ReplicaClass::Pistol,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReplicaClass::Aeg => "aeg",
+            ReplicaClass::Dmr => "dmr",
+            ReplicaClass::Sniper => "sniper",
+            ReplicaClass::Pistol => "pistol",
+        }
+    }
+}
+
+impl FromStr for ReplicaClass {
+    type Err = UnknownVariant;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "aeg" => Ok(ReplicaClass::Aeg),
+            "dmr" => Ok(ReplicaClass::Dmr),
+            "sniper" => Ok(ReplicaClass::Sniper),
+            "pistol" => Ok(ReplicaClass::Pistol),
+            other => Err(UnknownVariant(other.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for ReplicaClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = match self {
+            ReplicaClass::Aeg => "AEG",
+            ReplicaClass::Dmr => "DMR",
+            ReplicaClass::Sniper => "Sniper",
+            ReplicaClass::Pistol => "Pistole",
+        };
+
+        write!(f, "{}", text)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct Replica {
+    pub id: Option<i64>,
+    pub player_id: i64,
+    pub name: String,
+    pub class: ReplicaClass,
+    /// Measured velocity with a BB of `bb_weight` gram.
+    pub fps: f64,
+    pub bb_weight: f64,
+}
+
+impl Replica {
+    pub fn joules(&self) -> f64 {
+        joules_from_fps(self.fps, self.bb_weight)
+    }
+
+    /// The measurement converted to the usual 0.20 g BB.
+    pub fn reference_fps(&self) -> f64 {
+        fps_from_joules(self.joules(), REFERENCE_BB_WEIGHT)
+    }
+
+    pub fn chrono_status(&self, limits: &[ClassLimit]) -> ChronoStatus {
+        match limits.iter().find(|limit| limit.class == self.class) {
+            Some(limit) if limit.allows(self.joules()) => ChronoStatus::Passed,
+            Some(_) => ChronoStatus::Failed,
+            None => ChronoStatus::NoLimit,
+        }
+    }
+}
+
+impl PrimaryKey for Replica {
+    fn key(&self) -> Option<i64> {
+        self.id
+    }
+}
+
+#[cfg(feature = "ssr")]
+impl From<database::models::Replica> for Replica {
+    fn from(value: database::models::Replica) -> Self {
+        Self {
+            id: Some(value.id),
+            player_id: value.player_id,
+            name: value.name,
+            class: value.class.parse().expect("replica class constrained by database"),
+            fps: value.fps,
+            bb_weight: value.bb_weight,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ClassLimit {
+    pub class: ReplicaClass,
+    pub max_joules: f64,
+    pub min_distance_meters: i32,
+}
+
+impl ClassLimit {
+    /// Small tolerance so a replica converted from exactly the limit still passes.
+    pub fn allows(&self, joules: f64) -> bool {
+        joules <= self.max_joules + 1e-9
+    }
+
+    pub fn max_reference_fps(&self) -> f64 {
+        fps_from_joules(self.max_joules, REFERENCE_BB_WEIGHT)
+    }
+}
+
+#[cfg(feature = "ssr")]
+impl From<database::models::PlayEventClassLimit> for ClassLimit {
+    fn from(value: database::models::PlayEventClassLimit) -> Self {
+        Self {
+            class: value.class.parse().expect("replica class constrained by database"),
+            max_joules: value.max_joules,
+            min_distance_meters: value.min_distance_meters,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChronoStatus {
+    Passed,
+    Failed,
+    NoLimit,
+}
+
+impl fmt::Display for ChronoStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = match self {
+            ChronoStatus::Passed => "Bestanden",
+            ChronoStatus::Failed => "Nicht bestanden",
+            ChronoStatus::NoLimit => "Kein Limit",
+        };
+
+        write!(f, "{}", text)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct PlayerGear {
+    pub replicas: Vec<Replica>,
+    pub editable: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct EventLimits {
+    pub limits: Vec<ClassLimit>,
+    pub editable: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ChronoEntry {
+    pub tag_name: String,
+    pub checked_in: bool,
+    pub replica: Replica,
+    pub status: ChronoStatus,
+}
+
+pub const ADULT_AGE: u32 = 18;
+
+/// Age in completed years on the given date.
+pub fn age_on(birth_date: NaiveDate, date: NaiveDate) -> u32 {
+    date.years_since(birth_date).unwrap_or(0)
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ConsentKind {
+    /// Signed digitally by the guardian in the app.
+    #[default]
+    Signed,
+    /// Paper form handed in, uploaded by an admin.
+    Document,
+}
+
+impl ConsentKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ConsentKind::Signed => "signed",
+            ConsentKind::Document => "document",
+        }
+    }
+}
+
+impl FromStr for ConsentKind {
+    type Err = UnknownVariant;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "signed" => Ok(ConsentKind::Signed),
+            "document" => Ok(ConsentKind::Document),
+            other => Err(UnknownVariant(other.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for ConsentKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = match self {
+            ConsentKind::Signed => "Digital unterschrieben",
+            ConsentKind::Document => "Dokument",
+        };
+
+        write!(f, "{}", text)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParentalConsent {
+    pub id: Option<i64>,
+    pub player_id: i64,
+    pub kind: ConsentKind,
+    pub guardian_name: String,
+    pub note: String,
+    pub recorded_by_id: Option<i64>,
+    pub valid_until: NaiveDate,
+    pub has_document: bool,
+}
+
+impl ParentalConsent {
+    pub fn valid_on(&self, date: NaiveDate) -> bool {
+        date <= self.valid_until
+    }
+}
+
+impl PrimaryKey for ParentalConsent {
+    fn key(&self) -> Option<i64> {
+        self.id
+    }
+}
+
+#[cfg(feature = "ssr")]
+impl From<(database::models::ParentalConsent, bool)> for ParentalConsent {
+    fn from((value, has_document): (database::models::ParentalConsent, bool)) -> Self {
+        Self {
+            id: Some(value.id),
+            player_id: value.player_id,
+            kind: value.kind.parse().expect("consent kind constrained by database"),
+            guardian_name: value.guardian_name,
+            note: value.note,
+            recorded_by_id: value.recorded_by_id,
+            valid_until: value.valid_until,
+            has_document,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParticipationBlock {
+    BirthDateMissing,
+    TooYoung(u32),
+    ParentalConsentMissing,
+    /// Suspended on the event day, until the given date or indefinitely.
+    Suspended(Option<NaiveDate>),
+}
+
+impl fmt::Display for ParticipationBlock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParticipationBlock::BirthDateMissing => {
+                write!(f, "Für dieses Spiel muss im Profil ein Geburtsdatum hinterlegt sein.")
+            }
+            ParticipationBlock::TooYoung(min_age) => {
+                write!(f, "Für dieses Spiel gilt ein Mindestalter von {} Jahren.", min_age)
+            }
+            ParticipationBlock::ParentalConsentMissing => write!(
+                f,
+                "Minderjährige benötigen eine am Spieltag gültige Einverständniserklärung der Eltern."
+            ),
+            ParticipationBlock::Suspended(Some(ends_on)) => {
+                write!(f, "Du bist bis zum {} für Spiele gesperrt.", ends_on.format("%d.%m.%Y"))
+            }
+            ParticipationBlock::Suspended(None) => write!(f, "Du bist bis auf Weiteres für Spiele gesperrt."),
+        }
+    }
+}
+
+/// Checks age limit and parental consent for the age the player has when the event starts.
+/// Without a birth date the player is treated as adult unless the event has an age limit.
+pub fn check_participation(
+    birth_date: Option<NaiveDate>,
+    event: &PlayEvent,
+    consents: &[ParentalConsent],
+) -> Result<(), ParticipationBlock> {
+    let event_date = event.starts_at.date_naive();
+    let min_age = event.min_age.and_then(|min_age| u32::try_from(min_age).ok());
+
+    let Some(birth_date) = birth_date else {
+        return match min_age {
+            Some(_) => Err(ParticipationBlock::BirthDateMissing),
+            None => Ok(()),
+        };
+    };
+
+    let age = age_on(birth_date, event_date);
+
+    if let Some(min_age) = min_age.filter(|min_age| age < *min_age) {
+        return Err(ParticipationBlock::TooYoung(min_age));
+    }
+
+    if age < ADULT_AGE && !consents.iter().any(|consent| consent.valid_on(event_date)) {
+        return Err(ParticipationBlock::ParentalConsentMissing);
+    }
+
+    Ok(())
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct PlayerConsents {
+    pub consents: Vec<ParentalConsent>,
+    pub minor: bool,
+    /// Only admins record signatures, upload documents and delete consents, so minors can't consent for themselves.
+    pub can_manage: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum IncidentCategory {
+    /// Not calling hits.
+    #[default]
+    Cheating,
+    Safety,
+    Misconduct,
+}
+
+impl IncidentCategory {
+    pub const ALL: [IncidentCategory; 3] = [
+        IncidentCategory::Cheating,
+        IncidentCategory::Safety,
+        IncidentCategory::Misconduct,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IncidentCategory::Cheating => "cheating",
+            IncidentCategory::Safety => "safety",
+            IncidentCategory::Misconduct => "misconduct",
+        }
+    }
+}
+
+impl FromStr for IncidentCategory {
+    type Err = UnknownVariant;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "cheating" => Ok(IncidentCategory::Cheating),
+            "safety" => Ok(IncidentCategory::Safety),
+            "misconduct" => Ok(IncidentCategory::Misconduct),
+            other => Err(UnknownVariant(other.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for IncidentCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = match self {
+            IncidentCategory::Cheating => "Treffer nicht angesagt",
+            IncidentCategory::Safety => "Sicherheitsverstoß",
+            IncidentCategory::Misconduct => "Fehlverhalten",
+        };
+
+        write!(f, "{}", text)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct IncidentReport {
+    pub id: Option<i64>,
+    pub player_id: i64,
+    pub play_event_id: Option<i64>,
+    pub play_event_name: Option<String>,
+    pub reported_by_id: Option<i64>,
+    pub reported_by_name: Option<String>,
+    pub category: IncidentCategory,
+    pub description: String,
+    pub reported_at: DateTime<Utc>,
+}
+
+impl PrimaryKey for IncidentReport {
+    fn key(&self) -> Option<i64> {
+        self.id
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct Suspension {
+    pub id: Option<i64>,
+    pub player_id: i64,
+    pub incident_report_id: Option<i64>,
+    pub issued_by_id: Option<i64>,
+    pub reason: String,
+    pub starts_on: NaiveDate,
+    /// Without an end the suspension lasts until it is lifted.
+    pub ends_on: Option<NaiveDate>,
+}
+
+impl Suspension {
+    pub fn active_on(&self, date: NaiveDate) -> bool {
+        self.starts_on <= date && self.ends_on.is_none_or(|ends_on| date <= ends_on)
+    }
+}
+
+impl PrimaryKey for Suspension {
+    fn key(&self) -> Option<i64> {
+        self.id
+    }
+}
+
+#[cfg(feature = "ssr")]
+impl From<database::models::Suspension> for Suspension {
+    fn from(value: database::models::Suspension) -> Self {
+        Self {
+            id: Some(value.id),
+            player_id: value.player_id,
+            incident_report_id: value.incident_report_id,
+            issued_by_id: value.issued_by_id,
+            reason: value.reason,
+            starts_on: value.starts_on,
+            ends_on: value.ends_on,
+        }
+    }
+}
+
+/// The suspension active on the date that lasts longest, overlapping suspensions do not add up.
+pub fn active_suspension(suspensions: &[Suspension], date: NaiveDate) -> Option<&Suspension> {
+    suspensions
+        .iter()
+        .filter(|suspension| suspension.active_on(date))
+        .max_by_key(|suspension| suspension.ends_on.unwrap_or(NaiveDate::MAX))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct DisciplinaryRecord {
+    pub incidents: Vec<IncidentReport>,
+    pub suspensions: Vec<Suspension>,
+    pub active_suspension: Option<Suspension>,
+    /// Events the player took part in, an incident may refer to one of them.
+    pub play_events: Vec<PlayEvent>,
+    /// Only admins suspend players and remove reports.
+    pub can_manage: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LegalDocumentKind {
+    #[default]
+    Waiver,
+    CodeOfConduct,
+}
+
+impl LegalDocumentKind {
+    pub const ALL: [LegalDocumentKind; 2] = [LegalDocumentKind::Waiver, LegalDocumentKind::CodeOfConduct];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LegalDocumentKind::Waiver => "waiver",
+            LegalDocumentKind::CodeOfConduct => "code_of_conduct",
+        }
+    }
+}
+
+impl FromStr for LegalDocumentKind {
+    type Err = UnknownVariant;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "waiver" => Ok(LegalDocumentKind::Waiver),
+            "code_of_conduct" => Ok(LegalDocumentKind::CodeOfConduct),
+            other => Err(UnknownVariant(other.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for LegalDocumentKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = match self {
+            LegalDocumentKind::Waiver => "Haftungsverzicht",
+            LegalDocumentKind::CodeOfConduct => "Vereinsregeln",
+        };
+
+        write!(f, "{}", text)
+    }
+}
+
+/// Only the published document with the highest version of each kind has to be accepted.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct LegalDocument {
+    pub id: Option<i64>,
+    pub kind: LegalDocumentKind,
+    pub version: i32,
+    pub title: String,
+    pub content: String,
+    pub author_id: Option<i64>,
+    pub published_at: Option<DateTime<Utc>>,
+}
+
+impl LegalDocument {
+    pub fn is_published(&self) -> bool {
+        self.published_at.is_some()
+    }
+}
+
+impl PrimaryKey for LegalDocument {
+    fn key(&self) -> Option<i64> {
+        self.id
+    }
+}
+
+#[cfg(feature = "ssr")]
+impl From<database::models::LegalDocument> for LegalDocument {
+    fn from(value: database::models::LegalDocument) -> Self {
+        Self {
+            id: Some(value.id),
+            kind: value.kind.parse().expect("legal document kind constrained by database"),
+            version: value.version,
+            title: value.title,
+            content: value.content,
+            author_id: value.author_id,
+            published_at: value.published_at,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct LegalAcceptance {
+    pub document_id: i64,
+    pub kind: LegalDocumentKind,
+    pub version: i32,
+    pub title: String,
+    pub accepted_at: DateTime<Utc>,
+    pub ip_address: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct CheckInList {
+    pub participants: Vec<Participant>,
+    /// Players who accepted the current version of every legal document.
+    pub accepted_player_ids: HashSet<i64>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum FeeKind {
+    #[default]
+    Membership,
+    Event,
+    Rental,
+}
+
+impl FeeKind {
+    pub const ALL: [FeeKind; 3] = [FeeKind::Membership, FeeKind::Event, FeeKind::Rental];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FeeKind::Membership => "membership",
+            FeeKind::Event => "event",
+            FeeKind::Rental => "rental",
+        }
+    }
+}
+
+impl FromStr for FeeKind {
+    type Err = UnknownVariant;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "membership" => Ok(FeeKind::Membership),
+            "event" => Ok(FeeKind::Event),
+            "rental" => Ok(FeeKind::Rental),
+            other => Err(UnknownVariant(other.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for FeeKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = match self {
+            FeeKind::Membership => "Mitgliedsbeitrag",
+            FeeKind::Event => "Eventgebühr",
+            FeeKind::Rental => "Leihgebühr",
+        };
+
+        write!(f, "{}", text)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PaymentMethod {
+    #[default]
+    Cash,
+    Transfer,
+}
+
+impl PaymentMethod {
+    pub const ALL: [PaymentMethod; 2] = [PaymentMethod::Cash, PaymentMethod::Transfer];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PaymentMethod::Cash => "cash",
+            PaymentMethod::Transfer => "transfer",
+        }
+    }
+}
+
+impl FromStr for PaymentMethod {
+    type Err = UnknownVariant;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "cash" => Ok(PaymentMethod::Cash),
+            "transfer" => Ok(PaymentMethod::Transfer),
+            other => Err(UnknownVariant(other.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for PaymentMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = match self {
+            PaymentMethod::Cash => "Bar",
+            PaymentMethod::Transfer => "Überweisung",
+        };
+
+        write!(f, "{}", text)
+    }
+}
+
+pub const CHARGE_REFERENCE_PREFIX: &str = "AS";
+
+/// All amounts are integer cents, so sums stay exact.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct Fee {
+    pub id: Option<i64>,
+    pub name: String,
+    pub kind: FeeKind,
+    pub amount_cents: i64,
+    pub play_event_id: Option<i64>,
+}
+
+impl PrimaryKey for Fee {
+    fn key(&self) -> Option<i64> {
+        self.id
+    }
+}
+
+#[cfg(feature = "ssr")]
+impl From<database::models::Fee> for Fee {
+    fn from(value: database::models::Fee) -> Self {
+        Self {
+            id: Some(value.id),
+            name: value.name,
+            kind: value.kind.parse().expect("fee kind constrained by database"),
+            amount_cents: value.amount_cents,
+            play_event_id: value.play_event_id,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct Charge {
+    pub id: Option<i64>,
+    pub player_id: i64,
+    pub fee_id: Option<i64>,
+    pub description: String,
+    pub amount_cents: i64,
+    pub created_by_id: Option<i64>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Charge {
+    /// Text players put into the transfer reference so the bank import finds the charge.
+    pub fn reference(&self) -> Option<String> {
+        self.id.map(|id| format!("{}-{}", CHARGE_REFERENCE_PREFIX, id))
+    }
+}
+
+impl PrimaryKey for Charge {
+    fn key(&self) -> Option<i64> {
+        self.id
+    }
+}
+
+#[cfg(feature = "ssr")]
+impl From<database::models::Charge> for Charge {
+    fn from(value: database::models::Charge) -> Self {
+        Self {
+            id: Some(value.id),
+            player_id: value.player_id,
+            fee_id: value.fee_id,
+            description: value.description,
+            amount_cents: value.amount_cents,
+            created_by_id: value.created_by_id,
+            created_at: value.created_at,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct Payment {
+    pub id: Option<i64>,
+    pub player_id: i64,
+    pub charge_id: Option<i64>,
+    pub amount_cents: i64,
+    pub method: PaymentMethod,
+    pub reference: String,
+    pub paid_on: NaiveDate,
+    pub recorded_by_id: Option<i64>,
+}
+
+impl PrimaryKey for Payment {
+    fn key(&self) -> Option<i64> {
+        self.id
+    }
+}
+
+#[cfg(feature = "ssr")]
+impl From<database::models::Payment> for Payment {
+    fn from(value: database::models::Payment) -> Self {
+        Self {
+            id: Some(value.id),
+            player_id: value.player_id,
+            charge_id: value.charge_id,
+            amount_cents: value.amount_cents,
+            method: value.method.parse().expect("payment method constrained by database"),
+            reference: value.reference,
+            paid_on: value.paid_on,
+            recorded_by_id: value.recorded_by_id,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct PlayerLedger {
+    pub charges: Vec<Charge>,
+    pub payments: Vec<Payment>,
+    pub editable: bool,
+}
+
+impl PlayerLedger {
+    pub fn balance(&self) -> PlayerBalance {
+        PlayerBalance {
+            charged_cents: self.charges.iter().map(|charge| charge.amount_cents).sum(),
+            paid_cents: self.payments.iter().map(|payment| payment.amount_cents).sum(),
+            ..Default::default()
+        }
+    }
+
+    /// Paid amount of every charge, from the payments booked against it.
+    pub fn paid_per_charge(&self) -> HashMap<i64, i64> {
+        let mut paid = HashMap::new();
+
+        for payment in &self.payments {
+            if let Some(charge_id) = payment.charge_id {
+                *paid.entry(charge_id).or_default() += payment.amount_cents;
+            }
+        }
+
+        paid
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct PlayerBalance {
+    pub player_id: i64,
+    pub tag_name: String,
+    pub charged_cents: i64,
+    pub paid_cents: i64,
+}
+
+impl PlayerBalance {
+    /// Positive when the player owes money, negative for credit.
+    pub fn outstanding_cents(&self) -> i64 {
+        self.charged_cents - self.paid_cents
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct TreasurerReport {
+    pub year: i32,
+    pub charged_by_kind: Vec<(Option<FeeKind>, i64)>,
+    pub paid_by_method: Vec<(PaymentMethod, i64)>,
+    /// Players with open amounts over all years.
+    pub debtors: Vec<PlayerBalance>,
+}
+
+impl TreasurerReport {
+    pub fn charged_cents(&self) -> i64 {
+        self.charged_by_kind.iter().map(|(_, cents)| cents).sum()
+    }
+
+    pub fn paid_cents(&self) -> i64 {
+        self.paid_by_method.iter().map(|(_, cents)| cents).sum()
+    }
+
+    pub fn outstanding_cents(&self) -> i64 {
+        self.debtors.iter().map(PlayerBalance::outstanding_cents).sum()
+    }
+}
+
+/// An incoming transfer from a bank statement. Open until it is booked as payment or ignored.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct BankTransaction {
+    pub id: Option<i64>,
+    /// Identifies the transfer across imports, so overlapping statements don't book twice.
+    pub import_key: String,
+    pub booked_on: NaiveDate,
+    pub amount_cents: i64,
+    pub counterparty: String,
+    pub reference: String,
+    pub payment_id: Option<i64>,
+    pub ignored: bool,
+}
+
+impl BankTransaction {
+    pub fn is_open(&self) -> bool {
+        self.payment_id.is_none() && !self.ignored
+    }
+}
+
+impl PrimaryKey for BankTransaction {
+    fn key(&self) -> Option<i64> {
+        self.id
+    }
+}
+
+#[cfg(feature = "ssr")]
+impl From<database::models::BankTransaction> for BankTransaction {
+    fn from(value: database::models::BankTransaction) -> Self {
+        Self {
+            id: Some(value.id),
+            import_key: value.import_key,
+            booked_on: value.booked_on,
+            amount_cents: value.amount_cents,
+            counterparty: value.counterparty,
+            reference: value.reference,
+            payment_id: value.payment_id,
+            ignored: value.ignored,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct OpenCharge {
+    pub charge: Charge,
+    pub tag_name: String,
+    pub outstanding_cents: i64,
+}
+
+/// A player a transfer can be assigned to, with the names used for matching.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct Payer {
+    pub player_id: i64,
+    pub tag_name: String,
+    pub real_name: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchReason {
+    Reference,
+    Name,
+    Amount,
+}
+
+impl fmt::Display for MatchReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = match self {
+            MatchReason::Reference => "Verwendungszweck",
+            MatchReason::Name => "Name",
+            MatchReason::Amount => "Betrag",
+        };
+
+        write!(f, "{}", text)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct MatchSuggestion {
+    pub player_id: i64,
+    pub tag_name: String,
+    pub charge_id: Option<i64>,
+    pub charge_description: Option<String>,
+    pub reason: MatchReason,
+}
+
+impl MatchSuggestion {
+    fn for_charge(open_charge: &OpenCharge, reason: MatchReason) -> Self {
+        Self {
+            player_id: open_charge.charge.player_id,
+            tag_name: open_charge.tag_name.clone(),
+            charge_id: open_charge.charge.id,
+            charge_description: Some(open_charge.charge.description.clone()),
+            reason,
+        }
+    }
+}
+
+/// Parses amounts like `12`, `12,5` or `12.50` into cents without going through floats.
+pub fn parse_cents(value: &str) -> Option<i64> {
+    let value = value.trim().trim_end_matches('€').trim().replace(',', ".");
+    let (euros, fraction) = value.split_once('.').unwrap_or((&value, ""));
+
+    if euros.is_empty() || fraction.len() > 2
+        || !euros.chars().all(|c| c.is_ascii_digit())
+        || !fraction.chars().all(|c| c.is_ascii_digit())
+    {
+        return None;
+    }
+
+    let cents = format!("{:0<2}", fraction).parse::<i64>().ok()?;
+
+    euros.parse::<i64>().ok()?.checked_mul(100)?.checked_add(cents)
+}
+
+/// Tag names shorter than this match too many unrelated words in references.
+const MIN_MATCHED_TAG_NAME_LENGTH: usize = 4;
+
+/// Suggests whom a transfer belongs to, trying the charge reference first, then
+/// the payer's name and finally a unique open amount.
+pub fn suggest_match(
+    transaction: &BankTransaction,
+    open_charges: &[OpenCharge],
+    payers: &[Payer],
+) -> Option<MatchSuggestion> {
+    let referenced_ids = charge_references(&transaction.reference);
+
+    if let Some(open_charge) = open_charges
+        .iter()
+        .find(|open_charge| open_charge.charge.id.is_some_and(|id| referenced_ids.contains(&id)))
+    {
+        return Some(MatchSuggestion::for_charge(open_charge, MatchReason::Reference));
+    }
+
+    let counterparty = normalize_name(&transaction.counterparty);
+    let reference = normalize_name(&transaction.reference);
+
+    let named_payers: Vec<&Payer> = payers
+        .iter()
+        .filter(|payer| {
+            let real_name_matches = payer.real_name
+                .as_deref()
+                .map(normalize_name)
+                .filter(|real_name| real_name.contains(' '))
+                .is_some_and(|real_name| contains_words(&counterparty, &real_name) || contains_words(&reference, &real_name));
+            let tag_name = normalize_name(&payer.tag_name);
+            let tag_name_matches = tag_name.len() >= MIN_MATCHED_TAG_NAME_LENGTH && contains_words(&reference, &tag_name);
+
+            real_name_matches || tag_name_matches
+        })
+        .collect();
+
+    if let [payer] = named_payers.as_slice() {
+        let player_charges: Vec<&OpenCharge> = open_charges
+            .iter()
+            .filter(|open_charge| open_charge.charge.player_id == payer.player_id)
+            .collect();
+
+        let open_charge = player_charges
+            .iter()
+            .find(|open_charge| open_charge.outstanding_cents == transaction.amount_cents)
+            .or_else(|| player_charges.iter().min_by_key(|open_charge| open_charge.charge.created_at));
+
+        return Some(match open_charge {
+            Some(open_charge) => MatchSuggestion::for_charge(open_charge, MatchReason::Name),
+            None => MatchSuggestion {
+                player_id: payer.player_id,
+                tag_name: payer.tag_name.clone(),
+                charge_id: None,
+                charge_description: None,
+                reason: MatchReason::Name,
+            },
+        });
+    }
+
+    let mut same_amount = open_charges
+        .iter()
+        .filter(|open_charge| open_charge.outstanding_cents == transaction.amount_cents);
+
+    match (same_amount.next(), same_amount.next()) {
+        (Some(open_charge), None) => Some(MatchSuggestion::for_charge(open_charge, MatchReason::Amount)),
+        _ => None,
+    }
+}
+
+/// Charge ids referenced as `AS-12` or `AS 12` anywhere in the text.
+fn charge_references(text: &str) -> Vec<i64> {
+    let text = text.to_uppercase();
+
+    text.match_indices(CHARGE_REFERENCE_PREFIX)
+        .filter(|(index, _)| !text[..*index].ends_with(|c: char| c.is_alphanumeric()))
+        .filter_map(|(index, prefix)| {
+            let rest = text[index + prefix.len()..].trim_start_matches(['-', ' ']);
+            let digits: String = rest.chars().take_while(char::is_ascii_digit).collect();
+
+            digits.parse().ok()
+        })
+        .collect()
+}
+
+/// Lowercase words separated by single spaces, so punctuation and line breaks don't matter.
+fn normalize_name(text: &str) -> String {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Whether every word occurs in the text, in any order, as in `Mustermann, Max`.
+fn contains_words(text: &str, words: &str) -> bool {
+    let text_words: HashSet<&str> = text.split(' ').collect();
+
+    words.split(' ').all(|word| text_words.contains(word))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct BankReconciliation {
+    pub transactions: Vec<(BankTransaction, Option<MatchSuggestion>)>,
+    pub open_charges: Vec<OpenCharge>,
+    pub payers: Vec<Payer>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RentalKind {
+    #[default]
+    Replica,
+    Mask,
+    Vest,
+}
+
+impl RentalKind {
+    pub const ALL: [RentalKind; 3] = [
+        RentalKind::Replica,
+        RentalKind::Mask,
+        RentalKind::Vest,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RentalKind::Replica => "replica",
+            RentalKind::Mask => "mask",
+            RentalKind::Vest => "vest",
+        }
+    }
+}
+
+impl FromStr for RentalKind {
+    type Err = UnknownVariant;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "replica" => Ok(RentalKind::Replica),
+            "mask" => Ok(RentalKind::Mask),
+            "vest" => Ok(RentalKind::Vest),
+            other => Err(UnknownVariant(other.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for RentalKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = match self {
+            RentalKind::Replica => "Replika",
+            RentalKind::Mask => "Maske",
+            RentalKind::Vest => "Weste",
+        };
+
+        write!(f, "{}", text)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ItemCondition {
+    #[default]
+    Good,
+    Worn,
+    Damaged,
+}
+
+impl ItemCondition {
+    pub const ALL: [ItemCondition; 3] = [
+        ItemCondition::Good,
+        ItemCondition::Worn,
+        ItemCondition::Damaged,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ItemCondition::Good => "good",
+            ItemCondition::Worn => "worn",
+            ItemCondition::Damaged => "damaged",
+        }
+    }
+}
+
+impl FromStr for ItemCondition {
+    type Err = UnknownVariant;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "good" => Ok(ItemCondition::Good),
+            "worn" => Ok(ItemCondition::Worn),
+            "damaged" => Ok(ItemCondition::Damaged),
+            other => Err(UnknownVariant(other.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for ItemCondition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = match self {
+            ItemCondition::Good => "Gut",
+            ItemCondition::Worn => "Gebraucht",
+            ItemCondition::Damaged => "Beschädigt",
+        };
+
+        write!(f, "{}", text)
+    }
+}
+
+/// Set by the equipment manager. Whether an item is issued follows from its loans.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RentalItemStatus {
+    #[default]
+    Available,
+    Maintenance,
+    Retired,
+}
+
+impl RentalItemStatus {
+    pub const ALL: [RentalItemStatus; 3] = [
+        RentalItemStatus::Available,
+        RentalItemStatus::Maintenance,
+        RentalItemStatus::Retired,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RentalItemStatus::Available => "available",
+            RentalItemStatus::Maintenance => "maintenance",
+            RentalItemStatus::Retired => "retired",
+        }
+    }
+}
+
+impl FromStr for RentalItemStatus {
+    type Err = UnknownVariant;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "available" => Ok(RentalItemStatus::Available),
+            "maintenance" => Ok(RentalItemStatus::Maintenance),
+            "retired" => Ok(RentalItemStatus::Retired),
+            other => Err(UnknownVariant(other.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for RentalItemStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = match self {
+            RentalItemStatus::Available => "Verfügbar",
+            RentalItemStatus::Maintenance => "In Wartung",
+            RentalItemStatus::Retired => "Ausgemustert",
+        };
+
+        write!(f, "{}", text)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct RentalItem {
+    pub id: Option<i64>,
+    pub kind: RentalKind,
+    pub name: String,
+    pub serial_number: String,
+    pub condition: ItemCondition,
+    pub status: RentalItemStatus,
+}
+
+impl RentalItem {
+    /// Damaged items stay in the inventory but aren't handed out until repaired.
+    pub fn is_rentable(&self) -> bool {
+        self.status == RentalItemStatus::Available && self.condition != ItemCondition::Damaged
+    }
+}
+
+impl PrimaryKey for RentalItem {
+    fn key(&self) -> Option<i64> {
+        self.id
+    }
+}
+
+#[cfg(feature = "ssr")]
+impl From<database::models::RentalItem> for RentalItem {
+    fn from(value: database::models::RentalItem) -> Self {
+        Self {
+            id: Some(value.id),
+            kind: value.kind.parse().expect("rental kind constrained by database"),
+            name: value.name,
+            serial_number: value.serial_number,
+            condition: value.condition.parse().expect("item condition constrained by database"),
+            status: value.status.parse().expect("rental item status constrained by database"),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoanState {
+    Reserved,
+    Issued,
+    Returned,
+}
+
+impl fmt::Display for LoanState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = match self {
+            LoanState::Reserved => "Reserviert",
+            LoanState::Issued => "Ausgegeben",
+            LoanState::Returned => "Zurückgegeben",
+        };
+
+        write!(f, "{}", text)
+    }
+}
+
+/// A loan with the item, player and event it belongs to.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct RentalLoan {
+    pub id: i64,
+    pub item: RentalItem,
+    pub player_id: i64,
+    pub tag_name: String,
+    pub play_event_id: i64,
+    pub play_event_name: String,
+    pub play_event_ends_at: DateTime<Utc>,
+    pub checked_out_at: Option<DateTime<Utc>>,
+    pub returned_at: Option<DateTime<Utc>>,
+    pub damage_note: String,
+}
+
+impl RentalLoan {
+    pub fn state(&self) -> LoanState {
+        match (self.checked_out_at, self.returned_at) {
+            (_, Some(_)) => LoanState::Returned,
+            (Some(_), None) => LoanState::Issued,
+            (None, None) => LoanState::Reserved,
+        }
+    }
+
+    /// Issued items are due back when the event ends.
+    pub fn is_overdue(&self, now: DateTime<Utc>) -> bool {
+        self.state() == LoanState::Issued && self.play_event_ends_at < now
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct EventRentals {
+    pub reservations: Vec<RentalLoan>,
+    /// Number of items per kind that can still be reserved for the event.
+    pub available: Vec<(RentalKind, usize)>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct RentalOverview {
+    pub loans: Vec<RentalLoan>,
+    /// Rentable items that aren't out right now.
+    pub issuable_items: Vec<RentalItem>,
+    pub players: Vec<(i64, String)>,
+    pub play_events: Vec<(i64, String)>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct RentalItemDetails {
+    pub item: RentalItem,
+    pub loans: Vec<RentalLoan>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct GameMode {
+    pub id: Option<i64>,
+    pub name: String,
+    pub description: String,
+    /// Objectives every round of this mode starts with, one per line.
+    pub objectives: String,
+}
+
+impl GameMode {
+    pub fn objective_names(&self) -> Vec<String> {
+        self.objectives
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(str::to_string)
+            .collect()
+    }
+}
+
+impl PrimaryKey for GameMode {
+    fn key(&self) -> Option<i64> {
+        self.id
+    }
+}
+
+#[cfg(feature = "ssr")]
+impl From<database::models::GameMode> for GameMode {
+    fn from(value: database::models::GameMode) -> Self {
+        Self {
+            id: Some(value.id),
+            name: value.name,
+            description: value.description,
+            objectives: value.objectives,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct FactionScore {
+    pub faction_id: i64,
+    pub points: i32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct RoundObjective {
+    pub id: i64,
+    pub name: String,
+    /// The faction that took the objective.
+    pub faction_id: Option<i64>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RoundOutcome {
+    Open,
+    Draw,
+    Won(i64),
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct GameRound {
+    pub id: i64,
+    pub play_event_id: i64,
+    pub number: i32,
+    pub name: String,
+    pub notes: String,
+    pub game_mode_name: Option<String>,
+    pub scores: Vec<FactionScore>,
+    pub objectives: Vec<RoundObjective>,
+}
+
+impl GameRound {
+    pub fn points(&self, faction_id: i64) -> Option<i32> {
+        self.scores
+            .iter()
+            .find(|score| score.faction_id == faction_id)
+            .map(|score| score.points)
+    }
+
+    /// Rounds without scores are still open, the faction with the most points wins and a shared top score is a draw.
+    pub fn outcome(&self) -> RoundOutcome {
+        let Some(best) = self.scores.iter().map(|score| score.points).max() else {
+            return RoundOutcome::Open;
+        };
+
+        let mut leaders = self.scores.iter().filter(|score| score.points == best);
+
+        match (leaders.next(), leaders.next()) {
+            (Some(leader), None) => RoundOutcome::Won(leader.faction_id),
+            _ => RoundOutcome::Draw,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WinLoss {
+    pub wins: usize,
+    pub draws: usize,
+    pub losses: usize,
+}
+
+impl WinLoss {
+    pub fn rounds(&self) -> usize {
+        self.wins + self.draws + self.losses
+    }
+
+    /// Share of won rounds in percent, none before the first round.
+    pub fn win_rate(&self) -> Option<u32> {
+        (self.rounds() > 0).then(|| (self.wins * 100 / self.rounds()) as u32)
+    }
+
+    fn record(&mut self, outcome: RoundOutcome, faction_id: i64) {
+        match outcome {
+            RoundOutcome::Open => (),
+            RoundOutcome::Draw => self.draws += 1,
+            RoundOutcome::Won(winner) if winner == faction_id => self.wins += 1,
+            RoundOutcome::Won(_) => self.losses += 1,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct FactionStanding {
+    pub faction: Faction,
+    pub record: WinLoss,
+    pub points: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct EventResults {
+    pub standings: Vec<FactionStanding>,
+    pub rounds: Vec<GameRound>,
+    pub game_modes: Vec<GameMode>,
+    pub editable: bool,
+}
+
+impl EventResults {
+    /// Standings are ordered by won rounds, then by total points.
+    pub fn new(factions: Vec<Faction>, rounds: Vec<GameRound>, game_modes: Vec<GameMode>, editable: bool) -> Self {
+        let mut standings: Vec<FactionStanding> = factions
+            .into_iter()
+            .filter_map(|faction| {
+                let faction_id = faction.id?;
+                let mut record = WinLoss::default();
+
+                for round in &rounds {
+                    record.record(round.outcome(), faction_id);
+                }
+
+                Some(FactionStanding {
+                    faction,
+                    record,
+                    points: rounds.iter().flat_map(|round| round.points(faction_id)).map(i64::from).sum(),
+                })
+            })
+            .collect();
+
+        standings.sort_by(|a, b| b.record.wins.cmp(&a.record.wins).then(b.points.cmp(&a.points)));
+
+        Self { standings, rounds, game_modes, editable }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Scoreboard {
+    pub play_event_id: i64,
+    pub play_event_name: String,
+    pub standings: Vec<FactionStanding>,
+    /// The latest round, which is the one being played.
+    pub current_round: Option<GameRound>,
+    pub rounds_played: usize,
+}
+
+impl Scoreboard {
+    pub fn new(play_event: &PlayEvent, factions: Vec<Faction>, rounds: Vec<GameRound>) -> Self {
+        let current_round = rounds.last().cloned();
+        let rounds_played = rounds.len();
+
+        Self {
+            play_event_id: play_event.id.unwrap_or_default(),
+            play_event_name: play_event.name.clone(),
+            standings: EventResults::new(factions, rounds, Vec::new(), false).standings,
+            current_round,
+            rounds_played,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SeasonRecord {
+    pub id: i64,
+    pub name: String,
+    pub record: WinLoss,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct SeasonStandings {
+    pub season: i32,
+    /// Years with at least one round, newest first.
+    pub seasons: Vec<i32>,
+    pub teams: Vec<SeasonRecord>,
+    pub platoons: Vec<SeasonRecord>,
+}
+
+/// Win/loss records of player groups (teams or platoons) over the rounds. A group plays for the faction
+/// most of its members were assigned to at the event, groups split evenly between factions aren't counted.
+/// `group_of` gives the group a player belonged to at an event, from the event and player id.
+pub fn season_records<F>(
+    rounds: &[GameRound],
+    assignments: &[(i64, i64, i64)],
+    group_of: F,
+) -> HashMap<i64, WinLoss>
+where
+    F: Fn(i64, i64) -> Option<i64>,
+{
+    let mut members: HashMap<(i64, i64), HashMap<i64, usize>> = HashMap::new();
+
+    for (play_event_id, player_id, faction_id) in assignments {
+        if let Some(group_id) = group_of(*play_event_id, *player_id) {
+            *members
+                .entry((*play_event_id, group_id))
+                .or_default()
+                .entry(*faction_id)
+                .or_default() += 1;
+        }
+    }
+
+    let group_factions: Vec<(i64, i64, i64)> = members
+        .into_iter()
+        .filter_map(|((play_event_id, group_id), counts)| {
+            let most = counts.values().copied().max()?;
+            let mut leading = counts.into_iter().filter(|(_, count)| *count == most);
+
+            match (leading.next(), leading.next()) {
+                (Some((faction_id, _)), None) => Some((play_event_id, group_id, faction_id)),
+                _ => None,
+            }
+        })
+        .collect();
+
+    let mut records: HashMap<i64, WinLoss> = HashMap::new();
+
+    for round in rounds {
+        let outcome = round.outcome();
+
+        for (play_event_id, group_id, faction_id) in &group_factions {
+            if *play_event_id == round.play_event_id && outcome != RoundOutcome::Open {
+                records.entry(*group_id).or_default().record(outcome, *faction_id);
+            }
+        }
+    }
+
+    records
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BookingStatus {
+    Requested,
+    Confirmed,
+    Declined,
+    Cancelled,
+}
+
+impl BookingStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BookingStatus::Requested => "requested",
+            BookingStatus::Confirmed => "confirmed",
+            BookingStatus::Declined => "declined",
+            BookingStatus::Cancelled => "cancelled",
+        }
+    }
+
+    /// Requested and confirmed bookings hold on to a seat.
+    pub fn is_active(&self) -> bool {
+        matches!(self, BookingStatus::Requested | BookingStatus::Confirmed)
+    }
+}
+
+impl FromStr for BookingStatus {
+    type Err = UnknownVariant;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "requested" => Ok(BookingStatus::Requested),
+            "confirmed" => Ok(BookingStatus::Confirmed),
+            "declined" => Ok(BookingStatus::Declined),
+            "cancelled" => Ok(BookingStatus::Cancelled),
+            other => Err(UnknownVariant(other.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for BookingStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = match self {
+            BookingStatus::Requested => "Angefragt",
+            BookingStatus::Confirmed => "Bestätigt",
+            BookingStatus::Declined => "Abgelehnt",
+            BookingStatus::Cancelled => "Abgesagt",
+        };
+
+        write!(f, "{}", text)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct RideBooking {
+    pub id: i64,
+    pub ride_offer_id: i64,
+    pub passenger_id: i64,
+    pub passenger_name: String,
+    pub status: BookingStatus,
+    /// Reset whenever the passenger books or releases the seat, until the driver has seen it.
+    pub driver_seen: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct RideOffer {
+    pub id: Option<i64>,
+    pub play_event_id: i64,
+    pub driver_id: i64,
+    pub driver_name: String,
+    pub area: String,
+    pub start_location: String,
+    pub seats: i32,
+    pub departs_at: DateTime<Utc>,
+    pub notes: String,
+    pub bookings: Vec<RideBooking>,
+}
+
+impl RideOffer {
+    pub fn confirmed_seats(&self) -> i32 {
+        self.bookings
+            .iter()
+            .filter(|booking| booking.status == BookingStatus::Confirmed)
+            .count() as i32
+    }
+
+    pub fn free_seats(&self) -> i32 {
+        (self.seats - self.confirmed_seats()).max(0)
+    }
+
+    pub fn booking_of(&self, player_id: i64) -> Option<&RideBooking> {
+        self.bookings
+            .iter()
+            .find(|booking| booking.passenger_id == player_id && booking.status.is_active())
+    }
+
+    /// Bookings taken or released since the driver last looked.
+    pub fn unseen_changes(&self) -> Vec<&RideBooking> {
+        self.bookings.iter().filter(|booking| !booking.driver_seen).collect()
+    }
+}
+
+impl PrimaryKey for RideOffer {
+    fn key(&self) -> Option<i64> {
+        self.id
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct RideRequest {
+    pub id: Option<i64>,
+    pub play_event_id: i64,
+    pub passenger_id: i64,
+    pub passenger_name: String,
+    pub area: String,
+    pub notes: String,
+}
+
+impl PrimaryKey for RideRequest {
+    fn key(&self) -> Option<i64> {
+        self.id
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct CarpoolArea {
+    pub name: String,
+    pub offers: Vec<RideOffer>,
+    pub requests: Vec<RideRequest>,
+}
+
+/// What else changed when a player withdrew from an event.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Withdrawal {
+    /// Waitlisted players who moved up in their place.
+    pub promoted_ids: Vec<i64>,
+    /// Passengers whose seat in the player's car was cancelled.
+    pub stranded_passenger_ids: Vec<i64>,
+}
+
+/// Offers and requests of an event grouped by area, seen by one player.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Carpool {
+    pub areas: Vec<CarpoolArea>,
+    pub own_offer: Option<RideOffer>,
+    pub own_request: Option<RideRequest>,
+    /// The offer the player has an active booking on, with that booking.
+    pub own_ride: Option<(RideOffer, RideBooking)>,
+    pub participant: bool,
+}
+
+impl Carpool {
+    /// Areas are matched ignoring case and surrounding whitespace, the first spelling names the area.
+    pub fn new(offers: Vec<RideOffer>, requests: Vec<RideRequest>, player_id: i64, participant: bool) -> Self {
+        let own_offer = offers.iter().find(|offer| offer.driver_id == player_id).cloned();
+        let own_ride = offers
+            .iter()
+            .find_map(|offer| offer.booking_of(player_id).map(|booking| (offer.clone(), booking.clone())));
+        let own_request = requests.iter().find(|request| request.passenger_id == player_id).cloned();
+
+        let mut areas: BTreeMap<String, CarpoolArea> = BTreeMap::new();
+
+        for offer in offers {
+            areas
+                .entry(offer.area.trim().to_lowercase())
+                .or_insert_with(|| CarpoolArea { name: offer.area.trim().to_string(), offers: Vec::new(), requests: Vec::new() })
+                .offers
+                .push(offer);
+        }
+
+        for request in requests {
+            areas
+                .entry(request.area.trim().to_lowercase())
+                .or_insert_with(|| CarpoolArea { name: request.area.trim().to_string(), offers: Vec::new(), requests: Vec::new() })
+                .requests
+                .push(request);
+        }
+
+        Self {
+            areas: areas.into_values().collect(),
+            own_offer,
+            own_request,
+            own_ride,
+            participant,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum NotificationKind {
+    /// Changes to or cancellation of an event the player takes part in.
+    #[default]
+    Event,
+    Announcement,
+    TeamJoinRequest,
+    WaitlistPromotion,
+    Carpool,
+}
+
+impl NotificationKind {
+    pub const ALL: [NotificationKind; 5] = [
+        NotificationKind::Event,
+        NotificationKind::Announcement,
+        NotificationKind::TeamJoinRequest,
+        NotificationKind::WaitlistPromotion,
+        NotificationKind::Carpool,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NotificationKind::Event => "event",
+            NotificationKind::Announcement => "announcement",
+            NotificationKind::TeamJoinRequest => "team_join_request",
+            NotificationKind::WaitlistPromotion => "waitlist_promotion",
+            NotificationKind::Carpool => "carpool",
+        }
+    }
+}
+
+impl FromStr for NotificationKind {
+    type Err = UnknownVariant;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "event" => Ok(NotificationKind::Event),
+            "announcement" => Ok(NotificationKind::Announcement),
+            "team_join_request" => Ok(NotificationKind::TeamJoinRequest),
+            "waitlist_promotion" => Ok(NotificationKind::WaitlistPromotion),
+            "carpool" => Ok(NotificationKind::Carpool),
+            other => Err(UnknownVariant(other.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for NotificationKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = match self {
+            NotificationKind::Event => "Events",
+            NotificationKind::Announcement => "Ankündigungen",
+            NotificationKind::TeamJoinRequest => "Beitrittsanfragen",
+            NotificationKind::WaitlistPromotion => "Nachrücken von der Warteliste",
+            NotificationKind::Carpool => "Fahrgemeinschaften",
+        };
+
+        write!(f, "{}", text)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeliveryChannel {
+    Email,
+    Webhook,
+}
+
+impl DeliveryChannel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DeliveryChannel::Email => "email",
+            DeliveryChannel::Webhook => "webhook",
+        }
+    }
+}
+
+impl FromStr for DeliveryChannel {
+    type Err = UnknownVariant;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "email" => Ok(DeliveryChannel::Email),
+            "webhook" => Ok(DeliveryChannel::Webhook),
+            other => Err(UnknownVariant(other.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for DeliveryChannel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = match self {
+            DeliveryChannel::Email => "E-Mail",
+            DeliveryChannel::Webhook => "Webhook",
+        };
+
+        write!(f, "{}", text)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeliveryStatus {
+    Pending,
+    Sent,
+    /// Given up after the last retry.
+    Failed,
+}
+
+impl DeliveryStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DeliveryStatus::Pending => "pending",
+            DeliveryStatus::Sent => "sent",
+            DeliveryStatus::Failed => "failed",
+        }
+    }
+}
+
+impl FromStr for DeliveryStatus {
+    type Err = UnknownVariant;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pending" => Ok(DeliveryStatus::Pending),
+            "sent" => Ok(DeliveryStatus::Sent),
+            "failed" => Ok(DeliveryStatus::Failed),
+            other => Err(UnknownVariant(other.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for DeliveryStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = match self {
+            DeliveryStatus::Pending => "Ausstehend",
+            DeliveryStatus::Sent => "Zugestellt",
+            DeliveryStatus::Failed => "Fehlgeschlagen",
+        };
+
+        write!(f, "{}", text)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Notification {
+    pub id: Option<i64>,
+    pub kind: NotificationKind,
+    pub title: String,
+    pub body: String,
+    /// Path inside the app, empty if there is nothing to open.
+    pub link: String,
+    pub created_at: DateTime<Utc>,
+    pub read_at: Option<DateTime<Utc>>,
+}
+
+impl Notification {
+    pub fn new(kind: NotificationKind, title: impl Into<String>, body: impl Into<String>, link: impl Into<String>) -> Self {
+        Self {
+            id: None,
+            kind,
+            title: title.into(),
+            body: body.into(),
+            link: link.into(),
+            created_at: Utc::now(),
+            read_at: None,
+        }
+    }
+
+    pub fn is_unread(&self) -> bool {
+        self.read_at.is_none()
+    }
+}
+
+impl PrimaryKey for Notification {
+    fn key(&self) -> Option<i64> {
+        self.id
+    }
+}
+
+#[cfg(feature = "ssr")]
+impl From<database::models::Notification> for Notification {
+    fn from(value: database::models::Notification) -> Self {
+        Self {
+            id: Some(value.id),
+            kind: value.kind.parse().expect("notification kind constrained by database"),
+            title: value.title,
+            body: value.body,
+            link: value.link,
+            created_at: value.created_at,
+            read_at: value.read_at,
+        }
+    }
+}
+
+/// The inbox is always on, these choose the additional channels.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NotificationPreference {
+    pub kind: NotificationKind,
+    pub email: bool,
+    pub webhook: bool,
+}
+
+impl NotificationPreference {
+    pub fn default_for(kind: NotificationKind) -> Self {
+        Self {
+            kind,
+            email: true,
+            webhook: false,
+        }
+    }
+
+    pub fn wants(&self, channel: DeliveryChannel) -> bool {
+        match channel {
+            DeliveryChannel::Email => self.email,
+            DeliveryChannel::Webhook => self.webhook,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct NotificationSettings {
+    pub webhook_url: String,
+    /// One preference per kind, in the order of `NotificationKind::ALL`.
+    pub preferences: Vec<NotificationPreference>,
+}
+
+impl NotificationSettings {
+    pub fn preference(&self, kind: NotificationKind) -> NotificationPreference {
+        self.preferences
+            .iter()
+            .find(|preference| preference.kind == kind)
+            .copied()
+            .unwrap_or(NotificationPreference::default_for(kind))
+    }
+}
+
+/// A queued delivery together with everything needed to send it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingDelivery {
+    pub id: i64,
+    pub channel: DeliveryChannel,
+    pub attempts: i32,
+    pub recipient_email: String,
+    pub webhook_url: String,
+    pub notification: Notification,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WebhookEventType {
+    EventPublished,
+    /// Registration became possible, when an event is created or after its deadline was moved.
+    RegistrationOpened,
+    EventCancelled,
+    /// Only announcements addressed to everyone are posted.
+    AnnouncementPublished,
+    /// Sent by the test button, endpoints can't subscribe to it.
+    Ping,
+}
+
+impl WebhookEventType {
+    pub const SUBSCRIBABLE: [WebhookEventType; 4] = [
+        WebhookEventType::EventPublished,
+        WebhookEventType::RegistrationOpened,
+        WebhookEventType::EventCancelled,
+        WebhookEventType::AnnouncementPublished,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WebhookEventType::EventPublished => "event.published",
+            WebhookEventType::RegistrationOpened => "event.registration_opened",
+            WebhookEventType::EventCancelled => "event.cancelled",
+            WebhookEventType::AnnouncementPublished => "announcement.published",
+            WebhookEventType::Ping => "ping",
+        }
+    }
+}
+
+impl FromStr for WebhookEventType {
+    type Err = UnknownVariant;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "event.published" => Ok(WebhookEventType::EventPublished),
+            "event.registration_opened" => Ok(WebhookEventType::RegistrationOpened),
+            "event.cancelled" => Ok(WebhookEventType::EventCancelled),
+            "announcement.published" => Ok(WebhookEventType::AnnouncementPublished),
+            "ping" => Ok(WebhookEventType::Ping),
+            other => Err(UnknownVariant(other.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for WebhookEventType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = match self {
+            WebhookEventType::EventPublished => "Event veröffentlicht",
+            WebhookEventType::RegistrationOpened => "Anmeldung geöffnet",
+            WebhookEventType::EventCancelled => "Event abgesagt",
+            WebhookEventType::AnnouncementPublished => "Ankündigung veröffentlicht",
+            WebhookEventType::Ping => "Test",
+        };
+
+        write!(f, "{}", text)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct WebhookEndpoint {
+    pub id: Option<i64>,
+    pub name: String,
+    pub url: String,
+    /// Key for the HMAC signature, generated when the endpoint is created.
+    pub secret: String,
+    pub active: bool,
+    pub event_types: Vec<WebhookEventType>,
+}
+
+impl WebhookEndpoint {
+    pub fn subscribes_to(&self, event_type: WebhookEventType) -> bool {
+        self.event_types.contains(&event_type)
+    }
+}
+
+impl PrimaryKey for WebhookEndpoint {
+    fn key(&self) -> Option<i64> {
+        self.id
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct WebhookDelivery {
+    pub id: i64,
+    pub endpoint_id: i64,
+    pub event_type: WebhookEventType,
+    /// The JSON body exactly as it is signed and sent.
+    pub payload: String,
+    pub status: DeliveryStatus,
+    pub attempts: i32,
+    pub next_attempt_at: DateTime<Utc>,
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub sent_at: Option<DateTime<Utc>>,
+}
+
+#[cfg(feature = "ssr")]
+impl From<database::models::WebhookDelivery> for WebhookDelivery {
+    fn from(value: database::models::WebhookDelivery) -> Self {
+        Self {
+            id: value.id,
+            endpoint_id: value.endpoint_id,
+            event_type: value.event_type.parse().expect("webhook event type written by the app"),
+            payload: value.payload,
+            status: value.status.parse().expect("delivery status constrained by database"),
+            attempts: value.attempts,
+            next_attempt_at: value.next_attempt_at,
+            last_error: value.last_error,
+            created_at: value.created_at,
+            sent_at: value.sent_at,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct WebhookEndpointDetails {
+    pub endpoint: WebhookEndpoint,
+    pub deliveries: Vec<WebhookDelivery>,
+}
+
+/// Token for the REST API. Only its hash is stored, the token itself is shown once on creation.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ApiToken {
+    pub id: i64,
+    pub name: String,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[cfg(feature = "ssr")]
+impl From<database::models::ApiToken> for ApiToken {
+    fn from(value: database::models::ApiToken) -> Self {
+        Self {
+            id: value.id,
+            name: value.name,
+            last_used_at: value.last_used_at,
+            created_at: value.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownVariant(pub String);
+
+pub trait PrimaryKey {
+    fn key(&self) -> Option<i64>;
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn at(day: u32, hour: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 5, day, hour, 0, 0).unwrap()
+    }
+
+    fn record(player_id: i64, play_event_id: i64, day: u32) -> AttendanceRecord {
+        AttendanceRecord {
+            player_id,
+            play_event_id,
+            starts_at: at(day, 10),
+            ends_at: at(day, 18),
+            registration_deadline: None,
+            status: RegistrationStatus::Registered,
+            withdrawn_at: None,
+            checked_in_at: None,
+        }
+    }
+
+    fn attended(record: AttendanceRecord) -> AttendanceRecord {
+        AttendanceRecord { checked_in_at: Some(record.starts_at), ..record }
+    }
+
+    fn withdrawn(record: AttendanceRecord, withdrawn_at: DateTime<Utc>) -> AttendanceRecord {
+        AttendanceRecord { status: RegistrationStatus::Withdrawn, withdrawn_at: Some(withdrawn_at), ..record }
+    }
+
+    fn player(id: i64, team_id: Option<i64>) -> Player {
+        Player {
+            id: Some(id),
+            tag_name: format!("Spieler{}", id),
+            team_id,
+            ..Default::default()
+        }
+    }
+
+    fn team(id: i64) -> Team {
+        Team {
+            id: Some(id),
+            name: format!("Team {}", id),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn attendance_leaves_out_timely_withdrawals_and_the_waitlist() {
+        let records = [
+            attended(record(1, 1, 1)),
+            withdrawn(record(1, 2, 8), at(3, 12)),
+            withdrawn(record(1, 3, 15), at(15, 8)),
+            record(1, 4, 22),
+            AttendanceRecord { status: RegistrationStatus::Waitlisted, ..record(1, 5, 23) },
+            attended(record(1, 6, 30)),
+        ];
+
+        let stats = AttendanceStats::from_records(&records, at(29, 0));
+
+        assert_eq!(stats, AttendanceStats {
+            registrations: 3,
+            attended: 1,
+            no_shows: 1,
+            late_withdrawals: 1,
+            current_streak: 0,
+            longest_streak: 1,
+        });
+        assert_eq!(stats.reliability(), Some(33));
+    }
+
+    #[test]
+    fn late_withdrawal_uses_the_registration_deadline() {
+        let with_deadline = AttendanceRecord { registration_deadline: Some(at(5, 0)), ..record(1, 1, 10) };
+
+        assert!(withdrawn(with_deadline.clone(), at(6, 0)).late_withdrawal());
+        assert!(!withdrawn(with_deadline, at(4, 23)).late_withdrawal());
+
+        // without a deadline withdrawals in the last 48 hours are late
+        assert!(!withdrawn(record(1, 1, 10), at(8, 10)).late_withdrawal());
+        assert!(withdrawn(record(1, 1, 10), at(8, 11)).late_withdrawal());
+    }
+
+    #[test]
+    fn team_attendance_follows_the_membership_at_each_event() {
+        // player 1 moved from team 1 to team 2 between the events, player 2 stayed in team 1
+        let records = [
+            attended(record(1, 1, 1)),
+            attended(record(1, 2, 8)),
+            attended(record(2, 1, 1)),
+            record(2, 2, 8),
+        ];
+        let team_of = |play_event_id: i64, player_id: i64| match (play_event_id, player_id) {
+            (1, _) | (_, 2) => Some(1),
+            _ => Some(2),
+        };
+
+        let report = AttendanceReport::new(
+            vec![player(1, Some(2)), player(2, Some(1)), player(3, None)],
+            vec![team(1), team(2)],
+            &records,
+            team_of,
+            at(20, 0),
+        );
+
+        let first = &report.teams[0];
+        assert_eq!(first.members.iter().map(|member| member.player_id).collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!((first.stats.registrations, first.stats.attended, first.stats.no_shows), (3, 2, 1));
+
+        let second = &report.teams[1];
+        assert_eq!(second.members.iter().map(|member| member.player_id).collect::<Vec<_>>(), vec![1]);
+        assert_eq!((second.stats.registrations, second.stats.attended), (1, 1));
+
+        assert_eq!(report.players[0].stats.registrations, 2);
+        assert_eq!(report.players[2].stats, AttendanceStats::default());
+    }
+
+    fn participant(player_id: i64, team_id: Option<i64>, platoon_id: Option<i64>) -> Participant {
+        Participant {
+            player_id,
+            tag_name: format!("Spieler{}", player_id),
+            team_id,
+            team_name: None,
+            platoon_id,
+            platoon_name: None,
+            status: RegistrationStatus::Registered,
+            registered_at: at(1, 10),
+            checked_in_at: None,
+        }
+    }
+
+    /// Players of each faction, in faction order.
+    fn faction_members(assignments: &[(i64, i64)], faction_ids: &[i64]) -> Vec<Vec<i64>> {
+        faction_ids
+            .iter()
+            .map(|faction_id| {
+                let mut player_ids: Vec<i64> = assignments
+                    .iter()
+                    .filter(|(_, assigned_faction_id)| assigned_faction_id == faction_id)
+                    .map(|(player_id, _)| *player_id)
+                    .collect();
+                player_ids.sort_unstable();
+                player_ids
+            })
+            .collect()
+    }
+
+    #[test]
+    fn balancing_keeps_platoons_together_when_they_are_even() {
+        // platoon 1 with teams 1 and 2, platoon 2 with team 3
+        let participants: Vec<Participant> = (1..=3)
+            .map(|player_id| participant(player_id, Some(1), Some(1)))
+            .chain((4..=6).map(|player_id| participant(player_id, Some(2), Some(1))))
+            .chain((7..=12).map(|player_id| participant(player_id, Some(3), Some(2))))
+            .collect();
+
+        let assignments = balance_factions(&participants, &[10, 20]);
+
+        assert_eq!(faction_members(&assignments, &[10, 20]), vec![
+            vec![1, 2, 3, 4, 5, 6],
+            vec![7, 8, 9, 10, 11, 12],
+        ]);
+    }
+
+    #[test]
+    fn balancing_splits_uneven_platoons_into_teams() {
+        let participants: Vec<Participant> = (1..=4)
+            .map(|player_id| participant(player_id, Some(1), Some(1)))
+            .chain((5..=8).map(|player_id| participant(player_id, Some(2), Some(1))))
+            .chain((9..=10).map(|player_id| participant(player_id, Some(3), Some(2))))
+            .collect();
+
+        let assignments = balance_factions(&participants, &[10, 20]);
+
+        assert_eq!(faction_members(&assignments, &[10, 20]), vec![
+            vec![1, 2, 3, 4, 9, 10],
+            vec![5, 6, 7, 8],
+        ]);
+    }
+
+    #[test]
+    fn balancing_spreads_players_without_team_or_platoon() {
+        let participants: Vec<Participant> = (1..=3)
+            .map(|player_id| participant(player_id, Some(1), None))
+            .chain((4..=8).map(|player_id| participant(player_id, None, None)))
+            .collect();
+
+        let assignments = balance_factions(&participants, &[10, 20]);
+        let members = faction_members(&assignments, &[10, 20]);
+
+        assert_eq!(assignments.len(), 8);
+        assert_eq!(members.iter().map(Vec::len).collect::<Vec<_>>(), vec![4, 4]);
+        assert!(members.iter().any(|player_ids| [1, 2, 3].iter().all(|id| player_ids.contains(id))));
+    }
+
+    #[test]
+    fn balancing_with_more_factions_than_units_leaves_factions_empty() {
+        let participants: Vec<Participant> = (1..=4)
+            .map(|player_id| participant(player_id, Some(1), Some(1)))
+            .collect();
+
+        let assignments = balance_factions(&participants, &[10, 20, 30]);
+
+        assert_eq!(faction_members(&assignments, &[10, 20, 30]), vec![vec![1, 2, 3, 4], vec![], vec![]]);
+    }
+
+    #[test]
+    fn balancing_only_assigns_registered_players() {
+        let participants = vec![
+            participant(1, None, None),
+            Participant { status: RegistrationStatus::Waitlisted, ..participant(2, None, None) },
+            Participant { status: RegistrationStatus::Withdrawn, ..participant(3, None, None) },
+        ];
+
+        assert_eq!(balance_factions(&participants, &[10, 20]), vec![(1, 10)]);
+        assert!(balance_factions(&participants, &[]).is_empty());
+    }
+
+    fn limit(class: ReplicaClass, max_joules: f64) -> ClassLimit {
+        ClassLimit { class, max_joules, min_distance_meters: 0 }
+    }
+
+    fn replica(class: ReplicaClass, fps: f64, bb_weight: f64) -> Replica {
+        Replica { class, fps, bb_weight, ..Default::default() }
+    }
+
+    #[test]
+    fn fps_and_joules_convert_both_ways() {
+        assert!((joules_from_fps(328.0, 0.20) - 0.99949).abs() < 1e-5);
+        assert!((fps_from_joules(1.0, 0.20) - 328.084).abs() < 1e-3);
+
+        for bb_weight in [0.20, 0.25, 0.28, 0.43] {
+            for joules in [0.5, 1.0, 1.49, 2.3] {
+                let fps = fps_from_joules(joules, bb_weight);
+
+                assert!((joules_from_fps(fps, bb_weight) - joules).abs() < 1e-12);
+            }
+        }
+
+        // the same energy needs less velocity with a heavier BB
+        assert!(fps_from_joules(1.0, 0.25) < fps_from_joules(1.0, 0.20));
+    }
+
+    #[test]
+    fn class_limit_allows_exactly_the_limit() {
+        let aeg = limit(ReplicaClass::Aeg, 1.0);
+
+        assert!(aeg.allows(1.0));
+        assert!(aeg.allows(1.0 + 1e-10));
+        assert!(!aeg.allows(1.0 + 1e-8));
+        assert!(!aeg.allows(1.001));
+
+        // converting the limit to FPS and back must not fail the replica on rounding
+        for bb_weight in [0.20, 0.25, 0.30] {
+            let fps = fps_from_joules(1.0, bb_weight);
+
+            assert!(aeg.allows(joules_from_fps(fps, bb_weight)));
+            assert!(!aeg.allows(joules_from_fps(fps + 0.1, bb_weight)));
+        }
+    }
+
+    #[test]
+    fn chrono_status_uses_the_limit_of_the_replica_class() {
+        let limits = [limit(ReplicaClass::Aeg, 1.0), limit(ReplicaClass::Sniper, 2.3)];
+        let max_fps = limits[0].max_reference_fps();
+
+        assert_eq!(replica(ReplicaClass::Aeg, max_fps, REFERENCE_BB_WEIGHT).chrono_status(&limits), ChronoStatus::Passed);
+        assert_eq!(replica(ReplicaClass::Aeg, max_fps + 0.1, REFERENCE_BB_WEIGHT).chrono_status(&limits), ChronoStatus::Failed);
+        assert_eq!(replica(ReplicaClass::Sniper, 400.0, 0.28).chrono_status(&limits), ChronoStatus::Passed);
+        assert_eq!(replica(ReplicaClass::Pistol, 300.0, 0.20).chrono_status(&limits), ChronoStatus::NoLimit);
+
+        let heavy = replica(ReplicaClass::Aeg, 300.0, 0.25);
+        assert!((heavy.reference_fps() - 300.0 * (0.25_f64 / 0.20).sqrt()).abs() < 1e-9);
+    }
+
+    fn day(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn event_on(date: NaiveDate, min_age: Option<i32>) -> PlayEvent {
+        let starts_at = date.and_hms_opt(9, 0, 0).unwrap().and_utc();
+
+        PlayEvent {
+            starts_at,
+            ends_at: starts_at + Duration::hours(8),
+            min_age,
+            ..Default::default()
+        }
+    }
+
+    fn consent_until(valid_until: NaiveDate) -> ParentalConsent {
+        ParentalConsent {
+            player_id: 1,
+            guardian_name: "Erika Mustermann".to_string(),
+            valid_until,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn age_counts_completed_years() {
+        let birth_date = day(2008, 6, 15);
+
+        assert_eq!(age_on(birth_date, day(2026, 6, 14)), 17);
+        assert_eq!(age_on(birth_date, day(2026, 6, 15)), 18);
+        assert_eq!(age_on(birth_date, day(2007, 1, 1)), 0);
+    }
+
+    #[test]
+    fn leap_day_births_age_on_the_first_of_march() {
+        let birth_date = day(2008, 2, 29);
+
+        assert_eq!(age_on(birth_date, day(2026, 2, 28)), 17);
+        assert_eq!(age_on(birth_date, day(2026, 3, 1)), 18);
+        assert_eq!(age_on(birth_date, day(2028, 2, 28)), 19);
+        assert_eq!(age_on(birth_date, day(2028, 2, 29)), 20);
+    }
+
+    #[test]
+    fn minors_need_a_consent_valid_on_the_event_day() {
+        let event = event_on(day(2026, 6, 15), None);
+        let minor = Some(day(2008, 6, 16));
+
+        assert_eq!(check_participation(minor, &event, &[]), Err(ParticipationBlock::ParentalConsentMissing));
+        assert_eq!(
+            check_participation(minor, &event, &[consent_until(day(2026, 6, 14))]),
+            Err(ParticipationBlock::ParentalConsentMissing),
+        );
+        assert_eq!(check_participation(minor, &event, &[consent_until(day(2026, 6, 15))]), Ok(()));
+
+        // turning 18 on the event day needs no consent
+        assert_eq!(check_participation(Some(day(2008, 6, 15)), &event, &[]), Ok(()));
+    }
+
+    #[test]
+    fn age_limits_come_before_the_consent() {
+        let event = event_on(day(2026, 6, 15), Some(18));
+        let consents = [consent_until(day(2026, 12, 31))];
+
+        assert_eq!(check_participation(Some(day(2008, 6, 16)), &event, &consents), Err(ParticipationBlock::TooYoung(18)));
+        assert_eq!(check_participation(Some(day(2008, 6, 15)), &event, &[]), Ok(()));
+        assert_eq!(check_participation(None, &event, &[]), Err(ParticipationBlock::BirthDateMissing));
+
+        let event = event_on(day(2026, 6, 15), Some(16));
+
+        assert_eq!(check_participation(Some(day(2010, 6, 15)), &event, &consents), Ok(()));
+        assert_eq!(check_participation(Some(day(2010, 6, 16)), &event, &consents), Err(ParticipationBlock::TooYoung(16)));
+        assert_eq!(check_participation(None, &event_on(day(2026, 6, 15), None), &[]), Ok(()));
+    }
+
+    fn charge(id: i64, amount_cents: i64) -> Charge {
+        Charge { id: Some(id), player_id: 1, description: format!("Beitrag {}", id), amount_cents, ..Default::default() }
+    }
+
+    fn payment(charge_id: Option<i64>, amount_cents: i64) -> Payment {
+        Payment { player_id: 1, charge_id, amount_cents, ..Default::default() }
+    }
+
+    #[test]
+    fn ledger_balance_sums_charges_and_payments_in_cents() {
+        let ledger = PlayerLedger {
+            charges: vec![charge(1, 3000), charge(2, 1550), charge(3, 10)],
+            payments: vec![payment(Some(1), 3000), payment(Some(2), 1000), payment(None, 500)],
+            editable: false,
+        };
+
+        let balance = ledger.balance();
+
+        assert_eq!((balance.charged_cents, balance.paid_cents), (4560, 4500));
+        assert_eq!(balance.outstanding_cents(), 60);
+    }
+
+    #[test]
+    fn paid_per_charge_only_counts_payments_booked_against_a_charge() {
+        let ledger = PlayerLedger {
+            charges: vec![charge(1, 3000), charge(2, 1550)],
+            payments: vec![payment(Some(2), 1000), payment(Some(2), 550), payment(None, 500), payment(Some(1), 1)],
+            editable: false,
+        };
+
+        let paid = ledger.paid_per_charge();
+
+        assert_eq!(paid.len(), 2);
+        assert_eq!(paid[&1], 1);
+        assert_eq!(paid[&2], 1550);
+    }
+
+    #[test]
+    fn overpayment_is_credit() {
+        let ledger = PlayerLedger {
+            charges: vec![charge(1, 2000)],
+            payments: vec![payment(Some(1), 2000), payment(None, 2500)],
+            editable: false,
+        };
+
+        assert_eq!(ledger.balance().outstanding_cents(), -2500);
+        assert_eq!(PlayerLedger { charges: vec![], payments: vec![], editable: false }.balance().outstanding_cents(), 0);
+    }
+
+    #[test]
+    fn treasurer_report_totals() {
+        let report = TreasurerReport {
+            year: 2026,
+            charged_by_kind: vec![(Some(FeeKind::Membership), 12000), (Some(FeeKind::Event), 4550), (None, 5)],
+            paid_by_method: vec![(PaymentMethod::Cash, 3000), (PaymentMethod::Transfer, 10050)],
+            debtors: vec![
+                PlayerBalance { player_id: 1, charged_cents: 5000, paid_cents: 2000, ..Default::default() },
+                PlayerBalance { player_id: 2, charged_cents: 1505, paid_cents: 1500, ..Default::default() },
+            ],
+        };
+
+        assert_eq!(report.charged_cents(), 16555);
+        assert_eq!(report.paid_cents(), 13050);
+        assert_eq!(report.outstanding_cents(), 3005);
+    }
+
+    #[test]
+    fn amounts_parse_into_cents_without_rounding() {
+        assert_eq!(parse_cents("12"), Some(1200));
+        assert_eq!(parse_cents("12,5"), Some(1250));
+        assert_eq!(parse_cents(" 12.05 € "), Some(1205));
+        assert_eq!(parse_cents("0,29"), Some(29));
+        assert_eq!(parse_cents("0.1"), Some(10));
+
+        for invalid in ["", ",5", "12,345", "-3", "1.234,56", "12 Euro", "9223372036854775807"] {
+            assert_eq!(parse_cents(invalid), None, "{}", invalid);
+        }
+    }
 }
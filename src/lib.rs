@@ -1,8 +1,27 @@
+#[cfg(feature = "ssr")]
+pub mod api;
 pub mod app;
 pub mod auth;
+#[cfg(feature = "ssr")]
+pub mod bank_statement;
+#[cfg(feature = "ssr")]
+pub mod calendar;
 pub mod components;
+#[cfg(feature = "ssr")]
+pub mod consent;
 pub mod database;
 pub mod domain;
+#[cfg(feature = "ssr")]
+pub mod location;
+pub mod markdown;
+#[cfg(feature = "ssr")]
+pub mod notification;
+#[cfg(feature = "ssr")]
+pub mod scoreboard;
+#[cfg(feature = "ssr")]
+pub mod ticket;
+#[cfg(feature = "ssr")]
+pub mod webhook;
 
 #[cfg(feature = "hydrate")]
 #[wasm_bindgen::prelude::wasm_bindgen]
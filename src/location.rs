@@ -0,0 +1,58 @@
+use axum::{
+    extract::Path,
+    http::{header, StatusCode},
+    response::IntoResponse,
+    routing::get,
+    Extension, Router,
+};
+
+use crate::auth::AuthSession;
+use crate::database::{self, DieselPool};
+
+/// Largest site map that is accepted for upload.
+pub const MAX_MAP_SIZE: usize = 2 * 1024 * 1024;
+
+/// Routes serving the uploaded site maps, only to logged in players.
+pub fn router<S>(pool: DieselPool) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    Router::new()
+        .route("/locations/{id}/map", get(location_map))
+        .layer(Extension(pool))
+}
+
+/// Tells the image type from the file's magic bytes instead of trusting the upload's content type.
+pub fn detect_map_content_type(data: &[u8]) -> Option<&'static str> {
+    if data.starts_with(b"\x89PNG\r\n\x1a\n") {
+        Some("image/png")
+    } else if data.starts_with(b"\xff\xd8\xff") {
+        Some("image/jpeg")
+    } else if data.len() >= 12 && &data[..4] == b"RIFF" && &data[8..12] == b"WEBP" {
+        Some("image/webp")
+    } else {
+        None
+    }
+}
+
+async fn location_map(
+    auth_session: AuthSession,
+    Extension(pool): Extension<DieselPool>,
+    Path(id): Path<i64>,
+) -> Result<axum::response::Response, StatusCode> {
+    if auth_session.user.is_none() {
+        return Err(StatusCode::UNAUTHORIZED);
+    }
+
+    let (content_type, data) = database::find_location_map(id, &pool)
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, content_type),
+            (header::CACHE_CONTROL, "private, no-cache".to_string()),
+        ],
+        data,
+    ).into_response())
+}
@@ -2,10 +2,18 @@
 #[tokio::main]
 async fn main() {
     use std::env;
+    use std::net::SocketAddr;
 
+    use as_manager::api;
     use as_manager::app::*;
     use as_manager::auth::*;
+    use as_manager::calendar;
+    use as_manager::consent;
     use as_manager::database::DieselPool;
+    use as_manager::location;
+    use as_manager::notification::{self, DeliveryChannels};
+    use as_manager::scoreboard::{self, ScoreboardHub};
+    use as_manager::ticket::TicketSecret;
     use axum::Router;
     use axum_login::{
         tower_sessions::{MemoryStore, SessionManagerLayer},
@@ -28,6 +36,13 @@ async fn main() {
         .build(manager)
         .expect("failed creating diesel connection pool");
 
+    let ticket_secret = TicketSecret(env::var("TICKET_SECRET").expect("TICKET_SECRET must be set"));
+
+    let scoreboard_hub = ScoreboardHub::default();
+
+    let delivery_channels = DeliveryChannels::from_env();
+    notification::spawn_worker(pool.clone(), delivery_channels.clone());
+
     let session_store = MemoryStore::default();
     let session_layer = SessionManagerLayer::new(session_store);
 
@@ -39,12 +54,20 @@ async fn main() {
     let leptos_options = conf.leptos_options;
 
     let app = Router::new()
+        .merge(api::router(pool.clone()))
+        .merge(calendar::router(pool.clone()))
+        .merge(consent::router(pool.clone()))
+        .merge(location::router(pool.clone()))
+        .merge(scoreboard::router(pool.clone(), scoreboard_hub.clone()))
         .leptos_routes_with_context(
             &leptos_options.clone(),
             generate_route_list(App),
             move || {
                 provide_context(pool.clone());
                 provide_context(pool.clone());
+                provide_context(ticket_secret.clone());
+                provide_context(scoreboard_hub.clone());
+                provide_context(delivery_channels.clone());
             },
             {
                 let leptos_options = leptos_options.clone();
@@ -59,7 +82,7 @@ async fn main() {
     // `axum::Server` is a re-export of `hyper::Server`
     log!("listening on http://{}", &addr);
     let listener = tokio::net::TcpListener::bind(&addr).await.unwrap();
-    axum::serve(listener, app.into_make_service())
+    axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>())
         .await
         .unwrap();
 }
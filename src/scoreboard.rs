@@ -0,0 +1,205 @@
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::sync::{Arc, Mutex};
+
+use axum::{
+    extract::Path,
+    http::StatusCode,
+    response::sse::{Event, KeepAlive, Sse},
+    routing::get,
+    Extension, Router,
+};
+use futures::{stream, Stream, StreamExt};
+use tokio::sync::broadcast;
+
+use crate::auth::AuthSession;
+use crate::database::{self, DatabaseError, DieselPool};
+use crate::domain::{PlayEvent, Scoreboard};
+
+const CHANNEL_CAPACITY: usize = 16;
+
+/// Hands scoreboard updates to everyone watching the event. Every update carries the whole
+/// scoreboard, so a watcher that falls behind only needs the latest one.
+#[derive(Clone, Default)]
+pub struct ScoreboardHub {
+    channels: Arc<Mutex<HashMap<i64, broadcast::Sender<Scoreboard>>>>,
+}
+
+impl ScoreboardHub {
+    pub fn subscribe(&self, play_event_id: i64) -> Subscription {
+        let receiver = self.channels
+            .lock()
+            .expect("scoreboard channels")
+            .entry(play_event_id)
+            .or_insert_with(|| broadcast::channel(CHANNEL_CAPACITY).0)
+            .subscribe();
+
+        Subscription {
+            hub: self.clone(),
+            play_event_id,
+            receiver: Some(receiver),
+        }
+    }
+
+    /// Updates for events nobody is watching are dropped.
+    pub fn publish(&self, scoreboard: Scoreboard) {
+        let channels = self.channels.lock().expect("scoreboard channels");
+
+        if let Some(sender) = channels.get(&scoreboard.play_event_id) {
+            let _ = sender.send(scoreboard);
+        }
+    }
+
+    /// Drops the event's channel once nobody is watching anymore.
+    fn prune(&self, play_event_id: i64) {
+        let mut channels = self.channels.lock().expect("scoreboard channels");
+
+        if channels.get(&play_event_id).is_some_and(|sender| sender.receiver_count() == 0) {
+            channels.remove(&play_event_id);
+        }
+    }
+}
+
+/// Receives the updates of one event and gives the channel back to the hub when dropped.
+pub struct Subscription {
+    hub: ScoreboardHub,
+    play_event_id: i64,
+    receiver: Option<broadcast::Receiver<Scoreboard>>,
+}
+
+impl Subscription {
+    pub async fn recv(&mut self) -> Result<Scoreboard, broadcast::error::RecvError> {
+        self.receiver
+            .as_mut()
+            .expect("receiver is only taken when dropped")
+            .recv()
+            .await
+    }
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        // the receiver has to be gone before the hub counts the remaining ones
+        drop(self.receiver.take());
+        self.hub.prune(self.play_event_id);
+    }
+}
+
+/// Routes streaming the scoreboards as server-sent events to logged in players.
+pub fn router<S>(pool: DieselPool, hub: ScoreboardHub) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    Router::new()
+        .route("/scoreboard/events/{id}/stream", get(scoreboard_stream))
+        .layer(Extension(pool))
+        .layer(Extension(hub))
+}
+
+pub fn load_scoreboard(play_event_id: i64, pool: &DieselPool) -> Result<Option<Scoreboard>, DatabaseError> {
+    let Some(play_event) = database::find_play_event_for_id(play_event_id, pool)? else {
+        return Ok(None);
+    };
+    let play_event: PlayEvent = play_event.into();
+
+    let factions = database::get_factions_for_event(play_event_id, pool)?
+        .into_iter()
+        .map(Into::into)
+        .collect();
+    let rounds = database::get_game_rounds_for_events(vec![play_event_id], pool)?;
+
+    Ok(Some(Scoreboard::new(&play_event, factions, rounds)))
+}
+
+async fn scoreboard_stream(
+    auth_session: AuthSession,
+    Extension(pool): Extension<DieselPool>,
+    Extension(hub): Extension<ScoreboardHub>,
+    Path(id): Path<i64>,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, StatusCode> {
+    if auth_session.user.is_none() {
+        return Err(StatusCode::UNAUTHORIZED);
+    }
+
+    // unknown events never get a channel
+    database::find_play_event_for_id(id, &pool)
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    // subscribe before loading, so no update between loading and subscribing gets lost
+    let subscription = hub.subscribe(id);
+    let scoreboard = load_scoreboard(id, &pool)
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    let updates = stream::unfold(subscription, |mut subscription| async move {
+        loop {
+            match subscription.recv().await {
+                Ok(scoreboard) => return Some((scoreboard, subscription)),
+                Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    });
+
+    let events = stream::once(async { scoreboard })
+        .chain(updates)
+        .map(|scoreboard| Ok(Event::default().json_data(scoreboard).unwrap_or_default()));
+
+    Ok(Sse::new(events).keep_alive(KeepAlive::default()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scoreboard(play_event_id: i64) -> Scoreboard {
+        Scoreboard {
+            play_event_id,
+            play_event_name: "Nachtspiel".to_string(),
+            standings: Vec::new(),
+            current_round: None,
+            rounds_played: 0,
+        }
+    }
+
+    fn channel_count(hub: &ScoreboardHub) -> usize {
+        hub.channels.lock().unwrap().len()
+    }
+
+    #[tokio::test]
+    async fn watchers_receive_the_updates_of_their_event() {
+        let hub = ScoreboardHub::default();
+        let mut first = hub.subscribe(1);
+        let mut second = hub.subscribe(1);
+        let _other = hub.subscribe(2);
+
+        hub.publish(scoreboard(1));
+
+        assert_eq!(first.recv().await.unwrap(), scoreboard(1));
+        assert_eq!(second.recv().await.unwrap(), scoreboard(1));
+        assert_eq!(channel_count(&hub), 2);
+    }
+
+    #[test]
+    fn the_channel_goes_away_with_the_last_watcher() {
+        let hub = ScoreboardHub::default();
+        let first = hub.subscribe(1);
+        let second = hub.subscribe(1);
+
+        drop(first);
+        assert_eq!(channel_count(&hub), 1);
+
+        drop(second);
+        assert_eq!(channel_count(&hub), 0);
+    }
+
+    #[test]
+    fn publishing_without_watchers_creates_no_channel() {
+        let hub = ScoreboardHub::default();
+
+        hub.publish(scoreboard(1));
+
+        assert_eq!(channel_count(&hub), 0);
+    }
+}
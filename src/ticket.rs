@@ -0,0 +1,99 @@
+use crypto_hashes::sha2::Sha256;
+use hmac::{Hmac, Mac};
+
+const SIGNATURE_LENGTH: usize = 32;
+
+/// Secret used to sign event tickets, read from `TICKET_SECRET` on startup.
+#[derive(Clone)]
+pub struct TicketSecret(pub String);
+
+/// Builds the ticket token for a registration as `<registration id>.<signature>`.
+pub fn sign_ticket(registration_id: i64, secret: &TicketSecret) -> String {
+    format!("{}.{}", registration_id, signature(registration_id, secret))
+}
+
+/// Returns the registration id if the token carries a valid signature.
+pub fn verify_ticket(token: &str, secret: &TicketSecret) -> Option<i64> {
+    let (registration_id, token_signature) = token.trim().split_once('.')?;
+    let registration_id: i64 = registration_id.parse().ok()?;
+
+    if token_signature.len() != SIGNATURE_LENGTH {
+        return None;
+    }
+
+    // the signature is the truncated hex digest, `Mac` compares it in constant time
+    mac(registration_id, secret)
+        .verify_truncated_left(&decode_hex(token_signature)?)
+        .ok()
+        .map(|_| registration_id)
+}
+
+/// Decodes lowercase or uppercase hex digits, `None` for anything else.
+pub fn decode_hex(text: &str) -> Option<Vec<u8>> {
+    if !text.len().is_multiple_of(2) || !text.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    (0..text.len())
+        .step_by(2)
+        .map(|index| u8::from_str_radix(&text[index..index + 2], 16).ok())
+        .collect()
+}
+
+pub fn render_ticket_svg(token: &str) -> String {
+    use qrcode::{render::svg, QrCode};
+
+    QrCode::new(token.as_bytes())
+        .expect("ticket token fits into a qr code")
+        .render::<svg::Color>()
+        .min_dimensions(200, 200)
+        .build()
+}
+
+fn mac(registration_id: i64, secret: &TicketSecret) -> Hmac<Sha256> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.0.as_bytes())
+        .expect("hmac accepts keys of any length");
+    mac.update(format!("ticket:{}", registration_id).as_bytes());
+
+    mac
+}
+
+fn signature(registration_id: i64, secret: &TicketSecret) -> String {
+    let digest = mac(registration_id, secret).finalize().into_bytes();
+    let mut signature: String = digest.iter().map(|byte| format!("{:02x}", byte)).collect();
+    signature.truncate(SIGNATURE_LENGTH);
+
+    signature
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tickets_round_trip_and_reject_tampering() {
+        let secret = TicketSecret("s3cr3t".to_string());
+        let token = sign_ticket(42, &secret);
+        let (_, signature) = token.split_once('.').unwrap();
+
+        assert_eq!(signature.len(), SIGNATURE_LENGTH);
+        assert_eq!(verify_ticket(&token, &secret), Some(42));
+        assert_eq!(verify_ticket(&format!(" {} ", token), &secret), Some(42));
+
+        assert_eq!(verify_ticket(&token.replacen("42", "43", 1), &secret), None);
+        assert_eq!(verify_ticket(&token, &TicketSecret("other".to_string())), None);
+        assert_eq!(verify_ticket(&token[..token.len() - 2], &secret), None);
+        assert_eq!(verify_ticket(&format!("{}00", token), &secret), None);
+        assert_eq!(verify_ticket(&format!("42.{}", "zz".repeat(SIGNATURE_LENGTH / 2)), &secret), None);
+        assert_eq!(verify_ticket("42", &secret), None);
+    }
+
+    #[test]
+    fn hex_decoding_is_strict() {
+        assert_eq!(decode_hex("00ffA0"), Some(vec![0x00, 0xff, 0xa0]));
+        assert_eq!(decode_hex(""), Some(vec![]));
+        assert_eq!(decode_hex("abc"), None);
+        assert_eq!(decode_hex("+f"), None);
+        assert_eq!(decode_hex("äb"), None);
+    }
+}
@@ -0,0 +1,331 @@
+use chrono::{DateTime, Utc};
+use crypto_hashes::sha2::Sha256;
+use hmac::{Hmac, Mac};
+use serde::Serialize;
+
+use crate::database::{self, DatabaseError, DieselPool};
+use crate::domain::{Announcement, PlayEvent, WebhookDelivery, WebhookEndpoint, WebhookEventType};
+use crate::notification::{lease, retry_delay, DeliveryError, WebhookSender};
+use crate::ticket::decode_hex;
+
+pub const EVENT_HEADER: &str = "X-AS-Manager-Event";
+pub const DELIVERY_HEADER: &str = "X-AS-Manager-Delivery";
+pub const TIMESTAMP_HEADER: &str = "X-AS-Manager-Timestamp";
+pub const SIGNATURE_HEADER: &str = "X-AS-Manager-Signature";
+
+const SECRET_LENGTH: usize = 40;
+const BATCH_SIZE: i64 = 50;
+
+pub fn generate_secret() -> String {
+    use rand::{distributions::Alphanumeric, Rng};
+
+    rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(SECRET_LENGTH)
+        .map(char::from)
+        .collect()
+}
+
+/// HMAC-SHA256 over `{timestamp}.{body}` as sent in the signature header, e.g. `sha256=3f2a…`.
+/// The timestamp is part of the signature so receivers can reject replayed requests.
+pub fn sign_payload(secret: &str, timestamp: i64, body: &str) -> String {
+    let digest = payload_mac(secret, timestamp, body).finalize().into_bytes();
+    let signature: String = digest.iter().map(|byte| format!("{:02x}", byte)).collect();
+
+    format!("sha256={}", signature)
+}
+
+/// Checks a received signature header, meant for receivers written in Rust.
+pub fn verify_payload(secret: &str, timestamp: i64, body: &str, signature: &str) -> bool {
+    signature
+        .strip_prefix("sha256=")
+        .and_then(decode_hex)
+        .is_some_and(|signature| payload_mac(secret, timestamp, body).verify_slice(&signature).is_ok())
+}
+
+fn payload_mac(secret: &str, timestamp: i64, body: &str) -> Hmac<Sha256> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+        .expect("hmac accepts keys of any length");
+    mac.update(format!("{}.{}", timestamp, body).as_bytes());
+
+    mac
+}
+
+#[derive(Serialize)]
+struct Envelope<T> {
+    #[serde(rename = "type")]
+    event_type: &'static str,
+    created_at: DateTime<Utc>,
+    data: T,
+}
+
+#[derive(Serialize)]
+pub struct PlayEventPayload {
+    pub id: i64,
+    pub name: String,
+    pub description: String,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+    pub registration_deadline: Option<DateTime<Utc>>,
+    pub capacity: Option<i32>,
+    pub url: String,
+}
+
+impl PlayEventPayload {
+    pub fn new(play_event: &PlayEvent, public_url: &str) -> Self {
+        let id = play_event.id.expect("stored play event");
+
+        Self {
+            id,
+            name: play_event.name.clone(),
+            description: play_event.description.clone(),
+            starts_at: play_event.starts_at,
+            ends_at: play_event.ends_at,
+            registration_deadline: play_event.registration_deadline,
+            capacity: play_event.capacity,
+            url: format!("{}/events/{}", public_url, id),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct AnnouncementPayload {
+    pub id: i64,
+    pub title: String,
+    pub content: String,
+    pub publish_at: DateTime<Utc>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub url: String,
+}
+
+impl AnnouncementPayload {
+    pub fn new(announcement: &Announcement, public_url: &str) -> Self {
+        Self {
+            id: announcement.id.expect("stored announcement"),
+            title: announcement.title.clone(),
+            content: announcement.content.clone(),
+            publish_at: announcement.publish_at,
+            expires_at: announcement.expires_at,
+            url: format!("{}/", public_url),
+        }
+    }
+}
+
+fn envelope(event_type: WebhookEventType, data: impl Serialize) -> String {
+    serde_json::to_string(&Envelope {
+        event_type: event_type.as_str(),
+        created_at: Utc::now(),
+        data,
+    })
+    .expect("webhook payload serializes")
+}
+
+/// Queues the event for every subscribed endpoint. The payload is stored as it will be signed,
+/// so retries send exactly the same body.
+pub fn publish(event_type: WebhookEventType, data: impl Serialize, pool: &DieselPool) -> Result<usize, DatabaseError> {
+    database::enqueue_webhook_deliveries(event_type, &envelope(event_type, data), pool)
+}
+
+async fn deliver(
+    delivery: &WebhookDelivery,
+    endpoint: &WebhookEndpoint,
+    sender: &dyn WebhookSender,
+) -> Result<(), DeliveryError> {
+    let timestamp = Utc::now().timestamp();
+    let headers = vec![
+        (EVENT_HEADER, delivery.event_type.as_str().to_string()),
+        (DELIVERY_HEADER, delivery.id.to_string()),
+        (TIMESTAMP_HEADER, timestamp.to_string()),
+        (SIGNATURE_HEADER, sign_payload(&endpoint.secret, timestamp, &delivery.payload)),
+    ];
+
+    sender.post(&endpoint.url, headers, delivery.payload.clone()).await
+}
+
+/// Sends everything that is due and puts failed deliveries back with a growing delay.
+pub async fn deliver_due(pool: &DieselPool, sender: &dyn WebhookSender) -> Result<(), DatabaseError> {
+    for (delivery, endpoint) in database::claim_due_webhook_deliveries(BATCH_SIZE, lease(), pool)? {
+        let outcome = deliver(&delivery, &endpoint, sender).await.map_err(|e| e.to_string());
+        let retry_at = match outcome {
+            Ok(()) => None,
+            Err(_) => retry_delay(delivery.attempts).map(|delay| Utc::now() + delay),
+        };
+
+        database::finish_webhook_delivery(delivery.id, outcome, retry_at, pool)?;
+    }
+
+    Ok(())
+}
+
+/// Sends a ping to the endpoint right away, without retries, and returns the logged delivery.
+pub async fn send_test(
+    endpoint: &WebhookEndpoint,
+    sender: &dyn WebhookSender,
+    pool: &DieselPool,
+) -> Result<WebhookDelivery, DatabaseError> {
+    #[derive(Serialize)]
+    struct Ping<'a> {
+        endpoint: &'a str,
+    }
+
+    let payload = envelope(WebhookEventType::Ping, Ping { endpoint: &endpoint.name });
+    let endpoint_id = endpoint.id.expect("stored endpoint");
+    let delivery_id = database::create_immediate_webhook_delivery(endpoint_id, WebhookEventType::Ping, &payload, lease(), pool)?;
+    let delivery = database::find_webhook_delivery_for_id(delivery_id, pool)?
+        .ok_or(DatabaseError::EntityNotFound)?;
+
+    let outcome = deliver(&delivery, endpoint, sender).await.map_err(|e| e.to_string());
+    database::finish_webhook_delivery(delivery_id, outcome, None, pool)?;
+
+    database::find_webhook_delivery_for_id(delivery_id, pool)?
+        .ok_or(DatabaseError::EntityNotFound)
+}
+
+#[cfg(test)]
+mod tests {
+    use async_trait::async_trait;
+    use chrono::Duration;
+
+    use super::*;
+    use crate::domain::DeliveryStatus;
+    use crate::notification::LocalWebhookSender;
+
+    const SECRET: &str = "s3cr3t";
+    const TIMESTAMP: i64 = 1_700_000_000;
+    const BODY: &str = r#"{"type":"ping","data":{}}"#;
+
+    struct FailingWebhookSender;
+
+    #[async_trait]
+    impl WebhookSender for FailingWebhookSender {
+        async fn post(&self, _: &str, _: Vec<(&'static str, String)>, _: String) -> Result<(), DeliveryError> {
+            Err(DeliveryError::Transport("connection refused".to_string()))
+        }
+    }
+
+    fn header<'a>(headers: &'a [(&'static str, String)], name: &str) -> &'a str {
+        headers
+            .iter()
+            .find(|(header, _)| *header == name)
+            .map(|(_, value)| value.as_str())
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn signature_matches_a_plain_hmac_sha256() {
+        // same as `printf '1700000000.{"type":"ping","data":{}}' | openssl dgst -sha256 -hmac s3cr3t`
+        assert_eq!(
+            sign_payload(SECRET, TIMESTAMP, BODY),
+            "sha256=490c2727e1cde3f522bb95ef86fe19e0ee19d07be85dfc2cb0a2cd398e18f4d6",
+        );
+    }
+
+    #[test]
+    fn signature_round_trips() {
+        let signature = sign_payload(SECRET, TIMESTAMP, BODY);
+
+        assert!(verify_payload(SECRET, TIMESTAMP, BODY, &signature));
+    }
+
+    #[test]
+    fn signature_rejects_tampered_requests() {
+        let signature = sign_payload(SECRET, TIMESTAMP, BODY);
+
+        assert!(!verify_payload(SECRET, TIMESTAMP, r#"{"type":"ping","data":{"x":1}}"#, &signature));
+        assert!(!verify_payload(SECRET, TIMESTAMP + 1, BODY, &signature));
+        assert!(!verify_payload("other secret", TIMESTAMP, BODY, &signature));
+        assert!(!verify_payload(SECRET, TIMESTAMP, BODY, &signature[..signature.len() - 1]));
+        assert!(!verify_payload(SECRET, TIMESTAMP, BODY, &signature[..signature.len() - 2]));
+        assert!(!verify_payload(SECRET, TIMESTAMP, BODY, signature.trim_start_matches("sha256=")));
+        assert!(!verify_payload(SECRET, TIMESTAMP, BODY, ""));
+    }
+
+    #[tokio::test]
+    async fn deliver_sends_signed_headers() {
+        let endpoint = WebhookEndpoint {
+            id: Some(1),
+            name: "Discord".to_string(),
+            url: "https://hooks.example.com/asm".to_string(),
+            secret: SECRET.to_string(),
+            active: true,
+            event_types: vec![WebhookEventType::EventPublished],
+        };
+        let delivery = WebhookDelivery {
+            id: 42,
+            endpoint_id: 1,
+            event_type: WebhookEventType::EventPublished,
+            payload: BODY.to_string(),
+            status: DeliveryStatus::Pending,
+            attempts: 1,
+            next_attempt_at: Utc::now(),
+            last_error: None,
+            created_at: Utc::now(),
+            sent_at: None,
+        };
+        let sender = LocalWebhookSender::default();
+
+        deliver(&delivery, &endpoint, &sender).await.unwrap();
+
+        let sent = sender.sent();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].url, endpoint.url);
+        assert_eq!(sent[0].body, BODY);
+        assert_eq!(header(&sent[0].headers, EVENT_HEADER), "event.published");
+        assert_eq!(header(&sent[0].headers, DELIVERY_HEADER), "42");
+
+        let timestamp: i64 = header(&sent[0].headers, TIMESTAMP_HEADER).parse().unwrap();
+        assert!(verify_payload(SECRET, timestamp, BODY, header(&sent[0].headers, SIGNATURE_HEADER)));
+    }
+
+    /// Runs against the database in `TEST_DATABASE_URL`, e.g. with
+    /// `TEST_DATABASE_URL=postgres://… cargo test --features ssr --lib -- --ignored`.
+    #[tokio::test]
+    #[ignore = "needs a migrated database in TEST_DATABASE_URL"]
+    async fn deliver_due_sends_and_schedules_retries() {
+        use diesel::{r2d2::{ConnectionManager, Pool}, PgConnection};
+
+        let connection_url = std::env::var("TEST_DATABASE_URL").expect("TEST_DATABASE_URL must be set");
+        let pool: DieselPool = Pool::builder()
+            .max_size(2)
+            .build(ConnectionManager::<PgConnection>::new(connection_url))
+            .expect("test database");
+
+        let endpoint_id = database::create_webhook_endpoint(WebhookEndpoint {
+            id: None,
+            name: "deliver_due test".to_string(),
+            url: format!("https://hooks.example.com/{}", generate_secret()),
+            secret: SECRET.to_string(),
+            active: true,
+            event_types: vec![WebhookEventType::EventCancelled],
+        }, &pool).unwrap();
+        let endpoint = database::find_webhook_endpoint_for_id(endpoint_id, &pool).unwrap().unwrap();
+
+        assert!(publish(WebhookEventType::EventCancelled, serde_json::json!({ "id": 1 }), &pool).unwrap() >= 1);
+
+        let sender = LocalWebhookSender::default();
+        deliver_due(&pool, &sender).await.unwrap();
+
+        let sent: Vec<_> = sender.sent().into_iter().filter(|sent| sent.url == endpoint.url).collect();
+        assert_eq!(sent.len(), 1);
+        let timestamp: i64 = header(&sent[0].headers, TIMESTAMP_HEADER).parse().unwrap();
+        assert!(verify_payload(SECRET, timestamp, &sent[0].body, header(&sent[0].headers, SIGNATURE_HEADER)));
+
+        let delivered = database::get_webhook_deliveries_for_endpoint(endpoint_id, 10, &pool).unwrap();
+        assert_eq!(delivered.len(), 1);
+        assert_eq!(delivered[0].status, DeliveryStatus::Sent);
+        assert_eq!(delivered[0].attempts, 1);
+
+        publish(WebhookEventType::EventCancelled, serde_json::json!({ "id": 2 }), &pool).unwrap();
+        let before = Utc::now();
+        deliver_due(&pool, &FailingWebhookSender).await.unwrap();
+
+        let failed = &database::get_webhook_deliveries_for_endpoint(endpoint_id, 10, &pool).unwrap()[0];
+        assert_eq!(failed.status, DeliveryStatus::Pending);
+        assert_eq!(failed.attempts, 1);
+        assert_eq!(failed.last_error.as_deref(), Some("delivery failed: connection refused"));
+        assert!(failed.next_attempt_at >= before + Duration::seconds(30));
+        assert!(failed.next_attempt_at <= Utc::now() + Duration::seconds(30));
+
+        database::delete_webhook_endpoint(endpoint_id, &pool).unwrap();
+    }
+}